@@ -45,6 +45,8 @@ pub struct QueryRequest {
     pub query: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<usize>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub history: Vec<HistoryTurn>,
 }
@@ -112,6 +114,28 @@ pub struct JobStatus {
 pub struct AnswerResult {
     pub answer: String,
     pub sources: Vec<ChunkResult>,
+    /// The model that wrote `answer`; `None` from a server that predates it.
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// One line of a streamed answer (`/api/answer/stream`): the sources and
+/// model first, then the answer's text in pieces. An `error` line ends a
+/// stream whose generation failed midway.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AnswerEvent {
+    Start {
+        sources: Vec<ChunkResult>,
+        #[serde(default)]
+        model: Option<String>,
+    },
+    Text {
+        text: String,
+    },
+    Error {
+        error: String,
+    },
 }
 
 #[cfg(test)]
@@ -124,6 +148,7 @@ mod tests {
             vault_id: "v".into(),
             query: "q".into(),
             context_size: None,
+            top_k: None,
             history: vec![],
         };
         let json = serde_json::to_string(&req).unwrap();
@@ -139,6 +164,7 @@ mod tests {
             vault_id: "v".into(),
             query: "q".into(),
             context_size: None,
+            top_k: None,
             history: vec![HistoryTurn {
                 question: "q1".into(),
                 answer: "a1".into(),
@@ -159,6 +185,30 @@ mod tests {
         assert!(health.llm_provider.is_none());
     }
 
+    #[test]
+    fn answer_result_carries_the_model_when_the_server_sends_it() {
+        let json = r#"{"answer":"a","sources":[],"model":"gemini-2.5-flash"}"#;
+        let answer: AnswerResult = serde_json::from_str(json).unwrap();
+        assert_eq!(answer.model.as_deref(), Some("gemini-2.5-flash"));
+
+        // An older server omits it.
+        let answer: AnswerResult = serde_json::from_str(r#"{"answer":"a","sources":[]}"#).unwrap();
+        assert!(answer.model.is_none());
+    }
+
+    #[test]
+    fn answer_events_parse_by_their_fields() {
+        let start: AnswerEvent =
+            serde_json::from_str(r#"{"sources":[],"model":"gemini-2.5-flash"}"#).unwrap();
+        assert!(
+            matches!(start, AnswerEvent::Start { model: Some(m), .. } if m == "gemini-2.5-flash")
+        );
+        let text: AnswerEvent = serde_json::from_str(r#"{"text":"Hel"}"#).unwrap();
+        assert!(matches!(text, AnswerEvent::Text { text } if text == "Hel"));
+        let error: AnswerEvent = serde_json::from_str(r#"{"error":"quota"}"#).unwrap();
+        assert!(matches!(error, AnswerEvent::Error { error } if error == "quota"));
+    }
+
     #[test]
     fn health_parses_configured_llm_provider() {
         let json =
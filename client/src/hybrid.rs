@@ -0,0 +1,105 @@
+//! Hybrid search: the vault's local keyword (FTS) search and the server's
+//! semantic search, merged with reciprocal rank fusion. Keyword search finds
+//! the exact term the user remembers; semantic search finds the note they
+//! phrased differently. Fusing ranks rather than scores sidesteps the two
+//! scales being incomparable.
+
+use std::collections::HashMap;
+
+use kimun_core::{NoteVault, nfs::VaultPath};
+
+use crate::{ContextSize, RagClient, RagError};
+
+/// RRF's damping constant: a note at rank `r` in one list contributes
+/// `1 / (RRF_K + r)`. 60 is the value from the original RRF paper; it keeps a
+/// single first place from drowning out agreement between the lists.
+pub const RRF_K: f32 = 60.0;
+
+/// Fuses ranked lists of notes, best-first, into one `(path, score)` list,
+/// best-first. Each list is ranked from 1; a note's score is the sum of its
+/// `1 / (RRF_K + rank)` over the lists that contain it. Ties keep the order in
+/// which the notes were first seen.
+pub fn reciprocal_rank_fusion(lists: &[Vec<VaultPath>]) -> Vec<(VaultPath, f32)> {
+    let mut scores: HashMap<VaultPath, (f32, usize)> = HashMap::new();
+    let mut seen = 0;
+    for list in lists {
+        for (i, path) in list.iter().enumerate() {
+            let entry = scores.entry(path.clone()).or_insert_with(|| {
+                seen += 1;
+                (0.0, seen)
+            });
+            entry.0 += 1.0 / (RRF_K + (i + 1) as f32);
+        }
+    }
+    let mut fused: Vec<(VaultPath, f32, usize)> = scores
+        .into_iter()
+        .map(|(path, (score, order))| (path, score, order))
+        .collect();
+    fused.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.2.cmp(&b.2)));
+    fused
+        .into_iter()
+        .map(|(path, score, _)| (path, score))
+        .collect()
+}
+
+/// Runs `query` through both the vault's keyword search and the server's
+/// semantic search and fuses the results (see [`reciprocal_rank_fusion`]).
+///
+/// The vault's FTS index returns matches unranked, so every keyword hit counts
+/// as rank 1: a keyword match is a flat boost, and the semantic rank orders
+/// notes within it. A failed keyword search (e.g. unbalanced query syntax)
+/// degrades to semantic-only rather than failing the lookup.
+pub async fn hybrid_search(
+    vault: &NoteVault,
+    client: &RagClient,
+    query: &str,
+    context_size: Option<ContextSize>,
+) -> Result<Vec<(VaultPath, f32)>, RagError> {
+    let semantic: Vec<VaultPath> = client
+        .search(query, context_size)
+        .await?
+        .into_iter()
+        .map(|chunk| VaultPath::new(&chunk.path))
+        .collect();
+    let keyword: Vec<VaultPath> = vault
+        .search_notes(query)
+        .await
+        .map(|notes| notes.into_iter().map(|(entry, _)| entry.path).collect())
+        .unwrap_or_default();
+
+    // Keyword hits as a flat rank-1 boost: one single-item list per hit.
+    let mut lists: Vec<Vec<VaultPath>> = vec![semantic];
+    lists.extend(keyword.into_iter().map(|path| vec![path]));
+    Ok(reciprocal_rank_fusion(&lists))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(names: &[&str]) -> Vec<VaultPath> {
+        names.iter().map(VaultPath::note_path_from).collect()
+    }
+
+    #[test]
+    fn agreement_between_lists_beats_a_single_first_place() {
+        let fused = reciprocal_rank_fusion(&[paths(&["a", "b", "c"]), paths(&["b", "d"])]);
+        let order: Vec<String> = fused.iter().map(|(p, _)| p.get_clean_name()).collect();
+        assert_eq!(order, vec!["b", "a", "d", "c"]);
+        let b = 1.0 / (RRF_K + 2.0) + 1.0 / (RRF_K + 1.0);
+        assert!((fused[0].1 - b).abs() < 1e-6);
+    }
+
+    #[test]
+    fn ties_keep_first_seen_order() {
+        let fused = reciprocal_rank_fusion(&[paths(&["x"]), paths(&["y"]), paths(&["z"])]);
+        let order: Vec<String> = fused.iter().map(|(p, _)| p.get_clean_name()).collect();
+        assert_eq!(order, vec!["x", "y", "z"]);
+    }
+
+    #[test]
+    fn empty_input_fuses_to_nothing() {
+        assert!(reciprocal_rank_fusion(&[]).is_empty());
+        assert!(reciprocal_rank_fusion(&[vec![], vec![]]).is_empty());
+    }
+}
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 pub mod dto;
+pub mod hybrid;
 pub mod observer;
 pub mod reconcile;
 
@@ -14,11 +15,12 @@ pub mod sync;
 
 use async_trait::async_trait;
 use dto::{
-    AnswerResult, DeleteRequest, EmbeddingsResponse, Health, HistoryTurn, IndexDocsRequest,
-    JobAccepted, JobStatus, QueryRequest, WireDoc,
+    AnswerEvent, AnswerResult, DeleteRequest, EmbeddingsResponse, Health, HistoryTurn,
+    IndexDocsRequest, JobAccepted, JobStatus, QueryRequest, WireDoc,
 };
 
 pub use dto::{ChunkResult, WireSection};
+pub use hybrid::{hybrid_search, reciprocal_rank_fusion};
 pub use observer::{DirtyOp, DirtySet, RagObserver};
 pub use reconcile::{ReconcilePlan, diff as reconcile_diff};
 
@@ -82,21 +84,27 @@ pub fn hash_string(hash: u64) -> String {
     hash.to_string()
 }
 
-/// Number of results to request. Maps to the server's `context_size` variants,
-/// so a caller can't send an invalid string (which the server rejects with 400).
+/// Number of results to request. The named sizes map to the server's
+/// `context_size` variants, so a caller can't send an invalid string (which the
+/// server rejects with 400); `Exact` sends a literal `top_k`.
 #[derive(Debug, Clone, Copy)]
 pub enum ContextSize {
     Small,
     Medium,
     Large,
+    Exact(usize),
 }
 
 impl ContextSize {
-    fn as_str(self) -> &'static str {
-        match self {
-            ContextSize::Small => "small",
-            ContextSize::Medium => "medium",
-            ContextSize::Large => "large",
+    /// The `(context_size, top_k)` request fields this size serializes to.
+    fn wire(size: Option<Self>) -> (Option<String>, Option<usize>) {
+        let name = |s: &str| (Some(s.to_string()), None);
+        match size {
+            None => (None, None),
+            Some(ContextSize::Small) => name("small"),
+            Some(ContextSize::Medium) => name("medium"),
+            Some(ContextSize::Large) => name("large"),
+            Some(ContextSize::Exact(n)) => (None, Some(n)),
         }
     }
 }
@@ -115,6 +123,10 @@ const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 /// [`REQUEST_TIMEOUT`] on a slow link.
 const PUSH_TIMEOUT: Duration = Duration::from_secs(120);
 
+/// Bound on a streamed answer ([`RagClient::ask_stream`]), which holds one
+/// request open while the LLM writes: as long as the polled path waits.
+const ANSWER_STREAM_TIMEOUT: Duration = Duration::from_secs(720);
+
 /// One process-wide HTTP client, so every [`RagClient`] — however short-lived —
 /// shares the same connection pool and keep-alive connections instead of
 /// paying a fresh TCP+TLS handshake per construction.
@@ -237,10 +249,12 @@ impl RagClient {
         query: &str,
         context_size: Option<ContextSize>,
     ) -> Result<Vec<ChunkResult>, RagError> {
+        let (context_size, top_k) = ContextSize::wire(context_size);
         let body = QueryRequest {
             vault_id: self.vault_id.clone(),
             query: query.to_string(),
-            context_size: context_size.map(|c| c.as_str().to_string()),
+            context_size,
+            top_k,
             history: vec![],
         };
         let resp = self
@@ -262,10 +276,61 @@ impl RagClient {
         history: &[(String, String)],
         context_size: Option<ContextSize>,
     ) -> Result<AnswerResult, RagError> {
-        let body = QueryRequest {
+        let body = self.answer_request(query, history, context_size);
+        let resp = self
+            .auth(self.http.post(self.url("/api/answer")).json(&body))
+            .send()
+            .await?;
+        let job_id = Self::ok(resp).await?.json::<JobAccepted>().await?.job_id;
+        self.poll_answer(&job_id).await
+    }
+
+    /// [`Self::ask`] with the answer streamed: `on_text` gets each piece of
+    /// it as the server sends it, and the result holds the whole answer. A
+    /// server without the streaming route is asked the polled way, with the
+    /// answer handed to `on_text` in one piece.
+    pub async fn ask_stream(
+        &self,
+        query: &str,
+        history: &[(String, String)],
+        context_size: Option<ContextSize>,
+        mut on_text: impl FnMut(&str),
+    ) -> Result<AnswerResult, RagError> {
+        let body = self.answer_request(query, history, context_size);
+        let resp = self
+            .auth(self.http.post(self.url("/api/answer/stream")).json(&body))
+            .timeout(ANSWER_STREAM_TIMEOUT)
+            .send()
+            .await?;
+        let mut resp = match Self::ok(resp).await {
+            // An unknown route has no error body, unlike the server's own 404s.
+            Err(RagError::Status { status: 404, body }) if body.is_empty() => {
+                let answer = self.ask(query, history, context_size).await?;
+                on_text(&answer.answer);
+                return Ok(answer);
+            }
+            resp => resp?,
+        };
+        let mut reader = AnswerStreamReader::default();
+        while let Some(bytes) = resp.chunk().await? {
+            reader.feed(&bytes, &mut on_text)?;
+        }
+        reader.finish(&mut on_text)
+    }
+
+    /// The request body `/api/answer` and `/api/answer/stream` share.
+    fn answer_request(
+        &self,
+        query: &str,
+        history: &[(String, String)],
+        context_size: Option<ContextSize>,
+    ) -> QueryRequest {
+        let (context_size, top_k) = ContextSize::wire(context_size);
+        QueryRequest {
             vault_id: self.vault_id.clone(),
             query: query.to_string(),
-            context_size: context_size.map(|c| c.as_str().to_string()),
+            context_size,
+            top_k,
             history: history
                 .iter()
                 .map(|(q, a)| HistoryTurn {
@@ -273,13 +338,7 @@ impl RagClient {
                     answer: a.clone(),
                 })
                 .collect(),
-        };
-        let resp = self
-            .auth(self.http.post(self.url("/api/answer")).json(&body))
-            .send()
-            .await?;
-        let job_id = Self::ok(resp).await?.json::<JobAccepted>().await?.job_id;
-        self.poll_answer(&job_id).await
+        }
     }
 
     /// Poll iterations before giving up on an answer job, at ~1s each: ~12
@@ -338,10 +397,105 @@ impl RagClient {
     }
 }
 
+/// Reassembles a streamed answer from the body's bytes, which arrive cut
+/// anywhere: complete lines are parsed as [`AnswerEvent`]s, the rest waits
+/// for the next chunk.
+#[derive(Default)]
+struct AnswerStreamReader {
+    pending: Vec<u8>,
+    answer: Option<AnswerResult>,
+}
+
+impl AnswerStreamReader {
+    fn feed(&mut self, bytes: &[u8], on_text: &mut impl FnMut(&str)) -> Result<(), RagError> {
+        self.pending.extend_from_slice(bytes);
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            self.line(&line, on_text)?;
+        }
+        Ok(())
+    }
+
+    /// The answer once the body has ended.
+    fn finish(mut self, on_text: &mut impl FnMut(&str)) -> Result<AnswerResult, RagError> {
+        let rest = std::mem::take(&mut self.pending);
+        self.line(&rest, on_text)?;
+        self.answer
+            .ok_or_else(|| RagError::Protocol("answer stream ended before it started".into()))
+    }
+
+    fn line(&mut self, line: &[u8], on_text: &mut impl FnMut(&str)) -> Result<(), RagError> {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return Ok(());
+        }
+        let event = serde_json::from_slice::<AnswerEvent>(line)
+            .map_err(|e| RagError::Protocol(format!("bad answer stream line: {e}")))?;
+        match event {
+            AnswerEvent::Start { sources, model } if self.answer.is_none() => {
+                self.answer = Some(AnswerResult {
+                    answer: String::new(),
+                    sources,
+                    model,
+                });
+            }
+            AnswerEvent::Text { text } => {
+                let answer = self
+                    .answer
+                    .as_mut()
+                    .ok_or_else(|| RagError::Protocol("answer text before its sources".into()))?;
+                on_text(&text);
+                answer.answer.push_str(&text);
+            }
+            AnswerEvent::Error { error } => return Err(RagError::Protocol(error)),
+            AnswerEvent::Start { .. } => {
+                return Err(RagError::Protocol("answer stream started twice".into()));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn read_stream(chunks: &[&str]) -> (Result<AnswerResult, RagError>, Vec<String>) {
+        let mut pieces = vec![];
+        let mut on_text = |text: &str| pieces.push(text.to_string());
+        let mut reader = AnswerStreamReader::default();
+        let result = chunks
+            .iter()
+            .try_for_each(|chunk| reader.feed(chunk.as_bytes(), &mut on_text))
+            .and_then(|()| reader.finish(&mut on_text));
+        (result, pieces)
+    }
+
+    #[test]
+    fn answer_stream_reassembles_lines_cut_across_chunks() {
+        let (answer, pieces) = read_stream(&[
+            "{\"sources\":[],\"model\":\"m\"}\n{\"te",
+            "xt\":\"Hello\"}\n",
+            "{\"text\":\", world\"}",
+        ]);
+        let answer = answer.unwrap();
+        assert_eq!(answer.answer, "Hello, world");
+        assert_eq!(answer.model.as_deref(), Some("m"));
+        assert_eq!(pieces, ["Hello", ", world"]);
+    }
+
+    #[test]
+    fn answer_stream_surfaces_errors_and_truncation() {
+        let (answer, pieces) =
+            read_stream(&["{\"sources\":[]}\n{\"text\":\"Hal\"}\n{\"error\":\"quota\"}\n"]);
+        assert!(matches!(answer, Err(RagError::Protocol(e)) if e == "quota"));
+        assert_eq!(pieces, ["Hal"]);
+
+        let (answer, _) = read_stream(&[""]);
+        assert!(matches!(answer, Err(RagError::Protocol(_))));
+        let (answer, _) = read_stream(&["{\"text\":\"orphan\"}\n"]);
+        assert!(matches!(answer, Err(RagError::Protocol(_))));
+    }
+
     #[test]
     fn is_auth_matches_only_credential_rejections() {
         let status = |status| RagError::Status {
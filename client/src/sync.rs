@@ -65,6 +65,9 @@ pub struct RagSync {
     /// *only* ours (by identity) and never a newer one that replaced it.
     observer: Arc<dyn IndexObserver>,
     client: RagClient,
+    /// Directories whose notes are kept off the server (see
+    /// [`exclude`](Self::exclude)).
+    exclude: Vec<VaultPath>,
 }
 
 impl RagSync {
@@ -77,14 +80,27 @@ impl RagSync {
         let dirty = Arc::new(DirtySet::default());
         let observer: Arc<dyn IndexObserver> = Arc::new(RagObserver::new(dirty.clone()));
         vault.set_index_observer(observer.clone());
+        let exclude = vec![vault.journal_path().clone()];
         Self {
             vault,
             dirty,
             observer,
             client,
+            exclude,
         }
     }
 
+    /// Keeps the notes under `dirs` (at any depth) off the server, replacing
+    /// the default — the vault's journal directory, whose daily entries are
+    /// mostly low-value retrieval noise. Pass the journal again to keep it
+    /// excluded alongside others; pass `&[]` to sync everything. Notes already
+    /// on the server that fall under an excluded directory are deleted there by
+    /// the next reconcile.
+    pub fn exclude(mut self, dirs: &[VaultPath]) -> Self {
+        self.exclude = dirs.to_vec();
+        self
+    }
+
     /// Probe reachability, capability, and auth in one `/health` request:
     /// `None` = offline; otherwise a [`ServerProbe`] carrying the server's
     /// [`ServerCapability`] — `Unconfigured` (no embedder: don't sync),
@@ -108,8 +124,8 @@ impl RagSync {
     /// Returns `false` when either half was skipped because the local index
     /// is not ready yet — call again once it is.
     pub async fn tick(&self) -> Result<bool, RagError> {
-        let drained = drain(&self.vault, &self.dirty, &self.client).await?;
-        let reconciled = reconcile(&self.vault, &self.client).await?;
+        let drained = drain(&self.vault, &self.dirty, &self.client, &self.exclude).await?;
+        let reconciled = reconcile(&self.vault, &self.client, &self.exclude).await?;
         Ok(drained && reconciled)
     }
 
@@ -118,14 +134,14 @@ impl RagSync {
     /// occasionally as the safety net. Returns `false` when skipped because
     /// the local index is not ready.
     pub async fn drain(&self) -> Result<bool, RagError> {
-        drain(&self.vault, &self.dirty, &self.client).await
+        drain(&self.vault, &self.dirty, &self.client, &self.exclude).await
     }
 
     /// Full hash-diff reconciliation only — an index-wide read + a full-collection
     /// hash fetch. The periodic backbone; not needed on every tick. Returns
     /// `false` when skipped because the local index is not ready.
     pub async fn reconcile(&self) -> Result<bool, RagError> {
-        reconcile(&self.vault, &self.client).await
+        reconcile(&self.vault, &self.client, &self.exclude).await
     }
 
     /// The underlying client, for queries (search / ask).
@@ -172,8 +188,19 @@ pub async fn build_doc(
     }))
 }
 
+/// Whether `path` lies under one of the `exclude` directories. A root entry
+/// is ignored — a journal configured at the vault root must not exclude every
+/// note.
+fn is_excluded(path: &VaultPath, exclude: &[VaultPath]) -> bool {
+    exclude
+        .iter()
+        .any(|dir| !dir.is_root_or_empty() && path.is_in(dir))
+}
+
 /// Flushes the dirty-set to the server. Failed operations are re-queued so the
-/// next drain (or a reconcile) retries them.
+/// next drain (or a reconcile) retries them. Upserts of notes under `exclude`
+/// become deletes, so a note moved into an excluded directory leaves the
+/// server.
 ///
 /// Returns `false` (doing nothing) when the local index is not ready: an
 /// unready (healed/rebuilding) index reads as empty, so build_doc would find
@@ -183,6 +210,7 @@ pub async fn drain<T: RagTransport>(
     vault: &NoteVault,
     dirty: &DirtySet,
     transport: &T,
+    exclude: &[VaultPath],
 ) -> Result<bool, RagError> {
     if !vault.index_ready() {
         return Ok(false);
@@ -196,6 +224,7 @@ pub async fn drain<T: RagTransport>(
     let mut deletes: Vec<String> = Vec::new();
     for (path, op) in ops {
         match op {
+            DirtyOp::Upsert(_) if is_excluded(&path, exclude) => deletes.push(path.to_string()),
             DirtyOp::Upsert(hash) => upserts.push((path, hash)),
             DirtyOp::Delete => deletes.push(path.to_string()),
         }
@@ -246,9 +275,13 @@ pub async fn drain<T: RagTransport>(
 /// healed/rebuilding index reads as an empty vault, and diffing against that
 /// snapshot would put every server doc in `to_delete` — wiping the collection.
 /// Callers should retry once the index is filled.
+///
+/// Notes under `exclude` count as absent locally, so the diff deletes any copy
+/// the server still holds.
 pub async fn reconcile<T: RagTransport>(
     vault: &NoteVault,
     transport: &T,
+    exclude: &[VaultPath],
 ) -> Result<bool, RagError> {
     if !vault.index_ready() {
         return Ok(false);
@@ -260,6 +293,7 @@ pub async fn reconcile<T: RagTransport>(
 
     let local_hashes: HashMap<String, u64> = notes
         .into_iter()
+        .filter(|(entry, _)| !is_excluded(&entry.path, exclude))
         .map(|(entry, content)| (entry.path.to_string(), content.hash))
         .collect();
     let local_str: HashMap<String, String> = local_hashes
@@ -390,7 +424,7 @@ mod tests {
             .create_note(&VaultPath::new("a.md"), "# Title\n\nbody")
             .await
             .unwrap();
-        drain(&vault, &dirty, &transport).await.unwrap();
+        drain(&vault, &dirty, &transport, &[]).await.unwrap();
 
         // Block-scoped: clippy's await_holding_lock tracks lexical scope, so an
         // explicit drop() before the awaits below wouldn't silence it.
@@ -403,11 +437,45 @@ mod tests {
         }
 
         vault.delete_note(&VaultPath::new("a.md")).await.unwrap();
-        drain(&vault, &dirty, &transport).await.unwrap();
+        drain(&vault, &dirty, &transport, &[]).await.unwrap();
+        assert_eq!(
+            *transport.deleted.lock().unwrap(),
+            vec!["/a.md".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn drain_deletes_a_renamed_notes_old_path() {
+        let dir = TempDir::new().unwrap();
+        let vault = vault(dir.path()).await;
+        let dirty = register(&vault);
+        let transport = FakeTransport::default();
+
+        vault
+            .create_note(&VaultPath::new("a.md"), "# Title\n\nbody")
+            .await
+            .unwrap();
+        drain(&vault, &dirty, &transport, &[]).await.unwrap();
+        transport.pushed.lock().unwrap().clear();
+
+        vault
+            .rename_note(
+                &VaultPath::new("a.md"),
+                &VaultPath::new("b.md"),
+                kimun_core::ConflictPolicy::Error,
+            )
+            .await
+            .unwrap();
+        drain(&vault, &dirty, &transport, &[]).await.unwrap();
+
+        // The old path's vectors go, the new path is indexed.
         assert_eq!(
             *transport.deleted.lock().unwrap(),
             vec!["/a.md".to_string()]
         );
+        let pushed = transport.pushed.lock().unwrap();
+        assert_eq!(pushed.len(), 1);
+        assert_eq!(pushed[0].path, "/b.md");
     }
 
     #[tokio::test]
@@ -422,7 +490,7 @@ mod tests {
             .create_note(&VaultPath::new("a.md"), "body")
             .await
             .unwrap();
-        assert!(drain(&vault, &dirty, &transport).await.is_err());
+        assert!(drain(&vault, &dirty, &transport, &[]).await.is_err());
         // The op survived for a later retry.
         assert_eq!(dirty.len(), 1);
     }
@@ -445,7 +513,7 @@ mod tests {
             .unwrap()
             .insert("/gone.md".to_string(), "oldhash".to_string());
 
-        assert!(reconcile(&vault, &transport).await.unwrap());
+        assert!(reconcile(&vault, &transport, &[]).await.unwrap());
 
         let pushed = transport.pushed.lock().unwrap();
         assert!(pushed.iter().any(|d| d.path == "/keep.md"));
@@ -455,6 +523,67 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn excluded_directories_stay_off_the_server() {
+        let dir = TempDir::new().unwrap();
+        let vault = vault(dir.path()).await;
+        let dirty = register(&vault);
+        let transport = FakeTransport::default();
+        let journal = vault.journal_path().clone();
+
+        vault
+            .create_note(&VaultPath::new("project.md"), "# Project\n\nplan")
+            .await
+            .unwrap();
+        vault
+            .create_note(
+                &journal.append(&VaultPath::note_path_from("standup")),
+                "# Standup\n\nmentioned the project",
+            )
+            .await
+            .unwrap();
+        // A journal note synced before the exclusion existed.
+        transport
+            .server
+            .lock()
+            .unwrap()
+            .insert(format!("{journal}/old.md"), "oldhash".to_string());
+
+        let exclude = [journal.clone()];
+        drain(&vault, &dirty, &transport, &exclude).await.unwrap();
+        assert!(reconcile(&vault, &transport, &exclude).await.unwrap());
+
+        let pushed: Vec<String> = transport
+            .pushed
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|d| d.path.clone())
+            .collect();
+        assert!(pushed.contains(&"/project.md".to_string()));
+        assert!(
+            !pushed.iter().any(|p| VaultPath::new(p).is_in(&journal)),
+            "journal notes must not be pushed: {pushed:?}"
+        );
+        assert!(
+            transport
+                .deleted
+                .lock()
+                .unwrap()
+                .contains(&format!("{journal}/old.md")),
+            "a previously synced journal note is removed"
+        );
+    }
+
+    #[test]
+    fn root_exclusion_is_ignored() {
+        assert!(!is_excluded(&VaultPath::new("/a.md"), &[VaultPath::root()]));
+        assert!(is_excluded(
+            &VaultPath::new("/journal/a.md"),
+            &[VaultPath::new("/journal")]
+        ));
+    }
+
     #[tokio::test]
     async fn reconcile_skipped_while_index_not_ready() {
         let dir = TempDir::new().unwrap();
@@ -470,7 +599,7 @@ mod tests {
             .unwrap()
             .insert("/precious.md".to_string(), "hash".to_string());
 
-        assert!(!reconcile(&vault, &transport).await.unwrap());
+        assert!(!reconcile(&vault, &transport, &[]).await.unwrap());
         assert!(transport.deleted.lock().unwrap().is_empty());
         assert!(transport.pushed.lock().unwrap().is_empty());
 
@@ -482,7 +611,7 @@ mod tests {
             path: VaultPath::new("precious.md"),
             hash: 1,
         });
-        assert!(!drain(&vault, &dirty, &transport).await.unwrap());
+        assert!(!drain(&vault, &dirty, &transport, &[]).await.unwrap());
         assert_eq!(dirty.len(), 1);
         assert!(transport.deleted.lock().unwrap().is_empty());
     }
@@ -24,8 +24,10 @@ pub enum VaultError {
         path: VaultPath,
     },
     /// An index/database operation failed; see the wrapped [`DBError`].
+    /// A lock held by another connection surfaces as [`VaultError::Busy`]
+    /// instead.
     #[error("DB Error: {0}")]
-    DBError(#[from] DBError),
+    DBError(#[source] DBError),
     /// A filesystem operation failed; see the wrapped [`FSError`].
     #[error("File System Error: {0}")]
     FSError(#[from] FSError),
@@ -63,6 +65,13 @@ pub enum VaultError {
         /// The compiler's explanation of the failure.
         message: String,
     },
+    /// A label name is not something the indexer would recognize as a
+    /// `#label`.
+    #[error("Invalid label '{label}': use only letters, digits and underscores")]
+    InvalidLabel {
+        /// The rejected label, as given.
+        label: String,
+    },
     /// A vault scan found paths that collide once compared case-insensitively.
     #[error("Case-sensitivity conflicts detected in vault:\n{}", conflicts.join("\n"))]
     CaseConflict {
@@ -72,11 +81,29 @@ pub enum VaultError {
     /// A spawned background task panicked or was cancelled before completing.
     #[error("Background task failed: {0}")]
     TaskJoin(String),
+    /// The operation was cancelled by its caller before it completed.
+    #[error("Operation cancelled")]
+    Cancelled,
+    /// The index is locked by another connection (`SQLITE_BUSY` /
+    /// `SQLITE_LOCKED`) and stayed locked past the write retries. Nothing was
+    /// changed; the operation can be retried.
+    #[error("The vault index is busy, try again")]
+    Busy,
+}
+
+impl From<DBError> for VaultError {
+    fn from(e: DBError) -> Self {
+        if e.is_busy() {
+            VaultError::Busy
+        } else {
+            VaultError::DBError(e)
+        }
+    }
 }
 
 impl From<sqlx::Error> for VaultError {
     fn from(e: sqlx::Error) -> Self {
-        VaultError::DBError(DBError::from(e))
+        VaultError::from(DBError::from(e))
     }
 }
 
@@ -90,6 +117,13 @@ impl VaultError {
         }
     }
 
+    /// `true` when the operation failed on a transient condition and can be
+    /// retried as is ([`VaultError::Busy`]), rather than failing the same
+    /// way again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, VaultError::Busy)
+    }
+
     /// `true` when the failure is the caller's fault and actionable rather than
     /// an internal failure. Equivalent to `user_message().is_some()`.
     pub fn is_user_error(&self) -> bool {
@@ -98,8 +132,9 @@ impl VaultError {
 
     /// The message to show a human or an LLM when this error is the caller's
     /// fault — a missing or already-existing note/directory, an absent or
-    /// non-unique replacement target, an invalid regex or path — or `None` for
-    /// an internal failure (DB, raw I/O, decoding, a panicked task).
+    /// non-unique replacement target, an invalid regex or path — or something
+    /// it can act on (a cancelled operation, a busy index to retry), or `None`
+    /// for an internal failure (DB, raw I/O, decoding, a panicked task).
     ///
     /// The single source of truth for user-facing error wording: the CLI prints
     /// it (clean message, distinct exit code) and the MCP server returns it as a
@@ -130,12 +165,16 @@ impl VaultError {
             // These error Displays are already clear, single-path messages.
             VaultError::ReplaceTextNotFound { .. }
             | VaultError::ReplaceTextNotUnique { .. }
-            | VaultError::InvalidRegex { .. } => Some(self.to_string()),
+            | VaultError::InvalidRegex { .. }
+            | VaultError::InvalidLabel { .. }
+            | VaultError::Cancelled
+            | VaultError::Busy
+            | VaultError::FSError(FSError::ArchiveError(_)) => Some(self.to_string()),
             // Internal failures — no actionable user message.
             VaultError::DBError(_)
             | VaultError::CaseConflict { .. }
             | VaultError::TaskJoin(_)
-            | VaultError::FSError(FSError::ReadFileError(_))
+            | VaultError::FSError(FSError::ReadFileError { .. })
             | VaultError::FSError(FSError::EncodingError(_))
             | VaultError::FSError(FSError::SerializationError(_)) => None,
         }
@@ -149,9 +188,16 @@ impl VaultError {
 /// higher-level [`VaultError`] before they reach the public API.
 #[derive(Error, Debug)]
 pub enum FSError {
-    /// An underlying `std::io` operation (read, write, create, rename) failed.
-    #[error("IO Error: {0}")]
-    ReadFileError(#[from] std::io::Error),
+    /// An underlying `std::io` operation (read, write, create, rename) failed
+    /// on `path`.
+    #[error("IO Error at {}: {source}", path.display())]
+    ReadFileError {
+        /// OS path of the file or directory being operated on.
+        path: std::path::PathBuf,
+        /// The underlying I/O failure.
+        #[source]
+        source: std::io::Error,
+    },
     /// File contents could not be decoded as UTF-8.
     #[error("Decoding Error: {0}")]
     EncodingError(#[from] std::string::FromUtf8Error),
@@ -186,9 +232,21 @@ pub enum FSError {
     /// failed to (de)serialize.
     #[error("Serialization error: {0}")]
     SerializationError(String),
+    /// A vault archive could not be written or read: not a zip file, or a
+    /// corrupt or unsupported entry.
+    #[error("Archive error: {0}")]
+    ArchiveError(String),
 }
 
 impl FSError {
+    /// Wraps an I/O failure on `path`.
+    pub(crate) fn io<P: AsRef<std::path::Path>>(path: P, source: std::io::Error) -> Self {
+        FSError::ReadFileError {
+            path: path.as_ref().to_path_buf(),
+            source,
+        }
+    }
+
     /// Returns `true` if this error means the target path was not found.
     pub fn is_not_found(&self) -> bool {
         matches!(
@@ -226,6 +284,19 @@ pub enum DBError {
     PoolError(String),
 }
 
+impl DBError {
+    /// Whether this is SQLite reporting a lock held by another connection
+    /// (`SQLITE_BUSY` or `SQLITE_LOCKED`, including their extended codes).
+    pub(crate) fn is_busy(&self) -> bool {
+        let DBError::DBError(sqlx::Error::Database(db)) = self else {
+            return false;
+        };
+        db.code()
+            .and_then(|code| code.parse::<i32>().ok())
+            .is_some_and(|code| matches!(code & 0xff, 5 | 6))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,6 +350,38 @@ mod tests {
         assert!(!VaultError::DBError(DBError::DBConnectionClosed).is_user_error());
     }
 
+    #[tokio::test]
+    async fn sqlite_lock_contention_surfaces_as_busy() {
+        use sqlx::sqlite::SqliteConnectOptions;
+        use sqlx::ConnectOptions;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let options = SqliteConnectOptions::new()
+            .filename(tmp.path().join("busy.sqlite"))
+            .create_if_missing(true)
+            .busy_timeout(std::time::Duration::ZERO);
+        let mut holder = options.connect().await.unwrap();
+        let mut other = options.connect().await.unwrap();
+        sqlx::query("CREATE TABLE t (x)")
+            .execute(&mut holder)
+            .await
+            .unwrap();
+        sqlx::query("BEGIN IMMEDIATE")
+            .execute(&mut holder)
+            .await
+            .unwrap();
+
+        let err = sqlx::query("INSERT INTO t VALUES (1)")
+            .execute(&mut other)
+            .await
+            .unwrap_err();
+        let err = VaultError::from(err);
+        assert!(matches!(err, VaultError::Busy));
+        assert!(err.is_retryable());
+        assert!(!VaultError::DBError(DBError::DBConnectionClosed).is_retryable());
+        assert!(!VaultError::Cancelled.is_retryable());
+    }
+
     #[test]
     fn not_found_recognized_through_the_fs_layer() {
         assert!(VaultError::FSError(FSError::VaultPathNotFound {
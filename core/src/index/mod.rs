@@ -1,17 +1,21 @@
+mod search_index;
 pub(crate) mod search_terms;
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use chrono::{NaiveDate, NaiveTime};
 use log::{debug, error};
-use search_terms::{OrderBy, SearchTerms};
-use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
-use sqlx::{Row, Sqlite, Transaction};
+use search_terms::{DateField, OrderBy, Proximity, SearchTerms};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use sqlx::{ConnectOptions, Connection, Row, Sqlite, Transaction};
 
 use crate::note::{ContentChunk, LinkType, NoteContentData, NoteDetails};
+pub use search_index::SearchIndex;
+use search_index::SqliteSearchIndex;
 
 /// A note change reported by the [`NoteIndex`] the moment it is recorded, for
 /// consumers outside core (the RAG client). Thin by design — it carries a path,
@@ -42,6 +46,7 @@ fn row_to_note_entry(
     let size: i64 = row.try_get("size")?;
     let modified: i64 = row.try_get("modified")?;
     let hash: String = row.try_get("hash")?;
+    let excerpt: String = row.try_get("excerpt")?;
 
     let hash_val: u64 = hash.parse().unwrap_or_else(|e| {
         // A non-numeric hash means a corrupt row (or schema drift). Falling
@@ -62,7 +67,7 @@ fn row_to_note_entry(
         size: size as u64,
         modified_secs: modified as u64,
     };
-    let content = NoteContentData::new(title, hash_val);
+    let content = NoteContentData::new(title, hash_val).with_excerpt(excerpt);
     Ok((entry, content))
 }
 
@@ -70,11 +75,11 @@ use super::error::DBError;
 
 /// All columns after `path` for `SELECT … FROM notes` queries. Used to build
 /// qualified column lists without `.split_once` + `.unwrap()`.
-const NOTE_COLUMNS_REST: &str = "title, size, modified, hash, noteName";
+const NOTE_COLUMNS_REST: &str = "title, size, modified, hash, noteName, excerpt";
 
 /// Column list shared by every `SELECT … FROM notes` query that maps rows
 /// through `row_to_note_entry`. Order must match the `try_get` calls there.
-const NOTE_COLUMNS: &str = "path, title, size, modified, hash, noteName";
+const NOTE_COLUMNS: &str = "path, title, size, modified, hash, noteName, excerpt";
 
 /// Prefixes each comma-separated column name in `cols` with `prefix.`, useful
 /// for join queries that disambiguate which table a column comes from.
@@ -114,14 +119,80 @@ use super::{
 //       relative+absolute duplicates) that canonical reads no longer match.
 //       Bump forces a clean reindex so every row is rewritten canonical and
 //       stale duplicates are dropped.
-const VERSION: &str = "0.11";
+// 0.12: Added `created` column to `notes` (the note's modification time when
+//       it first entered the index; upserts leave it alone) for the
+//       `created:` search predicate. Bump forces a clean reindex so the
+//       column exists and is populated for existing vaults.
+// 0.13: Added `chunkStart`/`chunkEnd` columns to `notesContent` (each
+//       chunk's byte range in the note's raw text). Bump forces a clean
+//       reindex so the columns exist and are populated for existing vaults.
+// 0.14: `%%comments%%` are left out of indexed note text. Bump forces a
+//       clean reindex so existing rows drop them.
+// 0.15: Titles prefer a frontmatter `title` over the first body line. Bump
+//       forces a clean reindex so existing rows pick it up.
+// 0.16: Titles and breadcrumbs drop the backticks around inline code, and a
+//       heading wrapped over several lines is kept whole. Bump forces a clean
+//       reindex so existing rows pick both up.
+// 0.17: Link targets are percent-decoded before they are stored, so
+//       `[x](my%20note.md)` points at `my note.md`. Bump forces a clean
+//       reindex so existing links resolve.
+// 0.18: Added `excerpt` column to `notes` (the opening of the body as plain
+//       text, surfaced as `NoteContentData::excerpt` for list previews). Bump
+//       forces a clean reindex so the column exists and is populated.
+// 0.19: Added `body` column to `notes` (the raw note text as last indexed,
+//       the base `NoteVault::diff_note` diffs the file against). Bump forces
+//       a clean reindex so the column exists and is populated.
+// 0.20: Added `titleKey` column to `notes` (the title lowercased in Rust,
+//       which folds every script; SQLite's `LOWER()` only folds ASCII) for
+//       case-insensitive title matching. Bump forces a clean reindex so the
+//       column exists and is populated.
+pub(crate) const VERSION: &str = "0.20";
 pub(crate) const DB_FILE: &str = "kimun.sqlite";
 
+/// Reads the schema version and note count of the database at `db_path`
+/// without opening it as an index: the connection is read-only, nothing is
+/// created and the schema is never healed. Each value is `None` when it
+/// can't be read (not a SQLite file, foreign or partial schema).
+pub(crate) async fn inspect(db_path: &Path) -> (Option<String>, Option<usize>) {
+    let options = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .busy_timeout(BUSY_TIMEOUT);
+    let mut conn = match options.connect().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            debug!("Can't inspect index at {}: {e}", db_path.display());
+            return (None, None);
+        }
+    };
+    let version: Option<String> =
+        sqlx::query_scalar("SELECT value FROM appData WHERE name = 'version'")
+            .fetch_optional(&mut conn)
+            .await
+            .ok()
+            .flatten();
+    let count: Option<i64> = sqlx::query_scalar("SELECT COUNT(*) FROM notes")
+        .fetch_one(&mut conn)
+        .await
+        .ok();
+    conn.close().await.ok();
+    (version, count.map(|c| c.max(0) as usize))
+}
+
+/// How long a connection waits on another connection's lock before SQLite
+/// reports `SQLITE_BUSY`.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Extra attempts a write transaction gets after failing with `SQLITE_BUSY`.
+/// The busy timeout covers most contention; a retry covers what it can't —
+/// a deferred transaction upgrading to a write while another writer holds
+/// the lock fails at once rather than waiting.
+const BUSY_RETRIES: u32 = 3;
+const BUSY_RETRY_BACKOFF: Duration = Duration::from_millis(50);
+
 /// The diff a vault sync walk produces and `NoteIndex::apply` consumes in
 /// one atomic operation — the currency crossing the index's interface.
-/// The order of `to_add` and `to_modify` is non-deterministic: they are
-/// populated by parallel walker threads and entries land in the order each
-/// thread completes its file read.
+/// Each list is sorted by path, whatever order the walker threads found the
+/// notes in, so the same vault always yields the same diff.
 pub struct IndexDiff {
     /// Notes present in the vault but absent from the index, each paired with
     /// its full text content for FTS insertion.
@@ -142,6 +213,8 @@ pub struct IndexDiff {
 #[derive(Debug, Clone)]
 pub(crate) struct NoteIndex {
     pool: SqlitePool,
+    /// OS path of the SQLite database file backing this index.
+    db_path: Arc<Path>,
     /// `true` while the index is valid but possibly *empty*: set when
     /// [`open`](Self::open) recreated a missing/outdated/invalid schema
     /// (self-heal) or when [`recreate`](Self::recreate) dropped the
@@ -153,6 +226,20 @@ pub(crate) struct NoteIndex {
     /// pool) so every handle emits to the same consumer; `None` until a caller
     /// registers one, in which case emission is a no-op.
     observer: Arc<RwLock<Option<Arc<dyn IndexObserver>>>>,
+    /// The full-text engine a search's free-text words go to: SQLite's own
+    /// FTS tables ([`SqliteSearchIndex`]) unless another was plugged in
+    /// with [`with_search_index`](Self::with_search_index).
+    search_index: Arc<dyn SearchIndex>,
+    /// Whether writes are mirrored into `search_index` — `false` for the
+    /// built-in engine, whose tables the index writes itself.
+    mirrored: bool,
+    /// Set when a mirror call failed, so `search_index` has missed a write;
+    /// the next search rebuilds and refills it first. Shared across clones.
+    search_stale: Arc<AtomicBool>,
+    /// When `true`, a note's `created` is its frontmatter date when it has
+    /// one (see [`NoteDetails::frontmatter_date_of`]) instead of the file
+    /// time it was first indexed with.
+    frontmatter_dates: bool,
 }
 
 impl NoteIndex {
@@ -166,12 +253,18 @@ impl NoteIndex {
         if let Some(parent) = db_path.parent() {
             crate::nfs::ensure_dir(parent).map_err(|e| DBError::Other(e.to_string()))?;
         }
-        let connection_string = format!("sqlite:{}?mode=rwc", db_path.display());
+        // WAL lets the sync walk read while the editor's save writes; the
+        // busy timeout makes writers queue on each other instead of failing.
+        let options = SqliteConnectOptions::new()
+            .filename(&db_path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(BUSY_TIMEOUT);
 
         let pool = SqlitePoolOptions::new()
             .max_connections(5)
             .acquire_timeout(Duration::from_secs(30))
-            .connect(&connection_string)
+            .connect_with(options)
             .await?;
 
         // Only a *readable* schema that is missing or stale heals (the
@@ -189,28 +282,168 @@ impl NoteIndex {
         };
 
         Ok(Self {
+            search_index: Arc::new(SqliteSearchIndex::new(pool.clone())),
             pool,
+            db_path: Arc::from(db_path.as_path()),
             healed: Arc::new(AtomicBool::new(healed)),
             observer: Arc::new(RwLock::new(None)),
+            mirrored: false,
+            search_stale: Arc::new(AtomicBool::new(false)),
+            frontmatter_dates: false,
         })
     }
 
+    /// Takes `created` from each note's frontmatter date when it has one, for
+    /// notes written from here on (see the `frontmatter_dates` field).
+    pub(crate) fn with_frontmatter_dates(mut self, frontmatter_dates: bool) -> Self {
+        self.frontmatter_dates = frontmatter_dates;
+        self
+    }
+
+    /// Hands searches' free text to `search_index` and mirrors every write
+    /// into it from here on. The engine is rebuilt and refilled from the
+    /// notes already indexed, so attaching it to an existing vault needs no
+    /// re-save.
+    pub(crate) async fn with_search_index(
+        mut self,
+        search_index: Arc<dyn SearchIndex>,
+    ) -> Result<Self, DBError> {
+        self.search_index = search_index;
+        self.mirrored = true;
+        self.refill_search_index().await?;
+        Ok(self)
+    }
+
+    /// Whether a write must report the paths it touches: an observer or a
+    /// mirrored search index is listening.
+    fn has_listeners(&self) -> bool {
+        self.has_observer() || self.mirrored
+    }
+
+    /// Rebuilds the mirrored search index and feeds it every note with the
+    /// chunks SQLite holds for it.
+    async fn refill_search_index(&self) -> Result<(), DBError> {
+        self.search_index.rebuild().await?;
+        let sections = get_notes_sections(&self.pool, &VaultPath::root(), true).await?;
+        for (path, chunks) in &sections {
+            self.search_index.index_note(path, chunks).await?;
+        }
+        Ok(())
+    }
+
+    /// Refills the mirrored search index when a mirror call has failed since
+    /// the last refill. A refill that fails leaves it marked.
+    async fn refresh_search_index(&self) -> Result<(), DBError> {
+        if !self.search_stale.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        debug!("Search index missed a write — rebuilding it");
+        let refilled = self.refill_search_index().await;
+        if refilled.is_err() {
+            self.search_stale.store(true, Ordering::Relaxed);
+        }
+        refilled
+    }
+
+    /// Records a failed mirror call. The SQLite write it followed has
+    /// committed, so the failure isn't returned; the search index is marked
+    /// for a rebuild before the next search instead.
+    fn mirror_failed(&self, path: &VaultPath, e: DBError) {
+        error!("Search index missed the write to {path}, it will be rebuilt: {e}");
+        self.search_stale.store(true, Ordering::Relaxed);
+    }
+
+    /// Mirrors the notes at `paths` (canonical) into the search index, with
+    /// the chunks SQLite now holds for them. A no-op unless mirrored.
+    async fn mirror_stored(&self, paths: &[VaultPath]) {
+        if !self.mirrored {
+            return;
+        }
+        for path in paths {
+            let indexed = match get_notes_sections(&self.pool, path, false).await {
+                Ok(mut sections) => {
+                    let chunks = sections.remove(path).unwrap_or_default();
+                    self.search_index.index_note(path, &chunks).await
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = indexed {
+                self.mirror_failed(path, e);
+            }
+        }
+    }
+
+    /// Mirrors `chunks` as the note at `path` into the search index. A no-op
+    /// unless mirrored.
+    async fn mirror_chunks(&self, path: &VaultPath, chunks: &[ContentChunk]) {
+        if !self.mirrored {
+            return;
+        }
+        let path = path.canonical();
+        if let Err(e) = self.search_index.index_note(&path, chunks).await {
+            self.mirror_failed(&path, e);
+        }
+    }
+
+    /// Mirrors `text` as the note at `path` into the search index. A no-op
+    /// unless mirrored.
+    async fn mirror_text(&self, path: &VaultPath, text: &str) {
+        if self.mirrored {
+            self.mirror_chunks(path, &NoteDetails::content_chunks_of(text))
+                .await;
+        }
+    }
+
+    /// Removes the note at `path` from the search index. A no-op unless
+    /// mirrored.
+    async fn mirror_delete(&self, path: &VaultPath) {
+        if !self.mirrored {
+            return;
+        }
+        let path = path.canonical();
+        if let Err(e) = self.search_index.remove_note(&path).await {
+            self.mirror_failed(&path, e);
+        }
+    }
+
+    /// Every file the database occupies on disk: the database itself plus
+    /// SQLite's `-wal` and `-shm` sidecars. The sidecars only exist while the
+    /// database is in use, so callers must tolerate missing entries.
+    pub(crate) fn db_files(&self) -> Vec<PathBuf> {
+        let db = self.db_path.to_path_buf();
+        let sidecar = |suffix: &str| {
+            let mut name = db.as_os_str().to_owned();
+            name.push(suffix);
+            PathBuf::from(name)
+        };
+        vec![sidecar("-wal"), sidecar("-shm"), db.clone()]
+    }
+
     /// Registers the index observer, replacing any previous one. Shared across
     /// clones of this index.
     pub(crate) fn set_observer(&self, observer: Arc<dyn IndexObserver>) {
-        *self.observer.write().unwrap() = Some(observer);
+        *self
+            .observer
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(observer);
     }
 
     /// Removes the registered observer (if any), so it stops receiving events.
     pub(crate) fn clear_observer(&self) {
-        *self.observer.write().unwrap() = None;
+        *self
+            .observer
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = None;
     }
 
     /// Removes the observer only if it is the exact one passed in (by identity).
     /// Lets a consumer deregister its own observer on teardown without wiping a
     /// newer one that has since replaced it.
     pub(crate) fn clear_observer_if(&self, observer: &Arc<dyn IndexObserver>) {
-        let mut guard = self.observer.write().unwrap();
+        let mut guard = self
+            .observer
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
         if guard.as_ref().is_some_and(|cur| Arc::ptr_eq(cur, observer)) {
             *guard = None;
         }
@@ -219,14 +452,22 @@ impl NoteIndex {
     /// Whether an observer is registered. Lets callers skip building events
     /// (e.g. re-hashing every note in a bulk `apply`) when nobody listens.
     fn has_observer(&self) -> bool {
-        self.observer.read().unwrap().is_some()
+        self.observer
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
     }
 
     /// Hands `change` to the registered observer, if any. A no-op when none is
     /// registered. Paths are normalised to canonical vault-relative form so a
     /// note has one identity regardless of the write path that produced it.
     fn emit(&self, change: NoteChange) {
-        if let Some(observer) = self.observer.read().unwrap().as_ref() {
+        if let Some(observer) = self
+            .observer
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+        {
             observer.on_change(&change);
         }
     }
@@ -263,6 +504,32 @@ impl NoteIndex {
         self.healed.store(false, Ordering::Relaxed);
     }
 
+    /// Persists `at` as the time the last whole-vault sync completed.
+    pub(crate) async fn record_synced_at(&self, at: SystemTime) -> Result<(), DBError> {
+        let secs = at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            .to_string();
+        sqlx::query("INSERT OR REPLACE INTO appData (name, value) VALUES ('lastSynced', ?)")
+            .bind(secs)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// When the last whole-vault sync completed, `None` if none has since
+    /// the schema was (re)created.
+    pub(crate) async fn synced_at(&self) -> Result<Option<SystemTime>, DBError> {
+        let value: Option<String> =
+            sqlx::query_scalar("SELECT value FROM appData WHERE name = 'lastSynced'")
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(value
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs)))
+    }
+
     /// `true` when the stored schema version matches [`VERSION`].
     async fn schema_is_current(pool: &SqlitePool) -> Result<bool, DBError> {
         let version: Option<String> =
@@ -292,17 +559,51 @@ impl NoteIndex {
     pub(crate) async fn recreate(&self) -> Result<(), DBError> {
         init_db(&self.pool).await?;
         self.healed.store(true, Ordering::Relaxed);
+        if self.mirrored {
+            if let Err(e) = self.search_index.rebuild().await {
+                self.mirror_failed(&VaultPath::root(), e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Folds the write-ahead log back into the database and truncates it,
+    /// then rebuilds the database file to release the space freed by deleted
+    /// rows. Takes an exclusive lock for the duration, so concurrent writes
+    /// wait on (or, past the busy timeout, fail against) it.
+    pub(crate) async fn compact(&self) -> Result<(), DBError> {
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("VACUUM").execute(&self.pool).await?;
+        // VACUUM itself goes through the WAL; checkpoint again so the
+        // sidecar doesn't keep a full copy of the rebuilt database.
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.pool)
+            .await?;
         Ok(())
     }
 
     /// Applies a sync diff — adds, modifications, deletions — in one atomic
     /// operation.
     pub(crate) async fn apply(&self, diff: IndexDiff) -> Result<(), DBError> {
-        let mut tx = self.pool.begin().await?;
-        delete_notes(&mut tx, &diff.to_delete).await?;
-        insert_notes(&mut tx, &diff.to_add).await?;
-        update_notes(&mut tx, &diff.to_modify).await?;
-        tx.commit().await?;
+        let pool = &self.pool;
+        let diff_ref = &diff;
+        let frontmatter_dates = self.frontmatter_dates;
+        retry_busy(|| async move {
+            let mut tx = pool.begin().await?;
+            delete_notes(&mut tx, &diff_ref.to_delete).await?;
+            insert_notes(&mut tx, &diff_ref.to_add).await?;
+            update_notes(&mut tx, &diff_ref.to_modify).await?;
+            if frontmatter_dates {
+                for (entry, text) in diff_ref.to_add.iter().chain(&diff_ref.to_modify) {
+                    set_frontmatter_created(&mut tx, &entry.path, text).await?;
+                }
+            }
+            tx.commit().await?;
+            Ok(())
+        })
+        .await?;
         // Skip event construction (notably re-hashing every added/modified note)
         // when nothing is listening — the common case for non-RAG users.
         if self.has_observer() {
@@ -313,6 +614,12 @@ impl NoteIndex {
                 self.emit_upsert(&entry.path, NoteDetails::content_data_of(text).hash);
             }
         }
+        for path in &diff.to_delete {
+            self.mirror_delete(path).await;
+        }
+        for (entry, text) in diff.to_add.iter().chain(diff.to_modify.iter()) {
+            self.mirror_text(&entry.path, text).await;
+        }
         Ok(())
     }
 
@@ -324,24 +631,28 @@ impl NoteIndex {
         to: &VaultPath,
         rewritten: &[(NoteEntryData, String)],
     ) -> Result<(), DBError> {
-        let from = from.canonical();
-        let to = to.canonical();
-        let mut tx = self.pool.begin().await?;
-        // Capture the moving note's hash before the rows change, so observers
-        // can be told about the note under its new path (a rename leaves the
-        // content — and therefore the hash — untouched).
-        let moved_hash = if self.has_observer() {
-            note_hash(&mut tx, &from).await?
-        } else {
-            None
-        };
-        rename_note(&mut tx, &from, &to).await?;
-        update_notes(&mut tx, rewritten).await?;
-        tx.commit().await?;
+        let from = &from.canonical();
+        let to = &to.canonical();
+        let moved_hash = retry_busy(|| async move {
+            let mut tx = self.pool.begin().await?;
+            // Capture the moving note's hash before the rows change, so
+            // observers can be told about the note under its new path (a
+            // rename leaves the content — and therefore the hash — untouched).
+            let moved_hash = if self.has_observer() {
+                note_hash(&mut *tx, from).await?
+            } else {
+                None
+            };
+            rename_note(&mut tx, from, to).await?;
+            update_notes(&mut tx, rewritten).await?;
+            tx.commit().await?;
+            Ok(moved_hash)
+        })
+        .await?;
         if self.has_observer() {
             if let Some(hash) = moved_hash {
-                self.emit_delete(&from);
-                self.emit_upsert(&to, hash);
+                self.emit_delete(from);
+                self.emit_upsert(to, hash);
             }
             // The backlink victims' content changed: their links were
             // rewritten to the new name.
@@ -349,6 +660,11 @@ impl NoteIndex {
                 self.emit_upsert(&entry.path, NoteDetails::content_data_of(text).hash);
             }
         }
+        self.mirror_delete(from).await;
+        self.mirror_stored(std::slice::from_ref(to)).await;
+        for (entry, text) in rewritten {
+            self.mirror_text(&entry.path, text).await;
+        }
         Ok(())
     }
 
@@ -357,37 +673,52 @@ impl NoteIndex {
         from: &VaultPath,
         to: &VaultPath,
     ) -> Result<(), DBError> {
-        let from = from.canonical();
-        let to = to.canonical();
-        let mut tx = self.pool.begin().await?;
-        // Capture the affected notes before the prefix rewrite, so observers
-        // learn both sides of every move.
-        let moved = if self.has_observer() {
-            notes_under(&mut tx, &from).await?
-        } else {
-            Vec::new()
-        };
-        rename_directory(&mut tx, &from, &to).await?;
-        tx.commit().await?;
-        let from_prefix = dir_prefix(&from);
-        let to_prefix = dir_prefix(&to);
+        let from = &from.canonical();
+        let to = &to.canonical();
+        let moved = retry_busy(|| async move {
+            let mut tx = self.pool.begin().await?;
+            // Capture the affected notes before the prefix rewrite, so
+            // observers learn both sides of every move.
+            let moved = if self.has_listeners() {
+                notes_under(&mut tx, from).await?
+            } else {
+                Vec::new()
+            };
+            rename_directory(&mut tx, from, to).await?;
+            tx.commit().await?;
+            Ok(moved)
+        })
+        .await?;
+        let from_prefix = dir_prefix(from);
+        let to_prefix = dir_prefix(to);
+        let mut moved_to = Vec::with_capacity(moved.len());
         for (path, hash) in moved {
             self.emit_delete(&path);
+            self.mirror_delete(&path).await;
             // Mirror the SQL prefix rewrite to obtain the post-rename path.
             if let Some(rest) = path.to_string().strip_prefix(&from_prefix) {
-                self.emit_upsert(&VaultPath::new(format!("{to_prefix}{rest}")), hash);
+                let to = VaultPath::new(format!("{to_prefix}{rest}"));
+                self.emit_upsert(&to, hash);
+                moved_to.push(to);
             }
         }
+        self.mirror_stored(&moved_to).await;
         Ok(())
     }
 
     pub(crate) async fn delete_notes(&self, paths: &[VaultPath]) -> Result<(), DBError> {
         let canonical: Vec<VaultPath> = paths.iter().map(|p| p.canonical()).collect();
-        let mut tx = self.pool.begin().await?;
-        delete_notes(&mut tx, &canonical).await?;
-        tx.commit().await?;
+        let canonical_ref = &canonical;
+        retry_busy(|| async move {
+            let mut tx = self.pool.begin().await?;
+            delete_notes(&mut tx, canonical_ref).await?;
+            tx.commit().await?;
+            Ok(())
+        })
+        .await?;
         for path in &canonical {
             self.emit_delete(path);
+            self.mirror_delete(path).await;
         }
         Ok(())
     }
@@ -397,19 +728,25 @@ impl NoteIndex {
         directories: &[VaultPath],
     ) -> Result<(), DBError> {
         let canonical: Vec<VaultPath> = directories.iter().map(|p| p.canonical()).collect();
-        let mut tx = self.pool.begin().await?;
-        // Capture the contained notes before the rows go, so observers get a
-        // Delete per note — same contract as delete_notes above.
-        let mut removed = Vec::new();
-        if self.has_observer() {
-            for directory in &canonical {
-                removed.extend(notes_under(&mut tx, directory).await?);
+        let canonical_ref = &canonical;
+        let removed = retry_busy(|| async move {
+            let mut tx = self.pool.begin().await?;
+            // Capture the contained notes before the rows go, so observers
+            // get a Delete per note — same contract as delete_notes above.
+            let mut removed = Vec::new();
+            if self.has_listeners() {
+                for directory in canonical_ref {
+                    removed.extend(notes_under(&mut tx, directory).await?);
+                }
             }
-        }
-        delete_directories(&mut tx, &canonical).await?;
-        tx.commit().await?;
+            delete_directories(&mut tx, canonical_ref).await?;
+            tx.commit().await?;
+            Ok(removed)
+        })
+        .await?;
         for (path, _) in removed {
             self.emit_delete(&path);
+            self.mirror_delete(&path).await;
         }
         Ok(())
     }
@@ -421,16 +758,92 @@ impl NoteIndex {
         entry_data: &NoteEntryData,
         note_details: &NoteDetails,
     ) -> Result<NoteContentData, DBError> {
-        let data = save_note(&self.pool, entry_data, note_details).await?;
+        let data =
+            retry_busy(|| save_note(&self.pool, entry_data, note_details, self.frontmatter_dates))
+                .await?;
         self.emit_upsert(&entry_data.path, data.hash);
+        if self.mirrored {
+            self.mirror_chunks(&entry_data.path, &note_details.get_content_chunks())
+                .await;
+        }
         Ok(data)
     }
 
+    /// The indexed content hash of the note at `path`, or `None` when the
+    /// note isn't indexed.
+    pub(crate) async fn note_hash(&self, path: &VaultPath) -> Result<Option<u64>, DBError> {
+        note_hash(&self.pool, &path.canonical()).await
+    }
+
+    /// The raw text of the note at `path` as it was last indexed (empty for
+    /// an oversized note), or `None` when the note isn't indexed.
+    pub(crate) async fn note_body(&self, path: &VaultPath) -> Result<Option<String>, DBError> {
+        let body: Option<Option<String>> =
+            sqlx::query_scalar("SELECT body FROM notes WHERE path = ?")
+                .bind(path.canonical().to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(body.map(Option::unwrap_or_default))
+    }
+
+    /// Hands the free-text words of `search_terms` to a plugged-in search
+    /// index, taking them out of the terms so only the SQL-side clauses
+    /// remain. `None` when the query has no free text to match, or when the
+    /// engine is the built-in one: its FTS tables are then matched within
+    /// the one query that applies the rest, rather than listing every match
+    /// first.
+    async fn search_index_hits(
+        &self,
+        search_terms: &mut SearchTerms,
+    ) -> Result<Option<Vec<VaultPath>>, DBError> {
+        if !self.mirrored || search_terms.terms.is_empty() {
+            return Ok(None);
+        }
+        self.refresh_search_index().await?;
+        let terms = std::mem::take(&mut search_terms.terms);
+        Ok(Some(self.search_index.search(&terms).await?))
+    }
+
+    /// The notes matching `search_query`. Its free-text words go to a
+    /// plugged-in search index, whose matches the rest of the query filters
+    /// in SQL (see [`search_hits`]); otherwise one SQL query does it all.
     pub(crate) async fn search<S: AsRef<str>>(
         &self,
         search_query: S,
     ) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
-        search_terms(&self.pool, search_query).await
+        let mut search_terms = SearchTerms::from_query_string(search_query);
+        match self.search_index_hits(&mut search_terms).await? {
+            Some(hits) => search_hits(&self.pool, &search_terms, &hits).await,
+            None => search_terms_inner(&self.pool, &search_terms).await,
+        }
+    }
+
+    /// At most `limit` notes of [`Self::search`]'s matches, after skipping
+    /// the first `offset`, in a stable order (see [`search_terms_page`] and
+    /// [`search_hits_page`]).
+    pub(crate) async fn search_page<S: AsRef<str>>(
+        &self,
+        search_query: S,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
+        let mut search_terms = SearchTerms::from_query_string(search_query);
+        match self.search_index_hits(&mut search_terms).await? {
+            Some(hits) => search_hits_page(&self.pool, &search_terms, &hits, limit, offset).await,
+            None => search_terms_page(&self.pool, &search_terms, limit, offset).await,
+        }
+    }
+
+    /// How many notes [`Self::search`] would return, without loading them.
+    pub(crate) async fn count_search<S: AsRef<str>>(
+        &self,
+        search_query: S,
+    ) -> Result<usize, DBError> {
+        let mut search_terms = SearchTerms::from_query_string(search_query);
+        match self.search_index_hits(&mut search_terms).await? {
+            Some(hits) => count_search_hits(&self.pool, &search_terms, &hits).await,
+            None => count_search_terms(&self.pool, &search_terms).await,
+        }
     }
 
     pub(crate) async fn search_note_by_name<S: AsRef<str>>(
@@ -488,6 +901,83 @@ impl NoteIndex {
         notes_with_label(&self.pool, name).await
     }
 
+    pub(crate) async fn metadata_for(&self, paths: &[VaultPath]) -> Result<Vec<NoteMeta>, DBError> {
+        let paths: Vec<VaultPath> = paths.iter().map(VaultPath::canonical).collect();
+        metadata_for(&self.pool, &paths).await
+    }
+
+    pub(crate) async fn notes_larger_than(
+        &self,
+        bytes: u64,
+    ) -> Result<Vec<NoteEntryData>, DBError> {
+        notes_larger_than(&self.pool, bytes).await
+    }
+
+    pub(crate) async fn notes_count(&self) -> Result<usize, DBError> {
+        notes_count(&self.pool).await
+    }
+
+    pub(crate) async fn notes_count_in(
+        &self,
+        path: &VaultPath,
+        recursive: bool,
+    ) -> Result<usize, DBError> {
+        notes_count_in(&self.pool, &path.canonical(), recursive).await
+    }
+
+    pub(crate) async fn link_graph(
+        &self,
+    ) -> Result<(Vec<VaultPath>, Vec<(VaultPath, VaultPath)>), DBError> {
+        link_graph(&self.pool).await
+    }
+
+    pub(crate) async fn notes_size(&self) -> Result<u64, DBError> {
+        notes_size(&self.pool).await
+    }
+
+    pub(crate) async fn duplicate_titles(
+        &self,
+        case_insensitive: bool,
+        ignore_untitled: bool,
+    ) -> Result<Vec<(String, Vec<VaultPath>)>, DBError> {
+        duplicate_titles(&self.pool, case_insensitive, ignore_untitled).await
+    }
+
+    pub(crate) async fn duplicate_contents(
+        &self,
+        ignore_empty: bool,
+    ) -> Result<Vec<Vec<VaultPath>>, DBError> {
+        duplicate_contents(&self.pool, ignore_empty).await
+    }
+
+    pub(crate) async fn activity(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+    ) -> Result<BTreeMap<NaiveDate, usize>, DBError> {
+        activity(&self.pool, first, last).await
+    }
+
+    pub(crate) async fn recent_notes(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
+        recent_notes(&self.pool, limit).await
+    }
+
+    pub(crate) async fn random_note(
+        &self,
+        scope: Option<&VaultPath>,
+        exclude: Option<&VaultPath>,
+    ) -> Result<Option<VaultPath>, DBError> {
+        random_note(
+            &self.pool,
+            scope.map(VaultPath::canonical).as_ref(),
+            exclude.map(VaultPath::canonical).as_ref(),
+        )
+        .await
+    }
+
     pub(crate) async fn suggest_notes_by_prefix(
         &self,
         prefix: &str,
@@ -496,6 +986,14 @@ impl NoteIndex {
         suggest_notes_by_prefix(&self.pool, prefix, limit).await
     }
 
+    pub(crate) async fn suggest_links(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<NoteSuggestion>, DBError> {
+        suggest_links(&self.pool, query, limit).await
+    }
+
     pub(crate) async fn suggest_tags_by_prefix(
         &self,
         prefix: &str,
@@ -503,13 +1001,18 @@ impl NoteIndex {
     ) -> Result<Vec<TagSuggestion>, DBError> {
         suggest_tags_by_prefix(&self.pool, prefix, limit).await
     }
+
+    pub(crate) async fn suggest_query(&self, query: &str) -> Result<Vec<String>, DBError> {
+        suggest_query(&self.pool, query).await
+    }
 }
 
 #[cfg(test)]
 impl NoteIndex {
     /// Test-only pool accessor — index-internal tests exercise SQL and the
-    /// query builders directly through this internal seam.
-    fn pool(&self) -> &SqlitePool {
+    /// query builders directly through this internal seam, and vault tests
+    /// check that clones share the pool.
+    pub(crate) fn pool(&self) -> &SqlitePool {
         &self.pool
     }
 
@@ -519,6 +1022,29 @@ impl NoteIndex {
     }
 }
 
+/// Runs a write transaction, re-running it from the start (up to
+/// [`BUSY_RETRIES`] times, with a growing pause) while it fails because
+/// another connection holds the database lock. Any other error, and the
+/// last busy one, is returned as-is. `op` must open and commit its own
+/// transaction so a retry never sees half of a rolled-back attempt.
+async fn retry_busy<T, F, Fut>(mut op: F) -> Result<T, DBError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, DBError>>,
+{
+    let mut attempt = 0;
+    loop {
+        match op().await {
+            Err(e) if attempt < BUSY_RETRIES && e.is_busy() => {
+                attempt += 1;
+                debug!("Index is busy, retrying write ({attempt}/{BUSY_RETRIES})");
+                tokio::time::sleep(BUSY_RETRY_BACKOFF * attempt).await;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Deletes all tables and recreates them
 async fn init_db(pool: &SqlitePool) -> Result<(), DBError> {
     debug!("Deleting DB");
@@ -587,8 +1113,12 @@ async fn create_tables(pool: &SqlitePool) -> Result<(), DBError> {
             hash TEXT,
             size INTEGER,
             modified INTEGER,
+            created INTEGER,
             basePath TEXT,
-            noteName TEXT
+            noteName TEXT,
+            excerpt TEXT,
+            body TEXT,
+            titleKey TEXT
         )",
     )
     .execute(&mut *tx)
@@ -643,7 +1173,11 @@ async fn create_tables(pool: &SqlitePool) -> Result<(), DBError> {
         "CREATE VIRTUAL TABLE notesContent USING fts4(
             path,
             breadcrumb,
-            text
+            text,
+            chunkStart,
+            chunkEnd,
+            notindexed=chunkStart,
+            notindexed=chunkEnd
         )",
     )
     .execute(&mut *tx)
@@ -722,7 +1256,8 @@ fn build_like_conditions(
 /// Base query for the search fan-out. Aliases `notes.path` to `path` so the
 /// shared `row_to_note_entry` mapper finds all `NOTE_COLUMNS` keys. First
 /// column is qualified to disambiguate the `notesContent`/`notes` join; the
-/// rest are unique to `notes` and need no prefix.
+/// rest are unique to `notes` and need no prefix. The join yields a row per
+/// matching chunk; `DISTINCT` collapses them so each note is returned once.
 static SEARCH_BASE_SQL: std::sync::LazyLock<String> = std::sync::LazyLock::new(|| {
     format!(
         "SELECT DISTINCT notes.path as path, {} FROM notesContent JOIN notes ON notesContent.path = notes.path",
@@ -741,10 +1276,12 @@ fn build_search_sql_query_inner(search_terms: &SearchTerms) -> (String, Vec<Stri
 
     add_fts_query(search_terms, &mut var_num, &mut params, &mut queries);
     add_filename_query(search_terms, &mut var_num, &mut params, &mut queries);
+    add_title_query(search_terms, &mut var_num, &mut params, &mut queries);
     add_path_query(search_terms, &mut var_num, &mut params, &mut queries);
     add_labels_query(search_terms, &mut var_num, &mut params, &mut queries);
     add_links_query(search_terms, &mut var_num, &mut params, &mut queries);
     add_forward_links_query(search_terms, &mut var_num, &mut params, &mut queries);
+    add_dates_query(search_terms, &mut var_num, &mut params, &mut queries);
 
     if queries.is_empty() {
         debug!("No query provided");
@@ -759,27 +1296,41 @@ fn build_search_sql_query_inner(search_terms: &SearchTerms) -> (String, Vec<Stri
 /// (`breadcrumb:"x"`) is unreliable across builds, so the two cannot be folded
 /// into a single scan. Within each branch, the positive `MATCH` is ANDed with
 /// `NOT IN` subqueries for that field's exclusions (FTS4 has no reliable
-/// pure-negative / inline `-term`, so a subquery is used uniformly).
+/// pure-negative / inline `-term`, so a subquery is used uniformly) and, for
+/// content, one `IN` subquery per `a OR b` group and per `"a b"~n` proximity
+/// term.
 fn add_fts_query(
     s: &SearchTerms,
     var_num: &mut usize,
     params: &mut Vec<String>,
     queries: &mut Vec<String>,
 ) {
+    let required: Vec<String> = s
+        .any_terms
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .map(|t| fts4_quote(t))
+                .collect::<Vec<_>>()
+                .join(" OR ")
+        })
+        .chain(s.proximity.iter().map(fts4_near))
+        .collect();
     add_fts_field_query(
         &s.terms,
+        &required,
         &s.excluded_terms,
         "notesContent",
-        fts4_quote,
         var_num,
         params,
         queries,
     );
     add_fts_field_query(
         &s.breadcrumb,
+        &[],
         &s.excluded_breadcrumb,
         "notesContent.breadcrumb",
-        fts4_quote,
         var_num,
         params,
         queries,
@@ -788,18 +1339,21 @@ fn add_fts_query(
 
 /// Emits one FTS branch for a single field (`notesContent` for content,
 /// `notesContent.breadcrumb` for headings): a positive `MATCH` (all positive
-/// terms space-joined into one query) ANDed with one `NOT IN` subquery per
-/// excluded term. Pure-exclusion (no positives) drops the leading `MATCH`.
+/// terms space-joined into one query) ANDed with one `IN` subquery per
+/// `required` FTS expression (OR groups, proximity terms) and one `NOT IN`
+/// subquery per excluded term. A required expression gets its own subquery
+/// because FTS4's standard and enhanced syntaxes disagree on whether implicit
+/// AND or `OR` binds tighter. No positives drops the leading `MATCH`.
 fn add_fts_field_query(
     positives: &[String],
+    required: &[String],
     excludeds: &[String],
     match_target: &str,
-    quote: impl Fn(&str) -> String,
     var_num: &mut usize,
     params: &mut Vec<String>,
     queries: &mut Vec<String>,
 ) {
-    if positives.is_empty() && excludeds.is_empty() {
+    if positives.is_empty() && required.is_empty() && excludeds.is_empty() {
         return;
     }
 
@@ -810,19 +1364,28 @@ fn add_fts_field_query(
         params.push(
             positives
                 .iter()
-                .map(|t| quote(t))
+                .map(|t| fts4_quote(t))
                 .collect::<Vec<_>>()
                 .join(" "),
         );
         *var_num += 1;
     }
 
+    for expr in required {
+        conditions.push(format!(
+            "notes.path IN (SELECT DISTINCT notesContent.path FROM notesContent WHERE {} MATCH ?{})",
+            match_target, var_num
+        ));
+        params.push(expr.clone());
+        *var_num += 1;
+    }
+
     for term in excludeds {
         conditions.push(format!(
             "notes.path NOT IN (SELECT DISTINCT notesContent.path FROM notesContent WHERE {} MATCH ?{})",
             match_target, var_num
         ));
-        params.push(quote(term));
+        params.push(fts4_quote(term));
         *var_num += 1;
     }
 
@@ -864,6 +1427,82 @@ fn add_filename_query(
     }
 }
 
+fn add_title_query(
+    s: &SearchTerms,
+    var_num: &mut usize,
+    params: &mut Vec<String>,
+    queries: &mut Vec<String>,
+) {
+    if s.title.is_empty() && s.excluded_title.is_empty() {
+        return;
+    }
+    if let Some(final_where) = build_like_conditions(
+        &s.title,
+        &s.excluded_title,
+        |n| format!("notes.title LIKE ?{} ESCAPE '\\'", n),
+        |n| format!("notes.title NOT LIKE ?{} ESCAPE '\\'", n),
+        var_num,
+        params,
+        |t: &String| {
+            if t.contains('*') {
+                // Explicit wildcard: whole-title match, * → %.
+                escape_like_pattern(t).replace('*', "%")
+            } else {
+                format!("%{}%", escape_like_pattern(t))
+            }
+        },
+    ) {
+        queries.push(format!("{} WHERE {}", notes_base_sql(), final_where));
+    }
+}
+
+/// `created:` / `modified:` predicates: one notes-only branch ANDing a
+/// half-open range per filter. Day bounds are UTC midnights, compared with
+/// the stored epoch seconds.
+fn add_dates_query(
+    s: &SearchTerms,
+    var_num: &mut usize,
+    params: &mut Vec<String>,
+    queries: &mut Vec<String>,
+) {
+    let mut conditions = vec![];
+    for filter in &s.dates {
+        let column = match filter.field {
+            DateField::Created => "notes.created",
+            DateField::Modified => "notes.modified",
+        };
+        let mut range = vec![];
+        for (bound, op) in [(filter.from, ">="), (filter.until, "<")] {
+            if let Some(day) = bound {
+                range.push(format!("{} {} ?{}", column, op, var_num));
+                params.push(
+                    day.and_time(NaiveTime::MIN)
+                        .and_utc()
+                        .timestamp()
+                        .to_string(),
+                );
+                *var_num += 1;
+            }
+        }
+        if range.is_empty() {
+            continue;
+        }
+        let range = range.join(" AND ");
+        conditions.push(if filter.excluded {
+            format!("NOT ({})", range)
+        } else {
+            range
+        });
+    }
+    if !conditions.is_empty() {
+        queries.push(format!(
+            "{} WHERE {}",
+            notes_base_sql(),
+            conditions.join(" AND ")
+        ));
+    }
+}
+
 fn add_path_query(
     s: &SearchTerms,
     var_num: &mut usize,
@@ -1243,6 +1882,9 @@ pub struct NoteSuggestion {
     /// The note's full vault path, so the UI can disambiguate when several
     /// notes share a `name`. The link inserted on accept is still `name`.
     pub path: VaultPath,
+    /// The note's indexed title (empty for an untitled note), so a match on
+    /// the title rather than the name is visible in the popup.
+    pub title: String,
 }
 
 /// A tag suggestion for the autocomplete popup. `usage_count` is computed
@@ -1277,27 +1919,60 @@ async fn suggest_notes_by_prefix(
     // pattern is naturally case-insensitive; the explicit `LOWER()` is a
     // defensive belt-and-braces against any future code path that might
     // insert mixed case.
-    let sql = "SELECT path \
+    let sql = "SELECT path, title \
                FROM notes \
                WHERE LOWER(noteName) LIKE ?1 ESCAPE '\\' \
                ORDER BY noteName ASC, path ASC \
                LIMIT ?2";
-    let rows: Vec<(String,)> = sqlx::query_as(sql)
+    let rows: Vec<(String, String)> = sqlx::query_as(sql)
         .bind(&pattern)
         .bind(limit as i64)
         .fetch_all(pool)
         .await?;
-    Ok(rows
-        .into_iter()
-        .map(|(path,)| {
-            let vault_path = VaultPath::new(path);
-            let name = vault_path.get_clean_name();
-            NoteSuggestion {
-                name,
-                path: vault_path,
-            }
-        })
-        .collect())
+    Ok(rows.into_iter().map(note_suggestion).collect())
+}
+
+/// Returns notes whose name or title contains `query` (case-insensitive),
+/// capped at `limit`, best match first: name prefix, then title prefix,
+/// then a name or title that merely contains it. Ties sort by name and path,
+/// as in [`suggest_notes_by_prefix`].
+///
+/// Names and `titleKey` are lowercased in Rust when stored, so matching them
+/// against the lowercased query folds non-ASCII letters too (`Ärger` finds
+/// `ärger`), which SQLite's `LOWER()` would not. The leading-wildcard `LIKE`
+/// can't use an index, so this scans `notes`, reading only the short name
+/// and title columns of each row.
+async fn suggest_links(
+    pool: &SqlitePool,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<NoteSuggestion>, DBError> {
+    let escaped = escape_like_pattern(&query.to_lowercase());
+    let sql = "SELECT path, title \
+               FROM notes \
+               WHERE noteName LIKE ?2 ESCAPE '\\' OR titleKey LIKE ?2 ESCAPE '\\' \
+               ORDER BY CASE \
+                   WHEN noteName LIKE ?1 ESCAPE '\\' THEN 0 \
+                   WHEN titleKey LIKE ?1 ESCAPE '\\' THEN 1 \
+                   ELSE 2 END, \
+                 noteName ASC, path ASC \
+               LIMIT ?3";
+    let rows: Vec<(String, String)> = sqlx::query_as(sql)
+        .bind(format!("{escaped}%"))
+        .bind(format!("%{escaped}%"))
+        .bind(limit as i64)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(note_suggestion).collect())
+}
+
+fn note_suggestion((path, title): (String, String)) -> NoteSuggestion {
+    let path = VaultPath::new(path);
+    NoteSuggestion {
+        name: path.get_clean_name(),
+        path,
+        title,
+    }
 }
 
 /// Returns tag labels whose name starts with `prefix` (case-insensitive),
@@ -1333,6 +2008,135 @@ async fn suggest_tags_by_prefix(
         .collect())
 }
 
+/// How many corrected queries [`suggest_query`] offers at most.
+const MAX_QUERY_SUGGESTIONS: usize = 3;
+
+/// Words shorter than this are never corrected: at one or two letters every
+/// word is a single edit away from too many others to be a useful guess.
+const MIN_CORRECTED_WORD_LEN: usize = 3;
+
+/// "Did you mean" corrections for `query`, best first. Each bare word (a
+/// [`QueryTokenClass::Term`](search_terms::QueryTokenClass::Term) span) that
+/// is not in the indexed vocabulary is swapped for its closest indexed terms
+/// (see [`within_typo_distance`]), ranked by edit distance, then by how many
+/// notes contain the term. Operators, prefixed and excluded terms (`@`, `#`,
+/// `name:`, `-`…) and quoted phrases are kept verbatim. Empty when every word is known or
+/// nothing close enough is indexed.
+///
+/// The vocabulary comes from an `fts4aux` view of `notesContent`. It lives
+/// in the connection's `temp` schema, so the index file is never touched.
+async fn suggest_query(pool: &SqlitePool, query: &str) -> Result<Vec<String>, DBError> {
+    let mut conn = pool.acquire().await?;
+    sqlx::query(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS temp.notesVocab USING fts4aux(main, notesContent)",
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Only bare terms are candidates: a span right after another one is a
+    // field value or an excluded term, and quoted phrases and operators have
+    // classes of their own.
+    let mut words: Vec<std::ops::Range<usize>> = vec![];
+    let mut previous_end = None;
+    for span in search_terms::query_token_spans(query) {
+        if span.class == search_terms::QueryTokenClass::Term
+            && previous_end != Some(span.range.start)
+        {
+            words.push(span.range.clone());
+        }
+        previous_end = Some(span.range.end);
+    }
+    let mut corrections: Vec<Vec<String>> = Vec::with_capacity(words.len());
+    let mut any_corrected = false;
+    for range in &words {
+        let word = &query[range.clone()];
+        let is_plain = word.chars().count() >= MIN_CORRECTED_WORD_LEN
+            && word.chars().all(char::is_alphanumeric);
+        if !is_plain {
+            corrections.push(vec![]);
+            continue;
+        }
+        let word = word.to_lowercase();
+        let len = word.chars().count() as i64;
+        let candidates: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT term, documents FROM temp.notesVocab \
+             WHERE col = '*' AND length(term) BETWEEN ?1 AND ?2",
+        )
+        .bind(len - 2)
+        .bind(len + 2)
+        .fetch_all(&mut *conn)
+        .await?;
+        if candidates.iter().any(|(term, _)| *term == word) {
+            corrections.push(vec![]);
+            continue;
+        }
+        let mut close: Vec<(usize, i64, String)> = candidates
+            .into_iter()
+            .filter_map(|(term, docs)| {
+                within_typo_distance(&word, &term).map(|distance| (distance, docs, term))
+            })
+            .collect();
+        close.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+        close.truncate(MAX_QUERY_SUGGESTIONS);
+        any_corrected |= !close.is_empty();
+        corrections.push(close.into_iter().map(|(_, _, term)| term).collect());
+    }
+    if !any_corrected {
+        return Ok(vec![]);
+    }
+
+    // The n-th suggestion takes each word's n-th best correction, falling
+    // back to its best one when it has fewer. Everything between the
+    // corrected words is kept as typed.
+    let mut suggestions: Vec<String> = Vec::new();
+    for rank in 0..MAX_QUERY_SUGGESTIONS {
+        let mut suggestion = String::with_capacity(query.len());
+        let mut copied = 0;
+        for (range, fixes) in words.iter().zip(&corrections) {
+            if let Some(fix) = fixes.get(rank).or(fixes.first()) {
+                suggestion.push_str(&query[copied..range.start]);
+                suggestion.push_str(fix);
+                copied = range.end;
+            }
+        }
+        suggestion.push_str(&query[copied..]);
+        if !suggestions.contains(&suggestion) {
+            suggestions.push(suggestion);
+        }
+    }
+    Ok(suggestions)
+}
+
+/// Edit distance between `word` and `term` when it is small enough to be a
+/// typo: one edit for words up to five letters, two for longer ones. Counts
+/// an adjacent transposition ("recieve" → "receive") as a single edit.
+fn within_typo_distance(word: &str, term: &str) -> Option<usize> {
+    let a: Vec<char> = word.chars().collect();
+    let b: Vec<char> = term.chars().collect();
+    let max = if a.len() <= 5 { 1 } else { 2 };
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    // Optimal string alignment distance, three rows at a time.
+    let mut before_prev: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur: Vec<usize> = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(before_prev[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    let distance = prev[b.len()];
+    (distance <= max).then_some(distance)
+}
+
 async fn label_counts(pool: &SqlitePool) -> Result<Vec<(String, i64)>, DBError> {
     let rows: Vec<(String, i64)> =
         sqlx::query_as("SELECT name, COUNT(*) as cnt FROM labels GROUP BY name ORDER BY name")
@@ -1350,14 +2154,321 @@ async fn notes_with_label(pool: &SqlitePool, name: &str) -> Result<Vec<VaultPath
     Ok(rows.into_iter().map(|(p,)| VaultPath::new(p)).collect())
 }
 
+/// The list-rendering facts about one indexed note, without its content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteMeta {
+    /// The note's vault path, in canonical (vault-absolute) form.
+    pub path: VaultPath,
+    /// The note's indexed title (empty for an untitled note).
+    pub title: String,
+    /// Last-modified time, in whole seconds since the Unix epoch.
+    pub modified_secs: u64,
+    /// File size in bytes.
+    pub size: u64,
+    /// The note's labels, lowercased and sorted.
+    pub labels: Vec<String>,
+}
+
+/// [`NoteMeta`] for each indexed note among `paths` (canonical), in the order
+/// given; paths that aren't indexed are skipped and repeats collapse. One
+/// query per [`SQLITE_PARAM_BUDGET`] paths, joining in the labels.
+async fn metadata_for(pool: &SqlitePool, paths: &[VaultPath]) -> Result<Vec<NoteMeta>, DBError> {
+    let mut seen = HashSet::with_capacity(paths.len());
+    let keys: Vec<String> = paths
+        .iter()
+        .map(VaultPath::to_string)
+        .filter(|key| seen.insert(key.clone()))
+        .collect();
+    let mut found: HashMap<String, NoteMeta> = HashMap::with_capacity(keys.len());
+    for chunk in keys.chunks(SQLITE_PARAM_BUDGET) {
+        let sql = format!(
+            "SELECT notes.path, notes.title, notes.size, notes.modified, \
+             group_concat(labels.name) FROM notes \
+             LEFT JOIN labels ON labels.path = notes.path \
+             WHERE notes.path IN ({}) GROUP BY notes.path",
+            vec!["?"; chunk.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, (String, String, i64, i64, Option<String>)>(&sql);
+        for key in chunk {
+            query = query.bind(key);
+        }
+        for (path, title, size, modified, labels) in query.fetch_all(pool).await? {
+            // Label names are `[a-z0-9_]`, so the default `,` separator is safe.
+            let mut labels: Vec<String> = labels
+                .map(|l| l.split(',').map(str::to_string).collect())
+                .unwrap_or_default();
+            labels.sort();
+            found.insert(
+                path.clone(),
+                NoteMeta {
+                    path: VaultPath::new(path),
+                    title,
+                    modified_secs: modified.max(0) as u64,
+                    size: size.max(0) as u64,
+                    labels,
+                },
+            );
+        }
+    }
+    Ok(keys.iter().filter_map(|key| found.remove(key)).collect())
+}
+
+/// Number of notes last modified on each UTC day from `first` to `last`
+/// inclusive, in one grouped query. Days without a note are absent.
+async fn activity(
+    pool: &SqlitePool,
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Result<BTreeMap<NaiveDate, usize>, DBError> {
+    let midnight = |day: NaiveDate| day.and_time(NaiveTime::MIN).and_utc().timestamp();
+    let Some(after_last) = last.succ_opt() else {
+        return Ok(BTreeMap::new());
+    };
+    let rows: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT date(modified, 'unixepoch') AS day, COUNT(*) FROM notes \
+         WHERE modified >= ? AND modified < ? GROUP BY day",
+    )
+    .bind(midnight(first))
+    .bind(midnight(after_last))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(day, count)| {
+            let day = NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok()?;
+            Some((day, count.max(0) as usize))
+        })
+        .collect())
+}
+
+/// The `limit` most recently modified notes, newest first; notes modified in
+/// the same second follow the note path.
+async fn recent_notes(
+    pool: &SqlitePool,
+    limit: usize,
+) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
+    let sql = format!(
+        "SELECT {} FROM notes ORDER BY modified DESC, path ASC LIMIT ?",
+        NOTE_COLUMNS
+    );
+    let rows = sqlx::query(&sql)
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(pool)
+        .await?;
+    rows.iter().map(row_to_note_entry).collect()
+}
+
+/// One indexed note picked with `ORDER BY RANDOM()`, limited to the subtree
+/// under `scope` and leaving out the subtree under `exclude` when given.
+/// `None` when no note qualifies.
+async fn random_note(
+    pool: &SqlitePool,
+    scope: Option<&VaultPath>,
+    exclude: Option<&VaultPath>,
+) -> Result<Option<VaultPath>, DBError> {
+    let mut conditions = vec![];
+    let mut patterns = vec![];
+    if let Some(scope) = scope {
+        conditions.push("path LIKE (? || '%') ESCAPE '\\'");
+        patterns.push(escape_like_pattern(&dir_prefix(scope)));
+    }
+    if let Some(exclude) = exclude {
+        conditions.push("path NOT LIKE (? || '%') ESCAPE '\\'");
+        patterns.push(escape_like_pattern(&dir_prefix(exclude)));
+    }
+    let sql = if conditions.is_empty() {
+        "SELECT path FROM notes ORDER BY RANDOM() LIMIT 1".to_string()
+    } else {
+        format!(
+            "SELECT path FROM notes WHERE {} ORDER BY RANDOM() LIMIT 1",
+            conditions.join(" AND ")
+        )
+    };
+    let mut query = sqlx::query_scalar::<_, String>(&sql);
+    for pattern in &patterns {
+        query = query.bind(pattern);
+    }
+    Ok(query.fetch_optional(pool).await?.map(VaultPath::new))
+}
+
+/// Every indexed note path, plus the distinct `(source, target)` pairs of
+/// links that resolve to an indexed note, both sorted. A link stored with a
+/// full path resolves to that note; a bare-name wikilink resolves to every
+/// note with that name, as [`get_backlinks`] does. Links to missing notes
+/// are left out. Both reads share one transaction, so every edge endpoint
+/// is among the nodes.
+async fn link_graph(
+    pool: &SqlitePool,
+) -> Result<(Vec<VaultPath>, Vec<(VaultPath, VaultPath)>), DBError> {
+    let mut tx = pool.begin().await?;
+    let nodes: Vec<(String,)> = sqlx::query_as("SELECT path FROM notes ORDER BY path")
+        .fetch_all(&mut *tx)
+        .await?;
+    // Two joins instead of one `OR`, so each can use its index.
+    let edges: Vec<(String, String)> = sqlx::query_as(
+        "SELECT l.source, n.path FROM links l \
+         JOIN notes n ON n.path = l.destination \
+         UNION \
+         SELECT l.source, n.path FROM links l \
+         JOIN notes n ON n.noteName = l.destination \
+         ORDER BY 1, 2",
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok((
+        nodes.into_iter().map(|(p,)| VaultPath::new(p)).collect(),
+        edges
+            .into_iter()
+            .map(|(s, t)| (VaultPath::new(s), VaultPath::new(t)))
+            .collect(),
+    ))
+}
+
+/// Entry data of every indexed note over `bytes` in size, largest first.
+async fn notes_larger_than(pool: &SqlitePool, bytes: u64) -> Result<Vec<NoteEntryData>, DBError> {
+    let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+        "SELECT path, size, modified FROM notes WHERE size > ? ORDER BY size DESC, path",
+    )
+    .bind(i64::try_from(bytes).unwrap_or(i64::MAX))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(path, size, modified)| NoteEntryData {
+            path: VaultPath::new(path),
+            size: size.max(0) as u64,
+            modified_secs: modified.max(0) as u64,
+        })
+        .collect())
+}
+
+/// Number of indexed notes.
+async fn notes_count(pool: &SqlitePool) -> Result<usize, DBError> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM notes")
+        .fetch_one(pool)
+        .await?;
+    Ok(count.max(0) as usize)
+}
+
+/// Number of indexed notes [`get_notes`] would return for the same
+/// arguments.
+async fn notes_count_in(
+    pool: &SqlitePool,
+    path: &VaultPath,
+    recursive: bool,
+) -> Result<usize, DBError> {
+    let (where_clause, bind_value) = base_path_filter(path, recursive);
+    let sql = format!("SELECT COUNT(*) FROM notes WHERE {}", where_clause);
+    let count: i64 = sqlx::query_scalar(&sql)
+        .bind(bind_value)
+        .fetch_one(pool)
+        .await?;
+    Ok(count.max(0) as usize)
+}
+
+/// Total size in bytes of every indexed note, summed from the `size` column
+/// rather than by touching the files.
+async fn notes_size(pool: &SqlitePool) -> Result<u64, DBError> {
+    let total: i64 = sqlx::query_scalar("SELECT COALESCE(SUM(size), 0) FROM notes")
+        .fetch_one(pool)
+        .await?;
+    Ok(total.max(0) as u64)
+}
+
+/// Groups notes that share a title, returning one `(title, paths)` entry per
+/// title carried by more than one note. Groups are ordered by title and the
+/// paths within a group by path.
+///
+/// With `case_insensitive` the grouping key is the stored `titleKey` (the
+/// title lowercased in Rust, so non-ASCII letters fold too), and "Todo",
+/// "todo" and "TODO" land in the same group; the reported title is then the
+/// one of the group's first path. With `ignore_untitled`, notes whose title is empty or
+/// whitespace-only are left out instead of forming one large group.
+async fn duplicate_titles(
+    pool: &SqlitePool,
+    case_insensitive: bool,
+    ignore_untitled: bool,
+) -> Result<Vec<(String, Vec<VaultPath>)>, DBError> {
+    let key = if case_insensitive {
+        "titleKey"
+    } else {
+        "title"
+    };
+    let filter = if ignore_untitled {
+        "TRIM(title) <> ''"
+    } else {
+        "1"
+    };
+    let sql = format!(
+        "SELECT {key}, title, path FROM notes \
+         WHERE {filter} AND {key} IN \
+         (SELECT {key} FROM notes WHERE {filter} GROUP BY {key} HAVING COUNT(*) > 1) \
+         ORDER BY {key}, path"
+    );
+    let rows: Vec<(String, String, String)> = sqlx::query_as(&sql).fetch_all(pool).await?;
+
+    let mut groups: Vec<(String, Vec<VaultPath>)> = vec![];
+    let mut current_key: Option<String> = None;
+    for (group_key, title, path) in rows {
+        if current_key.as_deref() != Some(group_key.as_str()) {
+            groups.push((title, vec![]));
+            current_key = Some(group_key);
+        }
+        if let Some((_, paths)) = groups.last_mut() {
+            paths.push(VaultPath::new(path));
+        }
+    }
+    Ok(groups)
+}
+
+/// Groups notes whose stored content `hash` is identical, returning one
+/// cluster of paths per hash shared by more than one note. Clusters are
+/// ordered by their first path and the paths within a cluster by path.
+///
+/// Every empty note hashes the same, so `ignore_empty` leaves out notes with
+/// a zero-length body (`size = 0`) instead of reporting them as one cluster.
+async fn duplicate_contents(
+    pool: &SqlitePool,
+    ignore_empty: bool,
+) -> Result<Vec<Vec<VaultPath>>, DBError> {
+    let filter = if ignore_empty { "size > 0" } else { "1" };
+    let sql = format!(
+        "SELECT hash, path FROM notes \
+         WHERE {filter} AND hash IN \
+         (SELECT hash FROM notes WHERE {filter} GROUP BY hash HAVING COUNT(*) > 1) \
+         ORDER BY hash, path"
+    );
+    let rows: Vec<(String, String)> = sqlx::query_as(&sql).fetch_all(pool).await?;
+
+    let mut clusters: Vec<Vec<VaultPath>> = vec![];
+    let mut current_hash: Option<String> = None;
+    for (hash, path) in rows {
+        if current_hash.as_deref() != Some(hash.as_str()) {
+            clusters.push(vec![]);
+            current_hash = Some(hash);
+        }
+        if let Some(paths) = clusters.last_mut() {
+            paths.push(VaultPath::new(path));
+        }
+    }
+    clusters.sort_by(|a, b| a.first().cmp(&b.first()));
+    Ok(clusters)
+}
+
+#[cfg(test)]
 async fn search_terms<S: AsRef<str>>(
     pool: &SqlitePool,
     search_query: S,
 ) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
-    let search_query = search_query.as_ref();
-    let search_terms = SearchTerms::from_query_string(search_query);
-    let (query, params) = build_search_sql_query_inner(&search_terms);
-    let order_by = search_terms.order_by;
+    search_terms_inner(pool, &SearchTerms::from_query_string(search_query)).await
+}
+
+async fn search_terms_inner(
+    pool: &SqlitePool,
+    search_terms: &SearchTerms,
+) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
+    let (query, params) = build_search_sql_query_inner(search_terms);
 
     if query.is_empty() {
         debug!("No query provided");
@@ -1377,10 +2488,18 @@ async fn search_terms<S: AsRef<str>>(
         .iter()
         .map(row_to_note_entry)
         .collect::<Result<_, _>>()?;
+    sort_by_order_directive(&mut result, &search_terms.order_by);
 
+    Ok(result)
+}
+
+/// Sorts search results by the query's order directive (`or:`), keeping the
+/// existing order between notes the directive ties. Titles fold case fully,
+/// unlike the paged queries' SQL `lower()`.
+fn sort_by_order_directive(result: &mut [(NoteEntryData, NoteContentData)], order_by: &[OrderBy]) {
     if !order_by.is_empty() {
         result.sort_by(|(a_entry, a_content), (b_entry, b_content)| {
-            for ob in &order_by {
+            for ob in order_by {
                 let ord = match ob {
                     OrderBy::Title { asc } => {
                         let cmp = a_content
@@ -1406,13 +2525,214 @@ async fn search_terms<S: AsRef<str>>(
                     return ord;
                 }
             }
-            std::cmp::Ordering::Equal
-        });
+            std::cmp::Ordering::Equal
+        });
+    }
+}
+
+/// The `ORDER BY` keys for the query's order directive (`or:`), on the
+/// columns of `table`. Titles fold case with SQLite's `lower()`, which only
+/// folds ASCII letters.
+fn order_directive_sql(order_by: &[OrderBy], table: &str) -> Vec<String> {
+    order_by
+        .iter()
+        .map(|ob| match ob {
+            OrderBy::Title { asc: true } => format!("lower({table}title) ASC"),
+            OrderBy::Title { asc: false } => format!("lower({table}title) DESC"),
+            OrderBy::FileName { asc: true } => format!("{table}path ASC"),
+            OrderBy::FileName { asc: false } => format!("{table}path DESC"),
+        })
+        .collect()
+}
+
+/// The `ORDER BY` keys for a paged search: the query's order directive
+/// (`or:`), then `path`, so consecutive pages never overlap or skip a note.
+fn search_order_sql(order_by: &[OrderBy]) -> String {
+    let mut keys = order_directive_sql(order_by, "");
+    keys.push("path ASC".to_string());
+    keys.join(", ")
+}
+
+/// One page of [`search_terms`]: the same match, sorted and sliced by SQLite
+/// so only the page's rows are loaded. Without an order directive pages
+/// follow the note path.
+async fn search_terms_page(
+    pool: &SqlitePool,
+    search_terms: &SearchTerms,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
+    let (query, params) = build_search_sql_query_inner(search_terms);
+    if query.is_empty() {
+        debug!("No query provided");
+        return Ok(vec![]);
+    }
+
+    let next = params.len() + 1;
+    let sql = format!(
+        "SELECT * FROM ({}) ORDER BY {} LIMIT ?{} OFFSET ?{}",
+        query,
+        search_order_sql(&search_terms.order_by),
+        next,
+        next + 1
+    );
+    debug!("QUERY: {}", sql);
+
+    let mut sql_query = sqlx::query(&sql);
+    for param in params {
+        sql_query = sql_query.bind(param);
+    }
+    let rows = sql_query
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .bind(i64::try_from(offset).unwrap_or(i64::MAX))
+        .fetch_all(pool)
+        .await?;
+    rows.iter().map(row_to_note_entry).collect()
+}
+
+/// Number of notes [`search_terms`] matches, counted over the same query.
+async fn count_search_terms(
+    pool: &SqlitePool,
+    search_terms: &SearchTerms,
+) -> Result<usize, DBError> {
+    let (query, params) = build_search_sql_query_inner(search_terms);
+    if query.is_empty() {
+        return Ok(0);
+    }
+
+    let sql = format!("SELECT COUNT(*) FROM ({})", query);
+    let mut sql_query = sqlx::query_scalar::<_, i64>(&sql);
+    for param in params {
+        sql_query = sql_query.bind(param);
+    }
+    let count = sql_query.fetch_one(pool).await?;
+    Ok(count.max(0) as usize)
+}
+
+/// The notes among `hits` — a search index's matches for a query's free
+/// text, best first — that also match the rest of `search_terms`, as one
+/// query: the SQL clauses (every note when there are none) joined to the
+/// hits, bound as a JSON array so any number of them takes one parameter.
+/// The result has the note columns plus `hit.key`, each hit's rank.
+fn build_search_hits_sql(search_terms: &SearchTerms, hits: &[VaultPath]) -> (String, Vec<String>) {
+    let (query, mut params) = build_search_sql_query_inner(search_terms);
+    let filtered = if query.is_empty() {
+        format!("SELECT {} FROM notes", NOTE_COLUMNS)
+    } else {
+        query
+    };
+    let mut seen = HashSet::new();
+    let ranked = hits
+        .iter()
+        .map(VaultPath::to_string)
+        .filter(|hit| seen.insert(hit.clone()));
+    params.push(json_string_array(ranked));
+    let sql = format!(
+        "SELECT found.*, hit.key FROM ({}) AS found JOIN json_each(?{}) AS hit ON hit.value = found.path",
+        filtered,
+        params.len()
+    );
+    (sql, params)
+}
+
+/// `values` as a JSON array of strings.
+fn json_string_array(values: impl Iterator<Item = String>) -> String {
+    let mut json = String::from("[");
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        json.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => json.push_str("\\\""),
+                '\\' => json.push_str("\\\\"),
+                c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+                c => json.push(c),
+            }
+        }
+        json.push('"');
     }
+    json.push(']');
+    json
+}
+
+/// [`search_terms`] for a query whose free text a search index matched as
+/// `hits` (see [`build_search_hits_sql`]): in the engine's order, then by
+/// the query's order directive.
+async fn search_hits(
+    pool: &SqlitePool,
+    search_terms: &SearchTerms,
+    hits: &[VaultPath],
+) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
+    let (query, params) = build_search_hits_sql(search_terms, hits);
+    let sql = format!("{} ORDER BY hit.key", query);
+    debug!("QUERY: {}", sql);
 
+    let mut sql_query = sqlx::query(&sql);
+    for param in params {
+        sql_query = sql_query.bind(param);
+    }
+    let rows = sql_query.fetch_all(pool).await?;
+    let mut result: Vec<(NoteEntryData, NoteContentData)> = rows
+        .iter()
+        .map(row_to_note_entry)
+        .collect::<Result<_, _>>()?;
+    sort_by_order_directive(&mut result, &search_terms.order_by);
     Ok(result)
 }
 
+/// One page of [`search_hits`], sorted and sliced by SQLite so only the
+/// page's rows are loaded: by the query's order directive, then the
+/// engine's order.
+async fn search_hits_page(
+    pool: &SqlitePool,
+    search_terms: &SearchTerms,
+    hits: &[VaultPath],
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
+    let (query, params) = build_search_hits_sql(search_terms, hits);
+    let mut keys = order_directive_sql(&search_terms.order_by, "found.");
+    keys.push("hit.key".to_string());
+    let next = params.len() + 1;
+    let sql = format!(
+        "{} ORDER BY {} LIMIT ?{} OFFSET ?{}",
+        query,
+        keys.join(", "),
+        next,
+        next + 1
+    );
+    debug!("QUERY: {}", sql);
+
+    let mut sql_query = sqlx::query(&sql);
+    for param in params {
+        sql_query = sql_query.bind(param);
+    }
+    let rows = sql_query
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .bind(i64::try_from(offset).unwrap_or(i64::MAX))
+        .fetch_all(pool)
+        .await?;
+    rows.iter().map(row_to_note_entry).collect()
+}
+
+/// Number of notes [`search_hits`] returns, counted over the same query.
+async fn count_search_hits(
+    pool: &SqlitePool,
+    search_terms: &SearchTerms,
+    hits: &[VaultPath],
+) -> Result<usize, DBError> {
+    let (query, params) = build_search_hits_sql(search_terms, hits);
+    let sql = format!("SELECT COUNT(*) FROM ({})", query);
+    let mut sql_query = sqlx::query_scalar::<_, i64>(&sql);
+    for param in params {
+        sql_query = sql_query.bind(param);
+    }
+    let count = sql_query.fetch_one(pool).await?;
+    Ok(count.max(0) as usize)
+}
+
 async fn search_note_by_name<S: AsRef<str>>(
     pool: &SqlitePool,
     name: S,
@@ -1436,19 +2756,25 @@ async fn search_note_by_path(
     rows.iter().map(row_to_note_entry).collect()
 }
 
+/// The `notes` predicate selecting the notes directly in `path`, or anywhere
+/// under it when `recursive`, with the value to bind to it.
+fn base_path_filter(path: &VaultPath, recursive: bool) -> (&'static str, String) {
+    if recursive {
+        (
+            "basePath LIKE (? || '%') ESCAPE '\\'",
+            escape_like_pattern(&path.to_string()),
+        )
+    } else {
+        ("basePath = ?", path.to_string())
+    }
+}
+
 async fn get_notes(
     pool: &SqlitePool,
     path: &VaultPath,
     recursive: bool,
 ) -> Result<Vec<(NoteEntryData, NoteContentData)>, DBError> {
-    let (where_clause, bind_value) = if recursive {
-        (
-            "basePath LIKE (? || '%') ESCAPE '\\'".to_string(),
-            escape_like_pattern(&path.to_string()),
-        )
-    } else {
-        ("basePath = ?".to_string(), path.to_string())
-    };
+    let (where_clause, bind_value) = base_path_filter(path, recursive);
     let sql = format!("SELECT {} FROM notes where {}", NOTE_COLUMNS, where_clause);
     let rows = sqlx::query(&sql).bind(bind_value).fetch_all(pool).await?;
 
@@ -1491,18 +2817,19 @@ async fn get_notes_sections(
     let (sql, bind_value) = if path.is_note() {
         // Exact note path
         (
-            "SELECT path, breadcrumb, text FROM notesContent WHERE path = ?".to_string(),
+            "SELECT path, breadcrumb, text, chunkStart, chunkEnd FROM notesContent WHERE path = ?"
+                .to_string(),
             path.to_string(),
         )
     } else if recursive {
         // All notes under this directory tree
         (
-            "SELECT path, breadcrumb, text FROM notesContent WHERE path LIKE (? || '%') ESCAPE '\\'".to_string(),
+            "SELECT path, breadcrumb, text, chunkStart, chunkEnd FROM notesContent WHERE path LIKE (? || '%') ESCAPE '\\'".to_string(),
             escape_like_pattern(&path.to_string()),
         )
     } else {
         // Only notes directly in this directory (basePath join)
-        ("SELECT nc.path, nc.breadcrumb, nc.text FROM notesContent nc JOIN notes n ON nc.path = n.path WHERE n.basePath = ?".to_string(), path.to_string())
+        ("SELECT nc.path, nc.breadcrumb, nc.text, nc.chunkStart, nc.chunkEnd FROM notesContent nc JOIN notes n ON nc.path = n.path WHERE n.basePath = ?".to_string(), path.to_string())
     };
 
     let rows = sqlx::query(&sql).bind(bind_value).fetch_all(pool).await?;
@@ -1511,9 +2838,16 @@ async fn get_notes_sections(
         let path: String = row.try_get("path")?;
         let breadcrumb: String = row.try_get("breadcrumb")?;
         let text: String = row.try_get("text")?;
+        let start: i64 = row.try_get("chunkStart")?;
+        let end: i64 = row.try_get("chunkEnd")?;
 
         let path = VaultPath::new(path);
-        let chunk = ContentChunk { breadcrumb, text };
+        let chunk = ContentChunk {
+            breadcrumb,
+            text,
+            start: start.max(0) as usize,
+            end: end.max(0) as usize,
+        };
         result.entry(path).or_insert_with(Vec::new).push(chunk);
     }
 
@@ -1561,24 +2895,67 @@ async fn save_note(
     pool: &SqlitePool,
     entry_data: &NoteEntryData,
     note_details: &NoteDetails,
+    frontmatter_dates: bool,
 ) -> Result<NoteContentData, DBError> {
     // Parse once and hand the computed content data back to the caller, so
     // the full-text hash + title extraction is never done twice per save.
     let data = note_details.get_content_data();
+
+    let mut tx = pool.begin().await?;
+    let path = entry_data.path.canonical();
+    if note_hash(&mut *tx, &path).await? == Some(data.hash) {
+        // Same content as indexed (e.g. an autosave with no edits since the
+        // last one): chunks, links and labels are already current, so only
+        // the file metadata moves and the note is never re-chunked.
+        sqlx::query("UPDATE notes SET size = ?, modified = ? WHERE path = ?")
+            .bind(entry_data.size as i64)
+            .bind(entry_data.modified_secs as i64)
+            .bind(path.to_string())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        return Ok(data);
+    }
+
     let (chunks, links) = note_details.get_chunks_and_links();
     let label_count = links
         .iter()
         .filter(|l| matches!(l.ltype, LinkType::Hashtag))
         .count();
     let mut batch = NoteBatch::with_capacity(1, chunks.len(), links.len(), label_count);
-    batch.push(entry_data, data.clone(), chunks, links);
-
-    let mut tx = pool.begin().await?;
+    batch.push(
+        entry_data,
+        &note_details.raw_text,
+        data.clone(),
+        chunks,
+        links,
+    );
     batch.flush(&mut tx).await?;
+    if frontmatter_dates {
+        set_frontmatter_created(&mut tx, &entry_data.path, &note_details.raw_text).await?;
+    }
     tx.commit().await?;
     Ok(data)
 }
 
+/// Sets the `created` of the note at `path` to the frontmatter date in its
+/// `text` (see [`NoteDetails::frontmatter_date_of`]). Without one the note
+/// keeps the file time it was first indexed with.
+async fn set_frontmatter_created(
+    tx: &mut Transaction<'_, Sqlite>,
+    path: &VaultPath,
+    text: &str,
+) -> Result<(), DBError> {
+    if let Some(date) = NoteDetails::frontmatter_date_of(text) {
+        sqlx::query("UPDATE notes SET created = ? WHERE path = ?")
+            .bind(date.timestamp())
+            .bind(path.canonical().to_string())
+            .execute(&mut **tx)
+            .await?;
+    }
+    Ok(())
+}
+
 // SQLite default parameter limit is 999. Stay under for safety.
 const SQLITE_PARAM_BUDGET: usize = 900;
 
@@ -1590,12 +2967,17 @@ struct NoteRow {
     hash: String,
     base_path: String,
     name: String,
+    excerpt: String,
+    body: String,
+    title_key: String,
 }
 
 struct ChunkRow {
     path_idx: usize,
     breadcrumb: String,
     text: String,
+    start: i64,
+    end: i64,
 }
 
 struct LinkRow {
@@ -1630,7 +3012,7 @@ async fn upsert_notes_batched(
         // functions take the text by `AsRef<str>` and keep it borrowed.
         let data = NoteDetails::content_data_of(text);
         let (chunks, links) = NoteDetails::chunks_and_links_of(&entry_data.path, text);
-        batch.push(entry_data, data, chunks, links);
+        batch.push(entry_data, text, data, chunks, links);
     }
     batch.flush(tx).await
 }
@@ -1660,6 +3042,7 @@ impl NoteBatch {
     fn push(
         &mut self,
         entry_data: &NoteEntryData,
+        text: &str,
         data: NoteContentData,
         chunks: Vec<ContentChunk>,
         links: Vec<crate::note::NoteLink>,
@@ -1673,18 +3056,23 @@ impl NoteBatch {
         self.paths.push(canonical_path.to_string());
         self.notes.push(NoteRow {
             path_idx: idx,
+            title_key: data.title.to_lowercase(),
             title: data.title,
             size: entry_data.size as i64,
             modified: entry_data.modified_secs as i64,
             hash: data.hash.to_string(),
             base_path: parent_path.to_string(),
             name,
+            excerpt: data.excerpt,
+            body: text.to_owned(),
         });
         for c in chunks {
             self.chunks.push(ChunkRow {
                 path_idx: idx,
                 breadcrumb: c.breadcrumb,
                 text: c.text,
+                start: c.start as i64,
+                end: c.end as i64,
             });
         }
         for l in &links {
@@ -1799,13 +3187,16 @@ trait BulkInsertRow {
 
 impl BulkInsertRow for NoteRow {
     const HEADER: &'static str =
-        "INSERT INTO notes (path, title, size, modified, hash, basePath, noteName) VALUES ";
+        "INSERT INTO notes (path, title, size, modified, created, hash, basePath, noteName, excerpt, body, titleKey) VALUES ";
     const FOOTER: &'static str = " ON CONFLICT(path) DO UPDATE SET \
                                    title = excluded.title, \
                                    size = excluded.size, \
                                    modified = excluded.modified, \
-                                   hash = excluded.hash";
-    const COLS: usize = 7;
+                                   hash = excluded.hash, \
+                                   excerpt = excluded.excerpt, \
+                                   body = excluded.body, \
+                                   titleKey = excluded.titleKey";
+    const COLS: usize = 11;
 
     fn bind_to<'q>(
         &'q self,
@@ -1816,16 +3207,22 @@ impl BulkInsertRow for NoteRow {
             .bind(&self.title)
             .bind(self.size)
             .bind(self.modified)
+            // `created`: only an insert writes it; the upsert keeps the first.
+            .bind(self.modified)
             .bind(&self.hash)
             .bind(&self.base_path)
             .bind(&self.name)
+            .bind(&self.excerpt)
+            .bind(&self.body)
+            .bind(&self.title_key)
     }
 }
 
 impl BulkInsertRow for ChunkRow {
-    const HEADER: &'static str = "INSERT INTO notesContent (path, breadcrumb, text) VALUES ";
+    const HEADER: &'static str =
+        "INSERT INTO notesContent (path, breadcrumb, text, chunkStart, chunkEnd) VALUES ";
     const FOOTER: &'static str = "";
-    const COLS: usize = 3;
+    const COLS: usize = 5;
 
     fn bind_to<'q>(
         &'q self,
@@ -1835,6 +3232,8 @@ impl BulkInsertRow for ChunkRow {
         q.bind(&paths[self.path_idx])
             .bind(&self.breadcrumb)
             .bind(&self.text)
+            .bind(self.start)
+            .bind(self.end)
     }
 }
 
@@ -1904,6 +3303,16 @@ fn fts4_quote(term: &str) -> String {
     format!("\"{}\"", escaped)
 }
 
+/// A proximity term as an FTS4 `NEAR` chain: `"a" NEAR/5 "b"`, each word
+/// quoted like any other term.
+fn fts4_near(p: &Proximity) -> String {
+    p.words
+        .iter()
+        .map(|w| fts4_quote(w))
+        .collect::<Vec<_>>()
+        .join(&format!(" NEAR/{} ", p.within))
+}
+
 /// A directory path in the form the prefix `LIKE` predicates use: its
 /// canonical string with a trailing separator, so `<prefix> || '%'` matches
 /// exactly the rows under (not merely named like) the directory.
@@ -1919,13 +3328,13 @@ fn dir_prefix(path: &VaultPath) -> String {
 /// The stored content hash of one indexed note, or `None` when the path has
 /// no row. Read inside the caller's transaction so it reflects pre-mutation
 /// state.
-async fn note_hash(
-    tx: &mut Transaction<'_, Sqlite>,
-    path: &VaultPath,
-) -> Result<Option<u64>, DBError> {
+async fn note_hash<'e, E>(executor: E, path: &VaultPath) -> Result<Option<u64>, DBError>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
     let hash: Option<String> = sqlx::query_scalar("SELECT hash FROM notes WHERE path = ?")
         .bind(path.to_string())
-        .fetch_optional(&mut **tx)
+        .fetch_optional(executor)
         .await?;
     // A non-numeric hash is a corrupt row; 0 keeps the observer informed and
     // merely forces the consumer to treat the note as changed.
@@ -2153,6 +3562,59 @@ mod tests {
         db.close().await;
     }
 
+    #[tokio::test]
+    async fn open_uses_wal_journal() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let db = super::NoteIndex::open(tmp.path().join(DB_FILE))
+            .await
+            .unwrap();
+        let mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(mode, "wal");
+        db.close().await;
+    }
+
+    #[tokio::test]
+    async fn write_waits_out_a_lock_held_by_another_connection() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(DB_FILE);
+        let writer = super::NoteIndex::open(&path).await.unwrap();
+        let other = super::NoteIndex::open(&path).await.unwrap();
+
+        // Another handle (the editor's save, say) holds the write lock for a
+        // moment; the sync's apply must queue behind it, not fail.
+        let mut held = other.pool().begin().await.unwrap();
+        sqlx::query("DELETE FROM notes")
+            .execute(&mut *held)
+            .await
+            .unwrap();
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            held.commit().await.unwrap();
+        });
+
+        let diff = IndexDiff {
+            to_add: vec![(
+                NoteEntryData {
+                    path: VaultPath::new("/note.md"),
+                    size: 4,
+                    modified_secs: 0,
+                },
+                "text".to_string(),
+            )],
+            to_modify: vec![],
+            to_delete: vec![],
+        };
+        writer.apply(diff).await.unwrap();
+        release.await.unwrap();
+
+        assert_eq!(writer.get_all_notes().await.unwrap().len(), 1);
+        writer.close().await;
+        other.close().await;
+    }
+
     #[test]
     fn test_search_terms_query_empty() {
         let (sql, params) = build_search_sql_query("");
@@ -2165,7 +3627,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("foo bar");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"foo\" \"bar\"");
@@ -2176,7 +3638,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"keyword\"");
@@ -2187,7 +3649,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("@heading");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"heading\"");
@@ -2198,7 +3660,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("in:section");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"section\"");
@@ -2209,7 +3671,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("@heading1 in:heading2");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"heading1\" \"heading2\"");
@@ -2220,7 +3682,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("=filename");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?1 ESCAPE '\\'"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?1 ESCAPE '\\'"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "%filename%");
@@ -2231,7 +3693,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("name:directory");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?1 ESCAPE '\\'"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?1 ESCAPE '\\'"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "%directory%");
@@ -2244,7 +3706,7 @@ mod tests {
         // documented "all terms are ANDed" precedence).
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?1 ESCAPE '\\' AND notes.noteName LIKE ?2 ESCAPE '\\'"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?1 ESCAPE '\\' AND notes.noteName LIKE ?2 ESCAPE '\\'"
         );
         assert_eq!(params.len(), 2);
         assert_eq!(params[0], "%file1%");
@@ -2256,7 +3718,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword @section");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?2"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?2"
         );
         assert_eq!(params.len(), 2);
         assert_eq!(params[0], "\"keyword\"");
@@ -2268,7 +3730,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword =file");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?2 ESCAPE '\\'"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?2 ESCAPE '\\'"
         );
         assert_eq!(params.len(), 2);
         assert_eq!(params[0], "\"keyword\"");
@@ -2280,7 +3742,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("@heading =file");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?2 ESCAPE '\\'"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?2 ESCAPE '\\'"
         );
         assert_eq!(params.len(), 2);
         assert_eq!(params[0], "\"heading\"");
@@ -2292,7 +3754,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword @heading =file");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?2 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?3 ESCAPE '\\'"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?2 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?3 ESCAPE '\\'"
         );
         assert_eq!(params.len(), 3);
         assert_eq!(params[0], "\"keyword\"");
@@ -2305,7 +3767,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("\"exact phrase\" keyword");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"exact phrase\" \"keyword\"");
@@ -2316,7 +3778,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword or:title");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"keyword\"");
@@ -2327,7 +3789,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword -or:title");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"keyword\"");
@@ -2338,7 +3800,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword or:filename");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"keyword\"");
@@ -2349,7 +3811,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword or:f");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"keyword\"");
@@ -2360,7 +3822,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword or:t");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"keyword\"");
@@ -2371,7 +3833,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword ^title -^filename");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"keyword\"");
@@ -2382,7 +3844,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword @section =file ^title");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?2 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?3 ESCAPE '\\'"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?2 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notes.noteName LIKE ?3 ESCAPE '\\'"
         );
         assert_eq!(params.len(), 3);
         assert_eq!(params[0], "\"keyword\"");
@@ -2402,7 +3864,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("keyword ^invalid");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1"
         );
         assert_eq!(params.len(), 1);
         assert_eq!(params[0], "\"keyword\"");
@@ -2413,7 +3875,7 @@ mod tests {
         let (sql, params) = build_search_sql_query("  keyword   @section  ");
         assert_eq!(
             sql,
-            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?2"
+            "SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent MATCH ?1 INTERSECT SELECT DISTINCT notes.path as path, title, size, modified, hash, noteName, excerpt FROM notesContent JOIN notes ON notesContent.path = notes.path WHERE notesContent.breadcrumb MATCH ?2"
         );
         assert_eq!(params.len(), 2);
         assert_eq!(params[0], "\"keyword\"");
@@ -2438,6 +3900,37 @@ mod tests {
         assert!(sql.contains("SELECT DISTINCT"));
     }
 
+    #[test]
+    fn test_or_group_sql_generation() {
+        let (sql, params) = build_search_sql_query("plan draft OR final");
+        assert!(sql.contains(
+            "notesContent MATCH ?1 AND notes.path IN (SELECT DISTINCT notesContent.path FROM notesContent WHERE notesContent MATCH ?2)"
+        ));
+        assert_eq!(
+            params,
+            vec!["\"plan\"".to_string(), "\"draft\" OR \"final\"".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_proximity_sql_generation() {
+        let (sql, params) = build_search_sql_query(r#"notes "meeting agenda"~5"#);
+        assert!(sql.contains(
+            "notesContent MATCH ?1 AND notes.path IN (SELECT DISTINCT notesContent.path FROM notesContent WHERE notesContent MATCH ?2)"
+        ));
+        assert_eq!(
+            params,
+            vec![
+                "\"notes\"".to_string(),
+                "\"meeting\" NEAR/5 \"agenda\"".to_string()
+            ]
+        );
+
+        // Three words chain, each near the next.
+        let (_, params) = build_search_sql_query(r#""a b c"~2"#);
+        assert_eq!(params, vec!["\"a\" NEAR/2 \"b\" NEAR/2 \"c\"".to_string()]);
+    }
+
     #[test]
     fn test_exclusion_only_sql_generation() {
         // Critical test: exclusion-only queries MUST use NOT IN, not pure FTS4 MATCH
@@ -2640,6 +4133,55 @@ mod tests {
         db.close().await;
     }
 
+    #[tokio::test]
+    async fn saving_unchanged_content_keeps_the_indexed_chunks() {
+        use crate::nfs::{NoteEntryData, VaultPath};
+        use crate::note::NoteDetails;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let db = super::NoteIndex::open(tmp.path().join(DB_FILE))
+            .await
+            .unwrap();
+        let path = VaultPath::note_path_from("/n.md");
+        let details = NoteDetails::new(&path, "# One\nbody #tag");
+        let entry_at = |modified_secs| NoteEntryData {
+            path: path.clone(),
+            size: details.raw_text.len() as u64,
+            modified_secs,
+        };
+        let chunk_texts = || async {
+            sqlx::query_scalar::<_, String>("SELECT text FROM notesContent")
+                .fetch_all(db.pool())
+                .await
+                .unwrap()
+        };
+
+        db.save_note(&entry_at(1), &details).await.unwrap();
+        // Mark the stored chunk: a rewrite would replace it from the text.
+        sqlx::query("UPDATE notesContent SET text = 'marker'")
+            .execute(db.pool())
+            .await
+            .unwrap();
+        db.save_note(&entry_at(2), &details).await.unwrap();
+        assert_eq!(
+            chunk_texts().await,
+            ["marker"],
+            "chunks must not be rewritten"
+        );
+        let modified: i64 = sqlx::query_scalar("SELECT modified FROM notes WHERE path = ?")
+            .bind(path.to_string())
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(modified, 2);
+
+        let edited = NoteDetails::new(&path, "# One\nnew body");
+        db.save_note(&entry_at(3), &edited).await.unwrap();
+        assert_eq!(chunk_texts().await, ["new body"]);
+
+        db.close().await;
+    }
+
     #[tokio::test]
     async fn labels_are_removed_on_note_delete() {
         use crate::nfs::{NoteEntryData, VaultPath};
@@ -3202,6 +4744,51 @@ mod tests {
         db.close().await;
     }
 
+    #[tokio::test]
+    async fn a_note_matching_in_several_chunks_is_returned_once() {
+        use crate::nfs::{NoteEntryData, VaultPath};
+        let tmp = tempfile::TempDir::new().unwrap();
+        let db = super::NoteIndex::open(tmp.path().join(DB_FILE))
+            .await
+            .unwrap();
+        let body = "intro on planning\n# Planning\nplanning notes\n# Later\nmore planning";
+        let entry = NoteEntryData {
+            path: VaultPath::note_path_from("/plan.md"),
+            size: body.len() as u64,
+            modified_secs: 0,
+        };
+        let mut tx = db.pool().begin().await.unwrap();
+        super::insert_notes(&mut tx, &[(entry, body.to_string())])
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        // Content matches in three chunks, breadcrumb in one.
+        for query in [
+            "planning",
+            "@planning",
+            "planning @planning",
+            "planning -absent",
+        ] {
+            let r = super::search_terms(db.pool(), query).await.unwrap();
+            assert_eq!(r.len(), 1, "{query:?} returned {r:?}");
+        }
+
+        db.close().await;
+    }
+
+    #[test]
+    fn typo_distance_counts_transpositions_and_scales_with_length() {
+        assert_eq!(super::within_typo_distance("recieve", "receive"), Some(1));
+        assert_eq!(super::within_typo_distance("recive", "receive"), Some(1));
+        assert_eq!(super::within_typo_distance("reciev", "receive"), Some(2));
+        assert_eq!(super::within_typo_distance("mail", "mail"), Some(0));
+        // Short words tolerate a single edit only.
+        assert_eq!(super::within_typo_distance("mial", "mail"), Some(1));
+        assert_eq!(super::within_typo_distance("mil", "mole"), None);
+        assert_eq!(super::within_typo_distance("planning", "plan"), None);
+    }
+
     #[tokio::test]
     async fn search_by_label_returns_matching_notes() {
         use crate::nfs::{NoteEntryData, VaultPath};
@@ -3877,6 +5464,11 @@ mod tests {
             "->",
             "in:",
             "name:",
+            "\"",
+            "\"meeting",
+            "foo AND \"bar baz\" -qux",
+            "OR",
+            "meeting OR",
         ] {
             let res = super::search_terms(db.pool(), q).await;
             assert!(
@@ -3890,6 +5482,142 @@ mod tests {
         db.close().await;
     }
 
+    #[tokio::test]
+    async fn fts_boolean_operators_match() {
+        use crate::nfs::{NoteEntryData, VaultPath};
+        let tmp = tempfile::TempDir::new().unwrap();
+        let db_path = tmp.path().join("kimun.sqlite");
+        let db = super::NoteIndex::open(&db_path).await.unwrap();
+
+        let entry = NoteEntryData {
+            path: VaultPath::note_path_from("/a.md"),
+            size: 10,
+            modified_secs: 0,
+        };
+        let mut tx = db.pool().begin().await.unwrap();
+        super::insert_notes(&mut tx, &[(entry, "some meeting note".to_string())])
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        for (q, hits) in [
+            ("standup OR meeting", 1),
+            ("note standup OR meeting", 1),
+            ("standup OR retro", 0),
+            ("meeting AND standup", 0),
+            ("meeting NOT note", 0),
+            ("\"meeting note\"", 1),
+        ] {
+            let res = super::search_terms(db.pool(), q).await.unwrap();
+            assert_eq!(res.len(), hits, "query {q:?}");
+        }
+
+        db.close().await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_search_index_matches_the_fts_tables() {
+        use crate::nfs::{NoteEntryData, VaultPath};
+        let tmp = tempfile::TempDir::new().unwrap();
+        let db = super::NoteIndex::open(tmp.path().join(DB_FILE))
+            .await
+            .unwrap();
+        let entry = |path: &str| NoteEntryData {
+            path: VaultPath::note_path_from(path),
+            size: 10,
+            modified_secs: 0,
+        };
+        let mut tx = db.pool().begin().await.unwrap();
+        super::insert_notes(
+            &mut tx,
+            &[
+                (entry("/b.md"), "weekly meeting note".to_string()),
+                (entry("/a.md"), "meeting agenda".to_string()),
+            ],
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        let engine = SqliteSearchIndex::new(db.pool().clone());
+        let search = |terms: &[&str]| {
+            let terms: Vec<String> = terms.iter().map(|t| t.to_string()).collect();
+            let engine = engine.clone();
+            async move { engine.search(&terms).await.unwrap() }
+        };
+        assert_eq!(
+            search(&["meeting"]).await,
+            [VaultPath::new("/a.md"), VaultPath::new("/b.md")]
+        );
+        assert_eq!(search(&["meeting note"]).await, [VaultPath::new("/b.md")]);
+        assert!(search(&["note meeting"]).await.is_empty());
+
+        db.close().await;
+    }
+
+    #[tokio::test]
+    async fn title_tag_and_date_predicates() {
+        use crate::nfs::{NoteEntryData, VaultPath};
+        let tmp = tempfile::TempDir::new().unwrap();
+        let db_path = tmp.path().join("kimun.sqlite");
+        let db = super::NoteIndex::open(&db_path).await.unwrap();
+
+        // 2024-03-10 and 2024-07-01, UTC noon.
+        let entry = |path: &str, modified_secs| NoteEntryData {
+            path: VaultPath::note_path_from(path),
+            size: 10,
+            modified_secs,
+        };
+        let mut tx = db.pool().begin().await.unwrap();
+        super::insert_notes(
+            &mut tx,
+            &[
+                (
+                    entry("/spring.md", 1_710_072_000),
+                    "# Spring plan\n\n#work".to_string(),
+                ),
+                (
+                    entry("/summer.md", 1_719_835_200),
+                    "# Summer notes\n\n#home".to_string(),
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        // A later content update moves `modified` but keeps `created`.
+        let mut tx = db.pool().begin().await.unwrap();
+        super::insert_notes(
+            &mut tx,
+            &[(
+                entry("/spring.md", 1_735_732_800),
+                "# Spring plan\n\n#work".to_string(),
+            )],
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        for (q, expected) in [
+            ("title:plan", vec!["/spring.md"]),
+            ("-title:plan", vec!["/summer.md"]),
+            ("tag:home", vec!["/summer.md"]),
+            ("created:<2024-06", vec!["/spring.md"]),
+            ("created:2024-07-01", vec!["/summer.md"]),
+            ("modified:>2024-12", vec!["/spring.md"]),
+            ("-modified:2025", vec!["/summer.md"]),
+            ("created:2024 title:notes", vec!["/summer.md"]),
+        ] {
+            let res = super::search_terms(db.pool(), q).await.unwrap();
+            let mut paths: Vec<String> = res.iter().map(|(e, _)| e.path.to_string()).collect();
+            paths.sort();
+            assert_eq!(paths, expected, "query {q:?}");
+        }
+
+        db.close().await;
+    }
+
     #[tokio::test]
     async fn breadcrumb_term_with_metachar_does_not_error() {
         use crate::nfs::{NoteEntryData, VaultPath};
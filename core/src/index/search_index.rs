@@ -0,0 +1,112 @@
+//! SearchIndex — a pluggable full-text engine for vault search.
+//!
+//! The SQLite index always keeps the note metadata (paths, titles, links,
+//! labels) and its own FTS tables. A search's free-text words go to the
+//! vault's [`SearchIndex`] — by default [`SqliteSearchIndex`], which reads
+//! those FTS tables — and everything else in the query (tags, headings,
+//! names, paths, dates, exclusions, `OR` groups, proximity, ordering) is
+//! applied by the index in SQL over the engine's matches. The built-in
+//! engine is matched within that same SQL query. A vault configured with
+//! another engine
+//! ([`VaultConfig::with_search_index`](crate::VaultConfig::with_search_index))
+//! additionally mirrors every indexed note into it, so a very large vault can
+//! use an engine that scales better (e.g. Tantivy) without touching the
+//! metadata store.
+
+use futures_util::future::BoxFuture;
+use sqlx::SqlitePool;
+
+use crate::error::DBError;
+use crate::nfs::VaultPath;
+use crate::note::ContentChunk;
+
+use super::fts4_quote;
+
+/// A full-text engine fed by the vault index. The index calls
+/// [`index_note`](Self::index_note) and [`remove_note`](Self::remove_note)
+/// right after each of its own writes commits, with canonical
+/// (vault-absolute) paths, so an implementation always mirrors the SQLite
+/// index. The calls are awaited on the async runtime: an engine doing
+/// blocking I/O should move it off with `tokio::task::spawn_blocking`.
+///
+/// A failed mirror call doesn't fail the write that triggered it (that write
+/// already landed in SQLite): the index logs it and, before the next search,
+/// [`rebuild`](Self::rebuild)s the engine and refills it from the stored
+/// notes — as it does when the engine is first attached.
+pub trait SearchIndex: Send + Sync + std::fmt::Debug {
+    /// Indexes the note at `path`, replacing whatever was indexed for it.
+    /// `chunks` are its heading sections as the SQLite index stores them:
+    /// normalized text (markup, wikilinks and diacritics stripped) plus the
+    /// heading breadcrumb.
+    fn index_note<'a>(
+        &'a self,
+        path: &'a VaultPath,
+        chunks: &'a [ContentChunk],
+    ) -> BoxFuture<'a, Result<(), DBError>>;
+
+    /// Forgets the note at `path`. Removing a note that isn't indexed is not
+    /// an error.
+    fn remove_note<'a>(&'a self, path: &'a VaultPath) -> BoxFuture<'a, Result<(), DBError>>;
+
+    /// The notes whose text contains every one of `terms`, best match first.
+    /// A term holding a space is a phrase; terms are normalized like the
+    /// indexed text. The query's other clauses never reach the engine.
+    fn search<'a>(&'a self, terms: &'a [String]) -> BoxFuture<'a, Result<Vec<VaultPath>, DBError>>;
+
+    /// Drops every indexed note, ahead of a refill: when the SQLite index is
+    /// rebuilt from scratch (the sync pass that follows re-indexes every
+    /// note), and when the engine is attached or has missed a write.
+    fn rebuild(&self) -> BoxFuture<'_, Result<(), DBError>>;
+}
+
+/// The default [`SearchIndex`]: the index's own SQLite FTS tables. The index
+/// writes those in the same transaction as the note metadata, so there is
+/// nothing to mirror and writes never reach this engine. Vault searches
+/// match these tables inline instead of calling [`SearchIndex::search`],
+/// which would list every match before the rest of the query applies.
+#[derive(Debug, Clone)]
+pub(crate) struct SqliteSearchIndex {
+    pool: SqlitePool,
+}
+
+impl SqliteSearchIndex {
+    pub(crate) fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SearchIndex for SqliteSearchIndex {
+    fn index_note<'a>(
+        &'a self,
+        _path: &'a VaultPath,
+        _chunks: &'a [ContentChunk],
+    ) -> BoxFuture<'a, Result<(), DBError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn remove_note<'a>(&'a self, _path: &'a VaultPath) -> BoxFuture<'a, Result<(), DBError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Matches are ordered by path: FTS4 keeps no relevance rank.
+    fn search<'a>(&'a self, terms: &'a [String]) -> BoxFuture<'a, Result<Vec<VaultPath>, DBError>> {
+        Box::pin(async move {
+            let query = terms
+                .iter()
+                .map(|t| fts4_quote(t))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let paths: Vec<String> = sqlx::query_scalar(
+                "SELECT DISTINCT path FROM notesContent WHERE notesContent MATCH ? ORDER BY path",
+            )
+            .bind(query)
+            .fetch_all(&self.pool)
+            .await?;
+            Ok(paths.into_iter().map(VaultPath::new).collect())
+        })
+    }
+
+    fn rebuild(&self) -> BoxFuture<'_, Result<(), DBError>> {
+        Box::pin(async { Ok(()) })
+    }
+}
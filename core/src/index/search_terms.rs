@@ -1,12 +1,12 @@
 use std::vec;
 
+use chrono::{Datelike, NaiveDate};
 use log::debug;
 
 const ORDER_CHAR: &str = "^";
 const ORDER_LETTER: &str = "or";
 
 enum ElementType {
-    Invalid,
     Term,
     In,
     At,
@@ -22,11 +22,19 @@ enum ElementType {
     ExcludedLinks,
     ForwardLinks,
     ExcludedForwardLinks,
+    Title,
+    ExcludedTitle,
+    Date { field: DateField, excluded: bool },
 }
 
 struct QueryTermExtractor {
     el_type: ElementType,
     term: String,
+    /// True when the value was a closed `"…"` / `'…'` phrase. A quoted
+    /// `"AND"` is a literal word, never an operator.
+    quoted: bool,
+    /// The `n` of a `"…"~n` proximity suffix on a bare quoted term.
+    near: Option<usize>,
     remainder: String,
 }
 
@@ -51,6 +59,35 @@ fn prefix_table() -> [PrefixEntry; 12] {
     ]
 }
 
+// Prefixes with a long form only: aliases and the fields that have no sigil.
+// Excluded variants come first, as in `prefix_table`.
+type LongPrefixEntry = (&'static str, fn() -> ElementType);
+
+fn long_prefix_table() -> [LongPrefixEntry; 8] {
+    [
+        ("-tag:", || ElementType::ExcludedLabel),
+        ("-title:", || ElementType::ExcludedTitle),
+        ("-created:", || ElementType::Date {
+            field: DateField::Created,
+            excluded: true,
+        }),
+        ("-modified:", || ElementType::Date {
+            field: DateField::Modified,
+            excluded: true,
+        }),
+        ("tag:", || ElementType::Label),
+        ("title:", || ElementType::Title),
+        ("created:", || ElementType::Date {
+            field: DateField::Created,
+            excluded: false,
+        }),
+        ("modified:", || ElementType::Date {
+            field: DateField::Modified,
+            excluded: false,
+        }),
+    ]
+}
+
 fn detect_prefix(query: &str) -> Option<(ElementType, &str)> {
     for (long, short, make_type) in prefix_table() {
         if let Some(remaining) = query
@@ -60,6 +97,11 @@ fn detect_prefix(query: &str) -> Option<(ElementType, &str)> {
             return Some((make_type(), remaining));
         }
     }
+    for (long, make_type) in long_prefix_table() {
+        if let Some(remaining) = query.strip_prefix(long) {
+            return Some((make_type(), remaining));
+        }
+    }
     None
 }
 
@@ -95,45 +137,109 @@ impl QueryTermExtractor {
         } else if remaining.starts_with("'") {
             ('\'', remaining.chars().skip(1).collect())
         } else {
-            (' ', remaining)
+            (' ', remaining.clone())
         };
 
+        let quoted = sep_char != ' ';
         match term.find(sep_char) {
             Some(pos) => {
                 let mut remaining = term.split_off(pos);
                 remaining = remaining
                     .strip_prefix(sep_char)
-                    .map_or_else(|| remaining.trim().to_owned(), |s| s.trim().to_string());
+                    .map_or_else(|| remaining.trim().to_owned(), |s| s.to_string());
+                // Only a bare positive phrase takes a proximity suffix.
+                let near = match (&element_type, quoted) {
+                    (ElementType::Term, true) => {
+                        split_near(&remaining).map(|(n, rest)| (n, rest.to_string()))
+                    }
+                    _ => None,
+                };
+                if let Some((_, rest)) = &near {
+                    remaining = rest.clone();
+                }
+                let remaining = remaining.trim().to_string();
                 debug!("TERM: {}", term);
                 debug!("REMAINING: {}", remaining);
                 QueryTermExtractor {
                     el_type: element_type,
                     term,
+                    quoted,
+                    near: near.map(|(n, _)| n),
                     remainder: remaining,
                 }
             }
+            None if quoted => {
+                // An unbalanced quote degrades to a literal character: the
+                // token is read as if unquoted, stray quote included, and
+                // the index escapes it when building the FTS query.
+                let (term, remainder) = match remaining.split_once(' ') {
+                    Some((term, rest)) => (term.to_string(), rest.trim().to_string()),
+                    None => (remaining, String::new()),
+                };
+                QueryTermExtractor {
+                    el_type: element_type,
+                    term,
+                    quoted: false,
+                    near: None,
+                    remainder,
+                }
+            }
             None => {
-                if sep_char == ' ' {
-                    let term = term
-                        .strip_suffix(sep_char)
-                        .map_or_else(|| term.clone(), |s| s.to_string());
-                    QueryTermExtractor {
-                        el_type: element_type,
-                        term,
-                        remainder: String::new(),
-                    }
-                } else {
-                    QueryTermExtractor {
-                        el_type: ElementType::Invalid,
-                        term: String::new(),
-                        remainder: String::new(),
-                    }
+                let term = term
+                    .strip_suffix(sep_char)
+                    .map_or_else(|| term.clone(), |s| s.to_string());
+                QueryTermExtractor {
+                    el_type: element_type,
+                    term,
+                    quoted: false,
+                    near: None,
+                    remainder: String::new(),
                 }
             }
         }
     }
 }
 
+/// Splits a `~n` proximity suffix off the text right after a closing quote:
+/// `~` and digits, ending the token. Returns `n` and what follows.
+fn split_near(after_quote: &str) -> Option<(usize, &str)> {
+    let digits = after_quote.strip_prefix('~')?;
+    let end = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    let rest = &digits[end..];
+    if end == 0 || !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    Some((digits[..end].parse().ok()?, rest))
+}
+
+/// An unquoted, uppercase boolean operator between bare terms.
+enum BoolOperator {
+    And,
+    Or,
+    Not,
+}
+
+impl BoolOperator {
+    fn parse(term: &str) -> Option<Self> {
+        match term {
+            "AND" => Some(Self::And),
+            "OR" => Some(Self::Or),
+            "NOT" => Some(Self::Not),
+            _ => None,
+        }
+    }
+}
+
+/// Where a bare term landed: the plain `terms` list (always its last entry)
+/// or an `any_terms` group.
+#[derive(Clone, Copy)]
+enum TermSlot {
+    Single,
+    Group(usize),
+}
+
 /// A parsed `or:`/`^` order directive: the column to sort by together with
 /// its direction. Produced by the query parser when it encounters an order
 /// token; [`OrderField`] is the direction-free counterpart used by callers
@@ -177,6 +283,98 @@ pub enum OrderField {
     FileName,
 }
 
+/// The note timestamp a `created:` / `modified:` predicate filters on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    /// When the note first entered the index.
+    Created,
+    /// The note file's last modification.
+    Modified,
+}
+
+/// A `"word1 word2"~n` proximity term: note content where each word is at
+/// most `within` words from the next, in either order — "meeting agenda" as
+/// a concept, without requiring the exact phrase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proximity {
+    /// The quoted words, in order. Always two or more.
+    pub words: Vec<String>,
+    /// The `n` of `~n`: how many words may separate neighbouring words.
+    pub within: usize,
+}
+
+/// A `created:` / `modified:` predicate resolved to a half-open range of
+/// days: the timestamp must fall on or after `from` and before `until` (an
+/// absent bound is open). `excluded` negates the whole range.
+///
+/// The value is an optional comparison (`>`, `>=`, `<`, `<=`, `=`) followed
+/// by a `YYYY`, `YYYY-MM` or `YYYY-MM-DD` date naming a period; `>` means
+/// after the whole period and `<` before it starts, so `created:>2024-01`
+/// starts in February and `modified:<2024-06` ends with May.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateFilter {
+    /// Which timestamp is compared.
+    pub field: DateField,
+    /// Inclusive lower bound, if any.
+    pub from: Option<NaiveDate>,
+    /// Exclusive upper bound, if any.
+    pub until: Option<NaiveDate>,
+    /// `true` for the `-created:` / `-modified:` forms.
+    pub excluded: bool,
+}
+
+impl DateFilter {
+    fn parse(field: DateField, excluded: bool, value: &str) -> Option<Self> {
+        let (op, date) = ["<=", ">=", "<", ">", "="]
+            .into_iter()
+            .find_map(|op| value.strip_prefix(op).map(|rest| (op, rest)))
+            .unwrap_or(("=", value));
+        let (start, end) = parse_date_period(date)?;
+        let (from, until) = match op {
+            ">" => (Some(end), None),
+            ">=" => (Some(start), None),
+            "<" => (None, Some(start)),
+            "<=" => (None, Some(end)),
+            _ => (Some(start), Some(end)),
+        };
+        Some(Self {
+            field,
+            from,
+            until,
+            excluded,
+        })
+    }
+}
+
+/// The `[start, end)` days a `YYYY`, `YYYY-MM` or `YYYY-MM-DD` value names.
+fn parse_date_period(date: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let parts: Vec<&str> = date.split('-').collect();
+    let year: i32 = match parts.first() {
+        Some(y) if y.len() == 4 => y.parse().ok()?,
+        _ => return None,
+    };
+    match parts[1..] {
+        [] => Some((
+            NaiveDate::from_ymd_opt(year, 1, 1)?,
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+        )),
+        [month] => {
+            let start = NaiveDate::from_ymd_opt(year, month.parse().ok()?, 1)?;
+            let end = if start.month() == 12 {
+                NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+            } else {
+                NaiveDate::from_ymd_opt(year, start.month() + 1, 1)?
+            };
+            Some((start, end))
+        }
+        [month, day] => {
+            let start = NaiveDate::from_ymd_opt(year, month.parse().ok()?, day.parse().ok()?)?;
+            Some((start, start.succ_opt()?))
+        }
+        _ => None,
+    }
+}
+
 /// True if `token` is an order directive in any of its four forms:
 /// `or:<x>`, `-or:<x>`, `^<x>`, `-^<x>`. Allocation-free: strip an optional
 /// leading `-`, then the rest must start with `^` or `or:`.
@@ -255,11 +453,12 @@ pub fn expand_bare_note_prefixes(query: &str, target: &str) -> String {
         let value = &rest[prefix_len..];
         let token_len = match value.chars().next() {
             Some(quote @ ('"' | '\'')) => {
-                // Quoted value: token ends at the closing quote, or swallows
-                // the rest of the string when unterminated (as the parser does).
+                // Quoted value: token ends at the closing quote, or at the
+                // next space when unterminated (the parser then reads the
+                // quote literally).
                 match value[quote.len_utf8()..].find(quote) {
                     Some(pos) => prefix_len + quote.len_utf8() * 2 + pos,
-                    None => rest.len(),
+                    None => rest.find(' ').unwrap_or(rest.len()),
                 }
             }
             _ => rest.find(' ').unwrap_or(rest.len()),
@@ -324,7 +523,9 @@ pub fn with_order_directive(query: &str, field: OrderField, asc: bool) -> String
 /// - `in:` / `@` — breadcrumb (any path segment / parent directory)
 /// - `name:` / `=` — filename
 /// - `pt:` / `/` — full path
-/// - `lb:` / `#` — label (lowercased and deduplicated)
+/// - `lb:` or `tag:` / `#` — label (lowercased and deduplicated)
+/// - `title:` — note title
+/// - `created:` / `modified:` — date predicate (see [`DateFilter`])
 /// - `lk:` / `<` — backlinks (notes linking *to* the target)
 /// - `fwd:` / `>` — forward links (notes the target links *to*)
 /// - `or:` / `^` — order directive (`or:title`, `^file`, …)
@@ -332,11 +533,26 @@ pub fn with_order_directive(query: &str, field: OrderField, asc: bool) -> String
 /// Any prefix may be negated by a leading `-` (`-#draft`, `-lk:spec`) to
 /// route the token into the matching `excluded_*` field. Values may be quoted
 /// with `"` or `'` to include whitespace (e.g. `="my note"`); an unterminated
-/// quote discards the token. Bare prefixes with no value are dropped.
+/// quote is read as a literal character of an ordinary token. Bare prefixes
+/// with no value are dropped.
+///
+/// Bare terms also take the boolean operators `AND`, `OR` and `NOT`
+/// (uppercase, unquoted). `AND` is what juxtaposition already means, `NOT x`
+/// is `-x`, and `a OR b` groups neighbouring bare terms into
+/// [`any_terms`](Self::any_terms). An operator with nothing to join is
+/// dropped; quote it (`"OR"`) to search for the word itself.
+///
+/// A bare quoted phrase followed directly by `~n` (`"meeting agenda"~5`) is a
+/// [`Proximity`] term instead of an exact phrase.
 #[derive(Default, Debug)]
 pub struct SearchTerms {
     /// Bare full-text terms (no prefix). Matched against note content.
     pub terms: Vec<String>,
+    /// `a OR b` groups of bare terms: each group needs at least one of its
+    /// terms in the note content. Every group holds two or more terms.
+    pub any_terms: Vec<Vec<String>>,
+    /// `"a b"~n` proximity terms, matched against note content.
+    pub proximity: Vec<Proximity>,
     /// `in:` / `@` values: matched against any path segment (breadcrumb).
     pub breadcrumb: Vec<String>,
     /// `or:` / `^` order directives, in the order they appeared.
@@ -367,6 +583,13 @@ pub struct SearchTerms {
     pub excluded_links: Vec<String>,
     /// Negated `fwd:` / `>` values (`-fwd:`, `->`). Deduped, order preserved.
     pub excluded_forward_links: Vec<String>,
+    /// `title:` values: matched against the note title.
+    pub title: Vec<String>,
+    /// Negated `title:` values (`-title:`).
+    pub excluded_title: Vec<String>,
+    /// `created:` / `modified:` predicates, negated ones included. Values
+    /// that are not a valid date are dropped.
+    pub dates: Vec<DateFilter>,
 }
 
 /// Maximum byte length of a query string accepted by [`SearchTerms::from_query_string`].
@@ -420,13 +643,62 @@ impl SearchTerms {
         let mut excluded_labels = vec![];
         let mut excluded_links = vec![];
         let mut excluded_forward_links = vec![];
+        let mut title = vec![];
+        let mut excluded_title = vec![];
+        let mut dates = vec![];
+        let mut any_terms: Vec<Vec<String>> = vec![];
+        let mut proximity = vec![];
+        // Where the previous bare term went, and the slot an `OR` is waiting
+        // to extend; any other token breaks the chain.
+        let mut last_term: Option<TermSlot> = None;
+        let mut pending_or: Option<TermSlot> = None;
         while !query.is_empty() {
             let qp = QueryTermExtractor::extract_and_consume(query);
             query = qp.remainder;
+            let operator = match qp.el_type {
+                ElementType::Term if !qp.quoted => BoolOperator::parse(&qp.term),
+                _ => None,
+            };
+            if let Some(op) = operator {
+                match op {
+                    BoolOperator::And => {}
+                    BoolOperator::Or => pending_or = last_term,
+                    // `NOT x` is `-x`; `NOT -x` cancels out.
+                    BoolOperator::Not => {
+                        query = match query.strip_prefix('-') {
+                            Some(rest) => rest.to_string(),
+                            None => format!("-{query}"),
+                        };
+                    }
+                }
+                last_term = None;
+                continue;
+            }
+            let or_with = pending_or.take();
+            last_term = None;
             match qp.el_type {
                 ElementType::Term => {
-                    if !qp.term.is_empty() {
-                        terms.push(qp.term);
+                    let words: Vec<String> =
+                        qp.term.split_whitespace().map(str::to_string).collect();
+                    // A one-word "phrase" has nothing to be near: plain term.
+                    if let Some(within) = qp.near.filter(|_| words.len() > 1) {
+                        proximity.push(Proximity { words, within });
+                    } else if !qp.term.is_empty() {
+                        last_term = Some(match or_with {
+                            None => {
+                                terms.push(qp.term);
+                                TermSlot::Single
+                            }
+                            Some(TermSlot::Single) => {
+                                let prev = terms.pop().expect("the previous term was pushed");
+                                any_terms.push(vec![prev, qp.term]);
+                                TermSlot::Group(any_terms.len() - 1)
+                            }
+                            Some(TermSlot::Group(i)) => {
+                                any_terms[i].push(qp.term);
+                                TermSlot::Group(i)
+                            }
+                        });
                     }
                 }
                 ElementType::In => {
@@ -444,7 +716,6 @@ impl SearchTerms {
                         order_by.push(o);
                     }
                 }
-                ElementType::Invalid => {}
                 ElementType::Path => {
                     if !qp.term.is_empty() {
                         path.push(qp.term);
@@ -502,6 +773,21 @@ impl SearchTerms {
                         excluded_forward_links.push(qp.term);
                     }
                 }
+                ElementType::Title => {
+                    if !qp.term.is_empty() {
+                        title.push(qp.term);
+                    }
+                }
+                ElementType::ExcludedTitle => {
+                    if !qp.term.is_empty() {
+                        excluded_title.push(qp.term);
+                    }
+                }
+                ElementType::Date { field, excluded } => {
+                    if let Some(d) = DateFilter::parse(field, excluded, &qp.term) {
+                        dates.push(d);
+                    }
+                }
             }
         }
 
@@ -517,6 +803,8 @@ impl SearchTerms {
             filename,
             order_by,
             terms,
+            any_terms,
+            proximity,
             path,
             labels,
             links,
@@ -528,8 +816,66 @@ impl SearchTerms {
             excluded_labels,
             excluded_links,
             excluded_forward_links,
+            title,
+            excluded_title,
+            dates,
         }
     }
+
+    /// True when the query selects nothing: no term or filter of any kind.
+    /// An order directive alone (`or:title`) still counts as empty, since
+    /// there is nothing for it to order.
+    ///
+    /// ```
+    /// use kimun_core::SearchTerms;
+    ///
+    /// assert!(SearchTerms::from_query_string("  or:title ").is_empty());
+    /// assert!(!SearchTerms::from_query_string("-#draft").is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        // Destructured so a new field cannot be forgotten here.
+        let Self {
+            terms,
+            any_terms,
+            proximity,
+            breadcrumb,
+            order_by: _,
+            filename,
+            path,
+            labels,
+            links,
+            forward_links,
+            excluded_terms,
+            excluded_breadcrumb,
+            excluded_filename,
+            excluded_path,
+            excluded_labels,
+            excluded_links,
+            excluded_forward_links,
+            title,
+            excluded_title,
+            dates,
+        } = self;
+        terms.is_empty()
+            && any_terms.is_empty()
+            && proximity.is_empty()
+            && breadcrumb.is_empty()
+            && filename.is_empty()
+            && path.is_empty()
+            && labels.is_empty()
+            && links.is_empty()
+            && forward_links.is_empty()
+            && excluded_terms.is_empty()
+            && excluded_breadcrumb.is_empty()
+            && excluded_filename.is_empty()
+            && excluded_path.is_empty()
+            && excluded_labels.is_empty()
+            && excluded_links.is_empty()
+            && excluded_forward_links.is_empty()
+            && title.is_empty()
+            && excluded_title.is_empty()
+            && dates.is_empty()
+    }
 }
 
 fn dedup_preserving_order(v: &mut Vec<String>) {
@@ -549,7 +895,8 @@ pub enum QueryTokenClass {
     /// A leading `-` (exclusion).
     Negation,
     /// A field prefix: a sigil (`<` `>` `=` `@` `/` `#` `^`) or its long form
-    /// (`lk:` `fwd:` `name:` `in:` `pt:` `lb:` `or:`).
+    /// (`lk:` `fwd:` `name:` `in:` `pt:` `lb:` `or:`), or a long-only key
+    /// (`tag:` `title:` `created:` `modified:`).
     FieldKey,
     /// A note-targeting value (after `<` / `>` / `=` and long forms).
     LinkValue,
@@ -557,14 +904,17 @@ pub enum QueryTokenClass {
     TagValue,
     /// A quoted value (any field), quotes included.
     Quoted,
-    /// A bare `YYYY-MM-DD` date term.
+    /// A bare `YYYY-MM-DD` date term, or a `created:` / `modified:` value.
     Date,
     /// A bare numeric term.
     Number,
     /// A plain search term or unclassified value.
     Term,
-    /// An opening quote with no closing quote: the parser drops everything
-    /// from here on. The one real "parse error" the lenient grammar has.
+    /// A bare, unquoted `AND` / `OR` / `NOT` boolean operator.
+    Operator,
+    /// A token opening a quote it never closes: the parser reads the quote
+    /// as a literal character. The one problem worth flagging in the
+    /// lenient grammar.
     Unterminated,
 }
 
@@ -593,6 +943,7 @@ fn is_date_like(term: &str) -> bool {
 fn value_class(el: &ElementType, term: &str) -> QueryTokenClass {
     match el {
         ElementType::Label | ElementType::ExcludedLabel => QueryTokenClass::TagValue,
+        ElementType::Date { .. } => QueryTokenClass::Date,
         ElementType::Links
         | ElementType::ExcludedLinks
         | ElementType::ForwardLinks
@@ -615,8 +966,9 @@ fn value_class(el: &ElementType, term: &str) -> QueryTokenClass {
 /// Lex `query` into classified spans for syntax highlighting. Whitespace is
 /// not covered by any span. The lexer follows the parser's grammar exactly:
 /// tokens split on ASCII space, a prefix is recognized at a token start, a
-/// quote is honored only at a value start, and an unterminated quote swallows
-/// the rest of the string (classified [`QueryTokenClass::Unterminated`]).
+/// quote is honored only at a value start, and an unterminated quote is a
+/// literal character of a token running to the next space (classified
+/// [`QueryTokenClass::Unterminated`]).
 pub fn query_token_spans(query: &str) -> Vec<QueryTokenSpan> {
     let mut spans = Vec::new();
     let mut pos = 0usize;
@@ -661,6 +1013,16 @@ pub fn query_token_spans(query: &str) -> Vec<QueryTokenSpan> {
                             .map(|r| (make_type(), short.len(), r))
                     })
             })
+            .or_else(|| {
+                long_prefix_table()
+                    .into_iter()
+                    .find_map(|(long, make_type)| {
+                        let long = long.strip_prefix('-').unwrap_or(long);
+                        after_neg
+                            .strip_prefix(long)
+                            .map(|r| (make_type(), long.len(), r))
+                    })
+            })
             .or_else(|| {
                 let order_letter = format!("{ORDER_LETTER}:");
                 after_neg
@@ -706,7 +1068,13 @@ pub fn query_token_spans(query: &str) -> Vec<QueryTokenSpan> {
         if let Some(q) = value.chars().next().filter(|c| *c == '"' || *c == '\'') {
             match value[1..].find(q) {
                 Some(close_rel) => {
-                    let end = cursor + 1 + close_rel + 1;
+                    let mut end = cursor + 1 + close_rel + 1;
+                    // A bare phrase's `~n` proximity suffix belongs to it.
+                    if matches!(el, ElementType::Term) {
+                        if let Some((_, rest)) = split_near(&query[end..]) {
+                            end = len - rest.len();
+                        }
+                    }
                     spans.push(QueryTokenSpan {
                         range: cursor..end,
                         class: QueryTokenClass::Quoted,
@@ -714,19 +1082,27 @@ pub fn query_token_spans(query: &str) -> Vec<QueryTokenSpan> {
                     pos = end;
                 }
                 None => {
+                    let end = value.find(' ').map_or(len, |i| cursor + i);
                     spans.push(QueryTokenSpan {
-                        range: cursor..len,
+                        range: cursor..end,
                         class: QueryTokenClass::Unterminated,
                     });
-                    pos = len;
+                    pos = end;
                 }
             }
         } else {
             let value_end = value.find(' ').map_or(len, |i| cursor + i);
             if value_end > cursor {
+                let text = &query[cursor..value_end];
+                let class =
+                    if matches!(el, ElementType::Term) && BoolOperator::parse(text).is_some() {
+                        QueryTokenClass::Operator
+                    } else {
+                        value_class(&el, text)
+                    };
                 spans.push(QueryTokenSpan {
                     range: cursor..value_end,
-                    class: value_class(&el, &query[cursor..value_end]),
+                    class,
                 });
             }
             pos = value_end.max(cursor + usize::from(value_end == cursor));
@@ -735,13 +1111,13 @@ pub fn query_token_spans(query: &str) -> Vec<QueryTokenSpan> {
     spans
 }
 
-/// True if `query` ends in an unterminated quoted value — the only real
-/// parse error the lenient grammar produces (the parser silently drops the
-/// rest of the string).
+/// True if `query` has an unterminated quoted value — the only problem the
+/// lenient grammar flags (the parser reads the stray quote literally, which
+/// is rarely what was meant).
 pub fn query_has_unterminated_quote(query: &str) -> bool {
     query_token_spans(query)
-        .last()
-        .is_some_and(|s| s.class == QueryTokenClass::Unterminated)
+        .iter()
+        .any(|s| s.class == QueryTokenClass::Unterminated)
 }
 
 #[cfg(test)]
@@ -823,14 +1199,68 @@ mod lexer_tests {
     }
 
     #[test]
-    fn unterminated_quote_marks_the_tail() {
+    fn unterminated_quote_marks_its_token() {
         use QueryTokenClass as C;
         let got = classes(r#"plan #"half open"#);
-        assert_eq!(got.last().unwrap().0, C::Unterminated);
+        assert_eq!(
+            got,
+            vec![
+                (C::Term, "plan".into()),
+                (C::FieldKey, "#".into()),
+                (C::Unterminated, "\"half".into()),
+                (C::Term, "open".into()),
+            ]
+        );
         assert!(query_has_unterminated_quote(r#"plan #"half open"#));
         assert!(!query_has_unterminated_quote(r#"plan #"closed""#));
     }
 
+    #[test]
+    fn lexes_long_only_keys() {
+        use QueryTokenClass as C;
+        assert_eq!(
+            classes("tag:x -created:>2024-01"),
+            vec![
+                (C::FieldKey, "tag:".into()),
+                (C::TagValue, "x".into()),
+                (C::Negation, "-".into()),
+                (C::FieldKey, "created:".into()),
+                (C::Date, ">2024-01".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn lexes_bare_operators() {
+        use QueryTokenClass as C;
+        assert_eq!(
+            classes(r#"a OR "AND" -NOT"#),
+            vec![
+                (C::Term, "a".into()),
+                (C::Operator, "OR".into()),
+                (C::Quoted, "\"AND\"".into()),
+                (C::Negation, "-".into()),
+                (C::Term, "NOT".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn lexes_a_proximity_suffix_with_its_phrase() {
+        use QueryTokenClass as C;
+        assert_eq!(
+            classes(r#""meeting agenda"~5 #x ="a b"~2"#),
+            vec![
+                (C::Quoted, "\"meeting agenda\"~5".into()),
+                (C::FieldKey, "#".into()),
+                (C::TagValue, "x".into()),
+                (C::FieldKey, "=".into()),
+                (C::Quoted, "\"a b\"".into()),
+                (C::Term, "~2".into()),
+            ]
+        );
+    }
+
     /// The lexer and the parser must agree on tokenization: every value the
     /// parser extracts appears verbatim as a value span (not a key/negation).
     #[test]
@@ -867,7 +1297,8 @@ mod lexer_tests {
 #[cfg(test)]
 mod tests {
     use super::expand_bare_note_prefixes;
-    use super::SearchTerms;
+    use super::{DateField, DateFilter, SearchTerms};
+    use chrono::NaiveDate;
 
     #[test]
     fn expand_bare_short_note_prefixes() {
@@ -974,12 +1405,82 @@ mod tests {
 
     #[test]
     fn expand_with_unterminated_quote() {
-        // The first bare `<` expands; the unterminated quoted token swallows
-        // the rest of the string (matching the parser) and stays untouched.
+        // The first bare `<` expands; the unterminated quote is a literal
+        // character of an ordinary token (matching the parser), so a bare
+        // prefix after it still expands.
         assert_eq!(
             expand_bare_note_prefixes("< \"my no", "{note}"),
             "<{note} \"my no"
         );
+        assert_eq!(
+            expand_bare_note_prefixes("\"my <", "{note}"),
+            "\"my <{note}"
+        );
+    }
+
+    #[test]
+    fn boolean_operators_and_phrases() {
+        let s = SearchTerms::from_query_string(r#"foo AND "bar baz" -qux"#);
+        assert_eq!(s.terms, vec!["foo", "bar baz"]);
+        assert_eq!(s.excluded_terms, vec!["qux"]);
+        assert!(s.any_terms.is_empty());
+
+        let s = SearchTerms::from_query_string("plan a OR b OR c NOT draft");
+        assert_eq!(s.terms, vec!["plan"]);
+        assert_eq!(s.any_terms, vec![vec!["a", "b", "c"]]);
+        assert_eq!(s.excluded_terms, vec!["draft"]);
+    }
+
+    #[test]
+    fn proximity_suffix_on_a_bare_phrase() {
+        use super::Proximity;
+        let s = SearchTerms::from_query_string(r#"notes "meeting agenda"~5 #work"#);
+        assert_eq!(
+            s.proximity,
+            vec![Proximity {
+                words: vec!["meeting".into(), "agenda".into()],
+                within: 5,
+            }]
+        );
+        assert_eq!(s.terms, vec!["notes"]);
+        assert_eq!(s.labels, vec!["work"]);
+        assert!(!s.is_empty());
+
+        // A one-word phrase has nothing to be near: a plain term.
+        let s = SearchTerms::from_query_string(r#""agenda"~5"#);
+        assert!(s.proximity.is_empty());
+        assert_eq!(s.terms, vec!["agenda"]);
+
+        // Not a suffix: spaced off, non-numeric, or on another field.
+        let s = SearchTerms::from_query_string(r#""a b" ~5 "c d"~x title:"e f"~2"#);
+        assert!(s.proximity.is_empty());
+        assert_eq!(s.terms, vec!["a b", "~5", "c d", "~x", "~2"]);
+        assert_eq!(s.title, vec!["e f"]);
+    }
+
+    #[test]
+    fn operators_without_operands_are_dropped() {
+        let s = SearchTerms::from_query_string("OR a OR #x AND");
+        assert_eq!(s.terms, vec!["a"]);
+        assert!(s.any_terms.is_empty());
+        assert_eq!(s.labels, vec!["x"]);
+        // Quoted or lowercase, they are plain words.
+        let s = SearchTerms::from_query_string(r#"cats "OR" dogs or"#);
+        assert_eq!(s.terms, vec!["cats", "OR", "dogs", "or"]);
+        // `NOT -x` cancels out.
+        let s = SearchTerms::from_query_string("NOT -x");
+        assert_eq!(s.terms, vec!["x"]);
+    }
+
+    #[test]
+    fn unbalanced_quote_degrades_to_a_literal_term() {
+        let s = SearchTerms::from_query_string("\"");
+        assert_eq!(s.terms, vec!["\""]);
+        let s = SearchTerms::from_query_string(r#"foo "bar baz"#);
+        assert_eq!(s.terms, vec!["foo", "\"bar", "baz"]);
+        let s = SearchTerms::from_query_string(r#"#"half open"#);
+        assert_eq!(s.labels, vec!["\"half"]);
+        assert_eq!(s.terms, vec!["open"]);
     }
 
     #[test]
@@ -1069,12 +1570,12 @@ mod tests {
         let path = search_terms.path;
         let terms = search_terms.terms;
 
+        // The unclosed quote is read literally: the value stops at the next
+        // space and the rest is an ordinary term.
         assert!(breadcrumb.is_empty());
-        assert!(!filename.is_empty());
-        assert!(terms.is_empty());
         assert!(path.is_empty());
-        assert_eq!(1, filename.len());
-        assert!(filename.contains(&"file name".to_string()));
+        assert_eq!(filename, vec!["file name", "\"directory"]);
+        assert_eq!(terms, vec!["path"]);
     }
 
     #[test]
@@ -1329,6 +1830,71 @@ mod tests {
         assert_eq!(SearchTerms::from_query_string("#foo").labels, vec!["foo"]);
     }
 
+    #[test]
+    fn tag_and_title_prefixes() {
+        let s =
+            SearchTerms::from_query_string("tag:Work -tag:draft title:plan -title:\"old plan\"");
+        assert_eq!(s.labels, vec!["work"]);
+        assert_eq!(s.excluded_labels, vec!["draft"]);
+        assert_eq!(s.title, vec!["plan"]);
+        assert_eq!(s.excluded_title, vec!["old plan"]);
+        assert!(s.terms.is_empty());
+    }
+
+    #[test]
+    fn date_predicates_resolve_to_day_ranges() {
+        let d = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+        let s = SearchTerms::from_query_string(
+            "created:>2024-01-01 modified:<2024-06 created:2023 -modified:>=2024-12",
+        );
+        assert_eq!(
+            s.dates,
+            vec![
+                DateFilter {
+                    field: DateField::Created,
+                    from: d(2024, 1, 2),
+                    until: None,
+                    excluded: false,
+                },
+                DateFilter {
+                    field: DateField::Modified,
+                    from: None,
+                    until: d(2024, 6, 1),
+                    excluded: false,
+                },
+                DateFilter {
+                    field: DateField::Created,
+                    from: d(2023, 1, 1),
+                    until: d(2024, 1, 1),
+                    excluded: false,
+                },
+                DateFilter {
+                    field: DateField::Modified,
+                    from: d(2024, 12, 1),
+                    until: None,
+                    excluded: true,
+                },
+            ]
+        );
+        // `<=` takes the whole period; December rolls into the next year.
+        let s = SearchTerms::from_query_string("modified:<=2024-12");
+        assert_eq!(s.dates[0].until, d(2025, 1, 1));
+    }
+
+    #[test]
+    fn invalid_dates_are_dropped() {
+        for q in [
+            "created:",
+            "created:>soon",
+            "modified:2024-13",
+            "created:24-01-01",
+        ] {
+            let s = SearchTerms::from_query_string(q);
+            assert!(s.dates.is_empty(), "{q:?} produced {:?}", s.dates);
+            assert!(s.terms.is_empty(), "{q:?} produced terms {:?}", s.terms);
+        }
+    }
+
     #[test]
     fn from_query_string_caps_input_length() {
         let huge = "#a ".repeat(20_000); // 60 KB
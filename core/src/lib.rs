@@ -41,32 +41,38 @@
 pub mod error;
 pub(crate) mod index;
 pub(crate) mod link_rewrite;
+mod metrics;
 /// Filesystem layer: the only place that touches the OS filesystem directly,
 /// plus the [`VaultPath`] vault-internal path type.
 pub mod nfs;
 /// Note model: parsing Markdown into details, chunks, links, and tags.
 pub mod note;
+mod starter;
 pub(crate) mod sync;
 /// Small standalone helpers (paths, log directory, diacritic folding).
 pub mod utilities;
 pub use index::search_terms::{
     expand_bare_note_prefixes, query_has_unterminated_quote, query_token_spans, quote_query_term,
-    strip_order_directive, with_order_directive, OrderBy, OrderField, QueryTokenClass,
-    QueryTokenSpan, SearchTerms,
+    strip_order_directive, with_order_directive, DateField, DateFilter, OrderBy, OrderField,
+    Proximity, QueryTokenClass, QueryTokenSpan, SearchTerms,
 };
-pub use index::{IndexDiff, IndexObserver, NoteChange, NoteSuggestion, TagSuggestion};
+pub use index::{
+    IndexDiff, IndexObserver, NoteChange, NoteMeta, NoteSuggestion, SearchIndex, TagSuggestion,
+};
+pub use metrics::{MetricEvent, MetricOp};
 pub use nfs::saved_searches::{saved_search_name_matches, SavedSearch};
 pub use nfs::vault_id::VaultId;
 pub use nfs::EntryKind;
 pub use utilities::{app_log_dir, ensure_dir_exists};
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::Display,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
     sync::{
         mpsc::{Receiver, Sender},
-        Arc,
+        Arc, PoisonError,
     },
     time::{Duration, SystemTime},
 };
@@ -75,8 +81,8 @@ use chrono::{NaiveDate, Utc};
 use error::{FSError, VaultError};
 use index::NoteIndex;
 use link_rewrite::LinkRewrite;
-use log::debug;
-use nfs::{NoteEntryData, VaultPath};
+use log::{debug, warn};
+use nfs::{filename::FilenamePolicy, NoteEntryData, RecoveryCopy, VaultPath};
 use note::{ContentChunk, NoteContentData, NoteDetails};
 use sync::VaultSync;
 use utilities::path_to_string;
@@ -88,10 +94,18 @@ use crate::nfs::DirectoryEntryData;
 pub const DEFAULT_JOURNAL_PATH: &str = "/journal";
 /// Default directory for quick-capture notes (see [`NoteVault::quick_note`]).
 pub const DEFAULT_INBOX_PATH: &str = "/inbox";
+/// Default [`VaultConfig::max_note_bytes`]: 5 MiB.
+pub const DEFAULT_MAX_NOTE_BYTES: u64 = 5 * 1024 * 1024;
 /// Default directory for attachments (see
 /// [`NoteVault::default_attachments_path`]).
 pub const DEFAULT_ASSETS_PATH: &str = "/assets";
 
+/// How far the indexed note count may differ from the note files on disk
+/// before [`NoteVault::validate_and_init`] upgrades its existence-only sync to
+/// a size-checking one. A few notes added or removed while the vault was
+/// closed are picked up either way.
+const INDEX_DRIFT_TOLERANCE: usize = 10;
+
 /// Timing summary of an indexing pass.
 pub struct IndexReport {
     /// When the pass started.
@@ -133,6 +147,48 @@ pub struct VaultConfig {
     /// a hidden in-vault backup directory before mutating it. The TUI leaves this
     /// off; the CLI and MCP server turn it on.
     pub backup: bool,
+    /// When `true`, notes and directories the vault creates (or renames to)
+    /// get lowercase names on disk, as older versions always did. When
+    /// `false`, the default, they keep the caller's capitalization: `README`
+    /// is written as `README.md`. Either way paths compare, index and link
+    /// case-insensitively, and existing files keep their on-disk names.
+    pub lowercase_paths: bool,
+    /// Notes larger than this many bytes are indexed without their content:
+    /// they keep an index row (so they still list and open) but are never
+    /// read during a sync or full-text indexed. Guards against a stray log
+    /// or binary file saved as `.md`. See [`NoteVault::oversized_notes`].
+    pub max_note_bytes: u64,
+    /// Which characters new note and directory names keep on disk. `Strict`
+    /// (the default) keeps names portable across Linux, macOS and Windows;
+    /// `Posix` keeps `:`, `?` and the like, which those may not round-trip.
+    /// See [`FilenamePolicy`].
+    pub filename_policy: FilenamePolicy,
+    /// Directory under which journal entries are created. Defaults to
+    /// [`DEFAULT_JOURNAL_PATH`].
+    pub journal_path: VaultPath,
+    /// Directory under which quick-capture notes are created. Defaults to
+    /// [`DEFAULT_INBOX_PATH`].
+    pub inbox_path: VaultPath,
+    /// When `true`, syncs walk the vault on a single thread, visiting each
+    /// directory's entries sorted by name, so entries stream out of
+    /// [`NoteVault::browse_vault`] in the same order every run. Meant for
+    /// reproducible tests; the default parallel walk is faster and visits
+    /// files in no particular order. Either way the index ends up the same.
+    pub sequential_walk: bool,
+    /// An external full-text engine to match searches' free text, filled
+    /// from the index when the vault opens and mirrored on every write. The
+    /// rest of a query still runs on the index. When `None`, the default,
+    /// the index's own SQLite FTS tables match the free text. See
+    /// [`SearchIndex`].
+    pub search_index: Option<Arc<dyn SearchIndex>>,
+    /// When `true`, the index takes a note's creation date (what `created:`
+    /// searches match) from a `created` or `date` key in its frontmatter,
+    /// falling back to the file time when there is none. For archives
+    /// imported with their real dates in frontmatter and fresh file times.
+    /// Applies to notes as they are indexed; run
+    /// [`NoteVault::recreate_index`] after turning it on for an existing
+    /// vault.
+    pub frontmatter_dates: bool,
 }
 
 impl VaultConfig {
@@ -143,6 +199,14 @@ impl VaultConfig {
             workspace_path: workspace_path.into(),
             db_path: None,
             backup: false,
+            lowercase_paths: false,
+            max_note_bytes: DEFAULT_MAX_NOTE_BYTES,
+            filename_policy: FilenamePolicy::Strict,
+            journal_path: VaultPath::new(DEFAULT_JOURNAL_PATH),
+            inbox_path: VaultPath::new(DEFAULT_INBOX_PATH),
+            sequential_walk: false,
+            search_index: None,
+            frontmatter_dates: false,
         }
     }
 
@@ -159,6 +223,155 @@ impl VaultConfig {
         self.backup = backup;
         self
     }
+
+    /// Enables or disables lowercasing the on-disk names of new notes and
+    /// directories (see the [`lowercase_paths`](Self::lowercase_paths)
+    /// field).
+    pub fn with_lowercase_paths(mut self, lowercase_paths: bool) -> Self {
+        self.lowercase_paths = lowercase_paths;
+        self
+    }
+
+    /// Sets the size above which notes are indexed without their content
+    /// (see the [`max_note_bytes`](Self::max_note_bytes) field).
+    pub fn with_max_note_bytes(mut self, max_note_bytes: u64) -> Self {
+        self.max_note_bytes = max_note_bytes;
+        self
+    }
+
+    /// Sets which characters new names keep on disk (see
+    /// [`filename_policy`](Self::filename_policy)).
+    pub fn with_filename_policy(mut self, filename_policy: FilenamePolicy) -> Self {
+        self.filename_policy = filename_policy;
+        self
+    }
+
+    /// Sets the directory journal entries are created under (see
+    /// [`journal_path`](Self::journal_path)).
+    pub fn with_journal_path(mut self, journal_path: VaultPath) -> Self {
+        self.journal_path = journal_path;
+        self
+    }
+
+    /// Sets the directory quick-capture notes are created under (see
+    /// [`inbox_path`](Self::inbox_path)).
+    pub fn with_inbox_path(mut self, inbox_path: VaultPath) -> Self {
+        self.inbox_path = inbox_path;
+        self
+    }
+
+    /// Enables or disables the deterministic single-threaded sync walk (see
+    /// [`sequential_walk`](Self::sequential_walk)).
+    pub fn with_sequential_walk(mut self, sequential_walk: bool) -> Self {
+        self.sequential_walk = sequential_walk;
+        self
+    }
+
+    /// Enables or disables taking creation dates from frontmatter (see
+    /// [`frontmatter_dates`](Self::frontmatter_dates)).
+    pub fn with_frontmatter_dates(mut self, frontmatter_dates: bool) -> Self {
+        self.frontmatter_dates = frontmatter_dates;
+        self
+    }
+
+    /// OS path of the index database this config opens: the
+    /// [`db_path`](Self::db_path) override when set (e.g. a cache directory
+    /// outside a synced workspace), otherwise `kimun.sqlite` in the workspace.
+    pub fn index_path(&self) -> PathBuf {
+        self.db_path
+            .clone()
+            .unwrap_or_else(|| self.workspace_path.join(crate::index::DB_FILE))
+    }
+
+    /// Checks the workspace the way [`NoteVault::new`] does and reports on
+    /// the index already at [`index_path`](Self::index_path), if any (its
+    /// schema version and note count), so a caller can describe a vault
+    /// before opening it. Nothing is created or modified: the index is read
+    /// over a read-only connection and never healed.
+    pub async fn inspect(&self) -> Result<WorkspaceInfo, VaultError> {
+        check_workspace_dir(&self.workspace_path)?;
+        let db_path = self.index_path();
+        let index_exists = nfs::file_exists(&db_path);
+        let (index_version, note_count) = if index_exists {
+            index::inspect(&db_path).await
+        } else {
+            (None, None)
+        };
+        Ok(WorkspaceInfo {
+            index_current: index_version.as_deref() == Some(index::VERSION),
+            db_path,
+            index_exists,
+            index_version,
+            note_count,
+        })
+    }
+
+    /// Matches searches' free text with `search_index` instead of the
+    /// built-in SQLite FTS (see the [`search_index`](Self::search_index)
+    /// field).
+    pub fn with_search_index(mut self, search_index: Arc<dyn SearchIndex>) -> Self {
+        self.search_index = Some(search_index);
+        self
+    }
+
+    /// Opens the vault this config describes and brings its index up to
+    /// date: [`NoteVault::new`] followed by
+    /// [`NoteVault::validate_and_init`]. Use `new` directly to defer the
+    /// (possibly slow) sync pass.
+    pub async fn open(self) -> Result<(NoteVault, IndexReport), VaultError> {
+        let vault = NoteVault::new(self).await?;
+        let report = vault.validate_and_init().await?;
+        Ok((vault, report))
+    }
+}
+
+/// What [`VaultConfig::inspect`] (or [`NoteVault::validate_workspace`])
+/// found at a workspace path, without opening a vault there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceInfo {
+    /// OS path of the index database a vault opened with the inspected config
+    /// would use.
+    pub db_path: PathBuf,
+    /// Whether an index database file already exists there.
+    pub index_exists: bool,
+    /// Schema version stored in the existing index, when readable.
+    pub index_version: Option<String>,
+    /// Whether `index_version` is the one this build uses. When `false` an
+    /// existing index is rebuilt from scratch on open.
+    pub index_current: bool,
+    /// Number of notes in the existing index, when readable.
+    pub note_count: Option<usize>,
+}
+
+/// The vault's note-link graph ([`NoteVault::link_graph`]), ready to hand
+/// to a graph visualizer. Serializes to `{"nodes": [...], "edges": [[source,
+/// target], ...]}` with vault paths as strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct LinkGraph {
+    /// Every indexed note, sorted, including those with no links at all.
+    pub nodes: Vec<VaultPath>,
+    /// One `(source, target)` pair per linking note and linked note, sorted.
+    /// Only links that resolve to an indexed note appear.
+    pub edges: Vec<(VaultPath, VaultPath)>,
+}
+
+impl LinkGraph {
+    /// Nodes with no edge in either direction.
+    pub fn isolated(&self) -> impl Iterator<Item = &VaultPath> {
+        let linked: HashSet<&VaultPath> = self.edges.iter().flat_map(|(s, t)| [s, t]).collect();
+        self.nodes.iter().filter(move |n| !linked.contains(n))
+    }
+}
+
+/// One line of a [`NoteVault::diff_note`] diff, without its line break.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Present in both the indexed and the current content.
+    Same(String),
+    /// Only in the indexed content: removed on disk since.
+    Removed(String),
+    /// Only in the current content: added on disk since.
+    Added(String),
 }
 
 /// Result of a dry-run replace ([`NoteVault::preview_replace`]): how many matches
@@ -172,8 +385,77 @@ pub struct ReplacePreview {
     pub content: String,
 }
 
+/// What [`NoteVault::rename_note`] does when a note already exists at the
+/// destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Fail without touching anything. The default.
+    #[default]
+    Error,
+    /// Rename to the first free sibling name instead (`note_1.md`, …), as
+    /// picked by [`NoteVault::free_note_path`].
+    Rename,
+    /// Delete the existing note (backing it up first when backups are
+    /// enabled) and take its place.
+    Overwrite,
+}
+
+/// Which neighbour [`NoteVault::adjacent_journal`] looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// The closest earlier entry.
+    Previous,
+    /// The closest later entry.
+    Next,
+}
+
+/// A single match of [`NoteVault::find_in_note`] inside a note's text.
+///
+/// Offsets are given in both units so callers don't have to guess: byte
+/// offsets index the UTF-8 `String` directly, char offsets count Unicode
+/// scalar values for editors that position by character. Ranges are
+/// half-open (`start..end`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchSpan {
+    /// Byte offset of the first byte of the match.
+    pub byte_start: usize,
+    /// Byte offset one past the last byte of the match.
+    pub byte_end: usize,
+    /// Char offset of the first character of the match.
+    pub char_start: usize,
+    /// Char offset one past the last character of the match.
+    pub char_end: usize,
+    /// Zero-based line on which the match starts.
+    pub line: usize,
+}
+
+/// Disk usage of a vault, as reported by [`NoteVault::vault_size`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VaultSize {
+    /// Total size of every indexed note, in bytes.
+    pub notes_bytes: u64,
+    /// Total size of every attachment (non-note file) in the vault, in bytes.
+    pub attachments_bytes: u64,
+    /// Size of the index database, including its SQLite sidecar files, in
+    /// bytes.
+    pub db_bytes: u64,
+}
+
+impl VaultSize {
+    /// Sum of notes, attachments, and database bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.notes_bytes + self.attachments_bytes + self.db_bytes
+    }
+}
+
 /// Facade over a vault: a directory of Markdown notes plus its searchable
-/// index. Cheap to clone — clones share the index pool and per-note locks.
+/// index.
+///
+/// Cheap to clone, and meant to be cloned per task: cloning does no
+/// filesystem or database work and opens no connections. Every clone shares
+/// the one index connection pool, its readiness flag and observer, the
+/// per-note write locks and the cached vault id — each field is a shared
+/// handle, so keep it that way when adding one.
 #[derive(Debug, Clone)]
 pub struct NoteVault {
     /// Stored as `Arc<Path>` (not `Arc<PathBuf>`) because (a) it impls
@@ -189,6 +471,18 @@ pub struct NoteVault {
     /// Whether destructive writes back up the previous content first. Mirrors
     /// [`VaultConfig::backup`]; see its docs.
     backup: bool,
+    /// Whether new on-disk names are lowercased. Mirrors
+    /// [`VaultConfig::lowercase_paths`]; see its docs.
+    lowercase_paths: bool,
+    /// Size above which syncs index a note without its content. Mirrors
+    /// [`VaultConfig::max_note_bytes`].
+    max_note_bytes: u64,
+    /// Characters new on-disk names keep. Mirrors
+    /// [`VaultConfig::filename_policy`].
+    filename_policy: FilenamePolicy,
+    /// Whether syncs walk single-threaded in sorted order. Mirrors
+    /// [`VaultConfig::sequential_walk`].
+    sequential_walk: bool,
     /// Per-note in-process write locks. Concurrent content mutations to the same
     /// note (e.g. parallel MCP tool calls) serialize on these so a read-modify-
     /// write like `replace` can't lose an update. Shared across clones via `Arc`.
@@ -199,6 +493,8 @@ pub struct NoteVault {
     /// RAG query surface asks for it. Shared across clones; the id is stable
     /// for the life of the vault (adr/0020), so caching cannot go stale.
     vault_id: Arc<tokio::sync::OnceCell<nfs::vault_id::VaultId>>,
+    /// Timing hook for saves, searches and syncs; see [`Self::set_metrics`].
+    metrics: metrics::Metrics,
 }
 
 // SqlitePool doesn't implement PartialEq; two vaults are equivalent when they
@@ -212,44 +508,67 @@ impl PartialEq for NoteVault {
 impl NoteVault {
     /// Creates a new instance of the Note Vault.
     /// Make sure you call `NoteVault::validate_and_init(&self)` to initialize the DB index if
-    /// needed.
+    /// needed, or open through [`VaultConfig::open`], which does both.
     pub async fn new(config: VaultConfig) -> Result<Self, VaultError> {
         debug!("Creating new vault Instance");
+        let db_path = config.index_path();
         let backup = config.backup;
+        let lowercase_paths = config.lowercase_paths;
+        let max_note_bytes = config.max_note_bytes;
+        let filename_policy = config.filename_policy;
+        let journal_path = config.journal_path;
+        let inbox_path = config.inbox_path;
+        let sequential_walk = config.sequential_walk;
         let workspace_path = config.workspace_path;
-        if !workspace_path.exists() {
-            return Err(VaultError::VaultPathNotFound {
-                path: path_to_string(&workspace_path),
-            });
-        }
-        if !workspace_path.is_dir() {
-            return Err(VaultError::FSError(FSError::InvalidPath {
-                path: path_to_string(&workspace_path),
-                message: "Path provided is not a directory".to_string(),
-            }));
-        };
+        check_workspace_dir(&workspace_path)?;
 
-        let db_path = config
-            .db_path
-            .unwrap_or_else(|| workspace_path.join(crate::index::DB_FILE));
-        let index = NoteIndex::open(&db_path).await?;
+        let mut index = NoteIndex::open(&db_path)
+            .await?
+            .with_frontmatter_dates(config.frontmatter_dates);
+        if let Some(search_index) = config.search_index {
+            index = index.with_search_index(search_index).await?;
+        }
         let note_vault = Self {
             workspace_path: Arc::from(workspace_path.as_path()),
-            journal_path: VaultPath::new(DEFAULT_JOURNAL_PATH),
-            inbox_path: VaultPath::new(DEFAULT_INBOX_PATH),
+            journal_path,
+            inbox_path,
             index,
             backup,
+            lowercase_paths,
+            max_note_bytes,
+            filename_policy,
+            sequential_walk,
             note_locks: Arc::new(std::sync::Mutex::new(HashMap::new())),
             vault_id: Arc::new(tokio::sync::OnceCell::new()),
+            metrics: metrics::Metrics::default(),
         };
         Ok(note_vault)
     }
 
+    /// Checks `workspace_path` the way [`NoteVault::new`] does and reports on
+    /// the index already there, if any (its schema version and note count),
+    /// so a caller can describe a folder before committing to it. Looks for
+    /// the index at its default in-workspace location; use
+    /// [`VaultConfig::inspect`] for a vault whose index lives elsewhere.
+    pub async fn validate_workspace<P: AsRef<Path>>(
+        workspace_path: P,
+    ) -> Result<WorkspaceInfo, VaultError> {
+        VaultConfig::new(workspace_path.as_ref()).inspect().await
+    }
+
     /// OS path to the workspace root (filesystem root of this vault).
     pub fn workspace_path(&self) -> &Path {
         &self.workspace_path
     }
 
+    /// `path` as the filesystem layer should name it if it has to create it:
+    /// spelling dropped when the vault lowercases new names, characters kept
+    /// as the filename policy allows.
+    fn on_disk(&self, path: &VaultPath) -> VaultPath {
+        path.preserving_case(!self.lowercase_paths)
+            .with_filename_policy(self.filename_policy)
+    }
+
     /// Registers an [`IndexObserver`] that is notified of every note change the
     /// index records (see [`NoteChange`]). Zero-or-one per vault; registering
     /// again replaces the previous observer. The RAG client is its first
@@ -272,6 +591,20 @@ impl NoteVault {
         self.index.clear_observer_if(observer);
     }
 
+    /// Registers a hook that receives a [`MetricEvent`] for every completed
+    /// note save, search and sync pass, replacing any previous one. Shared
+    /// across clones, like the index observer. The hook runs inline on the
+    /// calling task, so keep it cheap (e.g. push into a histogram). Nothing
+    /// is timed while no hook is set.
+    pub fn set_metrics(&self, hook: Box<dyn Fn(MetricEvent) + Send + Sync>) {
+        self.metrics.set(hook);
+    }
+
+    /// Removes the metrics hook, if any.
+    pub fn clear_metrics(&self) {
+        self.metrics.clear();
+    }
+
     /// This vault's stable [`VaultId`], read from `.kimun/vault-id` (or
     /// generated and persisted there) on first call and cached in memory after
     /// that. Survives renames and moves, and keys the vault's collection on
@@ -309,18 +642,57 @@ impl NoteVault {
     /// already self-healed the index schema, so all that remains
     /// is a sync pass: a quick existence scan when the index was already
     /// current, or a full scan when it was just healed (and is thus empty).
+    /// When the index holds a noticeably different number of notes than the
+    /// disk (an interrupted first indexing, or a vault synced in from
+    /// elsewhere), the existence scan is upgraded to a size-checking one.
     /// This can be slow on large vaults.
     pub async fn validate_and_init(&self) -> Result<IndexReport, VaultError> {
         self.fail_on_case_conflicts().await?;
         if self.index.ready() {
-            // We only check if there are new notes
-            self.index_notes(NotesValidation::None).await
+            let validation = if self.index_drifted().await? {
+                debug!("Index note count drifted from the disk — running a fast sync");
+                NotesValidation::Fast
+            } else {
+                // We only check if there are new notes
+                NotesValidation::None
+            };
+            self.index_notes(validation).await
         } else {
             debug!("Index was healed on open — running a full sync");
             self.index_notes(NotesValidation::Full).await
         }
     }
 
+    /// Fast-start alternative to [`validate_and_init`](Self::validate_and_init)
+    /// for large vaults. When the index opened current, nothing is walked and
+    /// this returns `None` at once: the index keeps what it had, and each
+    /// directory catches up on disk changes when it's next browsed
+    /// ([`browse_vault`](Self::browse_vault) syncs the path it lists).
+    /// Search results may miss notes added outside the app until then, and
+    /// the case-conflict scan is skipped.
+    ///
+    /// When opening healed the index (missing or outdated schema), there is
+    /// nothing to defer to, so this runs the full sync and returns its report.
+    pub async fn init_lazy(&self) -> Result<Option<IndexReport>, VaultError> {
+        if self.index.ready() {
+            debug!("Index is current — deferring validation to directory browses");
+            return Ok(None);
+        }
+        self.validate_and_init().await.map(Some)
+    }
+
+    /// True when the indexed note count and the note files on disk differ by
+    /// more than [`INDEX_DRIFT_TOLERANCE`]. The disk side is a metadata-only
+    /// walk, so this stays cheap next to even a `None` sync.
+    async fn index_drifted(&self) -> Result<bool, VaultError> {
+        let workspace = self.workspace_path.clone();
+        let on_disk = tokio::task::spawn_blocking(move || nfs::count_notes(&workspace))
+            .await
+            .map_err(|e| VaultError::TaskJoin(format!("note count scan: {}", e)))??;
+        let indexed = self.index.notes_count().await?;
+        Ok(on_disk.abs_diff(indexed) > INDEX_DRIFT_TOLERANCE)
+    }
+
     /// Deletes all the cached data from the index by recreating its schema,
     /// then rebuilds it with a full sync pass.
     pub async fn recreate_index(&self) -> Result<IndexReport, VaultError> {
@@ -333,6 +705,20 @@ impl NoteVault {
             .await
     }
 
+    /// Shrinks the index database on disk: checkpoints and truncates the
+    /// write-ahead log, then `VACUUM`s the database to reclaim the space
+    /// left by deleted and rewritten notes. Long-lived vaults otherwise only
+    /// ever grow.
+    ///
+    /// Must not run concurrently with other writes to the vault (saves,
+    /// renames, sync passes): it holds an exclusive lock on the database
+    /// while it rebuilds, and writes that outwait the busy timeout fail.
+    /// Meant for an explicit maintenance action, not a background task.
+    pub async fn compact(&self) -> Result<(), VaultError> {
+        debug!("Compacting index database");
+        Ok(self.index.compact().await?)
+    }
+
     /// Traverses the whole vault directory and verifies the notes to
     /// update the cached data in the DB. The validation is defined by
     /// the validation mode:
@@ -355,15 +741,20 @@ impl NoteVault {
         mut index_report: IndexReport,
         validation_mode: NotesValidation,
     ) -> Result<IndexReport, VaultError> {
-        VaultSync::new(&self.index, self.workspace_path())
+        let started = self.metrics.start();
+        VaultSync::new(&self.index, self.workspace_path(), self.max_note_bytes)
+            .sequential(self.sequential_walk)
             .run(&VaultPath::root(), true, validation_mode, None)
             .await?;
         // A whole-vault sync just completed: the index mirrors the disk, so
         // the readiness probe reports true even when this instance healed or
         // recreated the schema earlier.
         self.index.mark_synced();
+        self.index.record_synced_at(SystemTime::now()).await?;
         index_report.finish();
         debug!("TIME: {}", index_report.duration.as_secs());
+        self.metrics
+            .record(MetricOp::Index, started, Some(&VaultPath::root()));
         Ok(index_report)
     }
 
@@ -425,6 +816,17 @@ impl NoteVault {
         }))
     }
 
+    /// Returns `path` if nothing exists there yet, otherwise the first free
+    /// sibling name picked by [`VaultPath::get_free_name`] (`note_1.md`,
+    /// `note_2.md`, … reusing gaps). Advisory only: create the note with
+    /// [`Self::create_note`] and retry on [`VaultError::NoteExists`] to stay
+    /// safe against a concurrent create.
+    pub async fn free_note_path(&self, path: &VaultPath) -> Result<VaultPath, VaultError> {
+        let (parent, _) = path.flatten().get_parent_path();
+        let taken = nfs::sibling_names(self.workspace_path(), &parent).await?;
+        Ok(path.get_free_name(&taken))
+    }
+
     /// Loads today's journal entry, creating it with a date heading if it does
     /// not exist yet. Returns the note details, its content, and `true` when the
     /// entry was freshly created.
@@ -449,6 +851,13 @@ impl NoteVault {
         )
     }
 
+    /// Whether `path` is a note inside the journal directory, at any depth.
+    /// Unlike [`Self::journal_date`] the name needn't be a date, so this is
+    /// the test for "belongs to the journal" when grouping or excluding notes.
+    pub fn is_journal_entry(&self, path: &VaultPath) -> bool {
+        path.is_note() && path.is_in(&self.journal_path)
+    }
+
     /// Parses the date out of a journal note path, or `None` when `note_path`
     /// is not a `YYYY-MM-DD` note directly under the journal directory.
     pub fn journal_date(&self, note_path: &VaultPath) -> Option<NaiveDate> {
@@ -465,6 +874,30 @@ impl NoteVault {
         }
     }
 
+    /// Finds the journal entry closest to `from` in direction `dir`, skipping
+    /// days without an entry. `from` itself never matches, so it need not
+    /// have an entry. Entries are listed from the index and dated with
+    /// [`Self::journal_date`]; returns `None` when there is no such entry.
+    pub async fn adjacent_journal(
+        &self,
+        from: NaiveDate,
+        dir: Direction,
+    ) -> Result<Option<(NaiveDate, VaultPath)>, VaultError> {
+        let entries = self.index.get_notes(&self.journal_path, false).await?;
+        let dated = entries.into_iter().filter_map(|(entry, _)| {
+            self.journal_date(&entry.path)
+                .map(|date| (date, entry.path))
+        });
+        Ok(match dir {
+            Direction::Previous => dated
+                .filter(|(date, _)| *date < from)
+                .max_by_key(|(date, _)| *date),
+            Direction::Next => dated
+                .filter(|(date, _)| *date > from)
+                .min_by_key(|(date, _)| *date),
+        })
+    }
+
     /// Loads the note at `path` if it exists; otherwise creates it with `default_text`
     /// (or empty if `None`) and returns that text.
     /// Returns the note's text and `true` when the note had to be created (it
@@ -505,6 +938,86 @@ impl NoteVault {
         Ok(NoteDetails::new(path, text))
     }
 
+    /// Returns the content hash of the note at `path` as of the last index
+    /// sync, read from the index without loading the note. Compare it with a
+    /// previously seen hash (or use [`NoteContentData::is_same_content`]) to
+    /// tell whether the note changed. Errors with
+    /// [`VaultError::VaultPathNotFound`] when the note isn't indexed.
+    pub async fn note_hash(&self, path: &VaultPath) -> Result<u64, VaultError> {
+        self.index
+            .note_hash(path)
+            .await?
+            .ok_or_else(|| VaultError::VaultPathNotFound {
+                path: path.to_string(),
+            })
+    }
+
+    /// Whether the note at `path` changed on disk since the index last saw
+    /// it, e.g. edited by another program while the app was open. Size and
+    /// mtime matching the index is taken as unchanged, as syncs do;
+    /// otherwise the file is read and its content hash compared, so a touch
+    /// without an edit is not dirty. Errors with
+    /// [`VaultError::VaultPathNotFound`] when the note isn't indexed.
+    pub async fn is_note_dirty(&self, path: &VaultPath) -> Result<bool, VaultError> {
+        let (indexed, content) = self.indexed_entry(path).await?;
+        let disk = nfs::note_entry_at(self.workspace_path(), path).await?;
+        if disk.size == indexed.size && disk.modified_secs == indexed.modified_secs {
+            return Ok(false);
+        }
+        // An oversized note is indexed with no content (so its stored hash
+        // is that of empty text) and is never read: any change counts.
+        if indexed.size > self.max_note_bytes || disk.size > self.max_note_bytes {
+            return Ok(true);
+        }
+        let text = self.get_note_text(path).await?;
+        Ok(nfs::hash_text(text) != content.hash)
+    }
+
+    /// Line diff from the note's indexed content to its content on disk,
+    /// for showing what an external edit changed before syncing it.
+    ///
+    /// Both sides are the raw text, markup included: the index keeps each
+    /// note's body as it was last indexed. Oversized notes (see
+    /// [`VaultConfig::max_note_bytes`]) keep no body and aren't read, so
+    /// their diff is empty. Errors with [`VaultError::VaultPathNotFound`]
+    /// when the note isn't indexed.
+    pub async fn diff_note(&self, path: &VaultPath) -> Result<Vec<DiffLine>, VaultError> {
+        let (indexed, _) = self.indexed_entry(path).await?;
+        let disk = nfs::note_entry_at(self.workspace_path(), path).await?;
+        if indexed.size > self.max_note_bytes || disk.size > self.max_note_bytes {
+            return Ok(vec![]);
+        }
+        let old = self.index.note_body(path).await?.unwrap_or_default();
+        let new = self.get_note_text(path).await?;
+        Ok(similar::TextDiff::from_lines(&old, &new)
+            .iter_all_changes()
+            .map(|change| {
+                let line = change.value().trim_end_matches('\n').to_string();
+                match change.tag() {
+                    similar::ChangeTag::Equal => DiffLine::Same(line),
+                    similar::ChangeTag::Delete => DiffLine::Removed(line),
+                    similar::ChangeTag::Insert => DiffLine::Added(line),
+                }
+            })
+            .collect())
+    }
+
+    /// The index's row for the note at `path`, or
+    /// [`VaultError::VaultPathNotFound`] when it has none.
+    async fn indexed_entry(
+        &self,
+        path: &VaultPath,
+    ) -> Result<(NoteEntryData, NoteContentData), VaultError> {
+        self.index
+            .search_note_by_path(path)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| VaultError::VaultPathNotFound {
+                path: path.to_string(),
+            })
+    }
+
     /// Returns the indexed content chunks for the note at `path`, keyed by the
     /// note path they belong to.
     pub async fn get_note_chunks(
@@ -517,15 +1030,77 @@ impl NoteVault {
 
     /// Searches notes using the vault's query syntax (see [`SearchTerms`]).
     /// Returns each matching note's entry and content data.
+    ///
+    /// A query with no terms (blank, or only an order directive; see
+    /// [`SearchTerms::is_empty`]) matches nothing and returns an empty list.
+    /// Frontends that show recent notes for an empty query should call
+    /// [`Self::search_or_recent`] instead.
+    ///
+    /// Reads the index only: notes created or edited outside kimün show up
+    /// once a sync has picked them up (see [`last_indexed_at`]). Run a
+    /// non-recursive [`browse_vault`] over a directory first to refresh
+    /// just that scope.
+    ///
+    /// [`last_indexed_at`]: Self::last_indexed_at
+    /// [`browse_vault`]: Self::browse_vault
     pub async fn search_notes<S: AsRef<str>>(
         &self,
         search_query: S,
     ) -> Result<Vec<(NoteEntryData, NoteContentData)>, VaultError> {
         let search_query = search_query.as_ref();
+        let started = self.metrics.start();
         let a = self.index.search(search_query).await?;
+        self.metrics.record(MetricOp::Search, started, None);
         Ok(a)
     }
 
+    /// One page of [`Self::search_notes`]: at most `limit` matching notes,
+    /// after skipping the first `offset`. Pages follow the query's order
+    /// directive (`or:`), then the note path, so stepping `offset` by
+    /// `limit` walks every match exactly once. Pair with
+    /// [`Self::count_search`] to show "20 of 340".
+    pub async fn search_notes_page<S: AsRef<str>>(
+        &self,
+        search_query: S,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<(NoteEntryData, NoteContentData)>, VaultError> {
+        let started = self.metrics.start();
+        let page = self
+            .index
+            .search_page(search_query.as_ref(), limit, offset)
+            .await?;
+        self.metrics.record(MetricOp::Search, started, None);
+        Ok(page)
+    }
+
+    /// The first `limit` results of [`Self::search_notes`] for
+    /// `search_query`, or, when the query has no terms (see
+    /// [`SearchTerms::is_empty`]), the `limit` most recently modified notes,
+    /// newest first. The single place the "empty query shows recent notes"
+    /// rule lives, so every frontend treats a blank search box the same way.
+    pub async fn search_or_recent<S: AsRef<str>>(
+        &self,
+        search_query: S,
+        limit: usize,
+    ) -> Result<Vec<(NoteEntryData, NoteContentData)>, VaultError> {
+        let search_query = search_query.as_ref();
+        if SearchTerms::from_query_string(search_query).is_empty() {
+            Ok(self.index.recent_notes(limit).await?)
+        } else {
+            self.search_notes_page(search_query, limit, 0).await
+        }
+    }
+
+    /// How many notes [`Self::search_notes`] returns for `search_query`,
+    /// counted in the index without loading them.
+    pub async fn count_search<S: AsRef<str>>(&self, search_query: S) -> Result<usize, VaultError> {
+        let started = self.metrics.start();
+        let count = self.index.count_search(search_query).await?;
+        self.metrics.record(MetricOp::Search, started, None);
+        Ok(count)
+    }
+
     /// Returns every distinct label persisted in the vault, lowercased.
     pub async fn list_labels(&self) -> Result<Vec<String>, VaultError> {
         Ok(self.index.list_labels().await?)
@@ -543,6 +1118,29 @@ impl NoteVault {
         Ok(self.index.suggest_notes_by_prefix(prefix, limit).await?)
     }
 
+    /// Wikilink completions for `query`: notes whose name or title contains
+    /// it, case-insensitive, capped at `limit`. Name prefixes rank first,
+    /// then title prefixes, then the remaining substring matches, so typing
+    /// a title finds a note whose filename reads differently. As with
+    /// [`Self::suggest_notes_by_prefix`], the link target to insert is the
+    /// suggestion's `name`.
+    pub async fn suggest_links(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<NoteSuggestion>, VaultError> {
+        Ok(self.index.suggest_links(query, limit).await?)
+    }
+
+    /// "Did you mean" corrections for a search that came back empty, best
+    /// first: `query` with each unknown plain word replaced by a close term
+    /// from the indexed notes ("recieve" → "receive"). Operators, prefixed
+    /// terms and quoted phrases are kept as typed. Empty when no word needs
+    /// (or has) a correction.
+    pub async fn suggest_query(&self, query: &str) -> Result<Vec<String>, VaultError> {
+        Ok(self.index.suggest_query(query).await?)
+    }
+
     /// Returns tag labels matching `prefix` (case-insensitive) paired with
     /// usage counts, capped at `limit`. Used to feed the hashtag autocomplete
     /// popup in both the editor and the search box.
@@ -570,6 +1168,171 @@ impl NoteVault {
         Ok(self.index.notes_with_label(name.as_ref()).await?)
     }
 
+    /// Renames the label `old` to `new` in every note: inline `#old` tokens
+    /// and frontmatter `tags` entries are rewritten on disk (`old` matches
+    /// case-insensitively, and a leading `#` on either argument is ignored),
+    /// while hashtags in code, links and URLs are left alone. Each changed
+    /// note is locked, backed up when backups are enabled, and re-indexed.
+    /// Returns the changed notes, sorted by path.
+    ///
+    /// Fails with [`VaultError::InvalidLabel`] when either name is not a
+    /// valid label (letters, digits and underscores).
+    pub async fn rename_tag(&self, old: &str, new: &str) -> Result<Vec<VaultPath>, VaultError> {
+        let old = old.strip_prefix('#').unwrap_or(old);
+        let new = new.strip_prefix('#').unwrap_or(new);
+        for label in [old, new] {
+            if !note::is_valid_label(label) {
+                return Err(VaultError::InvalidLabel {
+                    label: label.to_string(),
+                });
+            }
+        }
+
+        // Frontmatter tags are not indexed as labels, so every note is read;
+        // a substring check skips the rewrite for notes that cannot hold
+        // the label.
+        let old_lower = old.to_lowercase();
+        let mut paths: Vec<VaultPath> = self
+            .index
+            .get_all_notes()
+            .await?
+            .into_iter()
+            .map(|(entry, _)| entry.path)
+            .collect();
+        paths.sort();
+
+        let mut changed = Vec::new();
+        for path in paths {
+            let _guard = self.lock_note(&path).await;
+            let text = match self.get_note_text(&path).await {
+                Ok(text) => text,
+                // Deleted since the last sync: nothing to rename.
+                Err(e) if e.is_not_found() => continue,
+                Err(e) => return Err(e),
+            };
+            if !text.to_lowercase().contains(&old_lower) {
+                continue;
+            }
+            let (updated, modified) = note::replace_label(&text, old, new);
+            if modified {
+                self.save_note_unlocked(&path, updated).await?;
+                changed.push(path);
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Title, modification time, size and labels of each note in `paths`, in
+    /// the order given, straight from the index — no note is read. Meant for
+    /// rendering lists; notes that aren't indexed are left out.
+    pub async fn metadata_for(&self, paths: &[VaultPath]) -> Result<Vec<NoteMeta>, VaultError> {
+        Ok(self.index.metadata_for(paths).await?)
+    }
+
+    /// Reports how much disk space the vault uses. Note bytes are summed from
+    /// the index rather than the files, so they reflect the last sync;
+    /// attachments are measured by walking the vault (dot-entries excluded)
+    /// and the database by its files on disk.
+    pub async fn vault_size(&self) -> Result<VaultSize, VaultError> {
+        let notes_bytes = self.index.notes_size().await?;
+        let db_files = self.index.db_files();
+        let workspace = self.workspace_path.clone();
+        let (attachments_bytes, db_bytes) = tokio::task::spawn_blocking(move || {
+            let attachments = nfs::attachments_size(&workspace, &db_files)?;
+            Ok::<_, FSError>((attachments, nfs::files_size(&db_files)))
+        })
+        .await
+        .map_err(|e| VaultError::TaskJoin(format!("vault size scan: {}", e)))??;
+        Ok(VaultSize {
+            notes_bytes,
+            attachments_bytes,
+            db_bytes,
+        })
+    }
+
+    /// Returns every title shared by more than one note, paired with the
+    /// paths of the notes carrying it. Groups are sorted by title and paths
+    /// within a group by path.
+    ///
+    /// `case_insensitive` groups titles that differ only in case;
+    /// `ignore_untitled` leaves out notes with an empty title, which would
+    /// otherwise all collapse into a single group.
+    pub async fn duplicate_titles(
+        &self,
+        case_insensitive: bool,
+        ignore_untitled: bool,
+    ) -> Result<Vec<(String, Vec<VaultPath>)>, VaultError> {
+        Ok(self
+            .index
+            .duplicate_titles(case_insensitive, ignore_untitled)
+            .await?)
+    }
+
+    /// Returns clusters of notes whose content is identical, as recorded by
+    /// the content hash stored in the index. Only hashes shared by more than
+    /// one note are reported.
+    ///
+    /// All empty notes share a hash; pass `ignore_empty` to leave out notes
+    /// with a zero-length body.
+    pub async fn duplicate_contents(
+        &self,
+        ignore_empty: bool,
+    ) -> Result<Vec<Vec<VaultPath>>, VaultError> {
+        Ok(self.index.duplicate_contents(ignore_empty).await?)
+    }
+
+    /// When the last whole-vault sync ([`index_notes`](Self::index_notes),
+    /// [`validate_and_init`](Self::validate_and_init) or a recursive browse
+    /// from the root) completed. `None` until one has since the index was
+    /// created. Lets a UI show how stale search results may be.
+    pub async fn last_indexed_at(&self) -> Result<Option<SystemTime>, VaultError> {
+        Ok(self.index.synced_at().await?)
+    }
+
+    /// Every note and every resolved note-to-note link, read from the index
+    /// (see [`LinkGraph`]). Links to notes that don't exist are left out;
+    /// unlinked notes are still nodes.
+    pub async fn link_graph(&self) -> Result<LinkGraph, VaultError> {
+        let (nodes, edges) = self.index.link_graph().await?;
+        Ok(LinkGraph { nodes, edges })
+    }
+
+    /// Notes over [`VaultConfig::max_note_bytes`], largest first. Syncs index
+    /// these without their content, so they never match a content search;
+    /// surface them so the user can move or shrink them.
+    pub async fn oversized_notes(&self) -> Result<Vec<NoteEntryData>, VaultError> {
+        Ok(self.index.notes_larger_than(self.max_note_bytes).await?)
+    }
+
+    /// Counts notes per day over `range` (inclusive), for activity calendars
+    /// such as a heatmap. A note counts on the UTC day of its last
+    /// modification; days without a note are absent from the map.
+    pub async fn activity(
+        &self,
+        range: RangeInclusive<NaiveDate>,
+    ) -> Result<BTreeMap<NaiveDate, usize>, VaultError> {
+        if range.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+        Ok(self.index.activity(*range.start(), *range.end()).await?)
+    }
+
+    /// Picks a random indexed note, e.g. to resurface an old one. `scope`
+    /// limits the pick to the notes under that directory. Journal entries are
+    /// left out unless `scope` is the journal directory or lies inside it.
+    /// Returns `None` when no note qualifies.
+    pub async fn random_note(
+        &self,
+        scope: Option<VaultPath>,
+    ) -> Result<Option<VaultPath>, VaultError> {
+        let journal = self.journal_path();
+        let in_journal = scope
+            .as_ref()
+            .is_some_and(|s| s.is_like(journal) || s.is_in(journal));
+        let exclude = (!in_journal).then_some(journal);
+        Ok(self.index.random_note(scope.as_ref(), exclude).await?)
+    }
+
     /// Get notes under the given path. When `recursive` is false, only direct
     /// children are returned.
     pub async fn get_notes(
@@ -581,6 +1344,70 @@ impl NoteVault {
         Ok(notes)
     }
 
+    /// Number of notes in the vault, counted in the index without loading
+    /// them.
+    pub async fn count_notes(&self) -> Result<usize, VaultError> {
+        Ok(self.index.notes_count().await?)
+    }
+
+    /// `true` when the vault has no notes: none in the index and no note file
+    /// on disk, so a vault whose index hasn't been synced yet is not empty.
+    /// The disk side is a metadata-only walk. Onboarding uses it to offer
+    /// [`Self::seed_starter_notes`].
+    pub async fn is_empty(&self) -> Result<bool, VaultError> {
+        if self.count_notes().await? > 0 {
+            return Ok(false);
+        }
+        let workspace = self.workspace_path.clone();
+        let on_disk = tokio::task::spawn_blocking(move || nfs::count_notes(&workspace))
+            .await
+            .map_err(|e| VaultError::TaskJoin(format!("note count scan: {}", e)))??;
+        Ok(on_disk == 0)
+    }
+
+    /// Writes a few starter notes into an empty vault: a welcome note,
+    /// today's journal entry and, given its text, a keyboard-shortcuts note.
+    /// Core knows no key bindings, so `shortcuts` comes from the app that
+    /// owns them; `None` skips that note. Returns the paths created. Does
+    /// nothing when the vault already has notes (see [`Self::is_empty`]),
+    /// and never overwrites a file that is already on disk, so running it
+    /// again is harmless.
+    pub async fn seed_starter_notes(
+        &self,
+        shortcuts: Option<&str>,
+    ) -> Result<Vec<VaultPath>, VaultError> {
+        if !self.is_empty().await? {
+            return Ok(vec![]);
+        }
+        let (journal_title, journal_path) = self.get_todays_journal();
+        let mut notes = vec![(
+            VaultPath::note_path_from(starter::WELCOME_NOTE).absolute(),
+            starter::welcome_text(shortcuts.is_some()),
+        )];
+        if let Some(text) = shortcuts {
+            notes.push((
+                VaultPath::note_path_from(starter::SHORTCUTS_NOTE).absolute(),
+                text.to_string(),
+            ));
+        }
+        notes.push((journal_path, starter::journal_text(&journal_title)));
+        let mut created = vec![];
+        for (path, text) in notes {
+            match self.create_note(&path, text).await {
+                Ok(_) => created.push(path),
+                Err(VaultError::NoteExists { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(created)
+    }
+
+    /// Number of notes [`Self::get_notes`] would return for `path` and
+    /// `recursive`, counted in the index without loading them.
+    pub async fn count_in(&self, path: &VaultPath, recursive: bool) -> Result<usize, VaultError> {
+        Ok(self.index.notes_count_in(path, recursive).await?)
+    }
+
     /// Returns every note in the vault with its entry and content data.
     pub async fn get_all_notes(&self) -> Result<Vec<(NoteEntryData, NoteContentData)>, VaultError> {
         let a = self.index.get_all_notes().await?;
@@ -592,15 +1419,193 @@ impl NoteVault {
         path.to_pathbuf(self.workspace_path())
     }
 
+    /// Writes a zip archive of the vault to `writer` for backups: every note
+    /// and attachment, plus empty directories, at their workspace-relative
+    /// paths. Hidden entries (`.kimun`, `.trash`, dotfiles) and the index
+    /// database are left out; [`import_archive`](Self::import_archive)
+    /// restores the archive. Files are streamed in one at a time, so memory
+    /// stays bounded. Returns how many files were archived.
+    ///
+    /// Blocking: from async code, call it on a blocking thread.
+    pub fn export_archive<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: W,
+    ) -> Result<usize, VaultError> {
+        Ok(nfs::export_archive(self.workspace_path(), writer)?)
+    }
+
+    /// Imports a zip archive, one written by
+    /// [`export_archive`](Self::export_archive) or any other (an exported
+    /// Obsidian vault, say), under the `dest` directory. Markdown files become
+    /// notes and every other file an attachment, at the archive's relative
+    /// paths with each component sanitized as a [`VaultPath`] and named per
+    /// the vault's case and filename policies. Hidden entries and index files
+    /// are skipped. Entries are extracted one at a time, so memory stays
+    /// bounded.
+    ///
+    /// A file that already exists is handled per `on_conflict`:
+    /// [`ConflictPolicy::Error`] fails with [`FSError::AlreadyExists`] before
+    /// anything is written, `Rename` picks the first free sibling name and
+    /// `Overwrite` replaces it (backing up a replaced note when backups are
+    /// enabled). An entry that would land outside the vault fails with
+    /// [`FSError::InvalidPath`], also before anything is written. The
+    /// imported notes are indexed together in one transaction. Returns how
+    /// many files were imported. Reading the archive runs on blocking threads.
+    pub async fn import_archive<R: std::io::Read + std::io::Seek + Send + 'static>(
+        &self,
+        reader: R,
+        dest: &VaultPath,
+        on_conflict: ConflictPolicy,
+    ) -> Result<usize, VaultError> {
+        let dest = dest.flatten();
+        dest.ensure_directory()?;
+        let join_error =
+            |e: tokio::task::JoinError| VaultError::TaskJoin(format!("archive import: {}", e));
+        let (mut archive, entries) = tokio::task::spawn_blocking(move || {
+            let mut archive = nfs::ArchiveReader::open(reader)?;
+            let entries = archive.entries()?;
+            Ok::<_, FSError>((archive, entries))
+        })
+        .await
+        .map_err(join_error)??;
+
+        // Place every entry before extracting any, so a conflict under
+        // `ConflictPolicy::Error` leaves the vault untouched.
+        let key = |path: &VaultPath| path.flatten().absolute().to_string().to_lowercase();
+        let mut claimed: HashSet<String> = HashSet::new();
+        let mut directories = vec![];
+        let mut files = vec![];
+        for entry in entries {
+            let path = self.on_disk(&dest.append(&entry.path).flatten());
+            if entry.is_dir {
+                directories.push(path);
+                continue;
+            }
+            let taken = claimed.contains(&key(&path)) || self.exists(&path).await;
+            let (path, overwrite) = match on_conflict {
+                _ if !taken => (path, false),
+                ConflictPolicy::Error => return Err(FSError::AlreadyExists { path }.into()),
+                ConflictPolicy::Rename => {
+                    let (parent, _) = path.get_parent_path();
+                    let mut names = nfs::sibling_names(self.workspace_path(), &parent).await?;
+                    for other in &claimed {
+                        let dir = format!("{}{}", key(&parent), nfs::PATH_SEPARATOR);
+                        if let Some(name) = other.strip_prefix(&dir) {
+                            names.insert(name.to_string());
+                        }
+                    }
+                    (path.get_free_name(&names), false)
+                }
+                ConflictPolicy::Overwrite => (path, true),
+            };
+            claimed.insert(key(&path));
+            files.push((entry.index, path, overwrite));
+        }
+
+        for path in directories {
+            nfs::ensure_directory(self.workspace_path(), &path).await?;
+        }
+        let mut diff = IndexDiff {
+            to_add: vec![],
+            to_modify: vec![],
+            to_delete: vec![],
+        };
+        for (index, path, overwrite) in &files {
+            if *overwrite && path.is_note() {
+                self.backup_if_enabled(path).await?;
+            }
+            let (index, target, replace) = (*index, path.clone(), *overwrite);
+            let workspace = self.workspace_path.clone();
+            archive = tokio::task::spawn_blocking(move || {
+                archive.extract(index, &workspace, &target, replace)?;
+                Ok::<_, FSError>(archive)
+            })
+            .await
+            .map_err(join_error)??;
+            if !path.is_note() {
+                continue;
+            }
+            let entry = nfs::note_entry_at(self.workspace_path(), path).await?;
+            let text = if entry.size > self.max_note_bytes {
+                String::new()
+            } else {
+                self.get_note_text(path).await?
+            };
+            if *overwrite {
+                diff.to_modify.push((entry, text));
+            } else {
+                diff.to_add.push((entry, text));
+            }
+        }
+        self.index.apply(diff).await?;
+        Ok(files.len())
+    }
+
+    /// Resolves a resource link written inside `note`, such as the
+    /// `images/pic.png` of `![alt](images/pic.png)`, to its OS path. Relative
+    /// links resolve against the note's directory, a leading `/` against the
+    /// vault root, and `..` steps are applied as they go. A link that steps
+    /// above the vault root, or that reaches outside the workspace through a
+    /// symlink, is rejected with [`FSError::InvalidPath`]. The resource need
+    /// not exist.
+    pub async fn resolve_resource(
+        &self,
+        note: &VaultPath,
+        relative: &str,
+    ) -> Result<PathBuf, VaultError> {
+        let outside = || {
+            VaultError::FSError(FSError::InvalidPath {
+                path: relative.to_string(),
+                message: "resolves outside the vault".to_string(),
+            })
+        };
+        let target = relative.trim();
+        let mut slices = if target.starts_with(nfs::PATH_SEPARATOR) {
+            vec![]
+        } else {
+            note.flatten().get_parent_path().0.get_slices()
+        };
+        for part in target.split(nfs::PATH_SEPARATOR) {
+            match part {
+                "" | "." => {}
+                ".." => {
+                    slices.pop().ok_or_else(outside)?;
+                }
+                name => slices.push(name.to_string()),
+            }
+        }
+        let path = VaultPath::new(slices.join(&nfs::PATH_SEPARATOR.to_string())).absolute();
+        let os_path = nfs::resolve_path_on_disk(self.workspace_path(), &path).await;
+        // Symlinks are followed through the deepest existing ancestor, so a
+        // missing file behind a link out of the vault is caught as well.
+        let (target, workspace) = (os_path.clone(), self.workspace_path.clone());
+        let (real, root) = tokio::task::spawn_blocking(move || {
+            (
+                nfs::resolve_existing(&target),
+                nfs::resolve_existing(&workspace),
+            )
+        })
+        .await
+        .map_err(|e| VaultError::TaskJoin(format!("resource resolve: {}", e)))?;
+        if !real.starts_with(root) {
+            return Err(outside());
+        }
+        Ok(os_path)
+    }
+
     /// Walks the vault per `options`, streaming each entry as a
     /// [`SearchResult`] through the channel set up by
-    /// [`VaultBrowseOptionsBuilder::build`]. A recursive browse from the root
+    /// [`VaultBrowseOptionsBuilder::build`]: notes, subdirectories and
+    /// attachments alike, plus a directory result for the browsed path itself
+    /// (callers listing a folder skip it). A recursive browse from the root
     /// doubles as a full index sync.
     pub async fn browse_vault(&self, options: VaultBrowseOptions) -> Result<(), VaultError> {
         let start = std::time::SystemTime::now();
+        let started = self.metrics.start();
         debug!("> Start fetching files with Options:\n{}", options);
 
-        VaultSync::new(&self.index, self.workspace_path())
+        VaultSync::new(&self.index, self.workspace_path(), self.max_note_bytes)
+            .sequential(self.sequential_walk)
             .run(
                 &options.path,
                 options.recursive,
@@ -615,12 +1620,14 @@ impl NoteVault {
         // synced (the rest of a healed index could still be empty).
         if options.recursive && options.path.is_root_or_empty() {
             self.index.mark_synced();
+            self.index.record_synced_at(SystemTime::now()).await?;
         }
 
-        let time = std::time::SystemTime::now()
-            .duration_since(start)
-            .expect("Something's wrong with the time");
+        // A clock stepped backwards mid-browse only skews this debug line.
+        let time = start.elapsed().unwrap_or_default();
         debug!("> Files fetched in {} milliseconds", time.as_millis());
+        self.metrics
+            .record(MetricOp::Index, started, Some(&options.path));
 
         Ok(())
     }
@@ -767,31 +1774,60 @@ impl NoteVault {
         path: &VaultPath,
         text: S,
     ) -> Result<(NoteEntryData, NoteContentData), VaultError> {
-        let entry_data = nfs::create_note_exclusive(self.workspace_path(), path, &text)
-            .await
-            .map_err(|e| match e {
-                FSError::AlreadyExists { path } => VaultError::NoteExists { path },
-                other => VaultError::FSError(other),
-            })?;
+        let started = self.metrics.start();
+        let entry_data =
+            nfs::create_note_exclusive(self.workspace_path(), &self.on_disk(path), &text)
+                .await
+                .map_err(|e| match e {
+                    FSError::AlreadyExists { path } => VaultError::NoteExists { path },
+                    other => VaultError::FSError(other),
+                })?;
         let note_details = NoteDetails::new(path, text);
         let content_data = self.index.save_note(&entry_data, &note_details).await?;
+        self.metrics.record(MetricOp::Save, started, Some(path));
         Ok((entry_data, content_data))
     }
 
-    /// Creates a directory at `path`, failing with
-    /// [`VaultError::DirectoryExists`] if one is already there.
-    pub async fn create_directory(
+    /// Like [`Self::create_note`], but with a display title kept apart from
+    /// the filename: `title` is written into the note's frontmatter
+    /// (`title:`, added to any frontmatter `text` already has), so `path`
+    /// can stay a filesystem-safe slug while the note's title — which
+    /// prefers frontmatter — reads as typed.
+    pub async fn create_note_with_title<S: AsRef<str>>(
         &self,
         path: &VaultPath,
-    ) -> Result<DirectoryEntryData, VaultError> {
-        nfs::create_directory(self.workspace_path(), path)
-            .await
+        title: &str,
+        text: S,
+    ) -> Result<(NoteEntryData, NoteContentData), VaultError> {
+        let text = note::with_frontmatter_title(text.as_ref(), title);
+        self.create_note(path, text).await
+    }
+
+    /// Creates a directory at `path`, failing with
+    /// [`VaultError::DirectoryExists`] if one is already there.
+    pub async fn create_directory(
+        &self,
+        path: &VaultPath,
+    ) -> Result<DirectoryEntryData, VaultError> {
+        nfs::create_directory(self.workspace_path(), &self.on_disk(path))
+            .await
             .map_err(|e| match e {
                 FSError::AlreadyExists { path } => VaultError::DirectoryExists { path },
                 other => VaultError::FSError(other),
             })
     }
 
+    /// Makes sure a directory exists at `path`, creating it and any missing
+    /// parents. Unlike [`Self::create_directory`], an existing directory is
+    /// not an error, so this is the call for "make sure the folder is there"
+    /// flows. Fails if a note or other file occupies the path.
+    pub async fn ensure_directory(
+        &self,
+        path: &VaultPath,
+    ) -> Result<DirectoryEntryData, VaultError> {
+        Ok(nfs::ensure_directory(self.workspace_path(), &self.on_disk(path)).await?)
+    }
+
     /// Backs up the current content of `path` when this vault was opened with
     /// backups enabled (CLI/MCP), and is a no-op otherwise (TUI). Called before
     /// any destructive write so the previous content stays recoverable.
@@ -810,7 +1846,10 @@ impl NoteVault {
     async fn lock_note(&self, path: &VaultPath) -> tokio::sync::OwnedMutexGuard<()> {
         let key = path.flatten();
         let lock = {
-            let mut map = self.note_locks.lock().unwrap();
+            let mut map = self
+                .note_locks
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
             map.entry(key)
                 .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
                 .clone()
@@ -876,13 +1915,65 @@ impl NoteVault {
         path: &VaultPath,
         text: S,
     ) -> Result<(NoteEntryData, NoteContentData), VaultError> {
+        let started = self.metrics.start();
         self.backup_if_enabled(path).await?;
-        let entry_data = nfs::save_note(self.workspace_path(), path, &text).await?;
-        let note_details = NoteDetails::new(path, text);
+        let entry_data = nfs::save_note(self.workspace_path(), &self.on_disk(path), &text).await?;
+        // Same rule as a sync: oversized notes keep a row but no content.
+        let note_details = if entry_data.size > self.max_note_bytes {
+            NoteDetails::new(path, "")
+        } else {
+            NoteDetails::new(path, text)
+        };
         let content_data = self.index.save_note(&entry_data, &note_details).await?;
+        self.metrics.record(MetricOp::Save, started, Some(path));
         Ok((entry_data, content_data))
     }
 
+    /// Stores `text` as the recovery copy of `path`, in the hidden
+    /// `.kimun/recovery` directory, without touching the note or the index:
+    /// an editor's safety net for its unsaved buffer, written before a save
+    /// and cleared with [`Self::discard_recovery`] once the save lands, so a
+    /// failed save or a killed process leaves the text behind. A later copy
+    /// for the same note replaces the earlier one. See [`Self::list_recovery`]
+    /// and [`Self::recover`].
+    pub async fn save_recovery<S: AsRef<str>>(
+        &self,
+        path: &VaultPath,
+        text: S,
+    ) -> Result<(), VaultError> {
+        let path = path.flatten();
+        path.ensure_note()?;
+        nfs::write_recovery(self.workspace_path(), &path, text.as_ref()).await?;
+        Ok(())
+    }
+
+    /// The recovery copies waiting (see [`Self::save_recovery`]), sorted by
+    /// note path. A launch should offer to restore those that
+    /// [`are newer than their note`](RecoveryCopy::is_newer_than_note).
+    pub async fn list_recovery(&self) -> Result<Vec<RecoveryCopy>, VaultError> {
+        Ok(nfs::list_recovery(self.workspace_path()).await?)
+    }
+
+    /// Restores the recovery copy of `path`: saves it over the note (creating
+    /// the note if it is gone) and then removes the copy. Errors with
+    /// [`FSError::VaultPathNotFound`] when `path` has no recovery copy.
+    pub async fn recover(
+        &self,
+        path: &VaultPath,
+    ) -> Result<(NoteEntryData, NoteContentData), VaultError> {
+        let path = path.flatten();
+        let text = nfs::read_recovery(self.workspace_path(), &path).await?;
+        let saved = self.save_note(&path, text).await?;
+        nfs::remove_recovery(self.workspace_path(), &path).await?;
+        Ok(saved)
+    }
+
+    /// Drops the recovery copy of `path` without restoring it. A no-op when
+    /// there is none.
+    pub async fn discard_recovery(&self, path: &VaultPath) -> Result<(), VaultError> {
+        Ok(nfs::remove_recovery(self.workspace_path(), &path.flatten()).await?)
+    }
+
     /// Default attachments directory (e.g. `/assets`) inside the workspace.
     pub fn default_attachments_path(&self) -> VaultPath {
         VaultPath::new(DEFAULT_ASSETS_PATH)
@@ -929,6 +2020,36 @@ impl NoteVault {
         }
     }
 
+    /// Brings the index entry of the single note at `path` in line with the
+    /// file on disk, for notes edited outside the app: the file is read and
+    /// its row, chunks, links and labels are replaced in one transaction (or
+    /// inserted, for a note the index hasn't seen). When the file is gone the
+    /// note is removed from the index instead. The cheap alternative to a
+    /// [`browse_vault`](Self::browse_vault) over its directory.
+    pub async fn reindex_note(&self, path: &VaultPath) -> Result<(), VaultError> {
+        let path = path.flatten();
+        path.ensure_note()?;
+        let _guard = self.lock_note(&path).await;
+        let entry = match nfs::note_entry_at(self.workspace_path(), &path).await {
+            Ok(entry) => entry,
+            Err(FSError::VaultPathNotFound { .. }) => {
+                self.index.delete_notes(std::slice::from_ref(&path)).await?;
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        // Same rule as a sync: oversized notes keep a row but no content.
+        let text = if entry.size > self.max_note_bytes {
+            String::new()
+        } else {
+            self.get_note_text(&path).await?
+        };
+        self.index
+            .save_note(&entry, &NoteDetails::new(&path, text))
+            .await?;
+        Ok(())
+    }
+
     /// Deletes the note at `path` (backing it up first when backups are
     /// enabled). The index row is removed before the file, so the index never
     /// points at a missing file.
@@ -1056,6 +2177,67 @@ impl NoteVault {
         Ok(ReplacePreview { count, content })
     }
 
+    /// Locates every non-overlapping match of `pattern` in `text`. Pure — no
+    /// I/O.
+    ///
+    /// `pattern` is a literal substring unless `regex` is `true`, following
+    /// the same rules as `compute_replacement`. An empty literal pattern
+    /// matches nothing; an invalid regex yields [`VaultError::InvalidRegex`].
+    fn compute_matches(
+        text: &str,
+        pattern: &str,
+        regex: bool,
+    ) -> Result<Vec<MatchSpan>, VaultError> {
+        let ranges: Vec<(usize, usize)> = if regex {
+            let re = regex::Regex::new(pattern).map_err(|e| VaultError::InvalidRegex {
+                pattern: pattern.to_string(),
+                message: e.to_string(),
+            })?;
+            re.find_iter(text).map(|m| (m.start(), m.end())).collect()
+        } else if pattern.is_empty() {
+            vec![]
+        } else {
+            text.match_indices(pattern)
+                .map(|(start, m)| (start, start + m.len()))
+                .collect()
+        };
+
+        // Matches come in ascending order, so chars and lines are counted
+        // incrementally from the previous position instead of from the start.
+        let mut spans = Vec::with_capacity(ranges.len());
+        let (mut byte_pos, mut char_pos, mut line) = (0, 0, 0);
+        for (start, end) in ranges {
+            let before = &text[byte_pos..start];
+            char_pos += before.chars().count();
+            line += before.matches('\n').count();
+            let char_start = char_pos;
+            let char_end = char_start + text[start..end].chars().count();
+            spans.push(MatchSpan {
+                byte_start: start,
+                byte_end: end,
+                char_start,
+                char_end,
+                line,
+            });
+            byte_pos = start;
+        }
+        Ok(spans)
+    }
+
+    /// Returns the position of every match of `pattern` in the note at
+    /// `path`, so an editor can scroll to and select a hit. See
+    /// `compute_matches` for the literal vs `regex` rules. Reads the note
+    /// without taking the write lock; the result is advisory.
+    pub async fn find_in_note(
+        &self,
+        path: &VaultPath,
+        pattern: &str,
+        regex: bool,
+    ) -> Result<Vec<MatchSpan>, VaultError> {
+        let text = self.get_note_text(path).await?;
+        Self::compute_matches(&text, pattern, regex)
+    }
+
     /// Deletes the directory at `path` and its contents, removing the
     /// corresponding index rows first.
     pub async fn delete_directory(&self, path: &VaultPath) -> Result<(), VaultError> {
@@ -1073,12 +2255,30 @@ impl NoteVault {
 
     /// Renames the note `from` to `to`, rewriting links to it (wikilinks,
     /// Markdown links, and the note's own self-links) in every backlinking note
-    /// so they keep pointing at the renamed note. Fails if `to` already exists.
-    /// Source, destination, and all link victims are locked for the whole
-    /// operation so a concurrent in-process write can't interleave.
-    pub async fn rename_note(&self, from: &VaultPath, to: &VaultPath) -> Result<(), VaultError> {
+    /// so they keep pointing at the renamed note. Source, destination, and all
+    /// link victims are locked for the whole operation so a concurrent
+    /// in-process write can't interleave.
+    ///
+    /// `on_conflict` decides what happens when a note already exists at `to`
+    /// (see [`ConflictPolicy`]). Returns the path the note ended up at, which
+    /// differs from `to` only under [`ConflictPolicy::Rename`].
+    pub async fn rename_note(
+        &self,
+        from: &VaultPath,
+        to: &VaultPath,
+        on_conflict: ConflictPolicy,
+    ) -> Result<VaultPath, VaultError> {
         let from = from.flatten();
-        let to = to.flatten();
+        let mut to = to.flatten();
+        let overwrite = match on_conflict {
+            ConflictPolicy::Error => false,
+            ConflictPolicy::Rename if !from.is_like(&to) => {
+                to = self.free_note_path(&to).await?;
+                false
+            }
+            ConflictPolicy::Rename => false,
+            ConflictPolicy::Overwrite => !from.is_like(&to) && self.exists(&to).await,
+        };
 
         // Scout the linking notes, then lock the source, the destination, and
         // every victim for the whole rename, so a concurrent in-process write
@@ -1100,11 +2300,40 @@ impl NoteVault {
         // mutation yet, so a failure here aborts cleanly.
         let prepared = scouted.prepare().await?;
 
+        // Clear the way for an overwrite: the existing target (backed up
+        // first when enabled) is moved aside, not deleted, so a failed rename
+        // can put it back.
+        let replaced = if overwrite {
+            self.backup_if_enabled(&to).await?;
+            Some(nfs::set_aside_note(self.workspace_path(), &to).await?)
+        } else {
+            None
+        };
+
         // Rename the source note on disk. If this fails, victims remain
-        // untouched and the index is unchanged — clean abort.
-        nfs::rename_note(self.workspace_path(), &from, &to)
+        // untouched, an overwritten target is restored and the index is
+        // unchanged — clean abort.
+        let renamed = nfs::rename_note(self.workspace_path(), &from, &self.on_disk(&to))
             .await
-            .map_err(rename_dest_err)?;
+            .map_err(|e| match e {
+                FSError::AlreadyExists { path } => VaultError::NoteExists { path },
+                other => VaultError::FSError(other),
+            });
+        if let Err(e) = renamed {
+            if let Some(replaced) = replaced {
+                replaced.restore().await?;
+            }
+            return Err(e);
+        }
+
+        // The rename took the target's place: only now do its file and its
+        // index rows go.
+        if let Some(replaced) = replaced {
+            if let Err(e) = replaced.discard().await {
+                warn!("Couldn't delete the overwritten note {}: {}", to, e);
+            }
+            self.index.delete_notes(std::slice::from_ref(&to)).await?;
+        }
 
         // Write the rewritten victims and the renamed note's self-links.
         let notes_with_text = prepared.commit().await?;
@@ -1114,9 +2343,178 @@ impl NoteVault {
         // rename but the index is stale — next sync pass corrects.
         self.index.rename_note(&from, &to, &notes_with_text).await?;
 
+        Ok(to)
+    }
+
+    /// Merges the notes at `sources` into `dest`, in order, joining them with
+    /// `separator` (e.g. `"\n\n---\n\n"`). `dest` is created when missing;
+    /// otherwise its current content is kept and the sources are appended
+    /// after it. Each source becomes a section that opens with a heading: its
+    /// own leading heading when it has one, else one built from its file name.
+    /// Source frontmatter is dropped.
+    ///
+    /// With `delete_sources` the sources are deleted afterwards and every
+    /// link pointing at them — in other notes and in the merged text — is
+    /// rewritten to point at `dest`. Every touched note is locked for the
+    /// whole operation and backed up before it is changed when backups are
+    /// enabled. `dest` may not be one of the `sources`.
+    pub async fn merge_notes(
+        &self,
+        sources: &[VaultPath],
+        dest: &VaultPath,
+        separator: &str,
+        delete_sources: bool,
+    ) -> Result<(), VaultError> {
+        let dest = dest.flatten();
+        dest.ensure_note()?;
+        let sources: Vec<VaultPath> = sources.iter().map(|p| p.flatten()).collect();
+        for source in &sources {
+            source.ensure_note()?;
+            if source.is_like(&dest) {
+                return Err(VaultError::FSError(FSError::InvalidPath {
+                    path: dest.to_string(),
+                    message: "Destination is one of the notes being merged".to_string(),
+                }));
+            }
+        }
+
+        // Notes linking to a merged-away source; their links move to `dest`.
+        let mut victims = vec![];
+        if delete_sources {
+            for source in &sources {
+                for (entry, _) in self.index.get_backlinks(source).await? {
+                    if !sources.contains(&entry.path) && entry.path != dest {
+                        victims.push(entry.path);
+                    }
+                }
+            }
+            victims.sort();
+            victims.dedup();
+        }
+        let _guards = self
+            .lock_notes(
+                sources
+                    .iter()
+                    .chain(std::iter::once(&dest))
+                    .chain(victims.iter()),
+            )
+            .await;
+
+        // Read and build everything before writing, so a missing source or an
+        // unreadable victim aborts without touching the vault.
+        let mut sections = vec![];
+        match nfs::load_note(self.workspace_path(), &dest).await {
+            Ok(text) if !text.trim().is_empty() => sections.push(text.trim_end().to_string()),
+            Ok(_) => {}
+            Err(e) if e.is_not_found() => {}
+            Err(e) => return Err(e.into()),
+        }
+        for source in &sources {
+            let text = self.get_note_text(source).await?;
+            sections.push(note::section_for_merge(&text, &source.get_clean_name()));
+        }
+        let mut merged = sections.join(separator);
+        let mut rewritten = vec![];
+        if delete_sources {
+            for source in &sources {
+                merged = note::replace_note_links(&merged, source, &dest).0;
+            }
+            for victim in &victims {
+                let mut text = self.get_note_text(victim).await?;
+                for source in &sources {
+                    text = note::replace_note_links(&text, source, &dest).0;
+                }
+                rewritten.push((victim, text));
+            }
+        }
+
+        self.save_note_unlocked(&dest, merged).await?;
+        for (victim, text) in rewritten {
+            self.save_note_unlocked(victim, text).await?;
+        }
+        if delete_sources {
+            for source in &sources {
+                self.backup_if_enabled(source).await?;
+            }
+            self.index.delete_notes(&sources).await?;
+            for source in &sources {
+                nfs::delete_note(self.workspace_path(), source).await?;
+            }
+        }
         Ok(())
     }
 
+    /// Splits the note at `path` at every heading of `level` (`2` splits at
+    /// `##`), moving each section into a new note in the same directory named
+    /// after its heading. A taken name is bumped with
+    /// [`VaultPath::get_free_name`]. Returns the new notes in document
+    /// order; a note with no heading of that level is left untouched and an
+    /// empty list is returned.
+    ///
+    /// Whatever is not part of a section (frontmatter, text before the first
+    /// split heading, shallower headings) stays in the original note. With
+    /// `leave_index` a list of wikilinks to the new notes is appended to it;
+    /// otherwise the original is deleted when nothing else is left in it.
+    pub async fn split_note(
+        &self,
+        path: &VaultPath,
+        level: u8,
+        leave_index: bool,
+    ) -> Result<Vec<VaultPath>, VaultError> {
+        let path = path.flatten();
+        path.ensure_note()?;
+        let _guard = self.lock_note(&path).await;
+        let text = self.get_note_text(&path).await?;
+        let (remainder, sections) = note::split_sections(&text, level);
+        if sections.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let (parent, _) = path.get_parent_path();
+        let mut taken = nfs::sibling_names(self.workspace_path(), &parent).await?;
+        let mut created = Vec::with_capacity(sections.len());
+        for (title, section) in sections {
+            // The heading names one note, so a `/` in it must not open a
+            // directory; the rest is sanitized like any other path.
+            let name = title.replace(nfs::PATH_SEPARATOR, "_");
+            let wanted = parent.append(&VaultPath::note_path_from(name));
+            // Every candidate tried is taken from then on, so a name the
+            // create refuses is never picked twice.
+            let candidate = loop {
+                let candidate = wanted.get_free_name(&taken);
+                taken.insert(candidate.get_name());
+                match self.create_note(&candidate, section.trim_end()).await {
+                    Ok(_) => break candidate,
+                    Err(VaultError::NoteExists { .. }) => continue,
+                    Err(e) => return Err(e),
+                }
+            };
+            created.push(candidate);
+        }
+
+        let remainder = remainder.trim_end();
+        if leave_index {
+            let links = created
+                .iter()
+                .map(|p| format!("- [[{}]]", p.get_clean_name()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let index_text = if remainder.is_empty() {
+                links
+            } else {
+                format!("{remainder}\n\n{links}")
+            };
+            self.save_note_unlocked(&path, index_text).await?;
+        } else if remainder.trim().is_empty() {
+            self.backup_if_enabled(&path).await?;
+            self.index.delete_notes(std::slice::from_ref(&path)).await?;
+            nfs::delete_note(self.workspace_path(), &path).await?;
+        } else {
+            self.save_note_unlocked(&path, remainder).await?;
+        }
+        Ok(created)
+    }
+
     /// Renames the directory `from` to `to`, updating the index paths of all
     /// notes beneath it. Fails if `to` already exists.
     pub async fn rename_directory(
@@ -1127,7 +2525,7 @@ impl NoteVault {
         let from = from.flatten();
         let to = to.flatten();
 
-        nfs::rename_directory(self.workspace_path(), &from, &to)
+        nfs::rename_directory(self.workspace_path(), &from, &self.on_disk(&to))
             .await
             .map_err(rename_dest_err)?;
 
@@ -1214,7 +2612,10 @@ impl NoteVault {
     /// the UI uses to rename or move any entry without classifying it itself.
     pub async fn rename_entry(&self, from: &VaultPath, to: &VaultPath) -> Result<(), VaultError> {
         match self.entry_kind(from).await? {
-            EntryKind::Note => self.rename_note(from, to).await,
+            EntryKind::Note => self
+                .rename_note(from, to, ConflictPolicy::Error)
+                .await
+                .map(|_| ()),
             EntryKind::Directory => self.rename_directory(from, to).await,
             EntryKind::Attachment => self.rename_attachment(from, to).await,
         }
@@ -1430,10 +2831,27 @@ impl Display for NotesValidation {
     }
 }
 
+/// Fails unless `workspace_path` is an existing directory.
+fn check_workspace_dir(workspace_path: &Path) -> Result<(), VaultError> {
+    if !nfs::os_path_exists(workspace_path) {
+        return Err(VaultError::VaultPathNotFound {
+            path: path_to_string(workspace_path),
+        });
+    }
+    if !nfs::dir_exists(workspace_path) {
+        return Err(VaultError::FSError(FSError::InvalidPath {
+            path: path_to_string(workspace_path),
+            message: "Path provided is not a directory".to_string(),
+        }));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::NaiveDate;
+    use futures_util::future::BoxFuture;
     use std::time::Duration;
     use tempfile::TempDir;
 
@@ -1520,7 +2938,11 @@ mod tests {
         vault.set_index_observer(obs.clone());
 
         vault
-            .rename_note(&VaultPath::new("old.md"), &VaultPath::new("new.md"))
+            .rename_note(
+                &VaultPath::new("old.md"),
+                &VaultPath::new("new.md"),
+                ConflictPolicy::Error,
+            )
             .await
             .unwrap();
 
@@ -1654,6 +3076,239 @@ mod tests {
         );
     }
 
+    // ---- search index ----
+
+    /// Test search index: keeps each note's chunk text in memory and answers
+    /// a query with every note containing all its terms, in reverse path
+    /// order (so the results can't be mistaken for SQLite's). While
+    /// `failing` is set, indexing a note fails.
+    #[derive(Debug, Default)]
+    struct MemorySearchIndex {
+        notes: std::sync::Mutex<std::collections::BTreeMap<VaultPath, String>>,
+        rebuilds: std::sync::atomic::AtomicUsize,
+        failing: std::sync::atomic::AtomicBool,
+    }
+
+    impl SearchIndex for MemorySearchIndex {
+        fn index_note<'a>(
+            &'a self,
+            path: &'a VaultPath,
+            chunks: &'a [ContentChunk],
+        ) -> BoxFuture<'a, Result<(), error::DBError>> {
+            Box::pin(async move {
+                if self.failing.load(std::sync::atomic::Ordering::Relaxed) {
+                    return Err(error::DBError::Other("engine offline".to_string()));
+                }
+                let text = chunks.iter().map(|c| c.text.as_str()).collect::<Vec<_>>();
+                self.notes
+                    .lock()
+                    .unwrap()
+                    .insert(path.clone(), text.join("\n"));
+                Ok(())
+            })
+        }
+
+        fn remove_note<'a>(
+            &'a self,
+            path: &'a VaultPath,
+        ) -> BoxFuture<'a, Result<(), error::DBError>> {
+            Box::pin(async move {
+                self.notes.lock().unwrap().remove(path);
+                Ok(())
+            })
+        }
+
+        fn search<'a>(
+            &'a self,
+            terms: &'a [String],
+        ) -> BoxFuture<'a, Result<Vec<VaultPath>, error::DBError>> {
+            Box::pin(async move {
+                let notes = self.notes.lock().unwrap();
+                Ok(notes
+                    .iter()
+                    .rev()
+                    .filter(|(_, text)| terms.iter().all(|t| text.contains(t.as_str())))
+                    .map(|(path, _)| path.clone())
+                    .collect())
+            })
+        }
+
+        fn rebuild(&self) -> BoxFuture<'_, Result<(), error::DBError>> {
+            Box::pin(async move {
+                self.rebuilds
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                self.notes.lock().unwrap().clear();
+                Ok(())
+            })
+        }
+    }
+
+    fn result_paths(results: Vec<(NoteEntryData, NoteContentData)>) -> Vec<String> {
+        results
+            .into_iter()
+            .map(|(e, _)| e.path.to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn search_index_mirrors_writes_and_serves_searches() {
+        let dir = TempDir::new().unwrap();
+        let search_index = Arc::new(MemorySearchIndex::default());
+        let vault =
+            NoteVault::new(VaultConfig::new(dir.path()).with_search_index(search_index.clone()))
+                .await
+                .unwrap();
+        // Attaching the search index rebuilds it.
+        assert_eq!(
+            search_index
+                .rebuilds
+                .load(std::sync::atomic::Ordering::Relaxed),
+            1
+        );
+
+        vault
+            .create_note(&VaultPath::new("a.md"), "shared apple")
+            .await
+            .unwrap();
+        vault
+            .create_note(&VaultPath::new("b.md"), "shared pear")
+            .await
+            .unwrap();
+        let paths = |results: Vec<(NoteEntryData, NoteContentData)>| {
+            results
+                .into_iter()
+                .map(|(e, _)| e.path.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            paths(vault.search_notes("shared").await.unwrap()),
+            ["/b.md", "/a.md"]
+        );
+
+        vault
+            .rename_note(
+                &VaultPath::new("a.md"),
+                &VaultPath::new("c.md"),
+                ConflictPolicy::Error,
+            )
+            .await
+            .unwrap();
+        vault.delete_note(&VaultPath::new("b.md")).await.unwrap();
+        assert_eq!(
+            paths(vault.search_notes("shared").await.unwrap()),
+            ["/c.md"]
+        );
+        assert_eq!(paths(vault.search_notes("apple").await.unwrap()), ["/c.md"]);
+
+        vault.recreate_index().await.unwrap();
+        assert_eq!(
+            search_index
+                .rebuilds
+                .load(std::sync::atomic::Ordering::Relaxed),
+            2
+        );
+        assert_eq!(paths(vault.search_notes("apple").await.unwrap()), ["/c.md"]);
+    }
+
+    #[tokio::test]
+    async fn search_index_matches_are_filtered_by_the_rest_of_the_query() {
+        let dir = TempDir::new().unwrap();
+        let search_index = Arc::new(MemorySearchIndex::default());
+        let vault =
+            NoteVault::new(VaultConfig::new(dir.path()).with_search_index(search_index.clone()))
+                .await
+                .unwrap();
+        for (path, text) in [
+            ("a.md", "# Zebra\nshared apple #fruit"),
+            ("b.md", "# Apple\nshared pear"),
+            ("sub/c.md", "# Mango\nshared apple"),
+        ] {
+            vault
+                .create_note(&VaultPath::new(path), text)
+                .await
+                .unwrap();
+        }
+
+        let search = |query: &'static str| {
+            let vault = vault.clone();
+            async move { result_paths(vault.search_notes(query).await.unwrap()) }
+        };
+        // The engine's order (reverse path) is kept.
+        assert_eq!(search("shared").await, ["/sub/c.md", "/b.md", "/a.md"]);
+        assert_eq!(search("shared #fruit").await, ["/a.md"]);
+        assert_eq!(search("shared /sub").await, ["/sub/c.md"]);
+        assert_eq!(search("shared -pear").await, ["/sub/c.md", "/a.md"]);
+        assert_eq!(search("shared =b").await, ["/b.md"]);
+        assert_eq!(search("apple -#fruit").await, ["/sub/c.md"]);
+        assert_eq!(
+            search("shared or:title").await,
+            ["/b.md", "/sub/c.md", "/a.md"]
+        );
+        assert_eq!(vault.count_search("shared -pear").await.unwrap(), 2);
+
+        // Pages are cut in SQL, in the same order.
+        let page = vault.search_notes_page("shared", 2, 1).await.unwrap();
+        assert_eq!(result_paths(page), ["/b.md", "/a.md"]);
+        let page = vault
+            .search_notes_page("shared or:title", 2, 0)
+            .await
+            .unwrap();
+        assert_eq!(result_paths(page), ["/b.md", "/sub/c.md"]);
+    }
+
+    #[tokio::test]
+    async fn search_index_is_filled_from_the_index_when_attached() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        vault
+            .create_note(&VaultPath::new("a.md"), "existing apple")
+            .await
+            .unwrap();
+        drop(vault);
+
+        let search_index = Arc::new(MemorySearchIndex::default());
+        let vault =
+            NoteVault::new(VaultConfig::new(dir.path()).with_search_index(search_index.clone()))
+                .await
+                .unwrap();
+        assert_eq!(
+            result_paths(vault.search_notes("apple").await.unwrap()),
+            ["/a.md"]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_search_index_write_keeps_the_save_and_rebuilds_the_engine() {
+        let dir = TempDir::new().unwrap();
+        let search_index = Arc::new(MemorySearchIndex::default());
+        let vault =
+            NoteVault::new(VaultConfig::new(dir.path()).with_search_index(search_index.clone()))
+                .await
+                .unwrap();
+        search_index
+            .failing
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        vault
+            .create_note(&VaultPath::new("a.md"), "missed apple")
+            .await
+            .unwrap();
+        assert!(search_index.notes.lock().unwrap().is_empty());
+
+        search_index
+            .failing
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        assert_eq!(
+            result_paths(vault.search_notes("apple").await.unwrap()),
+            ["/a.md"]
+        );
+        assert_eq!(
+            search_index
+                .rebuilds
+                .load(std::sync::atomic::Ordering::Relaxed),
+            2
+        );
+    }
+
     #[tokio::test]
     async fn create_then_sync_keeps_one_row_in_canonical_form() {
         let dir = TempDir::new().unwrap();
@@ -1698,17 +3353,819 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn vault_id_is_stable_across_reopen_and_persisted() {
+    async fn note_hash_tracks_content_without_loading_the_note() {
         let dir = TempDir::new().unwrap();
-        let id1 = make_vault(dir.path()).await.vault_id().await.unwrap();
-        let id2 = make_vault(dir.path()).await.vault_id().await.unwrap();
-        assert_eq!(id1, id2, "reopening the vault must keep the same id");
-        assert!(
-            dir.path().join(".kimun").join("vault-id").exists(),
-            "vault id must be persisted under .kimun/"
-        );
-    }
-
+        let vault = make_vault(dir.path()).await;
+        let path = VaultPath::new("sub/note.md");
+        vault.create_note(&path, "first").await.unwrap();
+        let first = vault.note_hash(&path).await.unwrap();
+        assert_eq!(first, NoteDetails::content_data_of("first").hash);
+
+        vault.save_note(&path, "second").await.unwrap();
+        assert_ne!(vault.note_hash(&path).await.unwrap(), first);
+
+        let missing = vault.note_hash(&VaultPath::new("nope.md")).await;
+        assert!(missing.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn external_edits_make_a_note_dirty_and_show_in_its_diff() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let path = VaultPath::new("note.md");
+        vault
+            .create_note(&path, "# Plan\nfirst step\nsecond step")
+            .await
+            .unwrap();
+        assert!(!vault.is_note_dirty(&path).await.unwrap());
+
+        // Rewriting the same bytes moves nothing but the mtime.
+        let os_path = dir.path().join("note.md");
+        std::fs::write(&os_path, "# Plan\nfirst step\nsecond step").unwrap();
+        assert!(!vault.is_note_dirty(&path).await.unwrap());
+
+        std::fs::write(&os_path, "# Plan\nfirst step\nthird step").unwrap();
+        assert!(vault.is_note_dirty(&path).await.unwrap());
+        let diff = vault.diff_note(&path).await.unwrap();
+        assert_eq!(
+            diff,
+            [
+                DiffLine::Same("# Plan".to_string()),
+                DiffLine::Same("first step".to_string()),
+                DiffLine::Removed("second step".to_string()),
+                DiffLine::Added("third step".to_string()),
+            ]
+        );
+
+        // The diff is of the raw text: markup and accents count.
+        std::fs::write(&os_path, "# Plan\n**first** step\nsecond step").unwrap();
+        let diff = vault.diff_note(&path).await.unwrap();
+        assert!(diff.contains(&DiffLine::Removed("first step".to_string())));
+        assert!(diff.contains(&DiffLine::Added("**first** step".to_string())));
+        std::fs::write(&os_path, "# Plan\nfirst stép\nsecond step").unwrap();
+        let diff = vault.diff_note(&path).await.unwrap();
+        assert!(diff.contains(&DiffLine::Added("first stép".to_string())));
+
+        let missing = vault.is_note_dirty(&VaultPath::new("nope.md")).await;
+        assert!(missing.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn oversized_notes_are_dirty_only_when_the_file_moves() {
+        let dir = TempDir::new().unwrap();
+        let cfg = VaultConfig::new(dir.path()).with_max_note_bytes(32);
+        let vault = NoteVault::new(cfg).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+        let path = VaultPath::new("big.md");
+        vault.create_note(&path, "x".repeat(64)).await.unwrap();
+        assert!(!vault.is_note_dirty(&path).await.unwrap());
+        assert!(vault.diff_note(&path).await.unwrap().is_empty());
+
+        std::fs::write(dir.path().join("big.md"), "y".repeat(65)).unwrap();
+        assert!(vault.is_note_dirty(&path).await.unwrap());
+        assert!(vault.diff_note(&path).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn metadata_for_returns_indexed_facts_in_request_order() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let a = VaultPath::new("/a.md");
+        let b = VaultPath::new("/b.md");
+        vault.create_note(&a, "# Alpha\n#work #Idea").await.unwrap();
+        vault.create_note(&b, "Beta").await.unwrap();
+
+        // Enough missing paths to need several IN-clause batches.
+        let mut paths: Vec<VaultPath> = (0..2000)
+            .map(|i| VaultPath::new(format!("/missing{i}.md")))
+            .collect();
+        paths.push(b.clone());
+        paths.push(VaultPath::new("a.md"));
+        paths.push(b.clone());
+
+        let metas = vault.metadata_for(&paths).await.unwrap();
+        assert_eq!(metas.len(), 2);
+        assert_eq!(metas[0].path, b);
+        assert_eq!(metas[0].title, "Beta");
+        assert!(metas[0].labels.is_empty());
+        assert_eq!(metas[1].path, a);
+        assert_eq!(metas[1].title, "Alpha");
+        assert_eq!(metas[1].labels, ["idea", "work"]);
+        assert_eq!(metas[1].size, "# Alpha\n#work #Idea".len() as u64);
+        assert!(metas[1].modified_secs > 0);
+    }
+
+    #[tokio::test]
+    async fn frontmatter_dates_set_the_created_date_when_enabled() {
+        let dir = TempDir::new().unwrap();
+        let config = VaultConfig::new(dir.path()).with_frontmatter_dates(true);
+        let vault = NoteVault::new(config).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+        // Saved through the vault...
+        vault
+            .create_note(
+                &VaultPath::new("/saved.md"),
+                "---\ndate: 2019-03-04\n---\nA",
+            )
+            .await
+            .unwrap();
+        // ...and picked up by a sync.
+        std::fs::write(
+            dir.path().join("synced.md"),
+            "---\ncreated: 2019-03-04T10:00:00Z\n---\nB",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("undated.md"), "C").unwrap();
+        vault.validate_and_init().await.unwrap();
+
+        let found = |query: &'static str| {
+            let vault = &vault;
+            async move {
+                let mut paths: Vec<String> = vault
+                    .search_notes(query)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|(e, _)| e.path.to_string())
+                    .collect();
+                paths.sort();
+                paths
+            }
+        };
+        assert_eq!(
+            found("created:2019-03-04").await,
+            ["/saved.md", "/synced.md"]
+        );
+        assert!(found("created:<2000").await.is_empty());
+
+        // Off (the default), the file time is kept.
+        let plain = make_vault(dir.path()).await;
+        plain.recreate_index().await.unwrap();
+        assert!(plain
+            .search_notes("created:2019-03-04")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn proximity_search_matches_words_near_each_other() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        for (name, text) in [
+            ("near", "The meeting had a long agenda today"),
+            ("swapped", "Agenda meeting notes"),
+            (
+                "far",
+                "The meeting ran late and then everyone went home before the agenda",
+            ),
+        ] {
+            vault
+                .create_note(&VaultPath::new(format!("/{name}.md")), text)
+                .await
+                .unwrap();
+        }
+        let names = |notes: Vec<(NoteEntryData, NoteContentData)>| -> Vec<String> {
+            let mut names: Vec<String> =
+                notes.into_iter().map(|(e, _)| e.path.to_string()).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(
+            names(vault.search_notes(r#""meeting agenda"~5"#).await.unwrap()),
+            ["/near.md", "/swapped.md"]
+        );
+        assert_eq!(
+            names(vault.search_notes(r#""meeting agenda"~1"#).await.unwrap()),
+            ["/swapped.md"]
+        );
+        // Without the suffix it is still the exact phrase.
+        assert!(vault
+            .search_notes(r#""meeting agenda""#)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_or_recent_shows_recent_notes_for_an_empty_query() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let now = SystemTime::now();
+        for (name, age_secs) in [("old", 300), ("new", 10), ("mid", 100)] {
+            let path = VaultPath::new(format!("/{name}.md"));
+            vault
+                .create_note(&path, format!("{name} note"))
+                .await
+                .unwrap();
+            let file = std::fs::File::options()
+                .write(true)
+                .open(path.to_pathbuf(dir.path()))
+                .unwrap();
+            file.set_modified(now - Duration::from_secs(age_secs))
+                .unwrap();
+        }
+        // Pick the backdated modification times up.
+        vault.recreate_index().await.unwrap();
+        let names = |notes: Vec<(NoteEntryData, NoteContentData)>| -> Vec<String> {
+            notes.into_iter().map(|(e, _)| e.path.to_string()).collect()
+        };
+
+        assert!(vault.search_notes("").await.unwrap().is_empty());
+        for empty in ["", "   ", "or:title"] {
+            assert_eq!(
+                names(vault.search_or_recent(empty, 2).await.unwrap()),
+                ["/new.md", "/mid.md"],
+                "{empty:?}"
+            );
+        }
+        assert_eq!(
+            names(vault.search_or_recent("old", 10).await.unwrap()),
+            ["/old.md"]
+        );
+        assert!(vault
+            .search_or_recent("absent", 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_pages_walk_every_match_once() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        for (name, title) in [
+            ("e", "Apple"),
+            ("a", "date"),
+            ("d", "Cherry"),
+            ("b", "banana"),
+        ] {
+            let text = format!("# {title}\nshared word");
+            vault
+                .create_note(&VaultPath::new(format!("/{name}.md")), text)
+                .await
+                .unwrap();
+        }
+        vault
+            .create_note(&VaultPath::new("/c.md"), "unrelated")
+            .await
+            .unwrap();
+        let paths = |notes: Vec<(NoteEntryData, NoteContentData)>| -> Vec<String> {
+            notes.into_iter().map(|(e, _)| e.path.to_string()).collect()
+        };
+
+        assert_eq!(vault.count_search("shared").await.unwrap(), 4);
+        let mut walked = vec![];
+        for offset in (0..6).step_by(2) {
+            walked.extend(paths(
+                vault.search_notes_page("shared", 2, offset).await.unwrap(),
+            ));
+        }
+        assert_eq!(walked, ["/a.md", "/b.md", "/d.md", "/e.md"]);
+
+        let by_title = vault
+            .search_notes_page("shared -or:title", 3, 1)
+            .await
+            .unwrap();
+        assert_eq!(paths(by_title), ["/d.md", "/b.md", "/e.md"]);
+
+        assert_eq!(vault.count_search("").await.unwrap(), 0);
+        assert!(vault.search_notes_page("", 10, 0).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn metrics_hook_reports_saves_searches_and_syncs() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        vault
+            .clone()
+            .set_metrics(Box::new(move |event: MetricEvent| {
+                sink.lock().unwrap().push((event.op, event.path));
+            }));
+
+        let path = VaultPath::new("/a.md");
+        let created = VaultPath::new("/b.md");
+        vault.save_note(&path, "hello").await.unwrap();
+        vault.create_note(&created, "world").await.unwrap();
+        vault.search_notes("hello").await.unwrap();
+        vault.count_search("world").await.unwrap();
+        vault.index_notes(NotesValidation::None).await.unwrap();
+        vault.clear_metrics();
+        vault.save_note(&path, "unrecorded").await.unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                (MetricOp::Save, Some(path)),
+                (MetricOp::Save, Some(created)),
+                (MetricOp::Search, None),
+                (MetricOp::Search, None),
+                (MetricOp::Index, Some(VaultPath::root())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn starter_notes_seed_an_empty_vault_once() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        assert!(vault.is_empty().await.unwrap());
+
+        let created = vault
+            .seed_starter_notes(Some("# Keyboard shortcuts\n"))
+            .await
+            .unwrap();
+        let (_, journal) = vault.get_todays_journal();
+        let welcome = VaultPath::new("/welcome.md");
+        assert_eq!(
+            created,
+            [
+                welcome.clone(),
+                VaultPath::new("/keyboard-shortcuts.md"),
+                journal.clone()
+            ]
+        );
+        assert!(!vault.is_empty().await.unwrap());
+        assert!(vault
+            .get_note_text(&welcome)
+            .await
+            .unwrap()
+            .contains("[[keyboard-shortcuts]]"));
+        assert!(vault
+            .get_note_text(&journal)
+            .await
+            .unwrap()
+            .contains("[[welcome]]"));
+
+        assert!(vault.seed_starter_notes(None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn starter_notes_skip_the_shortcuts_note_without_its_text() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+
+        let created = vault.seed_starter_notes(None).await.unwrap();
+        let (_, journal) = vault.get_todays_journal();
+        let welcome = VaultPath::new("/welcome.md");
+        assert_eq!(created, [welcome.clone(), journal]);
+        assert!(!vault
+            .get_note_text(&welcome)
+            .await
+            .unwrap()
+            .contains("[[keyboard-shortcuts]]"));
+    }
+
+    #[tokio::test]
+    async fn starter_notes_leave_a_vault_with_unsynced_notes_alone() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("mine.md"), "mine").unwrap();
+        let vault = make_vault(dir.path()).await;
+        assert_eq!(vault.count_notes().await.unwrap(), 0);
+
+        assert!(!vault.is_empty().await.unwrap());
+        assert!(vault.seed_starter_notes(None).await.unwrap().is_empty());
+        assert!(!dir.path().join("welcome.md").exists());
+    }
+
+    #[tokio::test]
+    async fn count_in_matches_get_notes() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        for path in ["/a.md", "/d/b.md", "/d/e/c.md"] {
+            vault.create_note(&VaultPath::new(path), "x").await.unwrap();
+        }
+
+        assert_eq!(vault.count_notes().await.unwrap(), 3);
+        for (dir, recursive, expected) in [("/d", false, 1), ("/d", true, 2), ("/", true, 3)] {
+            let path = VaultPath::new(dir);
+            let count = vault.count_in(&path, recursive).await.unwrap();
+            assert_eq!(count, expected, "{dir} recursive={recursive}");
+            let notes = vault.get_notes(&path, recursive).await.unwrap();
+            assert_eq!(count, notes.len(), "{dir} recursive={recursive}");
+        }
+    }
+
+    #[tokio::test]
+    async fn recovery_copies_are_listed_restored_and_discarded() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let kept = VaultPath::new("/notes/kept.md");
+        let dropped = VaultPath::new("/dropped.md");
+        vault.create_note(&kept, "on disk").await.unwrap();
+        assert!(vault.list_recovery().await.unwrap().is_empty());
+
+        vault.save_recovery(&kept, "first attempt").await.unwrap();
+        vault.save_recovery(&kept, "unsaved edits").await.unwrap();
+        vault.save_recovery(&dropped, "scratch").await.unwrap();
+        assert!(vault
+            .save_recovery(&VaultPath::new("/notes"), "x")
+            .await
+            .is_err());
+        let copies = vault.list_recovery().await.unwrap();
+        let paths: Vec<_> = copies.iter().map(|c| c.path.clone()).collect();
+        assert_eq!(paths, [dropped.clone(), kept.clone()]);
+        // Written after the note (or with no note at all): worth restoring.
+        assert!(copies.iter().all(RecoveryCopy::is_newer_than_note));
+        // Recovery copies live in the hidden `.kimun`, out of the index.
+        vault.recreate_index().await.unwrap();
+        assert_eq!(vault.count_notes().await.unwrap(), 1);
+
+        let (_, content) = vault.recover(&kept).await.unwrap();
+        assert_eq!(content.title, "unsaved edits");
+        assert_eq!(vault.get_note_text(&kept).await.unwrap(), "unsaved edits");
+        vault.discard_recovery(&dropped).await.unwrap();
+        assert!(vault.list_recovery().await.unwrap().is_empty());
+        assert!(!vault.exists(&dropped).await);
+        assert!(vault.recover(&kept).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn recovery_copy_is_superseded_by_a_later_save() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let path = VaultPath::new("/a.md");
+        vault.create_note(&path, "first").await.unwrap();
+        vault.save_recovery(&path, "buffer").await.unwrap();
+        // Make the later save unambiguous on filesystems with coarse mtimes.
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        vault.save_note(&path, "buffer").await.unwrap();
+
+        let copies = vault.list_recovery().await.unwrap();
+        assert_eq!(copies.len(), 1);
+        assert!(copies[0].note_modified.is_some());
+        assert!(!copies[0].is_newer_than_note());
+    }
+
+    #[tokio::test]
+    async fn excerpt_is_indexed_and_follows_edits() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let path = VaultPath::new("/a.md");
+        vault
+            .create_note(&path, "# Plans\nShip the *first* draft")
+            .await
+            .unwrap();
+
+        let (_, listed) = vault
+            .get_notes(&VaultPath::root(), false)
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(listed.excerpt, "Ship the first draft");
+        let (_, found) = vault.search_notes("draft").await.unwrap().pop().unwrap();
+        assert_eq!(found.excerpt, "Ship the first draft");
+
+        vault.save_note(&path, "# Plans\nRevised").await.unwrap();
+        let (_, listed) = vault.get_all_notes().await.unwrap().pop().unwrap();
+        assert_eq!(listed.excerpt, "Revised");
+    }
+
+    #[tokio::test]
+    async fn create_note_with_title_keeps_slug_filename_and_frontmatter_title() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let path = VaultPath::new("/q3-plans.md");
+        let (_, content) = vault
+            .create_note_with_title(&path, "Q3 Plans: \"Draft\"", "# Goals\nship it")
+            .await
+            .unwrap();
+
+        assert_eq!(content.title, "Q3 Plans: \"Draft\"");
+        assert!(dir.path().join("q3-plans.md").exists());
+        assert_eq!(
+            vault.get_note_text(&path).await.unwrap(),
+            "---\ntitle: \"Q3 Plans: \\\"Draft\\\"\"\n---\n# Goals\nship it"
+        );
+        let (_, indexed) = vault.get_all_notes().await.unwrap().pop().unwrap();
+        assert_eq!(indexed.title, "Q3 Plans: \"Draft\"");
+    }
+
+    #[tokio::test]
+    async fn rename_tag_rewrites_inline_and_frontmatter_tags() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let inline = VaultPath::new("/inline.md");
+        let front = VaultPath::new("/front.md");
+        let untouched = VaultPath::new("/other.md");
+        vault
+            .create_note(
+                &inline,
+                "Doing #WIP things, not `#wip` or [x](https://x.io/#wip)",
+            )
+            .await
+            .unwrap();
+        vault
+            .create_note(&front, "---\ntags: [wip, idea]\n---\nbody")
+            .await
+            .unwrap();
+        vault
+            .create_note(&untouched, "#wipe and wip-list")
+            .await
+            .unwrap();
+
+        let changed = vault.rename_tag("#wip", "in_progress").await.unwrap();
+        assert_eq!(changed, vec![front.clone(), inline.clone()]);
+        assert_eq!(
+            vault.get_note_text(&inline).await.unwrap(),
+            "Doing #in_progress things, not `#wip` or [x](https://x.io/#wip)"
+        );
+        assert_eq!(
+            vault.get_note_text(&front).await.unwrap(),
+            "---\ntags: [in_progress, idea]\n---\nbody"
+        );
+        assert_eq!(
+            vault.get_note_text(&untouched).await.unwrap(),
+            "#wipe and wip-list"
+        );
+        assert!(vault.notes_with_label("wip").await.unwrap().is_empty());
+        assert_eq!(
+            vault.notes_with_label("in_progress").await.unwrap(),
+            vec![inline]
+        );
+
+        let err = vault.rename_tag("wip", "in-progress").await.unwrap_err();
+        assert!(matches!(err, VaultError::InvalidLabel { .. }));
+    }
+
+    #[tokio::test]
+    async fn comments_are_kept_on_disk_but_not_indexed() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let path = VaultPath::new("note.md");
+        let text = "visible %%secretword%% text\n\n%%\nhiddenblock\n%%\n";
+        vault.create_note(&path, text).await.unwrap();
+
+        assert!(vault.search_notes("secretword").await.unwrap().is_empty());
+        assert!(vault.search_notes("hiddenblock").await.unwrap().is_empty());
+        assert_eq!(vault.search_notes("visible").await.unwrap().len(), 1);
+        assert_eq!(vault.get_note_text(&path).await.unwrap(), text);
+    }
+
+    #[tokio::test]
+    async fn reindex_note_picks_up_external_edits_adds_and_deletes() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let path = VaultPath::new("note.md");
+        vault.create_note(&path, "old words").await.unwrap();
+
+        std::fs::write(dir.path().join("note.md"), "fresh words").unwrap();
+        std::fs::write(dir.path().join("new.md"), "fresh too").unwrap();
+        assert!(vault.search_notes("fresh").await.unwrap().is_empty());
+
+        vault.reindex_note(&path).await.unwrap();
+        vault.reindex_note(&VaultPath::new("new.md")).await.unwrap();
+        assert_eq!(vault.search_notes("fresh").await.unwrap().len(), 2);
+        assert!(vault.search_notes("old").await.unwrap().is_empty());
+        assert!(!vault.is_note_dirty(&path).await.unwrap());
+
+        std::fs::remove_file(dir.path().join("note.md")).unwrap();
+        vault.reindex_note(&path).await.unwrap();
+        let remaining: Vec<_> = vault
+            .get_all_notes()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(entry, _)| entry.path)
+            .collect();
+        assert_eq!(remaining, [VaultPath::new("new.md").canonical()]);
+    }
+
+    #[tokio::test]
+    async fn resolve_resource_joins_against_the_note_directory() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let note = VaultPath::new("/journal/today.md");
+        let resolve = |link: &'static str| vault.resolve_resource(&note, link);
+
+        assert_eq!(
+            resolve("images/pic.png").await.unwrap(),
+            dir.path().join("journal").join("images").join("pic.png")
+        );
+        assert_eq!(
+            resolve("./../assets/./pic.png").await.unwrap(),
+            dir.path().join("assets").join("pic.png")
+        );
+        assert_eq!(
+            resolve("/assets/pic.png").await.unwrap(),
+            dir.path().join("assets").join("pic.png")
+        );
+        for escape in ["../../secret.png", "/../secret.png"] {
+            let err = resolve(escape).await.unwrap_err();
+            assert!(
+                matches!(err, VaultError::FSError(FSError::InvalidPath { .. })),
+                "{escape} gave {err:?}"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn resolve_resource_rejects_symlinks_out_of_the_workspace() {
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.png"), "x").unwrap();
+        let dir = TempDir::new().unwrap();
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("linked")).unwrap();
+        let vault = make_vault(dir.path()).await;
+
+        for link in ["linked/secret.png", "linked/missing/pic.png"] {
+            let err = vault
+                .resolve_resource(&VaultPath::new("note.md"), link)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, VaultError::FSError(FSError::InvalidPath { .. })),
+                "{link} gave {err:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn archive_export_round_trips_into_an_empty_vault() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        vault
+            .create_note(&VaultPath::new("/a/note.md"), "archived words")
+            .await
+            .unwrap();
+        vault
+            .save_attachment(&VaultPath::new("/assets/pic.png"), &[0, 1, 2])
+            .await
+            .unwrap();
+        vault
+            .create_directory(&VaultPath::new("/empty"))
+            .await
+            .unwrap();
+        std::fs::create_dir_all(dir.path().join(".trash")).unwrap();
+        std::fs::write(dir.path().join(".trash/old.md"), "gone").unwrap();
+
+        let mut archive = std::io::Cursor::new(Vec::new());
+        assert_eq!(vault.export_archive(&mut archive).unwrap(), 2);
+
+        let names: Vec<String> = zip::ZipArchive::new(archive.clone())
+            .unwrap()
+            .file_names()
+            .map(str::to_string)
+            .collect();
+        assert!(names
+            .iter()
+            .all(|n| !n.starts_with('.') && !n.contains("sqlite")));
+
+        let restored_dir = TempDir::new().unwrap();
+        let restored = make_vault(restored_dir.path()).await;
+        archive.set_position(0);
+        let root = VaultPath::root();
+        let import = |archive, on_conflict| restored.import_archive(archive, &root, on_conflict);
+        assert_eq!(
+            import(archive.clone(), ConflictPolicy::Error)
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(restored.search_notes("archived").await.unwrap().len(), 1);
+        assert_eq!(
+            std::fs::read(restored_dir.path().join("assets/pic.png")).unwrap(),
+            [0, 1, 2]
+        );
+        assert!(restored_dir.path().join("empty").is_dir());
+
+        // A second import would overwrite: refused before writing anything.
+        archive.set_position(0);
+        let err = import(archive.clone(), ConflictPolicy::Error)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VaultError::FSError(FSError::AlreadyExists { .. })
+        ));
+        assert!(!restored_dir.path().join("a/note_1.md").exists());
+
+        archive.set_position(0);
+        import(archive, ConflictPolicy::Rename).await.unwrap();
+        assert!(restored_dir.path().join("a/note_1.md").exists());
+        assert!(restored_dir.path().join("assets/pic_1.png").exists());
+        assert_eq!(restored.search_notes("archived").await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn archive_import_renames_past_a_file_spelled_differently() {
+        use std::io::Write;
+
+        let mut archive = std::io::Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut archive);
+        zip.start_file("a_b.md", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"imported").unwrap();
+        zip.finish().unwrap();
+        archive.set_position(0);
+
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        // `a:b.md` is the note `/a_b.md`, though no file has that spelling.
+        std::fs::write(dir.path().join("a:b.md"), "on disk").unwrap();
+
+        vault
+            .import_archive(archive, &VaultPath::root(), ConflictPolicy::Rename)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a_b_1.md")).unwrap(),
+            "imported"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a:b.md")).unwrap(),
+            "on disk"
+        );
+    }
+
+    #[tokio::test]
+    async fn archive_import_places_entries_under_dest_and_overwrites_on_request() {
+        use std::io::Write;
+
+        let mut archive = std::io::Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut archive);
+        let options = zip::write::SimpleFileOptions::default();
+        for (name, body) in [
+            ("Vault/Daily Note.md", "from obsidian"),
+            ("Vault/attachments/diagram.canvas", "{}"),
+            ("Vault/.obsidian/app.json", "{}"),
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(body.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let dest = VaultPath::new("/imported");
+        vault
+            .create_note(&VaultPath::new("/imported/vault/daily note.md"), "mine")
+            .await
+            .unwrap();
+
+        archive.set_position(0);
+        let imported = vault
+            .import_archive(archive, &dest, ConflictPolicy::Overwrite)
+            .await
+            .unwrap();
+        assert_eq!(imported, 2);
+        // `Vault/` lands in the existing `vault/` directory.
+        assert!(dir
+            .path()
+            .join("imported/vault/attachments/diagram.canvas")
+            .exists());
+        assert!(!dir.path().join("imported/vault/.obsidian").exists());
+        let note = VaultPath::new("/imported/vault/daily note.md");
+        assert_eq!(vault.get_note_text(&note).await.unwrap(), "from obsidian");
+        assert_eq!(vault.search_notes("obsidian").await.unwrap().len(), 1);
+        assert!(vault.search_notes("mine").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn archive_import_rejects_entries_escaping_the_vault() {
+        use std::io::Write;
+
+        let mut archive = std::io::Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut archive);
+        zip.start_file("ok.md", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"fine").unwrap();
+        zip.start_file("../escape.md", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"bad").unwrap();
+        zip.finish().unwrap();
+
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        archive.set_position(0);
+        let err = vault
+            .import_archive(archive, &VaultPath::root(), ConflictPolicy::Error)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VaultError::FSError(FSError::InvalidPath { .. })
+        ));
+        assert!(!dir.path().join("ok.md").exists());
+    }
+
+    #[tokio::test]
+    async fn vault_id_is_stable_across_reopen_and_persisted() {
+        let dir = TempDir::new().unwrap();
+        let id1 = make_vault(dir.path()).await.vault_id().await.unwrap();
+        let id2 = make_vault(dir.path()).await.vault_id().await.unwrap();
+        assert_eq!(id1, id2, "reopening the vault must keep the same id");
+        assert!(
+            dir.path().join(".kimun").join("vault-id").exists(),
+            "vault id must be persisted under .kimun/"
+        );
+    }
+
     #[tokio::test]
     async fn writes_succeed_without_observer() {
         let dir = TempDir::new().unwrap();
@@ -2181,6 +4638,7 @@ mod tests {
             .rename_note(
                 &VaultPath::new("/target.md"),
                 &VaultPath::new("/renamed.md"),
+                ConflictPolicy::Error,
             )
             .await
             .unwrap();
@@ -2220,6 +4678,7 @@ mod tests {
             .rename_note(
                 &VaultPath::new("/target.md"),
                 &VaultPath::new("/renamed.md"),
+                ConflictPolicy::Error,
             )
             .await
             .unwrap();
@@ -2258,6 +4717,7 @@ mod tests {
             .rename_note(
                 &VaultPath::new("/target.md"),
                 &VaultPath::new("/renamed.md"),
+                ConflictPolicy::Error,
             )
             .await
             .unwrap();
@@ -2285,6 +4745,7 @@ mod tests {
             .rename_note(
                 &VaultPath::new("/target.md"),
                 &VaultPath::new("/renamed.md"),
+                ConflictPolicy::Error,
             )
             .await
             .unwrap();
@@ -2312,6 +4773,276 @@ mod tests {
         assert_eq!(all.len(), 1, "expected single DB row, got: {:?}", all);
     }
 
+    // ---- vault_size ----
+
+    #[tokio::test]
+    async fn vault_size_splits_notes_attachments_and_db() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        vault
+            .save_note(&VaultPath::new("/a.md"), "12345")
+            .await
+            .unwrap();
+        vault
+            .save_attachment(&VaultPath::new("/assets/img.png"), &[0u8; 10])
+            .await
+            .unwrap();
+        std::fs::create_dir(dir.path().join(".hidden")).unwrap();
+        std::fs::write(dir.path().join(".hidden").join("x.bin"), [0u8; 100]).unwrap();
+
+        let size = vault.vault_size().await.unwrap();
+
+        assert_eq!(size.notes_bytes, 5);
+        assert_eq!(size.attachments_bytes, 10);
+        assert!(size.db_bytes > 0);
+        assert_eq!(size.total_bytes(), 15 + size.db_bytes);
+    }
+
+    // ---- compact ----
+
+    #[tokio::test]
+    async fn compact_shrinks_the_db_and_keeps_the_index() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        let body = "lorem ipsum ".repeat(500);
+        for i in 0..50 {
+            vault
+                .save_note(&VaultPath::new(format!("/n{i}.md")), &body)
+                .await
+                .unwrap();
+        }
+        for i in 1..50 {
+            vault
+                .delete_note(&VaultPath::new(format!("/n{i}.md")))
+                .await
+                .unwrap();
+        }
+        let before = vault.vault_size().await.unwrap().db_bytes;
+
+        vault.compact().await.unwrap();
+
+        let after = vault.vault_size().await.unwrap().db_bytes;
+        assert!(after < before, "expected {after} < {before}");
+        let results = vault.search_notes("lorem").await.unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    // ---- merge_notes ----
+
+    #[tokio::test]
+    async fn merge_notes_concatenates_sources_into_new_dest() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        let a = VaultPath::new("/fleeting/a.md");
+        let b = VaultPath::new("/fleeting/b.md");
+        let dest = VaultPath::new("/merged.md");
+        vault.save_note(&a, "# Idea A\nfirst").await.unwrap();
+        vault.save_note(&b, "loose thought").await.unwrap();
+
+        vault
+            .merge_notes(&[a.clone(), b.clone()], &dest, "\n\n", false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vault.get_note_text(&dest).await.unwrap(),
+            "# Idea A\nfirst\n\n# b\n\nloose thought"
+        );
+        assert!(vault.exists(&a).await);
+        assert!(vault.exists(&b).await);
+    }
+
+    #[tokio::test]
+    async fn merge_notes_deletes_sources_and_rewrites_backlinks() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        let a = VaultPath::new("/a.md");
+        let dest = VaultPath::new("/dest.md");
+        let referrer = VaultPath::new("/referrer.md");
+        vault.save_note(&dest, "# Dest\nexisting").await.unwrap();
+        vault.save_note(&a, "# A\nbody").await.unwrap();
+        vault
+            .save_note(&referrer, "# Referrer\nSee [[a]].")
+            .await
+            .unwrap();
+
+        vault
+            .merge_notes(std::slice::from_ref(&a), &dest, "\n\n---\n\n", true)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vault.get_note_text(&dest).await.unwrap(),
+            "# Dest\nexisting\n\n---\n\n# A\nbody"
+        );
+        assert!(!vault.exists(&a).await);
+        let updated = vault.get_note_text(&referrer).await.unwrap();
+        assert!(updated.contains("[[dest]]"), "got: {updated}");
+        let backlinks = vault.get_backlinks(&dest).await.unwrap();
+        assert_eq!(backlinks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn merge_notes_rejects_dest_among_sources() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        let a = VaultPath::new("/a.md");
+        vault.save_note(&a, "body").await.unwrap();
+
+        let e = vault
+            .merge_notes(std::slice::from_ref(&a), &a, "\n", true)
+            .await
+            .unwrap_err();
+        assert!(e.is_user_error());
+        assert_eq!(vault.get_note_text(&a).await.unwrap(), "body");
+    }
+
+    // ---- free_note_path ----
+
+    #[tokio::test]
+    async fn free_note_path_is_gapless_for_repeated_creates() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        let wanted = VaultPath::new("/notes/note.md");
+
+        let mut created = vec![];
+        for _ in 0..3 {
+            let path = vault.free_note_path(&wanted).await.unwrap();
+            vault.create_note(&path, "x").await.unwrap();
+            created.push(path.to_string());
+        }
+        assert_eq!(
+            created,
+            vec!["/notes/note.md", "/notes/note_1.md", "/notes/note_2.md"]
+        );
+    }
+
+    #[tokio::test]
+    async fn free_note_path_reuses_gap_below_existing_suffix() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        vault
+            .create_note(&VaultPath::new("/note.md"), "x")
+            .await
+            .unwrap();
+        vault
+            .create_note(&VaultPath::new("/note_5.md"), "x")
+            .await
+            .unwrap();
+
+        let path = vault
+            .free_note_path(&VaultPath::new("/note.md"))
+            .await
+            .unwrap();
+        assert_eq!(path.to_string(), "/note_1.md");
+    }
+
+    // ---- split_note ----
+
+    #[tokio::test]
+    async fn split_note_moves_sections_and_leaves_index() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        let path = VaultPath::new("/dir/long.md");
+        vault
+            .save_note(&VaultPath::new("/dir/alpha.md"), "taken")
+            .await
+            .unwrap();
+        vault
+            .save_note(&path, "# Long\nintro\n## Alpha\na\n## Beta/Gamma\nb\n")
+            .await
+            .unwrap();
+
+        let created = vault.split_note(&path, 2, true).await.unwrap();
+
+        assert_eq!(
+            created,
+            vec![
+                VaultPath::new("/dir/alpha_1.md"),
+                VaultPath::new("/dir/beta_gamma.md"),
+            ]
+        );
+        assert_eq!(
+            vault.get_note_text(&created[0]).await.unwrap(),
+            "## Alpha\na"
+        );
+        assert_eq!(
+            vault.get_note_text(&path).await.unwrap(),
+            "# Long\nintro\n\n- [[alpha_1]]\n- [[beta_gamma]]"
+        );
+    }
+
+    #[tokio::test]
+    async fn split_note_without_index_deletes_emptied_note() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        let path = VaultPath::new("/long.md");
+        vault.save_note(&path, "# One\na\n# Two\nb").await.unwrap();
+
+        let created = vault.split_note(&path, 1, false).await.unwrap();
+
+        assert_eq!(created.len(), 2);
+        assert!(!vault.exists(&path).await);
+    }
+
+    #[tokio::test]
+    async fn split_note_without_matching_headings_is_a_no_op() {
+        let dir = TempDir::new().unwrap();
+        let vault = setup_vault_with_notes(dir.path()).await;
+        let path = VaultPath::new("/flat.md");
+        vault.save_note(&path, "# Title\nbody").await.unwrap();
+
+        assert!(vault.split_note(&path, 2, true).await.unwrap().is_empty());
+        assert_eq!(vault.get_note_text(&path).await.unwrap(), "# Title\nbody");
+    }
+
+    #[tokio::test]
+    async fn split_note_names_follow_the_filename_policy() {
+        let dir = TempDir::new().unwrap();
+        let config = VaultConfig::new(dir.path()).with_filename_policy(FilenamePolicy::Posix);
+        let vault = NoteVault::new(config).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+        let path = VaultPath::new("/long.md");
+        vault
+            .save_note(&path, "## Plans: Q3?\na\n## Up/Down\nb")
+            .await
+            .unwrap();
+
+        let created = vault.split_note(&path, 2, false).await.unwrap();
+
+        assert_eq!(
+            created,
+            vec![
+                VaultPath::new("/plans_ q3_.md"),
+                VaultPath::new("/up_down.md"),
+            ]
+        );
+        assert!(dir.path().join("Plans: Q3?.md").is_file());
+        assert!(dir.path().join("Up_Down.md").is_file());
+    }
+
+    #[tokio::test]
+    async fn split_note_bumps_a_name_taken_by_a_file_spelled_differently() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        // `a:b.md` is the note `/a_b.md`, though no file has that spelling.
+        std::fs::write(dir.path().join("a:b.md"), "on disk").unwrap();
+        let path = VaultPath::new("/long.md");
+        vault.save_note(&path, "# a:b\nsection").await.unwrap();
+
+        let created =
+            tokio::time::timeout(Duration::from_secs(5), vault.split_note(&path, 1, false))
+                .await
+                .expect("the split picks a free name")
+                .unwrap();
+
+        assert_eq!(created, vec![VaultPath::new("/a_b_1.md")]);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a:b.md")).unwrap(),
+            "on disk"
+        );
+    }
+
     #[test]
     fn test_index_report_finish() {
         let mut report = IndexReport::new();
@@ -2444,6 +5175,22 @@ mod tests {
         assert_eq!(date, NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
     }
 
+    #[tokio::test]
+    async fn is_journal_entry_covers_notes_under_the_journal() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(temp_dir.path()))
+            .await
+            .unwrap();
+
+        assert_eq!(vault.journal_path(), &VaultPath::new(DEFAULT_JOURNAL_PATH));
+        assert!(vault.is_journal_entry(&VaultPath::new("/journal/2023-12-25.md")));
+        assert!(vault.is_journal_entry(&VaultPath::new("/journal/trips/rome.md")));
+        assert!(!vault.is_journal_entry(&VaultPath::new("/journal")));
+        assert!(!vault.is_journal_entry(&VaultPath::new("/journal/images/pic.png")));
+        assert!(!vault.is_journal_entry(&VaultPath::new("/journals/2023-12-25.md")));
+        assert!(!vault.is_journal_entry(&VaultPath::new("/other/2023-12-25.md")));
+    }
+
     #[tokio::test]
     async fn test_journal_date_with_invalid_date_format() {
         let temp_dir = TempDir::new().unwrap();
@@ -2731,62 +5478,250 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn quick_note_creates_timestamped_note_in_inbox() {
-        let dir = tempfile::TempDir::new().unwrap();
-        let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+    async fn quick_note_creates_timestamped_note_in_inbox() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+
+        let details = vault.quick_note("my quick thought").await.unwrap();
+        let (parent, _) = details.path.get_parent_path();
+        assert!(parent.to_string().contains("inbox"));
+
+        let text = vault.get_note_text(&details.path).await.unwrap();
+        assert_eq!(text, "my quick thought");
+    }
+
+    #[tokio::test]
+    async fn quick_note_resolves_conflicts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+
+        let d1 = vault.quick_note("first").await.unwrap();
+        let d2 = vault.quick_note("second").await.unwrap();
+
+        assert_ne!(d1.path, d2.path);
+        assert_eq!(vault.get_note_text(&d1.path).await.unwrap(), "first");
+        assert_eq!(vault.get_note_text(&d2.path).await.unwrap(), "second");
+    }
+
+    #[tokio::test]
+    async fn quick_note_uses_custom_inbox_path() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+        vault.set_inbox_path(VaultPath::new("/capture"));
+
+        let details = vault.quick_note("test").await.unwrap();
+        let (parent, _) = details.path.get_parent_path();
+        assert!(parent.to_string().contains("capture"));
+    }
+
+    #[tokio::test]
+    async fn create_note_errors_when_file_exists() {
+        let dir = TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+
+        let path = VaultPath::new("/already.md");
+        vault.create_note(&path, "first").await.unwrap();
+
+        match vault.create_note(&path, "second").await {
+            Err(VaultError::NoteExists { path: p }) => assert_eq!(p, path.flatten()),
+            other => panic!("expected NoteExists, got {:?}", other.err()),
+        }
+
+        // The original content must be intact (no overwrite).
+        let text = vault.get_note_text(&path).await.unwrap();
+        assert_eq!(text, "first");
+    }
+
+    #[tokio::test]
+    async fn new_notes_keep_their_capitalization_on_disk() {
+        let dir = TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+
+        vault
+            .create_note(&VaultPath::new("/Projects/README.md"), "# Read me")
+            .await
+            .unwrap();
+
+        assert!(dir.path().join("Projects").join("README.md").is_file());
+        // Identity stays case-insensitive.
+        let text = vault
+            .get_note_text(&VaultPath::new("/projects/readme.md"))
+            .await
+            .unwrap();
+        assert_eq!(text, "# Read me");
+        let indexed: Vec<VaultPath> = vault
+            .get_all_notes()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(entry, _)| entry.path)
+            .collect();
+        assert_eq!(indexed, vec![VaultPath::new("/projects/readme.md")]);
+    }
+
+    #[tokio::test]
+    async fn lowercase_paths_lowercases_new_names_on_disk() {
+        let dir = TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(dir.path()).with_lowercase_paths(true))
+            .await
+            .unwrap();
         vault.validate_and_init().await.unwrap();
 
-        let details = vault.quick_note("my quick thought").await.unwrap();
-        let (parent, _) = details.path.get_parent_path();
-        assert!(parent.to_string().contains("inbox"));
+        vault
+            .create_note(&VaultPath::new("/Projects/README.md"), "x")
+            .await
+            .unwrap();
+        vault
+            .rename_note(
+                &VaultPath::new("/projects/readme.md"),
+                &VaultPath::new("/Projects/Guide.md"),
+                ConflictPolicy::Error,
+            )
+            .await
+            .unwrap();
 
-        let text = vault.get_note_text(&details.path).await.unwrap();
-        assert_eq!(text, "my quick thought");
+        let names: Vec<String> = std::fs::read_dir(dir.path().join("projects"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["guide.md"]);
     }
 
     #[tokio::test]
-    async fn quick_note_resolves_conflicts() {
-        let dir = tempfile::TempDir::new().unwrap();
-        let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+    async fn posix_filename_policy_keeps_portable_unsafe_characters() {
+        let dir = TempDir::new().unwrap();
+        let config = VaultConfig::new(dir.path()).with_filename_policy(FilenamePolicy::Posix);
+        let vault = NoteVault::new(config).await.unwrap();
         vault.validate_and_init().await.unwrap();
 
-        let d1 = vault.quick_note("first").await.unwrap();
-        let d2 = vault.quick_note("second").await.unwrap();
-
-        assert_ne!(d1.path, d2.path);
-        assert_eq!(vault.get_note_text(&d1.path).await.unwrap(), "first");
-        assert_eq!(vault.get_note_text(&d2.path).await.unwrap(), "second");
+        let path = VaultPath::new("/Plans: Q3?.md");
+        vault.create_note(&path, "first").await.unwrap();
+        assert!(dir.path().join("Plans: Q3?.md").is_file());
+        assert!(!dir.path().join("Plans_ Q3_.md").exists());
+
+        // Identity still follows the strict rules, and the note resolves
+        // back to its on-disk name.
+        vault.save_note(&path, "second").await.unwrap();
+        let strict = VaultPath::new("/plans_ q3_.md");
+        assert_eq!(vault.get_note_text(&strict).await.unwrap(), "second");
+        vault.validate_and_init().await.unwrap();
+        assert_eq!(vault.search_notes("second").await.unwrap().len(), 1);
     }
 
     #[tokio::test]
-    async fn quick_note_uses_custom_inbox_path() {
-        let dir = tempfile::TempDir::new().unwrap();
-        let mut vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+    async fn posix_filename_policy_rejects_names_sharing_an_identity() {
+        let dir = TempDir::new().unwrap();
+        let config = VaultConfig::new(dir.path()).with_filename_policy(FilenamePolicy::Posix);
+        let vault = NoteVault::new(config).await.unwrap();
         vault.validate_and_init().await.unwrap();
-        vault.set_inbox_path(VaultPath::new("/capture"));
+        vault
+            .create_note(&VaultPath::new("/a:b.md"), "colon")
+            .await
+            .unwrap();
 
-        let details = vault.quick_note("test").await.unwrap();
-        let (parent, _) = details.path.get_parent_path();
-        assert!(parent.to_string().contains("capture"));
+        let clash = VaultPath::new("/a?b.md");
+        assert!(matches!(
+            vault.create_note(&clash, "question").await,
+            Err(VaultError::NoteExists { .. })
+        ));
+        let other = VaultPath::new("/other.md");
+        vault.create_note(&other, "other").await.unwrap();
+        assert!(matches!(
+            vault
+                .rename_note(&other, &clash, ConflictPolicy::Error)
+                .await,
+            Err(VaultError::NoteExists { .. })
+        ));
+        assert!(!dir.path().join("a?b.md").exists());
+        assert_eq!(vault.get_note_text(&clash).await.unwrap(), "colon");
     }
 
     #[tokio::test]
-    async fn create_note_errors_when_file_exists() {
+    async fn strict_filename_policy_replaces_portable_unsafe_characters() {
         let dir = TempDir::new().unwrap();
         let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
         vault.validate_and_init().await.unwrap();
 
-        let path = VaultPath::new("/already.md");
-        vault.create_note(&path, "first").await.unwrap();
+        vault
+            .create_note(&VaultPath::new("/Plans: Q3?.md"), "x")
+            .await
+            .unwrap();
+        assert!(dir.path().join("Plans_ Q3_.md").is_file());
+    }
 
-        match vault.create_note(&path, "second").await {
-            Err(VaultError::NoteExists { path: p }) => assert_eq!(p, path.flatten()),
-            other => panic!("expected NoteExists, got {:?}", other.err()),
+    #[tokio::test]
+    async fn browse_vault_streams_notes_and_directories() {
+        let dir = TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+        vault
+            .create_note(&VaultPath::new("/box/note.md"), "# Note")
+            .await
+            .unwrap();
+        vault
+            .create_directory(&VaultPath::new("/box/inner"))
+            .await
+            .unwrap();
+        vault
+            .create_note(&VaultPath::new("/box/inner/deep.md"), "# Deep")
+            .await
+            .unwrap();
+
+        let browsed = VaultPath::new("/box");
+        let (options, rx) = VaultBrowseOptionsBuilder::new(&browsed).build();
+        vault.browse_vault(options).await.unwrap();
+
+        let mut notes = vec![];
+        let mut dirs = vec![];
+        for result in rx.try_iter() {
+            match result.rtype {
+                ResultType::Note(_) => notes.push(result.path),
+                ResultType::Directory if !result.path.is_like(&browsed) => dirs.push(result.path),
+                _ => {}
+            }
         }
+        assert_eq!(notes, vec![VaultPath::new("/box/note.md")]);
+        assert_eq!(dirs, vec![VaultPath::new("/box/inner")]);
+    }
 
-        // The original content must be intact (no overwrite).
-        let text = vault.get_note_text(&path).await.unwrap();
-        assert_eq!(text, "first");
+    #[tokio::test]
+    async fn ensure_directory_creates_parents_and_is_idempotent() {
+        let dir = TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+
+        let path = VaultPath::new("/projects/2026/q1");
+        let entry = vault.ensure_directory(&path).await.unwrap();
+        assert_eq!(entry.path, path);
+        assert!(dir.path().join("projects/2026/q1").is_dir());
+
+        vault.ensure_directory(&path).await.unwrap();
+        vault
+            .ensure_directory(&VaultPath::new("/projects"))
+            .await
+            .unwrap();
+        assert!(matches!(
+            vault.create_directory(&path).await,
+            Err(VaultError::DirectoryExists { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn ensure_directory_fails_on_a_note_in_the_way() {
+        let dir = TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(dir.path())).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+        std::fs::write(dir.path().join("blocked"), "not a dir").unwrap();
+
+        assert!(vault
+            .ensure_directory(&VaultPath::new("/blocked"))
+            .await
+            .is_err());
     }
 
     #[tokio::test]
@@ -2815,11 +5750,9 @@ mod tests {
         vault.create_note(&from, "src").await.unwrap();
         vault.create_note(&to, "dst").await.unwrap();
 
-        match vault.rename_note(&from, &to).await {
-            Err(VaultError::FSError(FSError::InvalidPath { message, .. })) => {
-                assert_eq!(message, "Destination path already exists");
-            }
-            other => panic!("expected destination-exists error, got {:?}", other.err()),
+        match vault.rename_note(&from, &to, ConflictPolicy::Error).await {
+            Err(VaultError::NoteExists { path }) => assert_eq!(path, to),
+            other => panic!("expected NoteExists, got {:?}", other.err()),
         }
 
         // Both files unchanged.
@@ -2858,6 +5791,68 @@ mod tests {
             names
         );
     }
+
+    // ---- find_in_note ----
+
+    #[test]
+    fn compute_matches_reports_byte_char_and_line_offsets() {
+        let text = "héllo\nwörld hello";
+        let spans = NoteVault::compute_matches(text, "llo", false).unwrap();
+        assert_eq!(
+            spans,
+            vec![
+                MatchSpan {
+                    byte_start: 3,
+                    byte_end: 6,
+                    char_start: 2,
+                    char_end: 5,
+                    line: 0,
+                },
+                MatchSpan {
+                    byte_start: 16,
+                    byte_end: 19,
+                    char_start: 14,
+                    char_end: 17,
+                    line: 1,
+                },
+            ]
+        );
+        assert_eq!(&text[spans[1].byte_start..spans[1].byte_end], "llo");
+    }
+
+    #[test]
+    fn compute_matches_regex_and_literal_modes() {
+        let text = "a.b axb";
+        assert_eq!(
+            NoteVault::compute_matches(text, "a.b", false)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            NoteVault::compute_matches(text, "a.b", true).unwrap().len(),
+            2
+        );
+        assert!(NoteVault::compute_matches(text, "", false)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            NoteVault::compute_matches(text, "(unclosed", true),
+            Err(VaultError::InvalidRegex { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn find_in_note_reads_the_note() {
+        let dir = TempDir::new().unwrap();
+        let vault = make_vault(dir.path()).await;
+        let path = VaultPath::new("note.md");
+        vault.create_note(&path, "one\ntwo\none").await.unwrap();
+
+        let spans = vault.find_in_note(&path, "one", false).await.unwrap();
+        let lines: Vec<usize> = spans.iter().map(|s| s.line).collect();
+        assert_eq!(lines, vec![0, 2]);
+    }
 }
 
 #[cfg(test)]
@@ -2895,31 +5890,354 @@ mod vault_config_tests {
     }
 
     #[tokio::test]
-    async fn note_vault_new_with_explicit_db_path_uses_override() {
-        use crate::{NoteVault, VaultConfig};
-        let workspace = tempfile::TempDir::new().unwrap();
-        let cache_dir = tempfile::TempDir::new().unwrap();
-        let custom_db = cache_dir.path().join("my-vault.kimuncache");
-        let vault = NoteVault::new(VaultConfig::new(workspace.path()).with_db_path(&custom_db))
+    async fn note_vault_new_with_explicit_db_path_uses_override() {
+        use crate::{NoteVault, VaultConfig};
+        let workspace = tempfile::TempDir::new().unwrap();
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let custom_db = cache_dir.path().join("my-vault.kimuncache");
+        let vault = NoteVault::new(VaultConfig::new(workspace.path()).with_db_path(&custom_db))
+            .await
+            .unwrap();
+        assert!(custom_db.exists());
+        assert!(!workspace.path().join("kimun.sqlite").exists());
+        drop(vault);
+    }
+
+    #[tokio::test]
+    async fn open_applies_the_config_and_indexes_the_vault() {
+        use crate::nfs::VaultPath;
+        let workspace = tempfile::TempDir::new().unwrap();
+        std::fs::write(workspace.path().join("a.md"), "a").unwrap();
+        let (vault, _report) = VaultConfig::new(workspace.path())
+            .with_journal_path(VaultPath::new("/daily"))
+            .with_inbox_path(VaultPath::new("/capture"))
+            .open()
+            .await
+            .unwrap();
+        assert!(vault.index_ready());
+        assert_eq!(vault.journal_path(), &VaultPath::new("/daily"));
+        assert_eq!(vault.inbox_path(), &VaultPath::new("/capture"));
+        assert_eq!(vault.get_all_notes().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn clones_share_state_without_touching_disk() {
+        use crate::NoteVault;
+        let workspace = tempfile::TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(workspace.path()))
+            .await
+            .unwrap();
+        let id = vault.vault_id().await.unwrap();
+        let connections = vault.index.pool().size();
+
+        // Nothing on disk is left for a clone to read.
+        std::fs::remove_dir_all(workspace.path().join(".kimun")).unwrap();
+        let clone = vault.clone();
+        assert_eq!(clone.vault_id().await.unwrap(), id, "cached id is shared");
+        assert!(!workspace.path().join(".kimun").exists());
+        assert_eq!(clone.index.pool().size(), connections);
+
+        // One pool: closing it through the original closes it for the clone.
+        vault.index.pool().close().await;
+        assert!(clone.index.pool().is_closed());
+    }
+
+    #[tokio::test]
+    async fn validate_workspace_reports_the_existing_index() {
+        use crate::nfs::VaultPath;
+        use crate::{NoteVault, VaultConfig};
+        let workspace = tempfile::TempDir::new().unwrap();
+        let info = NoteVault::validate_workspace(workspace.path())
+            .await
+            .unwrap();
+        assert!(!info.index_exists);
+        assert_eq!(info.note_count, None);
+        assert!(!info.db_path.exists(), "validation created the index");
+
+        let vault = NoteVault::new(VaultConfig::new(workspace.path()))
+            .await
+            .unwrap();
+        vault.validate_and_init().await.unwrap();
+        vault
+            .create_note(&VaultPath::note_path_from("/a.md"), "a")
+            .await
+            .unwrap();
+        vault
+            .create_note(&VaultPath::note_path_from("/b.md"), "b")
+            .await
+            .unwrap();
+        let info = NoteVault::validate_workspace(workspace.path())
+            .await
+            .unwrap();
+        assert!(info.index_exists);
+        assert!(info.index_current);
+        assert_eq!(info.index_version.as_deref(), Some(crate::index::VERSION));
+        assert_eq!(info.note_count, Some(2));
+    }
+
+    #[tokio::test]
+    async fn index_can_live_outside_the_workspace() {
+        use crate::nfs::VaultPath;
+        use crate::VaultConfig;
+        let workspace = tempfile::TempDir::new().unwrap();
+        let cache = tempfile::TempDir::new().unwrap();
+        let db_path = cache.path().join("nested").join("vault.sqlite");
+        let config = VaultConfig::new(workspace.path()).with_db_path(&db_path);
+        assert_eq!(config.index_path(), db_path);
+        assert!(!config.inspect().await.unwrap().index_exists);
+
+        let (vault, _) = config.clone().open().await.unwrap();
+        vault
+            .create_note(&VaultPath::note_path_from("/a.md"), "a")
+            .await
+            .unwrap();
+        assert!(!workspace.path().join(crate::index::DB_FILE).exists());
+        let info = config.inspect().await.unwrap();
+        assert_eq!(info.db_path, db_path);
+        assert!(info.index_current);
+        assert_eq!(info.note_count, Some(1));
+    }
+
+    #[tokio::test]
+    async fn validate_workspace_tolerates_an_unreadable_index() {
+        use crate::NoteVault;
+        let workspace = tempfile::TempDir::new().unwrap();
+        let db = workspace.path().join("kimun.sqlite");
+        std::fs::write(&db, "not a database").unwrap();
+        let info = NoteVault::validate_workspace(workspace.path())
+            .await
+            .unwrap();
+        assert!(info.index_exists);
+        assert!(!info.index_current);
+        assert_eq!(info.index_version, None);
+        assert_eq!(info.note_count, None);
+        assert_eq!(std::fs::read(&db).unwrap(), b"not a database");
+
+        let missing = workspace.path().join("missing");
+        assert!(matches!(
+            NoteVault::validate_workspace(&missing).await,
+            Err(crate::error::VaultError::VaultPathNotFound { .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod label_api_tests {
+    use super::*;
+    use crate::nfs::VaultPath;
+
+    async fn new_vault() -> (tempfile::TempDir, NoteVault) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cfg = VaultConfig::new(tmp.path().to_path_buf());
+        let vault = NoteVault::new(cfg).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+        (tmp, vault)
+    }
+
+    #[tokio::test]
+    async fn validate_and_init_escalates_when_the_note_count_drifts() {
+        let (tmp, vault) = new_vault().await;
+        vault
+            .create_note(&VaultPath::note_path_from("/edited.md"), "old words")
+            .await
+            .unwrap();
+        // Edited behind the index's back: an existence-only sync keeps the
+        // stale row, a size-checking one picks the edit up.
+        std::fs::write(tmp.path().join("edited.md"), "fresh longer words").unwrap();
+        let found =
+            |vault: NoteVault| async move { vault.search_notes("fresh").await.unwrap().len() };
+
+        vault.validate_and_init().await.unwrap();
+        assert_eq!(found(vault.clone()).await, 0);
+
+        for i in 0..=INDEX_DRIFT_TOLERANCE {
+            std::fs::write(tmp.path().join(format!("new{i}.md")), "x").unwrap();
+        }
+        vault.validate_and_init().await.unwrap();
+        assert_eq!(found(vault.clone()).await, 1);
+        assert_eq!(
+            vault.index.notes_count().await.unwrap(),
+            INDEX_DRIFT_TOLERANCE + 2
+        );
+    }
+
+    #[tokio::test]
+    async fn init_lazy_defers_validation_to_browsing() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("dir")).unwrap();
+        std::fs::write(tmp.path().join("dir/first.md"), "first").unwrap();
+        let open = || async { NoteVault::new(VaultConfig::new(tmp.path())).await.unwrap() };
+
+        // A fresh index has nothing to defer to: full sync.
+        let report = open().await.init_lazy().await.unwrap();
+        assert!(report.is_some());
+
+        std::fs::write(tmp.path().join("dir/second.md"), "second").unwrap();
+        let vault = open().await;
+        assert!(vault.init_lazy().await.unwrap().is_none());
+        assert_eq!(vault.get_all_notes().await.unwrap().len(), 1);
+
+        let (options, _rx) = VaultBrowseOptionsBuilder::new(&VaultPath::new("/dir"))
+            .recursive(false)
+            .validation(NotesValidation::Full)
+            .build();
+        vault.browse_vault(options).await.unwrap();
+        assert_eq!(vault.get_all_notes().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn sequential_walk_streams_entries_in_sorted_order() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        for path in ["b/z.md", "b/a.md", "a/y.md", "c.md", "a.md"] {
+            let os_path = tmp.path().join(path);
+            std::fs::create_dir_all(os_path.parent().unwrap()).unwrap();
+            std::fs::write(os_path, "x").unwrap();
+        }
+        let vault = NoteVault::new(VaultConfig::new(tmp.path()).with_sequential_walk(true))
+            .await
+            .unwrap();
+
+        let walk = || async {
+            let (options, rx) = VaultBrowseOptionsBuilder::new(&VaultPath::root())
+                .recursive(true)
+                .build();
+            vault.browse_vault(options).await.unwrap();
+            rx.try_iter()
+                .filter(|r| matches!(r.rtype, ResultType::Note(_)))
+                .map(|r| r.path.to_string())
+                .collect::<Vec<_>>()
+        };
+        let first = walk().await;
+        // Depth first, each directory's entries by file name: `a` < `a.md`.
+        assert_eq!(first, ["/a/y.md", "/a.md", "/b/a.md", "/b/z.md", "/c.md"]);
+        assert_eq!(walk().await, first);
+    }
+
+    #[tokio::test]
+    async fn browsing_the_root_skips_the_index_files() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("note.md"), "x").unwrap();
+        std::fs::create_dir(tmp.path().join("sub")).unwrap();
+        // Only the workspace-root database is the index; same-named files
+        // elsewhere are ordinary attachments.
+        std::fs::write(tmp.path().join("sub").join(crate::index::DB_FILE), "x").unwrap();
+        let (vault, _) = VaultConfig::new(tmp.path()).open().await.unwrap();
+        assert!(tmp.path().join(crate::index::DB_FILE).exists());
+
+        let (options, rx) = VaultBrowseOptionsBuilder::new(&VaultPath::root())
+            .recursive(true)
+            .build();
+        vault.browse_vault(options).await.unwrap();
+        let mut listed: Vec<_> = rx
+            .try_iter()
+            .filter(|r| !matches!(r.rtype, ResultType::Directory))
+            .map(|r| r.path.to_string())
+            .collect();
+        listed.sort();
+        assert_eq!(listed, ["/note.md", "/sub/kimun.sqlite"]);
+    }
+
+    #[tokio::test]
+    async fn oversized_notes_are_listed_but_not_content_indexed() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("small.md"), "needle").unwrap();
+        std::fs::write(
+            tmp.path().join("big.md"),
+            format!("needle {}", "x".repeat(64)),
+        )
+        .unwrap();
+        let cfg = VaultConfig::new(tmp.path().to_path_buf()).with_max_note_bytes(32);
+        let vault = NoteVault::new(cfg).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+
+        let oversized = vault.oversized_notes().await.unwrap();
+        assert_eq!(oversized.len(), 1);
+        assert_eq!(oversized[0].path, VaultPath::note_path_from("/big.md"));
+        assert_eq!(oversized[0].size, 71);
+
+        let found: Vec<VaultPath> = vault
+            .search_notes("needle")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(entry, _)| entry.path)
+            .collect();
+        assert_eq!(found, vec![VaultPath::note_path_from("/small.md")]);
+    }
+
+    #[tokio::test]
+    async fn saving_an_oversized_note_indexes_it_without_content() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cfg = VaultConfig::new(tmp.path().to_path_buf()).with_max_note_bytes(32);
+        let vault = NoteVault::new(cfg).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+
+        let big = VaultPath::note_path_from("/big.md");
+        vault
+            .save_note(&big, format!("needle {}", "x".repeat(64)))
             .await
             .unwrap();
-        assert!(custom_db.exists());
-        assert!(!workspace.path().join("kimun.sqlite").exists());
-        drop(vault);
+        vault
+            .save_note(&VaultPath::note_path_from("/small.md"), "needle")
+            .await
+            .unwrap();
+
+        let oversized = vault.oversized_notes().await.unwrap();
+        assert_eq!(oversized.len(), 1);
+        assert_eq!(oversized[0].path, big);
+        let found: Vec<VaultPath> = vault
+            .search_notes("needle")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(entry, _)| entry.path)
+            .collect();
+        assert_eq!(found, vec![VaultPath::note_path_from("/small.md")]);
+        // The file itself is written in full.
+        assert_eq!(vault.get_note_text(&big).await.unwrap().len(), 71);
     }
-}
 
-#[cfg(test)]
-mod label_api_tests {
-    use super::*;
-    use crate::nfs::VaultPath;
+    #[tokio::test]
+    async fn link_graph_resolves_links_and_keeps_isolated_notes() {
+        let (_tmp, vault) = new_vault().await;
+        let note = VaultPath::note_path_from;
+        vault
+            .create_directory(&VaultPath::new("/dir"))
+            .await
+            .unwrap();
+        for (path, body) in [
+            (
+                "/a.md",
+                "[[b]] and [c](dir/c.md) and [[b]] again and [[missing]]",
+            ),
+            ("/b.md", "back to [[a]]"),
+            ("/dir/c.md", "no links"),
+            ("/lonely.md", "nobody links here"),
+        ] {
+            vault.create_note(&note(path), body).await.unwrap();
+        }
 
-    async fn new_vault() -> (tempfile::TempDir, NoteVault) {
-        let tmp = tempfile::TempDir::new().unwrap();
-        let cfg = VaultConfig::new(tmp.path().to_path_buf());
-        let vault = NoteVault::new(cfg).await.unwrap();
-        vault.validate_and_init().await.unwrap();
-        (tmp, vault)
+        let graph = vault.link_graph().await.unwrap();
+        assert_eq!(
+            graph.nodes,
+            [
+                note("/a.md"),
+                note("/b.md"),
+                note("/dir/c.md"),
+                note("/lonely.md")
+            ]
+        );
+        assert_eq!(
+            graph.edges,
+            [
+                (note("/a.md"), note("/b.md")),
+                (note("/a.md"), note("/dir/c.md")),
+                (note("/b.md"), note("/a.md")),
+            ]
+        );
+        assert_eq!(graph.isolated().collect::<Vec<_>>(), [&note("/lonely.md")]);
+        let json = serde_json::to_value(&graph).unwrap();
+        assert_eq!(json["edges"][0], serde_json::json!(["/a.md", "/b.md"]));
     }
 
     #[tokio::test]
@@ -2996,6 +6314,225 @@ mod label_api_tests {
         let counts = vault.label_counts().await.unwrap();
         assert!(counts.is_empty());
     }
+
+    #[tokio::test]
+    async fn adjacent_journal_skips_missing_days() {
+        let (_tmp, vault) = new_vault().await;
+        let entry = |day: &str| {
+            vault
+                .journal_path()
+                .append(&VaultPath::note_path_from(day))
+                .absolute()
+        };
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        for day in ["2024-01-01", "2024-01-05", "2024-02-10"] {
+            vault.create_note(&entry(day), "").await.unwrap();
+        }
+        // Not a dated entry, so never a neighbour.
+        let other = vault
+            .journal_path()
+            .append(&VaultPath::note_path_from("ideas"));
+        vault.create_note(&other, "").await.unwrap();
+
+        let from = date("2024-01-05");
+        assert_eq!(
+            vault
+                .adjacent_journal(from, Direction::Previous)
+                .await
+                .unwrap(),
+            Some((date("2024-01-01"), entry("2024-01-01")))
+        );
+        assert_eq!(
+            vault.adjacent_journal(from, Direction::Next).await.unwrap(),
+            Some((date("2024-02-10"), entry("2024-02-10")))
+        );
+        // A day without an entry still finds both neighbours.
+        assert_eq!(
+            vault
+                .adjacent_journal(date("2024-01-20"), Direction::Previous)
+                .await
+                .unwrap()
+                .map(|(d, _)| d),
+            Some(from)
+        );
+        assert_eq!(
+            vault
+                .adjacent_journal(date("2024-02-10"), Direction::Next)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn activity_counts_notes_per_modified_day() {
+        let (_tmp, vault) = new_vault().await;
+        let today = Utc::now().date_naive();
+        assert!(vault.activity(today..=today).await.unwrap().is_empty());
+
+        vault
+            .create_note(&VaultPath::note_path_from("/a.md"), "a")
+            .await
+            .unwrap();
+        vault
+            .create_note(&VaultPath::note_path_from("/b.md"), "b")
+            .await
+            .unwrap();
+
+        let yesterday = today.pred_opt().unwrap();
+        let counts = vault.activity(yesterday..=today).await.unwrap();
+        assert_eq!(counts, BTreeMap::from([(today, 2)]));
+        assert!(vault
+            .activity(yesterday..=yesterday)
+            .await
+            .unwrap()
+            .is_empty());
+        let backwards = today..=yesterday;
+        assert!(vault.activity(backwards).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn indexed_chunks_keep_their_source_ranges() {
+        let (_tmp, vault) = new_vault().await;
+        let path = VaultPath::note_path_from("/ranges.md");
+        let text = "intro\n\n# Head\nbody text\n";
+        vault.create_note(&path, text).await.unwrap();
+
+        let chunks = vault.get_note_chunks(&path).await.unwrap();
+        let sections: Vec<&str> = chunks[&path].iter().map(|c| &text[c.range()]).collect();
+        assert_eq!(sections, vec!["intro", "# Head\nbody text"]);
+    }
+
+    #[tokio::test]
+    async fn last_indexed_at_tracks_whole_vault_syncs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let vault = NoteVault::new(VaultConfig::new(tmp.path())).await.unwrap();
+        assert_eq!(vault.last_indexed_at().await.unwrap(), None);
+
+        let before = SystemTime::now() - Duration::from_secs(1);
+        vault.validate_and_init().await.unwrap();
+        let indexed = vault.last_indexed_at().await.unwrap().unwrap();
+        assert!(indexed >= before);
+
+        // Persisted: a fresh instance over the same index reads it back.
+        let reopened = NoteVault::new(VaultConfig::new(tmp.path())).await.unwrap();
+        assert_eq!(reopened.last_indexed_at().await.unwrap(), Some(indexed));
+    }
+
+    #[tokio::test]
+    async fn random_note_respects_scope_and_skips_the_journal() {
+        let (_tmp, vault) = new_vault().await;
+        assert_eq!(vault.random_note(None).await.unwrap(), None);
+
+        let a = VaultPath::note_path_from("/a.md");
+        let b = VaultPath::note_path_from("/sub/b.md");
+        vault.create_note(&a, "a").await.unwrap();
+        vault.create_note(&b, "b").await.unwrap();
+        let (entry, _, _) = vault.journal_entry().await.unwrap();
+
+        for _ in 0..20 {
+            let any = vault.random_note(None).await.unwrap().unwrap();
+            assert!(any == a || any == b, "journal entry picked: {any}");
+        }
+        let sub = VaultPath::new("/sub");
+        assert_eq!(vault.random_note(Some(sub)).await.unwrap(), Some(b));
+        let journal = vault.journal_path().clone();
+        assert_eq!(
+            vault.random_note(Some(journal)).await.unwrap(),
+            Some(entry.path)
+        );
+        let empty = VaultPath::new("/nothing");
+        assert_eq!(vault.random_note(Some(empty)).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn duplicate_titles_groups_notes_sharing_a_title() {
+        let (_tmp, vault) = new_vault().await;
+        let a = VaultPath::note_path_from("/a.md");
+        let b = VaultPath::note_path_from("/sub/b.md");
+        let c = VaultPath::note_path_from("/c.md");
+        let d = VaultPath::note_path_from("/d.md");
+        vault.create_note(&a, "# Meeting\nfirst").await.unwrap();
+        vault.create_note(&b, "# Meeting\nsecond").await.unwrap();
+        vault.create_note(&c, "# meeting\nthird").await.unwrap();
+        vault.create_note(&d, "# Unique").await.unwrap();
+
+        let exact = vault.duplicate_titles(false, true).await.unwrap();
+        assert_eq!(
+            exact,
+            vec![("Meeting".to_string(), vec![a.clone(), b.clone()])]
+        );
+
+        let folded = vault.duplicate_titles(true, true).await.unwrap();
+        assert_eq!(folded, vec![("Meeting".to_string(), vec![a, c, b])]);
+    }
+
+    #[tokio::test]
+    async fn duplicate_titles_fold_non_ascii_case() {
+        let (_tmp, vault) = new_vault().await;
+        let a = VaultPath::note_path_from("/a.md");
+        let b = VaultPath::note_path_from("/b.md");
+        vault.create_note(&a, "# Ärger").await.unwrap();
+        vault.create_note(&b, "# ärger").await.unwrap();
+
+        assert!(vault
+            .duplicate_titles(false, true)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            vault.duplicate_titles(true, true).await.unwrap(),
+            vec![("Ärger".to_string(), vec![a, b])]
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicate_titles_can_skip_untitled_notes() {
+        let (_tmp, vault) = new_vault().await;
+        let a = VaultPath::note_path_from("/a.md");
+        let b = VaultPath::note_path_from("/b.md");
+        vault.create_note(&a, "").await.unwrap();
+        vault.create_note(&b, "").await.unwrap();
+
+        assert!(vault
+            .duplicate_titles(false, true)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            vault.duplicate_titles(false, false).await.unwrap(),
+            vec![(String::new(), vec![a, b])]
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicate_contents_clusters_identical_bodies() {
+        let (_tmp, vault) = new_vault().await;
+        let a = VaultPath::note_path_from("/a.md");
+        let b = VaultPath::note_path_from("/sub/b.md");
+        let c = VaultPath::note_path_from("/c.md");
+        vault.create_note(&a, "# Same\nbody").await.unwrap();
+        vault.create_note(&b, "# Same\nbody").await.unwrap();
+        vault.create_note(&c, "# Same\nother body").await.unwrap();
+
+        let clusters = vault.duplicate_contents(true).await.unwrap();
+        assert_eq!(clusters, vec![vec![a, b]]);
+    }
+
+    #[tokio::test]
+    async fn duplicate_contents_can_skip_empty_notes() {
+        let (_tmp, vault) = new_vault().await;
+        let a = VaultPath::note_path_from("/a.md");
+        let b = VaultPath::note_path_from("/b.md");
+        vault.create_note(&a, "").await.unwrap();
+        vault.create_note(&b, "").await.unwrap();
+
+        assert!(vault.duplicate_contents(true).await.unwrap().is_empty());
+        assert_eq!(
+            vault.duplicate_contents(false).await.unwrap(),
+            vec![vec![a, b]]
+        );
+    }
 }
 
 #[cfg(test)]
@@ -3124,6 +6661,88 @@ mod suggest_api_tests {
         assert!(got.is_empty());
     }
 
+    #[tokio::test]
+    async fn suggest_links_matches_titles_and_ranks_prefixes_first() {
+        let (_tmp, vault) = new_vault().await;
+        for (name, body) in [
+            ("/planning.md", "# Roadmap"),
+            ("/q3-plan.md", "# Quarter goals"),
+            ("/meeting.md", "# Weekly plan review"),
+            ("/travel.md", "# Plane tickets"),
+        ] {
+            vault
+                .create_note(&VaultPath::note_path_from(name), body)
+                .await
+                .unwrap();
+        }
+
+        let got = vault.suggest_links("PLAN", 50).await.unwrap();
+        let names: Vec<&str> = got.iter().map(|s| s.name.as_str()).collect();
+        // Name prefix, title prefix, then name/title substrings by name.
+        assert_eq!(names, ["planning", "travel", "meeting", "q3-plan"]);
+        assert_eq!(got[1].title, "Plane tickets");
+
+        let got = vault.suggest_links("roadmap", 50).await.unwrap();
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].name, "planning");
+        assert!(vault.suggest_links("%", 50).await.unwrap().is_empty());
+        assert_eq!(vault.suggest_links("", 2).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn suggest_links_folds_non_ascii_titles() {
+        let (_tmp, vault) = new_vault().await;
+        vault
+            .create_note(&VaultPath::note_path_from("/office.md"), "# Ärger im Büro")
+            .await
+            .unwrap();
+
+        for query in ["ärger", "ÄRGER", "büro", "BÜRO"] {
+            let got = vault.suggest_links(query, 50).await.unwrap();
+            assert_eq!(got.len(), 1, "{query}");
+            assert_eq!(got[0].title, "Ärger im Büro");
+        }
+    }
+
+    #[tokio::test]
+    async fn suggest_query_corrects_misspelled_words() {
+        let (_tmp, vault) = new_vault().await;
+        vault
+            .create_note(
+                &VaultPath::note_path_from("/mail.md"),
+                "How to receive mail at the office",
+            )
+            .await
+            .unwrap();
+
+        assert!(vault.search_notes("recieve").await.unwrap().is_empty());
+        assert_eq!(vault.suggest_query("recieve").await.unwrap(), ["receive"]);
+        // Known words and prefixed terms stay as typed.
+        assert_eq!(
+            vault.suggest_query("offce @mail recieve").await.unwrap(),
+            ["office @mail receive"]
+        );
+        // Quoted phrases are searched as typed, so they are never corrected.
+        assert_eq!(
+            vault
+                .suggest_query("\"to recieve mail\" offce")
+                .await
+                .unwrap(),
+            ["\"to recieve mail\" office"]
+        );
+        assert!(vault
+            .suggest_query("\"at the offce now\"")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(vault
+            .suggest_query("receive mail")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(vault.suggest_query("zzzzzz").await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn suggest_tags_ranks_by_usage_count_then_name() {
         let (_tmp, vault) = new_vault().await;
@@ -3187,7 +6806,7 @@ mod suggest_api_tests {
 
 #[cfg(test)]
 mod modify_backup_tests {
-    use super::{NoteVault, VaultConfig};
+    use super::{ConflictPolicy, NoteVault, VaultConfig};
     use crate::error::VaultError;
     use crate::nfs::VaultPath;
     use std::path::{Path, PathBuf};
@@ -3490,7 +7109,11 @@ mod modify_backup_tests {
             .unwrap();
 
         vault
-            .rename_note(&b, &VaultPath::note_path_from("/c.md"))
+            .rename_note(
+                &b,
+                &VaultPath::note_path_from("/c.md"),
+                ConflictPolicy::Error,
+            )
             .await
             .unwrap();
 
@@ -3508,6 +7131,95 @@ mod modify_backup_tests {
         assert!(vault.get_note_text(&a).await.unwrap().contains("[[c]]"));
     }
 
+    #[tokio::test]
+    async fn rename_with_rename_policy_picks_free_name() {
+        let (_temp, vault) = backup_vault().await;
+        let from = VaultPath::note_path_from("/source.md");
+        let to = VaultPath::note_path_from("/dest.md");
+        vault.create_note(&from, "src").await.unwrap();
+        vault.create_note(&to, "dst").await.unwrap();
+
+        let landed = vault
+            .rename_note(&from, &to, ConflictPolicy::Rename)
+            .await
+            .unwrap();
+
+        assert_eq!(landed, VaultPath::note_path_from("/dest_1.md"));
+        assert_eq!(vault.get_note_text(&landed).await.unwrap(), "src");
+        assert_eq!(vault.get_note_text(&to).await.unwrap(), "dst");
+        assert!(!vault.exists(&from).await);
+    }
+
+    #[tokio::test]
+    async fn rename_policy_skips_a_name_taken_by_a_file_spelled_differently() {
+        let (temp, vault) = backup_vault().await;
+        let from = VaultPath::note_path_from("/source.md");
+        vault.create_note(&from, "src").await.unwrap();
+        // `a:b.md` is the note `/a_b.md`, though no file has that spelling.
+        std::fs::write(temp.path().join("a:b.md"), "on disk").unwrap();
+
+        let landed = vault
+            .rename_note(&from, &VaultPath::new("/a_b.md"), ConflictPolicy::Rename)
+            .await
+            .unwrap();
+
+        assert_eq!(landed, VaultPath::new("/a_b_1.md"));
+        assert_eq!(vault.get_note_text(&landed).await.unwrap(), "src");
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("a:b.md")).unwrap(),
+            "on disk"
+        );
+    }
+
+    #[tokio::test]
+    async fn rename_with_overwrite_policy_replaces_and_backs_up_target() {
+        let (temp, vault) = backup_vault().await;
+        let from = VaultPath::note_path_from("/source.md");
+        let to = VaultPath::note_path_from("/dest.md");
+        vault.create_note(&from, "src").await.unwrap();
+        vault
+            .create_note(&to, "dst links [[source]]")
+            .await
+            .unwrap();
+
+        let landed = vault
+            .rename_note(&from, &to, ConflictPolicy::Overwrite)
+            .await
+            .unwrap();
+
+        assert_eq!(landed, to);
+        assert_eq!(vault.get_note_text(&to).await.unwrap(), "src");
+        assert!(!vault.exists(&from).await);
+        let backup = backups_dir_today(temp.path()).join("dest.md");
+        assert_eq!(
+            std::fs::read_to_string(backup).unwrap(),
+            "dst links [[source]]"
+        );
+        assert_eq!(vault.get_all_notes().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_overwriting_rename_keeps_the_target() {
+        let (temp, vault) = backup_vault().await;
+        let from = VaultPath::note_path_from("/source.md");
+        let to = VaultPath::note_path_from("/dest.md");
+        vault.create_note(&from, "src").await.unwrap();
+        vault.create_note(&to, "dst").await.unwrap();
+        // The source is gone from disk but still indexed, so the rename
+        // itself fails after the target was moved aside.
+        std::fs::remove_file(temp.path().join("source.md")).unwrap();
+
+        assert!(vault
+            .rename_note(&from, &to, ConflictPolicy::Overwrite)
+            .await
+            .is_err());
+
+        assert_eq!(vault.get_note_text(&to).await.unwrap(), "dst");
+        assert!(!temp.path().join(".dest.md.overwritten").exists());
+        let notes = vault.get_all_notes().await.unwrap();
+        assert!(notes.iter().any(|(entry, _)| entry.path == to));
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn concurrent_replaces_do_not_lose_updates() {
         let (_temp, vault) = backup_vault().await;
@@ -53,7 +53,9 @@ impl<'a> LinkRewrite<'a> {
     /// Stage 1: query the index once for the notes linking to `from`. The
     /// source itself is excluded — its self-links are rewritten at the new
     /// path during [`Prepared::commit`], never written back to `from` (which
-    /// would resurrect a file at the old path).
+    /// would resurrect a file at the old path). So is `to`: when it exists it
+    /// is about to be overwritten, and writing it back would clobber the
+    /// renamed note.
     ///
     /// Paths are flattened internally, so callers may pass them as-is.
     pub(crate) async fn scout(
@@ -69,7 +71,7 @@ impl<'a> LinkRewrite<'a> {
             .await?
             .into_iter()
             .map(|(e, _)| e.path)
-            .filter(|p| *p != from)
+            .filter(|p| *p != from && *p != to)
             .collect();
         Ok(Scouted {
             base: self,
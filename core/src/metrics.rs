@@ -0,0 +1,116 @@
+//! Metrics — optional timing callbacks for vault operations.
+//!
+//! A vault with a hook registered
+//! ([`NoteVault::set_metrics`](crate::NoteVault::set_metrics)) times its
+//! saves, searches and sync passes and reports each as a [`MetricEvent`], so
+//! an app can aggregate latencies instead of scraping debug logs. Without a
+//! hook nothing is timed: the operations only check that none is set.
+
+use std::{
+    fmt,
+    sync::{Arc, PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+
+use crate::nfs::VaultPath;
+
+/// The kind of operation a [`MetricEvent`] timed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricOp {
+    /// A note written to disk and re-indexed.
+    Save,
+    /// A search query run against the index.
+    Search,
+    /// A sync pass bringing the index in step with the disk, for the whole
+    /// vault or one browsed directory.
+    Index,
+}
+
+impl MetricOp {
+    /// Short lowercase name (`"save"`, `"search"`, `"index"`), for metric
+    /// labels.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricOp::Save => "save",
+            MetricOp::Search => "search",
+            MetricOp::Index => "index",
+        }
+    }
+}
+
+impl fmt::Display for MetricOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One timed operation, reported once it has completed successfully.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricEvent {
+    pub op: MetricOp,
+    /// Wall-clock time the operation took.
+    pub duration: Duration,
+    /// The note saved, or the directory a sync pass walked. `None` for
+    /// searches.
+    pub path: Option<VaultPath>,
+}
+
+type MetricsHook = dyn Fn(MetricEvent) + Send + Sync;
+
+/// The registered metrics hook, if any. Shared across vault clones (like the
+/// index observer), so a hook set on one clone sees every clone's work.
+#[derive(Clone, Default)]
+pub(crate) struct Metrics {
+    hook: Arc<RwLock<Option<Arc<MetricsHook>>>>,
+}
+
+impl fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metrics")
+            .field("enabled", &self.enabled())
+            .finish()
+    }
+}
+
+impl Metrics {
+    pub(crate) fn set(&self, hook: Box<MetricsHook>) {
+        *self.hook.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::from(hook));
+    }
+
+    pub(crate) fn clear(&self) {
+        *self.hook.write().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    fn enabled(&self) -> bool {
+        self.hook
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+    }
+
+    /// When a timed operation starts: `None`, without reading the clock,
+    /// when no hook is registered.
+    pub(crate) fn start(&self) -> Option<Instant> {
+        self.enabled().then(Instant::now)
+    }
+
+    /// Reports an operation begun at `started` (from [`Self::start`]). The
+    /// hook runs outside the lock, so it may itself use the vault.
+    pub(crate) fn record(&self, op: MetricOp, started: Option<Instant>, path: Option<&VaultPath>) {
+        let Some(started) = started else {
+            return;
+        };
+        let hook = self
+            .hook
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        if let Some(hook) = hook {
+            hook(MetricEvent {
+                op,
+                duration: started.elapsed(),
+                path: path.cloned(),
+            });
+        }
+    }
+}
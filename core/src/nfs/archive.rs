@@ -0,0 +1,149 @@
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::{
+    get_sorted_file_walker, is_index_file, resolve_path_on_disk_sync, VaultPath, PATH_SEPARATOR,
+};
+use crate::error::FSError;
+
+fn archive_error(e: impl std::fmt::Display) -> FSError {
+    FSError::ArchiveError(e.to_string())
+}
+
+/// Whether an archive entry should be skipped: hidden files and directories
+/// and the index database, which the walk never visits, but an archive made
+/// elsewhere may hold. The index is rebuilt from the notes.
+fn is_excluded(relative: &Path) -> bool {
+    let names: Vec<_> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    names.iter().any(|name| name.starts_with('.')) || (names.len() == 1 && is_index_file(&names[0]))
+}
+
+/// `relative` as an archive entry name: `/`-separated, no leading slash.
+fn entry_name(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(&PATH_SEPARATOR.to_string())
+}
+
+/// Zips every file and directory under `workspace_path` into `writer`,
+/// one file at a time, keeping paths relative to the workspace root. Hidden
+/// entries (`.kimun`, `.trash`, dotfiles) and the index database are left
+/// out. Returns how many files were written.
+pub(crate) fn export_archive<W: Write + Seek>(
+    workspace_path: &Path,
+    writer: W,
+) -> Result<usize, FSError> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut files = 0;
+    for entry in get_sorted_file_walker(workspace_path, &VaultPath::root(), true) {
+        let entry = entry.map_err(archive_error)?;
+        let Ok(relative) = entry.path().strip_prefix(workspace_path) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() || is_excluded(relative) {
+            continue;
+        }
+        let name = entry_name(relative);
+        if entry.file_type().is_some_and(|t| t.is_dir()) {
+            zip.add_directory(name, options).map_err(archive_error)?;
+            continue;
+        }
+        zip.start_file(name, options).map_err(archive_error)?;
+        let mut file =
+            std::fs::File::open(entry.path()).map_err(|e| FSError::io(entry.path(), e))?;
+        std::io::copy(&mut file, &mut zip).map_err(|e| FSError::io(entry.path(), e))?;
+        files += 1;
+    }
+    zip.finish().map_err(archive_error)?;
+    Ok(files)
+}
+
+/// An archive being imported into a vault: lists its entries as vault
+/// paths, then extracts them one at a time to wherever the vault places
+/// them.
+pub(crate) struct ArchiveReader<R> {
+    zip: ZipArchive<R>,
+}
+
+/// One importable archive entry.
+pub(crate) struct ArchiveEntry {
+    /// Position of the entry in the archive, for [`ArchiveReader::extract`].
+    pub index: usize,
+    /// The entry's relative path, each component sanitized as a
+    /// [`VaultPath`] slice.
+    pub path: VaultPath,
+    pub is_dir: bool,
+}
+
+impl<R: Read + Seek> ArchiveReader<R> {
+    pub(crate) fn open(reader: R) -> Result<Self, FSError> {
+        Ok(Self {
+            zip: ZipArchive::new(reader).map_err(archive_error)?,
+        })
+    }
+
+    /// Every entry worth importing, in archive order. Hidden entries and
+    /// index files are skipped. Errors with [`FSError::InvalidPath`] when
+    /// an entry's path is absolute or climbs out with `..`, before anything
+    /// is extracted.
+    pub(crate) fn entries(&mut self) -> Result<Vec<ArchiveEntry>, FSError> {
+        let mut entries = Vec::with_capacity(self.zip.len());
+        for index in 0..self.zip.len() {
+            let entry = self.zip.by_index(index).map_err(archive_error)?;
+            let Some(relative) = entry.enclosed_name() else {
+                return Err(FSError::InvalidPath {
+                    path: entry.name().to_string(),
+                    message: "archive entry resolves outside the vault".to_string(),
+                });
+            };
+            if relative.as_os_str().is_empty() || is_excluded(&relative) {
+                continue;
+            }
+            entries.push(ArchiveEntry {
+                index,
+                path: VaultPath::new(entry_name(&relative)),
+                is_dir: entry.is_dir(),
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Writes the file entry at `index` to `path`, creating its parent
+    /// directories. Fails with [`FSError::AlreadyExists`] when a file is
+    /// already there, unless `overwrite`.
+    pub(crate) fn extract<P: AsRef<Path>>(
+        &mut self,
+        index: usize,
+        workspace_path: P,
+        path: &VaultPath,
+        overwrite: bool,
+    ) -> Result<(), FSError> {
+        let target = resolve_path_on_disk_sync(workspace_path, path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| FSError::io(parent, e))?;
+        }
+        let mut entry = self.zip.by_index(index).map_err(archive_error)?;
+        let file = if overwrite {
+            std::fs::File::create(&target)
+        } else {
+            std::fs::File::create_new(&target)
+        };
+        let mut file = file.map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => FSError::AlreadyExists {
+                path: path.to_owned(),
+            },
+            _ => FSError::io(&target, e),
+        })?;
+        std::io::copy(&mut entry, &mut file).map_err(|e| FSError::io(&target, e))?;
+        Ok(())
+    }
+}
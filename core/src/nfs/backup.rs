@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::sync::PoisonError;
 
 use super::{resolve_path_on_disk, VaultPath};
 use crate::error::FSError;
@@ -27,7 +28,7 @@ async fn purge_old_backups(backups_root: &Path) {
     // the dir not existing yet, or a read error) is retried on the next backup.
     if LAST_PURGE
         .lock()
-        .unwrap()
+        .unwrap_or_else(PoisonError::into_inner)
         .as_ref()
         .is_some_and(|(root, day)| root == backups_root && *day == today)
     {
@@ -46,7 +47,8 @@ async fn purge_old_backups(backups_root: &Path) {
             }
         }
     }
-    *LAST_PURGE.lock().unwrap() = Some((backups_root.to_path_buf(), today));
+    *LAST_PURGE.lock().unwrap_or_else(PoisonError::into_inner) =
+        Some((backups_root.to_path_buf(), today));
 }
 
 /// Atomically reserves a free backup destination: tries the mirrored name first,
@@ -71,7 +73,7 @@ async fn reserve_backup_dest(base: &Path) -> Result<std::path::PathBuf, FSError>
                 candidate = base.with_file_name(name);
                 attempt = attempt.wrapping_add(1);
             }
-            Err(e) => return Err(FSError::ReadFileError(e)),
+            Err(e) => return Err(FSError::io(&candidate, e)),
         }
     }
 }
@@ -97,7 +99,7 @@ pub(crate) async fn backup_note<P: AsRef<Path>>(
     match tokio::fs::try_exists(&src).await {
         Ok(true) => {}
         Ok(false) => return Ok(()),
-        Err(e) => return Err(FSError::ReadFileError(e)),
+        Err(e) => return Err(FSError::io(&src, e)),
     }
 
     let rel = src
@@ -111,11 +113,15 @@ pub(crate) async fn backup_note<P: AsRef<Path>>(
     let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
     let base = backups_root.join(date).join(rel);
     if let Some(parent) = base.parent() {
-        tokio::fs::create_dir_all(parent).await?;
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| FSError::io(parent, e))?;
     }
     // Reserve a unique name, then stream the source into it — no full read into
     // memory, and the reserved name can't be clobbered by a concurrent backup.
     let dest = reserve_backup_dest(&base).await?;
-    tokio::fs::copy(&src, &dest).await?;
+    tokio::fs::copy(&src, &dest)
+        .await
+        .map_err(|e| FSError::io(&src, e))?;
     Ok(())
 }
@@ -23,6 +23,31 @@ pub(crate) static RX_PATH_NAME: LazyLock<Regex> =
 pub(crate) static RX_WIN_RESERVED: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(WINDOWS_RESERVED_NAMES_REGEX).unwrap());
 
+/// Which characters a vault keeps when it names a new note or directory on
+/// disk (see [`crate::VaultConfig::filename_policy`]). Path identity — index
+/// keys, comparisons, link resolution — always follows the strict rules, so
+/// `Plans: Q3?.md` and `Plans_ Q3_.md` are the same note under either policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilenamePolicy {
+    /// Names valid on Linux, macOS and Windows alike: every character
+    /// [`is_disallowed_char`] flags is replaced with `_`, trailing dots are
+    /// dropped and reserved device names prefixed. The default.
+    #[default]
+    Strict,
+    /// Only what POSIX itself forbids is replaced (`/` separates components;
+    /// NUL becomes `_`), so `:`, `?`, `*` and friends are kept. Surrounding
+    /// spaces are still trimmed and a leading `..` still rewritten. Files named
+    /// this way may not sync or open on Windows or macOS.
+    ///
+    /// Identity stays strict, so two names that only differ in such
+    /// characters (`a:b.md`, `a?b.md`, `a_b.md`) are one note to the vault:
+    /// creating or renaming onto the second while the first exists fails with
+    /// [`crate::error::VaultError::NoteExists`], and reads of either reach the
+    /// file already on disk. [`crate::nfs::VaultPath::is_valid`] checks the
+    /// strict rules too.
+    Posix,
+}
+
 /// Returns `true` if `c` is forbidden in a vault filename on any of the three
 /// supported platforms. Covers the path metacharacters (`\ / : * ? " < > |`),
 /// the markdown/link-hostile `[ ] ^ #`, and ASCII control characters. Callers
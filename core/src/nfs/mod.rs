@@ -1,5 +1,7 @@
+mod archive;
 mod backup;
 pub mod filename;
+mod recovery;
 pub mod saved_searches;
 pub mod vault_id;
 mod vault_path;
@@ -10,14 +12,19 @@ use std::{
     time::UNIX_EPOCH,
 };
 
-use ignore::{WalkBuilder, WalkParallel};
+use ignore::{Walk, WalkBuilder, WalkParallel};
 use twox_hash::XxHash64;
 
 use super::{error::FSError, DirectoryDetails, NoteDetails};
 
 use super::utilities::path_to_string;
 
+pub(crate) use archive::{export_archive, ArchiveReader};
 pub(crate) use backup::backup_note;
+pub use recovery::RecoveryCopy;
+pub(crate) use recovery::{list_recovery, read_recovery, remove_recovery, write_recovery};
+use vault_path::component_identity;
+pub(crate) use vault_path::resolve_existing;
 pub use vault_path::{with_note_extension, VaultPath, PATH_SEPARATOR};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -93,13 +100,15 @@ impl NoteEntryData {
     /// Reads the file at `os_path` directly (no case-insensitive resolution).
     /// Use when the real on-disk path is already known (e.g. from the walker).
     pub(crate) fn load_details_from_os_path(&self, os_path: &Path) -> Result<NoteDetails, FSError> {
-        let bytes = std::fs::read(os_path)?;
+        let bytes = std::fs::read(os_path).map_err(|e| FSError::io(os_path, e))?;
         let text = String::from_utf8(bytes)?;
         Ok(NoteDetails::new(&self.path, text))
     }
 
     async fn from_os_path(path: &VaultPath, file_path: &Path) -> Result<NoteEntryData, FSError> {
-        let metadata = tokio::fs::metadata(file_path).await?;
+        let metadata = tokio::fs::metadata(file_path)
+            .await
+            .map_err(|e| FSError::io(file_path, e))?;
         Ok(Self::from_metadata(path, &metadata))
     }
 
@@ -203,7 +212,7 @@ impl VaultEntry {
             std::io::ErrorKind::NotFound => FSError::NoFileOrDirectoryFound {
                 path: path_to_string(os_path),
             },
-            _ => FSError::ReadFileError(e),
+            _ => FSError::io(os_path, e),
         }
     }
 
@@ -232,13 +241,26 @@ impl Display for VaultEntry {
     }
 }
 
+/// Seed of the stored content hash. Changing it (or the algorithm) changes
+/// every stored hash, so the next sync rewrites every note row: bump the
+/// index `VERSION` alongside.
+const HASH_SEED: u64 = 42;
+
+/// Content hash stored in the index to detect changed notes. XXH64 is
+/// specified byte-for-byte and independent of CPU architecture and crate
+/// version, so an index built on one machine stays valid on another that
+/// syncs the same vault (e.g. through Dropbox) instead of forcing a full
+/// reindex. `test_hash_text_is_pinned` locks the output.
 pub(crate) fn hash_text<S: AsRef<str>>(text: S) -> u64 {
-    XxHash64::oneshot(42, text.as_ref().as_bytes())
+    XxHash64::oneshot(HASH_SEED, text.as_ref().as_bytes())
 }
 
 /// Resolves a VaultPath to the real PathBuf on disk by matching each component
-/// case-insensitively. When a component doesn't exist on disk yet, the stored
-/// (lowercase) name is used for the remainder of the path.
+/// by identity: case-insensitively, and regardless of characters sanitizing
+/// replaces (so `a:b.md` on disk resolves `a_b.md`). When a component doesn't
+/// exist on disk yet, the caller's spelling of it is used for the remainder of
+/// the path (lowercase unless the vault preserves case; see
+/// [`VaultPath::preserving_case`]).
 ///
 /// Fast path: stored paths are always lowercase, so `vault_path.to_pathbuf` is
 /// the canonical form. We try it directly first; only fall back to the
@@ -258,14 +280,14 @@ pub(crate) async fn resolve_path_on_disk<P: AsRef<Path>>(
         let real_name = async {
             let mut entries = tokio::fs::read_dir(&current).await.ok()?;
             while let Ok(Some(entry)) = entries.next_entry().await {
-                if entry.file_name().to_string_lossy().to_lowercase() == name {
+                if component_identity(&entry.file_name().to_string_lossy()) == name {
                     return Some(entry.file_name().to_string_lossy().into_owned());
                 }
             }
             None
         }
         .await
-        .unwrap_or(name);
+        .unwrap_or_else(|| slice.spelled());
         current = current.join(real_name);
     }
     current
@@ -288,10 +310,10 @@ pub(crate) fn resolve_path_on_disk_sync<P: AsRef<Path>>(
             .and_then(|entries| {
                 entries
                     .filter_map(|e| e.ok())
-                    .find(|e| e.file_name().to_string_lossy().to_lowercase() == name)
+                    .find(|e| component_identity(&e.file_name().to_string_lossy()) == name)
                     .map(|e| e.file_name().to_string_lossy().into_owned())
             })
-            .unwrap_or(name);
+            .unwrap_or_else(|| slice.spelled());
         current = current.join(real_name);
     }
     current
@@ -375,7 +397,7 @@ pub(crate) async fn load_note<P: AsRef<Path>>(
             std::io::ErrorKind::NotFound => Err(FSError::VaultPathNotFound {
                 path: path.to_owned(),
             }),
-            _ => Err(FSError::ReadFileError(e)),
+            _ => Err(FSError::io(&os_path, e)),
         },
     }
 }
@@ -390,7 +412,9 @@ pub(crate) async fn create_directory<P: AsRef<Path>>(
 
     let full_path = resolve_path_on_disk(&workspace_path, path).await;
     if let Some(parent) = full_path.parent() {
-        tokio::fs::create_dir_all(parent).await?;
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| FSError::io(parent, e))?;
     }
     match tokio::fs::create_dir(&full_path).await {
         Ok(()) => Ok(DirectoryEntryData {
@@ -399,10 +423,28 @@ pub(crate) async fn create_directory<P: AsRef<Path>>(
         Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(FSError::AlreadyExists {
             path: path.to_owned(),
         }),
-        Err(e) => Err(FSError::ReadFileError(e)),
+        Err(e) => Err(FSError::io(&full_path, e)),
     }
 }
 
+/// Creates the directory at `path` along with any missing parents. Succeeds
+/// when the directory (or a case-insensitive variant) already exists; fails
+/// when something other than a directory is in the way.
+pub(crate) async fn ensure_directory<P: AsRef<Path>>(
+    workspace_path: P,
+    path: &VaultPath,
+) -> Result<DirectoryEntryData, FSError> {
+    path.ensure_directory()?;
+
+    let full_path = resolve_path_on_disk(&workspace_path, path).await;
+    tokio::fs::create_dir_all(&full_path)
+        .await
+        .map_err(|e| FSError::io(&full_path, e))?;
+    Ok(DirectoryEntryData {
+        path: path.to_owned(),
+    })
+}
+
 /// Writes raw bytes (e.g. an image attachment) at `path` under the workspace,
 /// creating parent directories as needed. Unlike [`save_note`], does not require
 /// the path to be a note file and bypasses the case-insensitive note resolver.
@@ -413,9 +455,13 @@ pub(crate) async fn save_attachment<P: AsRef<Path>>(
 ) -> Result<(), FSError> {
     let full_path = path.flatten().to_pathbuf(workspace_path);
     if let Some(parent) = full_path.parent() {
-        tokio::fs::create_dir_all(parent).await?;
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| FSError::io(parent, e))?;
     }
-    tokio::fs::write(&full_path, bytes).await?;
+    tokio::fs::write(&full_path, bytes)
+        .await
+        .map_err(|e| FSError::io(&full_path, e))?;
     Ok(())
 }
 
@@ -495,13 +541,13 @@ pub(crate) async fn read_attachment<P: AsRef<Path>>(
                 path: path.to_owned(),
             });
         }
-        Err(e) => return Err(FSError::ReadFileError(e)),
+        Err(e) => return Err(FSError::io(&os_path, e)),
     };
     let (size, modified_secs) = size_and_mtime(&meta);
 
     let file = tokio::fs::File::open(&os_path)
         .await
-        .map_err(FSError::ReadFileError)?;
+        .map_err(|e| FSError::io(&os_path, e))?;
 
     // Phase 1: sniff the first window. A binary file reveals itself here, so we
     // never read the full cap just to reject it.
@@ -510,7 +556,7 @@ pub(crate) async fn read_attachment<P: AsRef<Path>>(
     reader
         .read_to_end(&mut buf)
         .await
-        .map_err(FSError::ReadFileError)?;
+        .map_err(|e| FSError::io(&os_path, e))?;
     if sniff_is_binary(&buf) {
         return Ok(AttachmentRead {
             size,
@@ -527,7 +573,7 @@ pub(crate) async fn read_attachment<P: AsRef<Path>>(
         .take(remaining as u64)
         .read_to_end(&mut buf)
         .await
-        .map_err(FSError::ReadFileError)?;
+        .map_err(|e| FSError::io(&os_path, e))?;
     let truncated = buf.len() > ATTACHMENT_PREVIEW_CAP;
     buf.truncate(ATTACHMENT_PREVIEW_CAP);
 
@@ -556,24 +602,41 @@ pub(crate) async fn metadata_at<P: AsRef<Path>>(
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(FSError::VaultPathNotFound {
             path: path.to_owned(),
         }),
-        Err(e) => Err(FSError::ReadFileError(e)),
+        Err(e) => Err(FSError::io(&os_path, e)),
     }
 }
 
+/// Reads the size and mtime of the note at `path` as they are on disk now,
+/// in the same form the index stores them. A missing file is
+/// [`FSError::VaultPathNotFound`].
+pub(crate) async fn note_entry_at<P: AsRef<Path>>(
+    workspace_path: P,
+    path: &VaultPath,
+) -> Result<NoteEntryData, FSError> {
+    let metadata = metadata_at(workspace_path, path).await?;
+    Ok(NoteEntryData::from_metadata(path, &metadata))
+}
+
 /// Resolves both endpoints, ensures the destination's parent directory exists,
 /// and renames atomically. Returns `FSError::AlreadyExists` if the destination
 /// is occupied (the OS rename would silently overwrite on Linux otherwise).
+///
+/// Occupied is judged case-insensitively: an existing `Foo.md` blocks a rename
+/// to `foo.md` on every filesystem, not only where the two names are the same
+/// file. The one exception is the source itself, so a case-only rename that
+/// normalizes a legacy mixed-case file still goes through.
 async fn rename_path<P: AsRef<Path>>(
     workspace_path: P,
     from: &VaultPath,
     to: &VaultPath,
 ) -> Result<(), FSError> {
     let full_from_path = resolve_path_on_disk(&workspace_path, from).await;
-    let (to_parent, to_name) = to.get_parent_path();
+    let (to_parent, _) = to.get_parent_path();
     let to_base = resolve_path_on_disk(&workspace_path, &to_parent).await;
-    let full_to_path = to_base.join(&to_name);
+    let full_to_path = to_base.join(to.spelled_name());
+    let occupant = resolve_path_on_disk(&workspace_path, to).await;
 
-    if matches!(tokio::fs::try_exists(&full_to_path).await, Ok(true)) {
+    if occupant != full_from_path && matches!(tokio::fs::try_exists(&occupant).await, Ok(true)) {
         return Err(FSError::AlreadyExists {
             path: to.to_owned(),
         });
@@ -582,10 +645,14 @@ async fn rename_path<P: AsRef<Path>>(
     match tokio::fs::metadata(&to_base).await {
         Ok(m) if m.is_dir() => {}
         _ => {
-            tokio::fs::create_dir_all(&to_base).await?;
+            tokio::fs::create_dir_all(&to_base)
+                .await
+                .map_err(|e| FSError::io(&to_base, e))?;
         }
     }
-    tokio::fs::rename(full_from_path, full_to_path).await?;
+    tokio::fs::rename(&full_from_path, full_to_path)
+        .await
+        .map_err(|e| FSError::io(&full_from_path, e))?;
     Ok(())
 }
 
@@ -618,9 +685,13 @@ pub(crate) async fn save_note<P: AsRef<Path>, S: AsRef<str>>(
     // written in place rather than creating a new lowercase `mynote.md` alongside it.
     let full_path = resolve_path_on_disk(&workspace_path, path).await;
     if let Some(base_path) = full_path.parent() {
-        tokio::fs::create_dir_all(base_path).await?;
+        tokio::fs::create_dir_all(base_path)
+            .await
+            .map_err(|e| FSError::io(base_path, e))?;
     }
-    tokio::fs::write(&full_path, text.as_ref().as_bytes()).await?;
+    tokio::fs::write(&full_path, text.as_ref().as_bytes())
+        .await
+        .map_err(|e| FSError::io(&full_path, e))?;
 
     let entry = NoteEntryData::from_os_path(path, &full_path).await?;
     Ok(entry)
@@ -636,7 +707,9 @@ pub(crate) async fn create_note_exclusive<P: AsRef<Path>, S: AsRef<str>>(
     path.ensure_note()?;
     let full_path = resolve_path_on_disk(&workspace_path, path).await;
     if let Some(base_path) = full_path.parent() {
-        tokio::fs::create_dir_all(base_path).await?;
+        tokio::fs::create_dir_all(base_path)
+            .await
+            .map_err(|e| FSError::io(base_path, e))?;
     }
     let mut file = match tokio::fs::OpenOptions::new()
         .write(true)
@@ -650,11 +723,13 @@ pub(crate) async fn create_note_exclusive<P: AsRef<Path>, S: AsRef<str>>(
                 path: path.to_owned(),
             });
         }
-        Err(e) => return Err(FSError::ReadFileError(e)),
+        Err(e) => return Err(FSError::io(&full_path, e)),
     };
     use tokio::io::AsyncWriteExt;
-    file.write_all(text.as_ref().as_bytes()).await?;
-    file.flush().await?;
+    file.write_all(text.as_ref().as_bytes())
+        .await
+        .map_err(|e| FSError::io(&full_path, e))?;
+    file.flush().await.map_err(|e| FSError::io(&full_path, e))?;
     drop(file);
 
     NoteEntryData::from_os_path(path, &full_path).await
@@ -670,6 +745,48 @@ pub(crate) async fn rename_note<P: AsRef<Path>>(
     rename_path(workspace_path, from, to).await
 }
 
+/// A note moved to a hidden sibling (`.<name>.overwritten`) so an
+/// overwriting rename can take its name. The walker skips dotfiles, so it is
+/// never indexed while it waits to be restored or discarded.
+pub(crate) struct SetAside {
+    original: PathBuf,
+    aside: PathBuf,
+}
+
+impl SetAside {
+    /// Puts the note back where it was.
+    pub(crate) async fn restore(self) -> Result<(), FSError> {
+        tokio::fs::rename(&self.aside, &self.original)
+            .await
+            .map_err(|e| FSError::io(&self.aside, e))
+    }
+
+    /// Deletes the set-aside note for good.
+    pub(crate) async fn discard(self) -> Result<(), FSError> {
+        tokio::fs::remove_file(&self.aside)
+            .await
+            .map_err(|e| FSError::io(&self.aside, e))
+    }
+}
+
+/// Moves the note at `path` out of the way, see [`SetAside`].
+pub(crate) async fn set_aside_note<P: AsRef<Path>>(
+    workspace_path: P,
+    path: &VaultPath,
+) -> Result<SetAside, FSError> {
+    path.ensure_note()?;
+    let original = resolve_path_on_disk(&workspace_path, path).await;
+    let name = original
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let aside = original.with_file_name(format!(".{name}.overwritten"));
+    tokio::fs::rename(&original, &aside)
+        .await
+        .map_err(|e| FSError::io(&original, e))?;
+    Ok(SetAside { original, aside })
+}
+
 pub(crate) async fn rename_directory<P: AsRef<Path>>(
     workspace_path: P,
     from: &VaultPath,
@@ -695,13 +812,41 @@ async fn remove_file_at<P: AsRef<Path>>(
     path: &VaultPath,
 ) -> Result<(), FSError> {
     let full_path = resolve_path_on_disk(&workspace_path, path).await;
-    tokio::fs::remove_file(full_path).await?;
+    tokio::fs::remove_file(&full_path)
+        .await
+        .map_err(|e| FSError::io(&full_path, e))?;
     Ok(())
 }
 
 /// Create `dir` and all missing parents. No-op if it already exists.
 pub(crate) fn ensure_dir(dir: &Path) -> Result<(), FSError> {
-    std::fs::create_dir_all(dir).map_err(FSError::ReadFileError)
+    std::fs::create_dir_all(dir).map_err(|e| FSError::io(dir, e))
+}
+
+/// The identities (see `component_identity`) of every entry directly inside
+/// the directory `dir`, for picking a free name with
+/// [`VaultPath::get_free_name`]: the same notion of a taken name the path
+/// resolvers use, so a free name never resolves to an existing entry. A
+/// missing directory has no entries.
+pub(crate) async fn sibling_names<P: AsRef<Path>>(
+    workspace_path: P,
+    dir: &VaultPath,
+) -> Result<std::collections::HashSet<String>, FSError> {
+    let full_path = resolve_path_on_disk(&workspace_path, dir).await;
+    let mut names = std::collections::HashSet::new();
+    let mut entries = match tokio::fs::read_dir(&full_path).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(names),
+        Err(e) => return Err(FSError::io(&full_path, e)),
+    };
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| FSError::io(&full_path, e))?
+    {
+        names.insert(component_identity(&entry.file_name().to_string_lossy()));
+    }
+    Ok(names)
 }
 
 /// Returns true if anything (file or directory) exists at the resolved
@@ -712,7 +857,9 @@ pub(crate) async fn path_exists<P: AsRef<Path>>(
     path: &VaultPath,
 ) -> Result<bool, FSError> {
     let full_path = resolve_path_on_disk(&workspace_path, path).await;
-    Ok(tokio::fs::try_exists(&full_path).await?)
+    tokio::fs::try_exists(&full_path)
+        .await
+        .map_err(|e| FSError::io(&full_path, e))
 }
 
 pub(crate) async fn delete_directory<P: AsRef<Path>>(
@@ -720,7 +867,9 @@ pub(crate) async fn delete_directory<P: AsRef<Path>>(
     path: &VaultPath,
 ) -> Result<(), FSError> {
     let full_path = resolve_path_on_disk(&workspace_path, path).await;
-    tokio::fs::remove_dir_all(full_path).await?;
+    tokio::fs::remove_dir_all(&full_path)
+        .await
+        .map_err(|e| FSError::io(&full_path, e))?;
     Ok(())
 }
 
@@ -737,6 +886,26 @@ fn filter_files(dir: &ignore::DirEntry) -> bool {
         .unwrap_or(true)
 }
 
+/// Whether `name`, a workspace-root entry, is the index database or one of
+/// its SQLite sidecars (`-wal`, `-shm`, `-journal`).
+pub(crate) fn is_index_file(name: &str) -> bool {
+    name.strip_prefix(crate::index::DB_FILE)
+        .is_some_and(|rest| matches!(rest, "" | "-wal" | "-shm" | "-journal"))
+}
+
+/// [`filter_files`] for a walk of the workspace at `workspace_path`, also
+/// pruning the index database and its sidecars when they sit at the
+/// workspace root (the default location), so they never show up as
+/// attachments.
+fn workspace_filter(workspace_path: &Path) -> impl Fn(&ignore::DirEntry) -> bool + Send + Sync {
+    let workspace_path = workspace_path.to_path_buf();
+    move |entry| {
+        filter_files(entry)
+            && !(entry.path().parent() == Some(workspace_path.as_path())
+                && entry.file_name().to_str().is_some_and(is_index_file))
+    }
+}
+
 pub(crate) fn list_directories<P: AsRef<Path>>(
     base_path: P,
     path: &VaultPath,
@@ -760,18 +929,119 @@ pub(crate) fn list_directories<P: AsRef<Path>>(
     Ok(dirs)
 }
 
+/// Total size in bytes of every attachment (non-note file) under the
+/// workspace, skipping dot-entries like the walker does and any path in
+/// `exclude` (the index database files when they live inside the vault).
+/// Each entry is stat'ed individually; entries that vanish mid-walk are
+/// skipped.
+pub(crate) fn attachments_size<P: AsRef<Path>>(
+    workspace_path: P,
+    exclude: &[PathBuf],
+) -> Result<u64, FSError> {
+    let workspace_path = workspace_path.as_ref();
+    let walker = WalkBuilder::new(workspace_path)
+        .filter_entry(workspace_filter(workspace_path))
+        .build();
+
+    let mut total = 0;
+    for entry in walker.flatten() {
+        let entry_path = entry.path();
+        if exclude.iter().any(|p| p == entry_path) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            continue;
+        }
+        let vault_path = VaultPath::from_path(workspace_path, entry_path)?;
+        if classify(&metadata, &vault_path) == EntryKind::Attachment {
+            total += metadata.len();
+        }
+    }
+    Ok(total)
+}
+
+/// Number of note files in the vault (dot-entries excluded), counted from
+/// directory metadata alone — no note is read.
+pub(crate) fn count_notes<P: AsRef<Path>>(workspace_path: P) -> Result<usize, FSError> {
+    let workspace_path = workspace_path.as_ref();
+    let walker = WalkBuilder::new(workspace_path)
+        .filter_entry(filter_files)
+        .build();
+
+    let mut count = 0;
+    for entry in walker.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            continue;
+        }
+        let vault_path = VaultPath::from_path(workspace_path, entry.path())?;
+        if classify(&metadata, &vault_path) == EntryKind::Note {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Summed size in bytes of the files at `paths`; missing files count as 0.
+pub(crate) fn files_size(paths: &[PathBuf]) -> u64 {
+    paths
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Whether anything exists at the OS path `path`, such as a workspace
+/// root. Any error reading it counts as nothing there.
+pub(crate) fn os_path_exists(path: &Path) -> bool {
+    path.exists()
+}
+
+/// Whether a directory exists at the OS path `path`.
+pub(crate) fn dir_exists(path: &Path) -> bool {
+    path.is_dir()
+}
+
+/// Whether a regular file exists at the OS path `path`, e.g. the index
+/// cache outside the workspace.
+pub(crate) fn file_exists(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn file_walk_builder<P: AsRef<Path>>(base_path: P, path: &VaultPath, recurse: bool) -> WalkBuilder {
+    let base_path = base_path.as_ref();
+    let mut builder = WalkBuilder::new(resolve_path_on_disk_sync(base_path, path));
+    builder
+        .max_depth(if recurse { None } else { Some(1) })
+        .filter_entry(workspace_filter(base_path));
+    builder
+}
+
 pub(crate) fn get_file_walker<P: AsRef<Path>>(
     base_path: P,
     path: &VaultPath,
     recurse: bool,
 ) -> WalkParallel {
-    let w = WalkBuilder::new(resolve_path_on_disk_sync(base_path, path))
-        .max_depth(if recurse { None } else { Some(1) })
-        .filter_entry(filter_files)
-        // .threads(0)
-        .build_parallel();
+    file_walk_builder(base_path, path, recurse).build_parallel()
+}
 
-    w
+/// Single-threaded sibling of [`get_file_walker`] that yields entries in a
+/// fixed order: depth first, each directory's entries sorted by file name.
+/// Slower on large vaults, but reproducible run to run.
+pub(crate) fn get_sorted_file_walker<P: AsRef<Path>>(
+    base_path: P,
+    path: &VaultPath,
+    recurse: bool,
+) -> Walk {
+    file_walk_builder(base_path, path, recurse)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build()
 }
 
 #[cfg(test)]
@@ -842,8 +1112,9 @@ mod tests {
     use crate::{
         error::FSError,
         nfs::{
-            create_directory, delete_directory, delete_note, rename_directory, rename_note,
-            save_note, DirectoryEntryData, EntryData, VaultEntry, VaultEntryDetails,
+            create_directory, create_note_exclusive, delete_directory, delete_note,
+            rename_directory, rename_note, save_note, DirectoryEntryData, EntryData, VaultEntry,
+            VaultEntryDetails,
         },
         DirectoryDetails, NoteDetails,
     };
@@ -1219,6 +1490,16 @@ mod tests {
         assert!(hash1 > 0);
     }
 
+    /// Stored hashes must not drift between builds or machines; a change
+    /// here invalidates every existing index.
+    #[test]
+    fn test_hash_text_is_pinned() {
+        use super::hash_text;
+
+        assert_eq!(hash_text(""), 11002672306508523268);
+        assert_eq!(hash_text("Hello, world!"), 8819744617804762963);
+    }
+
     #[tokio::test]
     async fn test_create_directory_with_note_path() {
         let workspace_path = Path::new("testdata");
@@ -1441,6 +1722,68 @@ mod tests {
         assert!(!tmp.path().join("Archive").exists());
     }
 
+    #[tokio::test]
+    async fn create_note_exclusive_refuses_differently_cased_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("Foo.md"), "original")
+            .await
+            .unwrap();
+
+        let result = create_note_exclusive(tmp.path(), &VaultPath::new("/foo.md"), "new").await;
+
+        assert!(matches!(result, Err(FSError::AlreadyExists { .. })));
+        let content = tokio::fs::read_to_string(tmp.path().join("Foo.md"))
+            .await
+            .unwrap();
+        assert_eq!(content, "original");
+    }
+
+    #[tokio::test]
+    async fn rename_note_refuses_differently_cased_destination() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("Foo.md"), "original")
+            .await
+            .unwrap();
+        tokio::fs::write(tmp.path().join("bar.md"), "bar")
+            .await
+            .unwrap();
+
+        let result = rename_note(
+            tmp.path(),
+            &VaultPath::new("/bar.md"),
+            &VaultPath::new("/foo.md"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(FSError::AlreadyExists { .. })));
+        let content = tokio::fs::read_to_string(tmp.path().join("Foo.md"))
+            .await
+            .unwrap();
+        assert_eq!(content, "original");
+        assert!(tmp.path().join("bar.md").exists());
+    }
+
+    #[tokio::test]
+    async fn rename_note_allows_case_only_rename_of_itself() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("Foo.md"), "data")
+            .await
+            .unwrap();
+
+        rename_note(
+            tmp.path(),
+            &VaultPath::new("/foo.md"),
+            &VaultPath::new("/foo.md"),
+        )
+        .await
+        .unwrap();
+
+        let content = tokio::fs::read_to_string(tmp.path().join("foo.md"))
+            .await
+            .unwrap();
+        assert_eq!(content, "data");
+    }
+
     #[tokio::test]
     async fn rename_note_finds_uppercase_source() {
         let tmp = tempfile::TempDir::new().unwrap();
@@ -1547,4 +1890,22 @@ mod tests {
         assert_eq!(entry.path.to_string(), "/projects/mynote.md");
         assert!(matches!(entry.data, EntryData::Note(_)));
     }
+
+    #[tokio::test]
+    async fn io_errors_name_the_path_that_failed() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        // A file where the note's directory should be: creating it fails.
+        tokio::fs::write(tmp.path().join("blocker"), "")
+            .await
+            .unwrap();
+
+        let err = save_note(tmp.path(), &VaultPath::new("/blocker/note.md"), "x")
+            .await
+            .unwrap_err();
+        let FSError::ReadFileError { path, .. } = &err else {
+            panic!("expected an IO error, got {err:?}");
+        };
+        assert_eq!(path, &tmp.path().join("blocker"));
+        assert!(err.to_string().contains("blocker"));
+    }
 }
@@ -0,0 +1,137 @@
+//! Recovery copies: an editor's unsaved buffer, written to
+//! `.kimun/recovery/<note path>` before each save and cleared once the save
+//! lands, so text survives a failed save or a killed process and the next
+//! launch can offer it back. `.kimun` is hidden, so the indexer's walker skips
+//! recovery files and they never appear in search. All filesystem access lives
+//! here per the project rule that fs ops belong in `nfs`.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::VaultPath;
+use crate::error::FSError;
+
+/// A recovery copy waiting in the vault, as listed by
+/// [`NoteVault::list_recovery`](crate::NoteVault::list_recovery).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryCopy {
+    /// The note the copy belongs to.
+    pub path: VaultPath,
+    /// When the copy was written.
+    pub saved: SystemTime,
+    /// When the note on disk was last modified, `None` if it no longer
+    /// exists.
+    pub note_modified: Option<SystemTime>,
+}
+
+impl RecoveryCopy {
+    /// Whether the copy holds text written after the note was last saved
+    /// (or the note is gone) — the copies worth offering to restore. An
+    /// older copy was superseded by a later save. Equal times (coarse
+    /// filesystem clocks) count as newer: an extra prompt beats lost text.
+    pub fn is_newer_than_note(&self) -> bool {
+        self.note_modified
+            .is_none_or(|note_modified| self.saved >= note_modified)
+    }
+}
+
+fn recovery_root(workspace_path: &Path) -> PathBuf {
+    workspace_path.join(".kimun").join("recovery")
+}
+
+/// Writes `text` as the recovery copy of the note at `path`, replacing any
+/// earlier copy (the newest unsaved text is the one worth keeping). A plain
+/// write with no index or note lock involved, so it still works when the
+/// regular save path is what failed.
+pub(crate) async fn write_recovery(
+    workspace_path: &Path,
+    path: &VaultPath,
+    text: &str,
+) -> Result<(), FSError> {
+    let file = path.to_pathbuf(recovery_root(workspace_path));
+    if let Some(parent) = file.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| FSError::io(parent, e))?;
+    }
+    tokio::fs::write(&file, text)
+        .await
+        .map_err(|e| FSError::io(&file, e))
+}
+
+/// Every recovery copy, sorted by note path. Empty when the recovery directory
+/// does not exist yet.
+pub(crate) async fn list_recovery(workspace_path: &Path) -> Result<Vec<RecoveryCopy>, FSError> {
+    let root = recovery_root(workspace_path);
+    let mut found = Vec::new();
+    let mut dirs = vec![root.clone()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(FSError::io(&dir, e)),
+        };
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| FSError::io(&dir, e))?
+        {
+            let file_type = entry
+                .file_type()
+                .await
+                .map_err(|e| FSError::io(entry.path(), e))?;
+            if file_type.is_dir() {
+                dirs.push(entry.path());
+            } else if file_type.is_file() {
+                let path = VaultPath::from_path(&root, entry.path())?;
+                if !path.is_note() {
+                    continue;
+                }
+                let saved = entry
+                    .metadata()
+                    .await
+                    .and_then(|m| m.modified())
+                    .map_err(|e| FSError::io(entry.path(), e))?;
+                let note_modified = tokio::fs::metadata(path.to_pathbuf(workspace_path))
+                    .await
+                    .and_then(|m| m.modified())
+                    .ok();
+                found.push(RecoveryCopy {
+                    path,
+                    saved,
+                    note_modified,
+                });
+            }
+        }
+    }
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(found)
+}
+
+/// The recovery copy of the note at `path`.
+pub(crate) async fn read_recovery(
+    workspace_path: &Path,
+    path: &VaultPath,
+) -> Result<String, FSError> {
+    let file = path.to_pathbuf(recovery_root(workspace_path));
+    match tokio::fs::read_to_string(&file).await {
+        Ok(text) => Ok(text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(FSError::VaultPathNotFound {
+            path: path.to_owned(),
+        }),
+        Err(e) => Err(FSError::io(&file, e)),
+    }
+}
+
+/// Deletes the recovery copy of the note at `path`, if there is one.
+pub(crate) async fn remove_recovery(
+    workspace_path: &Path,
+    path: &VaultPath,
+) -> Result<(), FSError> {
+    let file = path.to_pathbuf(recovery_root(workspace_path));
+    match tokio::fs::remove_file(&file).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(FSError::io(&file, e)),
+    }
+}
@@ -50,7 +50,7 @@ pub async fn read_saved_searches(workspace_path: &Path) -> Result<Vec<SavedSearc
             Ok(parsed.search)
         }
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
-        Err(e) => Err(FSError::ReadFileError(e)),
+        Err(e) => Err(FSError::io(&path, e)),
     }
 }
 
@@ -61,14 +61,18 @@ pub async fn write_saved_searches(
 ) -> Result<(), FSError> {
     let path = saved_searches_path(workspace_path);
     if let Some(parent) = path.parent() {
-        tokio::fs::create_dir_all(parent).await?;
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| FSError::io(parent, e))?;
     }
     let file = SavedSearchFile {
         search: searches.to_vec(),
     };
     let body =
         toml::to_string_pretty(&file).map_err(|e| FSError::SerializationError(e.to_string()))?;
-    tokio::fs::write(&path, body).await?;
+    tokio::fs::write(&path, body)
+        .await
+        .map_err(|e| FSError::io(&path, e))?;
     Ok(())
 }
 
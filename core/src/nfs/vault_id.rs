@@ -51,9 +51,10 @@ pub async fn read_or_create_vault_id(workspace_path: &Path) -> Result<VaultId, F
     }
     // Missing, empty, or corrupt — settle it on a blocking thread (std fs +
     // a blocking OS lock).
-    tokio::task::spawn_blocking(move || settle_vault_id(&path))
+    let settled = path.clone();
+    tokio::task::spawn_blocking(move || settle_vault_id(&settled))
         .await
-        .map_err(|e| FSError::ReadFileError(std::io::Error::other(e)))?
+        .map_err(|e| FSError::io(&path, std::io::Error::other(e)))?
 }
 
 /// Decides the vault id when the file is missing, empty, or corrupt.
@@ -69,14 +70,16 @@ pub async fn read_or_create_vault_id(workspace_path: &Path) -> Result<VaultId, F
 /// occur because the OS drops it with the process.
 fn settle_vault_id(path: &Path) -> Result<VaultId, FSError> {
     if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+        std::fs::create_dir_all(parent).map_err(|e| FSError::io(parent, e))?;
     }
+    let lock_path = path.with_extension("lock");
     let lock_file = std::fs::OpenOptions::new()
         .create(true)
         .truncate(false) // the file is only a lock anchor; content irrelevant
         .write(true)
-        .open(path.with_extension("lock"))?;
-    lock_file.lock()?; // held until `lock_file` drops
+        .open(&lock_path)
+        .map_err(|e| FSError::io(&lock_path, e))?;
+    lock_file.lock().map_err(|e| FSError::io(&lock_path, e))?; // held until `lock_file` drops
 
     // Re-read under the lock: whoever held it before us may already have
     // settled the id.
@@ -91,25 +94,25 @@ fn settle_vault_id(path: &Path) -> Result<VaultId, FSError> {
                 // an orphan from a crash between create and write — not a
                 // live writer mid-write. Clear it so the publish below works
                 // on every platform (Windows' rename won't replace).
-                std::fs::remove_file(path)?;
+                std::fs::remove_file(path).map_err(|e| FSError::io(path, e))?;
             } else {
                 // Corrupt: keep the evidence as `vault-id.corrupt` instead of
                 // silently destroying it.
                 let backup = path.with_extension("corrupt");
                 let _ = std::fs::remove_file(&backup);
-                std::fs::rename(path, &backup)?;
+                std::fs::rename(path, &backup).map_err(|e| FSError::io(path, e))?;
             }
         }
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
-        Err(e) => return Err(FSError::ReadFileError(e)),
+        Err(e) => return Err(FSError::io(path, e)),
     }
 
     // The path is vacant; publish a fresh id atomically so lock-free
     // fast-path readers can never observe a partial write.
     let id = VaultId::new_random();
     let tmp = path.with_extension("tmp");
-    std::fs::write(&tmp, id.to_string())?;
-    std::fs::rename(&tmp, path)?;
+    std::fs::write(&tmp, id.to_string()).map_err(|e| FSError::io(&tmp, e))?;
+    std::fs::rename(&tmp, path).map_err(|e| FSError::io(&tmp, e))?;
     Ok(id)
 }
 
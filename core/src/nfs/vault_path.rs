@@ -1,10 +1,11 @@
-use std::{fmt::Display, path::Path, path::PathBuf, str::FromStr, sync::LazyLock};
+use std::{borrow::Cow, fmt::Display, path::Path, path::PathBuf, str::FromStr, sync::LazyLock};
 
 use log::warn;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
 use regex::Regex;
 use serde::{de::Visitor, Deserialize, Serialize};
 
-use super::filename;
+use super::filename::{self, FilenamePolicy};
 use crate::error::FSError;
 use crate::utilities::path_to_string;
 
@@ -14,6 +15,27 @@ use crate::utilities::path_to_string;
 pub const PATH_SEPARATOR: char = '/';
 const NOTE_EXTENSION: &str = ".md";
 
+/// Characters escaped by [`VaultPath::encode_for_url`]: those a URL path or a
+/// markdown link destination can't hold as is. `/` stays a separator.
+const URL_PATH_ESCAPES: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'(')
+    .add(b')')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
 /// Appends the note extension to `name` if it is not already present, without
 /// sanitizing the rest of the string. Unlike [`VaultPath::note_path_from`] this
 /// leaves wildcards and other non-path characters intact, so search patterns
@@ -28,6 +50,27 @@ pub fn with_note_extension<S: AsRef<str>>(name: S) -> String {
     }
 }
 
+/// `path` with symlinks resolved, as [`std::fs::canonicalize`] would, for a
+/// path that may not exist yet: its deepest existing ancestor is resolved and
+/// the remaining components appended as written. A path with no existing
+/// ancestor comes back unchanged.
+pub(crate) fn resolve_existing(path: &Path) -> PathBuf {
+    let mut missing = vec![];
+    let mut current = path;
+    loop {
+        if let Ok(resolved) = std::fs::canonicalize(current) {
+            return missing.iter().rev().fold(resolved, |acc, c| acc.join(c));
+        }
+        match (current.parent(), current.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                current = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
 static RX_INCREMENT_SUFFIX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"_(?P<number>[0-9]+)$").unwrap());
 
@@ -132,9 +175,12 @@ impl VaultPath {
             .split(PATH_SEPARATOR)
             .filter(|p| !p.is_empty()) // We remove the empty ones,
             // so `//` are treated as `/`
-            .for_each(|slice| {
-                slices.push(VaultPathSlice::new(slice));
-            });
+            .map(VaultPathSlice::new)
+            // A component that sanitizes away entirely (e.g. `" "`) is
+            // dropped too: kept, it would print as `//`, or as a leading `/`
+            // that turns a relative path absolute on the next parse.
+            .filter(|slice| !slice.is_blank())
+            .for_each(|slice| slices.push(slice));
         Self { absolute, slices }
     }
 
@@ -154,6 +200,8 @@ impl VaultPath {
     /// sanitization: every component is valid on all three target filesystems
     /// and there are no doubled separators. Use this to validate caller-supplied
     /// strings up front; [`VaultPath::new`] will instead silently repair them.
+    /// The check is the strict one whatever the vault's
+    /// [`FilenamePolicy`], since path identity always follows it.
     ///
     /// ```
     /// use kimun_core::nfs::VaultPath;
@@ -174,6 +222,29 @@ impl VaultPath {
             .any(|s| !VaultPathSlice::is_valid(s))
     }
 
+    /// What `input` becomes on disk once [`VaultPath::new`] has sanitized it:
+    /// invalid characters replaced with `_`, surrounding spaces and trailing
+    /// dots stripped, reserved device names prefixed, empty components dropped.
+    /// The caller's casing is kept. Comparing the result with `input` tells a
+    /// UI whether anything was replaced.
+    ///
+    /// ```
+    /// use kimun_core::nfs::VaultPath;
+    /// assert_eq!(VaultPath::sanitize("Plans: Q3?"), "Plans_ Q3_");
+    /// assert_eq!(VaultPath::sanitize("/a//b./CON"), "/a/b/_CON");
+    /// assert_eq!(VaultPath::sanitize("Fine name"), "Fine name");
+    /// ```
+    pub fn sanitize<S: AsRef<str>>(input: S) -> String {
+        let path = Self::new(input);
+        let names: Vec<String> = path.slices.iter().map(VaultPathSlice::spelled).collect();
+        let joined = names.join(&PATH_SEPARATOR.to_string());
+        if path.absolute {
+            format!("{PATH_SEPARATOR}{joined}")
+        } else {
+            joined
+        }
+    }
+
     /// Builds a sanitized note path from `path`, ensuring it ends with the note
     /// extension. A trailing separator is dropped before the extension is added,
     /// so `notes/` becomes `notes.md`. Unlike [`with_note_extension`], the rest
@@ -237,7 +308,7 @@ impl VaultPath {
         let mut slices = self.slices.clone();
         match slices.pop() {
             Some(slice) => {
-                if let VaultPathSlice::PathSlice(name) = slice {
+                if let VaultPathSlice::PathSlice(name, _) = slice {
                     let new_name = if let Some(name) = name.strip_suffix(NOTE_EXTENSION) {
                         format!("{}{}", Self::increment(name), NOTE_EXTENSION)
                     } else {
@@ -256,6 +327,60 @@ impl VaultPath {
         }
     }
 
+    /// Returns the first free variant of this path given the names already
+    /// `taken` in its directory (compared as lowercase names, the way vault
+    /// paths are stored). The path itself is returned when its name is free.
+    ///
+    /// Otherwise the *smallest* unused `_N` suffix from `_1` up is picked, so
+    /// repeatedly creating `note.md` yields `note.md`, `note_1.md`,
+    /// `note_2.md` with no gaps. Gaps left by deleted notes are reused: with
+    /// only `note.md` and `note_5.md` present the next name is `note_1.md`,
+    /// not `note_6.md`. An existing `_N` suffix on this path is treated as
+    /// part of the numbering, so a taken `note_1.md` resolves like `note.md`.
+    /// The suffix goes before the extension of other files too, so a taken
+    /// `pic.png` becomes `pic_1.png`.
+    /// Unlike [`get_name_on_conflict`], which only bumps the immediate
+    /// candidate, this is deterministic for a given set of siblings.
+    ///
+    /// [`get_name_on_conflict`]: VaultPath::get_name_on_conflict
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use kimun_core::nfs::VaultPath;
+    /// let taken: HashSet<String> = ["note.md", "note_5.md"].map(String::from).into();
+    /// let free = VaultPath::new("/dir/note.md").get_free_name(&taken);
+    /// assert_eq!(free.to_string(), "/dir/note_1.md");
+    /// ```
+    pub fn get_free_name(&self, taken: &std::collections::HashSet<String>) -> VaultPath {
+        let path = self.flatten();
+        let name = path.get_name();
+        if name.is_empty() || !taken.contains(&name) {
+            return path;
+        }
+        let (stem, extension) = match name.strip_suffix(NOTE_EXTENSION) {
+            Some(stem) => (stem, NOTE_EXTENSION),
+            None => match name.rfind('.') {
+                Some(dot) if dot > 0 => name.split_at(dot),
+                _ => (name.as_str(), ""),
+            },
+        };
+        let stem = match RX_INCREMENT_SUFFIX.find(stem) {
+            Some(suffix) => &stem[..suffix.start()],
+            None => stem,
+        };
+        let free = (1u64..)
+            .map(|n| format!("{}_{}{}", stem, n, extension))
+            .find(|candidate| !taken.contains(candidate))
+            .unwrap_or_default();
+        let mut slices = path.slices;
+        slices.pop();
+        slices.push(VaultPathSlice::new(free));
+        VaultPath {
+            absolute: path.absolute,
+            slices,
+        }
+    }
+
     /// Returns the final component's name with the note extension stripped — the
     /// note's display title as derived from its filename. For directories (no
     /// extension) this is just the directory name. Compare [`get_name`], which
@@ -293,6 +418,67 @@ impl VaultPath {
         with_note_extension(self.to_string())
     }
 
+    /// The path percent-encoded for a link's `href` or a markdown link
+    /// destination, where spaces and brackets would end the link early.
+    /// Separators are kept; [`VaultPath::decode_url`] reverses it.
+    ///
+    /// ```
+    /// use kimun_core::nfs::VaultPath;
+    /// let path = VaultPath::new("/my notes/note (1).md");
+    /// assert_eq!(path.encode_for_url(), "/my%20notes/note%20%281%29.md");
+    /// assert_eq!(VaultPath::new(VaultPath::decode_url(&path.encode_for_url())), path);
+    /// ```
+    pub fn encode_for_url(&self) -> String {
+        utf8_percent_encode(&self.to_string(), URL_PATH_ESCAPES).to_string()
+    }
+
+    /// Decodes a percent-encoded link target (an `href` from
+    /// [`VaultPath::encode_for_url`], or a hand-written `my%20note.md`) back
+    /// to the path text it spells. `+` is not a space in a path. Input that
+    /// doesn't decode to UTF-8 is returned unchanged.
+    pub fn decode_url(url: &str) -> Cow<'_, str> {
+        percent_decode_str(url)
+            .decode_utf8()
+            .unwrap_or(Cow::Borrowed(url))
+    }
+
+    /// Returns the path as a string without the leading `/`, for UIs that show
+    /// vault paths (breadcrumbs, lists) where the root marker is noise. The
+    /// [`Display`] output keeps the slash.
+    ///
+    /// ```
+    /// use kimun_core::nfs::VaultPath;
+    /// assert_eq!(VaultPath::new("/projects/todo.md").display_name(), "projects/todo.md");
+    /// assert_eq!(VaultPath::root().display_name(), "");
+    /// ```
+    pub fn display_name(&self) -> String {
+        self.to_string_with(&PATH_SEPARATOR.to_string(), false)
+    }
+
+    /// Returns the path's components joined by `sep`, prefixed with `sep` when
+    /// `leading` is set and the path is absolute. Relative paths never get a
+    /// leading separator. [`Display`] is `to_string_with("/", true)`.
+    ///
+    /// ```
+    /// use kimun_core::nfs::VaultPath;
+    /// let path = VaultPath::new("/projects/todo.md");
+    /// assert_eq!(path.to_string_with(" / ", false), "projects / todo.md");
+    /// assert_eq!(path.to_string_with(" > ", true), " > projects > todo.md");
+    /// ```
+    pub fn to_string_with(&self, sep: &str, leading: bool) -> String {
+        let joined = self
+            .slices
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>()
+            .join(sep);
+        if leading && self.absolute {
+            format!("{sep}{joined}")
+        } else {
+            joined
+        }
+    }
+
     fn increment<S: AsRef<str>>(name: S) -> String {
         let name = name.as_ref();
         let (n, suffix_num) = if let Some(caps) = RX_INCREMENT_SUFFIX.captures(name) {
@@ -348,7 +534,7 @@ impl VaultPath {
         let mut slices = vec![];
         for slice in &self.slices {
             match slice {
-                VaultPathSlice::PathSlice(_name) => slices.push(slice.clone()),
+                VaultPathSlice::PathSlice(..) => slices.push(slice.clone()),
                 VaultPathSlice::Up => {
                     if slices.pop().is_none() {
                         warn!("Trying to move a directory up from root")
@@ -367,7 +553,7 @@ impl VaultPath {
     /// if it is a note, will return the note filename, if it is a directory, will return the directory name
     pub fn get_name(&self) -> String {
         self.flatten().slices.last().map_or_else(String::new, |s| {
-            if let VaultPathSlice::PathSlice(name) = s {
+            if let VaultPathSlice::PathSlice(name, _) = s {
                 name.to_owned()
             } else {
                 String::new()
@@ -423,19 +609,14 @@ impl VaultPath {
     /// ```
     pub fn get_relative_to(&self, reference_path: &VaultPath) -> VaultPath {
         let mut slices = vec![];
-        let ref_slices = reference_path.slices.clone();
-        let mut position = 0;
-        for (pos, slice) in self.slices.iter().enumerate() {
-            position = pos;
-            if let Some(reference) = ref_slices.get(pos) {
-                if !slice.eq(reference) {
-                    break;
-                }
-            } else {
-                break;
-            }
+        let mut position = Self::shared_prefix_len(&self.slices, &reference_path.slices);
+        // When all of `self` is shared, its last slice is still walked up out
+        // of and back into: relative to `/main/second/sub`, `/main/second` is
+        // `../../second`, not `..`.
+        if position > 0 && position == self.slices.len() {
+            position -= 1;
         }
-        ref_slices.iter().skip(position).for_each(|_| {
+        reference_path.slices.iter().skip(position).for_each(|_| {
             slices.push(VaultPathSlice::Up);
         });
         self.slices.iter().skip(position).for_each(|slice| {
@@ -448,24 +629,97 @@ impl VaultPath {
         }
     }
 
+    /// Returns the deepest directory shared by every path in `paths`, as an
+    /// absolute path. Each path is [`flatten`]ed and its *containing*
+    /// directory is used, so a single path yields its parent directory and
+    /// two notes in the same folder yield that folder. Paths with no shared
+    /// top-level directory, and an empty slice, yield the root.
+    ///
+    /// [`flatten`]: VaultPath::flatten
+    ///
+    /// ```
+    /// use kimun_core::nfs::VaultPath;
+    /// let paths = [
+    ///     VaultPath::new("/projects/rust/a.md"),
+    ///     VaultPath::new("/projects/go/b.md"),
+    /// ];
+    /// assert_eq!(VaultPath::common_ancestor(&paths).to_string(), "/projects");
+    /// ```
+    pub fn common_ancestor(paths: &[VaultPath]) -> VaultPath {
+        let mut parents = paths.iter().map(|p| p.flatten().get_parent_path().0);
+        let Some(first) = parents.next() else {
+            return VaultPath::root();
+        };
+        let mut slices = first.slices;
+        for parent in parents {
+            let len = Self::shared_prefix_len(&slices, &parent.slices);
+            slices.truncate(len);
+        }
+        VaultPath {
+            absolute: true,
+            slices,
+        }
+    }
+
+    /// `true` when this path lies strictly inside the directory `dir`, at any
+    /// depth. Both sides are [`flatten`]ed and compared by components, so
+    /// absolute and relative forms match; a path is never inside itself.
+    ///
+    /// [`flatten`]: VaultPath::flatten
+    ///
+    /// ```
+    /// use kimun_core::nfs::VaultPath;
+    /// let journal = VaultPath::new("/journal");
+    /// assert!(VaultPath::new("/journal/2024/01-15.md").is_in(&journal));
+    /// assert!(!VaultPath::new("/journalling.md").is_in(&journal));
+    /// assert!(!journal.is_in(&journal));
+    /// ```
+    pub fn is_in(&self, dir: &VaultPath) -> bool {
+        let path = self.flatten();
+        let dir = dir.flatten();
+        path.slices.len() > dir.slices.len()
+            && Self::shared_prefix_len(&path.slices, &dir.slices) == dir.slices.len()
+    }
+
+    /// Number of leading components `a` and `b` have in common.
+    fn shared_prefix_len(a: &[VaultPathSlice], b: &[VaultPathSlice]) -> usize {
+        a.iter().zip(b).take_while(|(x, y)| x == y).count()
+    }
+
     /// Converts a real on-disk path back into an absolute vault path by
     /// stripping the `workspace_path` prefix. Returns `FSError::InvalidPath` if
     /// `full_path` does not live inside the workspace. Each OS component is run
     /// through [`VaultPath::new`], so the result is sanitized and lowercased.
+    ///
+    /// When the prefix does not match as written, both paths are resolved
+    /// (symlinks followed, see [`resolve_existing`]) and compared again, so a
+    /// path reached through a different spelling of the workspace still
+    /// converts: macOS's `/var/…` and `/private/var/…`, or a workspace opened
+    /// through a symlink.
     pub fn from_path<P: AsRef<Path>, F: AsRef<Path>>(
         workspace_path: P,
         full_path: F,
     ) -> Result<Self, FSError> {
-        let fp = full_path.as_ref();
-        let relative = fp
-            .strip_prefix(&workspace_path)
-            .map_err(|_e| FSError::InvalidPath {
-                path: path_to_string(&full_path),
-                message: format!(
-                    "The path provided is not a path belonging to the workspace: {}",
-                    path_to_string(workspace_path)
-                ),
-            })?;
+        let (workspace_path, fp) = (workspace_path.as_ref(), full_path.as_ref());
+        let resolved;
+        let relative = match fp.strip_prefix(workspace_path) {
+            Ok(relative) => relative,
+            Err(_) => {
+                resolved = (resolve_existing(workspace_path), resolve_existing(fp));
+                resolved
+                    .1
+                    .strip_prefix(&resolved.0)
+                    .map_err(|_e| FSError::InvalidPath {
+                        path: path_to_string(fp),
+                        message: format!(
+                            "The path is outside the workspace {} (resolved: {} is not under {})",
+                            path_to_string(workspace_path),
+                            path_to_string(&resolved.1),
+                            path_to_string(&resolved.0)
+                        ),
+                    })?
+            }
+        };
         let mut path_list = vec![PATH_SEPARATOR.to_string()];
         relative.components().for_each(|component| {
             let os_str = component.as_os_str();
@@ -598,6 +852,88 @@ impl VaultPath {
         )
     }
 
+    /// The final component as the caller spelled it, before lowercasing (see
+    /// [`preserving_case`](Self::preserving_case)). Empty when the path has
+    /// no components.
+    pub(crate) fn spelled_name(&self) -> String {
+        self.slices
+            .last()
+            .map_or_else(String::new, VaultPathSlice::spelled)
+    }
+
+    /// Returns this path with its spelling kept (`true`) or dropped
+    /// (`false`). Components compare lowercased either way; the spelling only
+    /// decides the on-disk name of a file or directory created from the path,
+    /// so dropping it makes new entries lowercase.
+    ///
+    /// ```
+    /// use kimun_core::nfs::VaultPath;
+    /// let path = VaultPath::new("/Projects/README.md");
+    /// assert_eq!(path.preserving_case(false), path);
+    /// assert_eq!(path.to_string(), "/projects/readme.md");
+    /// ```
+    pub fn preserving_case(&self, preserve: bool) -> VaultPath {
+        if preserve {
+            return self.clone();
+        }
+        let slices = self
+            .slices
+            .iter()
+            .map(|slice| match slice {
+                VaultPathSlice::PathSlice(name, spelling) => VaultPathSlice::PathSlice(
+                    name.clone(),
+                    Spelling {
+                        name: name.clone(),
+                        posix: spelling.posix.to_lowercase(),
+                    },
+                ),
+                other => other.clone(),
+            })
+            .collect();
+        VaultPath {
+            absolute: self.absolute,
+            slices,
+        }
+    }
+
+    /// Returns this path spelled for `policy`. Like
+    /// [`preserving_case`](Self::preserving_case), this only decides the
+    /// on-disk name of a file or directory created from the path: under
+    /// [`FilenamePolicy::Posix`] each component keeps the characters only
+    /// Windows and macOS reject. Apply it after `preserving_case`.
+    ///
+    /// ```
+    /// use kimun_core::nfs::filename::FilenamePolicy;
+    /// use kimun_core::nfs::VaultPath;
+    /// let path = VaultPath::new("/Plans: Q3?.md");
+    /// let posix = path.with_filename_policy(FilenamePolicy::Posix);
+    /// assert_eq!(posix, path);
+    /// assert_eq!(posix.to_string(), "/plans_ q3_.md");
+    /// ```
+    pub fn with_filename_policy(&self, policy: FilenamePolicy) -> VaultPath {
+        if policy == FilenamePolicy::Strict {
+            return self.clone();
+        }
+        let slices = self
+            .slices
+            .iter()
+            .map(|slice| match slice {
+                VaultPathSlice::PathSlice(name, spelling) => VaultPathSlice::PathSlice(
+                    name.clone(),
+                    Spelling {
+                        name: spelling.posix.clone(),
+                        posix: spelling.posix.clone(),
+                    },
+                ),
+                other => other.clone(),
+            })
+            .collect();
+        VaultPath {
+            absolute: self.absolute,
+            slices,
+        }
+    }
+
     /// Appends `path` to this one. If `path` is absolute it wins outright and is
     /// returned as-is; otherwise its components are concatenated onto this path,
     /// keeping this path's absoluteness. The result is not flattened, so any
@@ -640,55 +976,94 @@ impl VaultPath {
 
 impl Display for VaultPath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.absolute {
-            write!(f, "{}", PATH_SEPARATOR)?;
-        }
-        write!(
-            f,
-            "{}",
-            self.slices
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>()
-                .join(&PATH_SEPARATOR.to_string())
-        )
+        f.write_str(&self.to_string_with(&PATH_SEPARATOR.to_string(), true))
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub(super) enum VaultPathSlice {
-    PathSlice(String),
+    /// A named component: its lowercased name — the identity every
+    /// comparison, index key and rendering uses — and the caller's spelling.
+    PathSlice(String, Spelling),
     Up,
     Current,
 }
 
+/// How a path component was spelled before lowercasing (sanitized all the
+/// same). Only consulted to name a file or directory that doesn't exist yet
+/// (see [`VaultPath::preserving_case`]); it never takes part in identity, so it
+/// compares, hashes and orders as equal to every other spelling.
+#[derive(Debug, Clone)]
+pub(super) struct Spelling {
+    /// The spelling used on disk.
+    name: String,
+    /// The caller's text sanitized under [`FilenamePolicy::Posix`] only,
+    /// adopted as `name` by [`VaultPath::with_filename_policy`].
+    posix: String,
+}
+
+impl PartialEq for Spelling {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Spelling {}
+
+impl std::hash::Hash for Spelling {
+    fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+}
+
+impl PartialOrd for Spelling {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Spelling {
+    fn cmp(&self, _other: &Self) -> std::cmp::Ordering {
+        std::cmp::Ordering::Equal
+    }
+}
+
 impl VaultPathSlice {
     fn new<S: AsRef<str>>(slice: S) -> Self {
+        // Trim first so the checks below see what will be stored: " ..foo"
+        // must not survive as "..foo", which reparses differently.
+        let slice = slice.as_ref().trim();
         // Replace runs of leading dots so "..foo" becomes "__foo".
-        let slice = if filename::RX_PATH_NAME.is_match(slice.as_ref()) {
-            slice.as_ref().replace(".", "_")
+        let slice = if filename::RX_PATH_NAME.is_match(slice) {
+            slice.replace(".", "_")
         } else {
-            slice.as_ref().to_string()
+            slice.to_string()
         };
         if slice.eq("..") {
             VaultPathSlice::Up
         } else if slice.eq(".") {
             VaultPathSlice::Current
         } else {
-            // Replace invalid chars, lowercase, strip leading/trailing spaces and
-            // trailing dots (Windows silently strips them, causing silent collisions).
-            let sanitized = filename::RX_PATH_CHARS
-                .replace_all(&slice, "_")
-                .to_lowercase();
-            let sanitized = sanitized.trim().trim_end_matches('.').to_string();
+            // Replace invalid chars, strip leading/trailing spaces and trailing
+            // dots (Windows silently strips them, causing silent collisions).
+            let posix = slice.replace('\0', "_");
+            let sanitized = filename::RX_PATH_CHARS.replace_all(&slice, "_");
+            // Trim dots and spaces together: "a. ." must not leave "a. ".
+            let sanitized = sanitized
+                .trim_end_matches(|c: char| c == '.' || c.is_whitespace())
+                .to_string();
             // Prefix Windows reserved device names so they don't map to device handles.
-            let final_slice = if filename::RX_WIN_RESERVED.is_match(&sanitized) {
+            let spelled = if filename::RX_WIN_RESERVED.is_match(&sanitized) {
                 format!("_{}", sanitized)
             } else {
                 sanitized
             };
 
-            VaultPathSlice::PathSlice(final_slice)
+            VaultPathSlice::PathSlice(
+                spelled.to_lowercase(),
+                Spelling {
+                    name: spelled,
+                    posix,
+                },
+            )
         }
     }
 
@@ -705,18 +1080,37 @@ impl VaultPathSlice {
             && !slice.ends_with(' ')
     }
 
+    /// A path component with no name left after sanitizing.
+    fn is_blank(&self) -> bool {
+        matches!(self, VaultPathSlice::PathSlice(name, _) if name.is_empty())
+    }
+
+    /// The component as the caller spelled it; `..`/`.` as themselves.
+    pub(super) fn spelled(&self) -> String {
+        match self {
+            VaultPathSlice::PathSlice(_, spelling) => spelling.name.clone(),
+            other => other.to_string(),
+        }
+    }
+
     fn is_note(&self) -> bool {
         match self {
-            VaultPathSlice::PathSlice(name) => name.ends_with(NOTE_EXTENSION),
+            VaultPathSlice::PathSlice(name, _) => name.ends_with(NOTE_EXTENSION),
             _ => false,
         }
     }
 }
 
+/// The identity an on-disk file or directory name has as a path component:
+/// sanitized and lowercased, as [`VaultPath::new`] would store it.
+pub(super) fn component_identity(name: &str) -> String {
+    VaultPathSlice::new(name).to_string()
+}
+
 impl Display for VaultPathSlice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            VaultPathSlice::PathSlice(name) => write!(f, "{}", name),
+            VaultPathSlice::PathSlice(name, _) => write!(f, "{}", name),
             VaultPathSlice::Up => write!(f, ".."),
             VaultPathSlice::Current => write!(f, "."),
         }
@@ -725,6 +1119,7 @@ impl Display for VaultPathSlice {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
     use std::path::PathBuf;
 
     use super::with_note_extension;
@@ -739,6 +1134,13 @@ mod tests {
         assert_eq!(with_note_extension("projects.md"), "projects.md");
     }
 
+    #[test]
+    fn to_string_with_never_leads_a_relative_path() {
+        let path = super::VaultPath::new("projects/todo.md");
+        assert_eq!(path.to_string_with(" / ", true), "projects / todo.md");
+        assert_eq!(path.display_name(), path.to_string());
+    }
+
     #[test]
     fn with_note_extension_preserves_wildcards_and_path() {
         // Unlike VaultPath, this does not sanitize `*` so search wildcards survive.
@@ -910,6 +1312,42 @@ mod tests {
         assert_eq!("sub/deep".to_string(), rel.to_string());
     }
 
+    #[test]
+    fn get_relative_to_ancestor() {
+        let path1 = VaultPath::new("/main/second/sub");
+        let path2 = VaultPath::new("/main/second");
+        let rel = path2.get_relative_to(&path1);
+
+        assert_eq!("../../second".to_string(), rel.to_string());
+    }
+
+    #[test]
+    fn common_ancestor_of_siblings_and_cousins() {
+        let paths = [
+            VaultPath::new("/main/path/first.md"),
+            VaultPath::new("/main/path/second.md"),
+        ];
+        assert_eq!("/main/path", VaultPath::common_ancestor(&paths).to_string());
+
+        let paths = [
+            VaultPath::new("/main/path/first.md"),
+            VaultPath::new("main/other/deep/second.md"),
+            VaultPath::new("/main/third.md"),
+        ];
+        assert_eq!("/main", VaultPath::common_ancestor(&paths).to_string());
+    }
+
+    #[test]
+    fn common_ancestor_edge_cases() {
+        assert_eq!("/", VaultPath::common_ancestor(&[]).to_string());
+        assert_eq!(
+            "/main/path",
+            VaultPath::common_ancestor(&[VaultPath::new("/main/path/note.md")]).to_string()
+        );
+        let disjoint = [VaultPath::new("/one/a.md"), VaultPath::new("/two/b.md")];
+        assert_eq!("/", VaultPath::common_ancestor(&disjoint).to_string());
+    }
+
     #[test]
     fn relative_link_from_note_uses_parent_dir() {
         let note = VaultPath::new("/notes/journal/today.md");
@@ -1029,8 +1467,9 @@ mod tests {
         let slice = VaultPathSlice::new(slice_str);
 
         assert_eq!("some_unvalid_chars_", slice.to_string());
-        if let VaultPathSlice::PathSlice(name) = slice {
+        if let VaultPathSlice::PathSlice(name, spelling) = slice {
             assert_eq!("some_unvalid_chars_", name);
+            assert_eq!("Some_unvalid_Chars_", spelling.name);
         }
     }
 
@@ -1082,6 +1521,44 @@ mod tests {
         assert_eq!("/workspace/note.md", entry.to_string());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn from_path_matches_a_workspace_reached_through_a_symlink() {
+        // The shape of macOS's `/var` -> `/private/var`: the workspace is
+        // opened through a symlink while paths arrive resolved, or the
+        // other way around.
+        let tmp = tempfile::TempDir::new().unwrap();
+        let real = tmp.path().join("private").join("var");
+        std::fs::create_dir_all(real.join("vault").join("sub")).unwrap();
+        let link = tmp.path().join("var");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        let resolved_note = real.join("vault").join("sub").join("note.md");
+        let path = VaultPath::from_path(link.join("vault"), &resolved_note).unwrap();
+        assert_eq!(path.to_string(), "/sub/note.md");
+
+        // A file that does not exist yet (an import target) converts too.
+        let linked_new = link.join("vault").join("sub").join("new.md");
+        let path = VaultPath::from_path(real.join("vault"), &linked_new).unwrap();
+        assert_eq!(path.to_string(), "/sub/new.md");
+
+        let outside = VaultPath::from_path(link.join("vault"), real.join("other.md"));
+        assert!(matches!(outside, Err(FSError::InvalidPath { .. })));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn from_path_matches_private_var_on_macos() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("note.md"), "").unwrap();
+        let private = std::fs::canonicalize(tmp.path()).unwrap();
+        // Temp dirs live under `/var/folders`, which resolves to `/private/var`.
+        assert!(private.starts_with("/private"));
+
+        let path = VaultPath::from_path(tmp.path(), private.join("note.md")).unwrap();
+        assert_eq!(path.to_string(), "/note.md");
+    }
+
     #[test]
     fn test_vault_path_serialization() {
         use serde_json;
@@ -1094,6 +1571,47 @@ mod tests {
         assert_eq!(deserialized, path);
     }
 
+    #[test]
+    fn serde_round_trips_root_and_empty() {
+        for path in [VaultPath::root(), VaultPath::empty()] {
+            let json = serde_json::to_string(&path).unwrap();
+            let back: VaultPath = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, path, "{json}");
+        }
+    }
+
+    fn raw_path_strategy() -> impl proptest::strategy::Strategy<Value = String> {
+        use proptest::prelude::*;
+        let segment = prop_oneof![
+            Just("..".to_string()),
+            Just(".".to_string()),
+            Just("con".to_string()),
+            Just(String::new()),
+            "[a-zA-Z0-9 ._:*?<>|#^\\\\-]{1,8}",
+        ];
+        (any::<bool>(), proptest::collection::vec(segment, 0..5)).prop_map(
+            |(absolute, segments)| {
+                let joined = segments.join("/");
+                if absolute {
+                    format!("/{joined}")
+                } else {
+                    joined
+                }
+            },
+        )
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn serde_round_trip_keeps_slices_and_absolute_flag(raw in raw_path_strategy()) {
+            let path = VaultPath::new(&raw);
+            let json = serde_json::to_string(&path).unwrap();
+            let back: VaultPath = serde_json::from_str(&json).unwrap();
+            proptest::prop_assert!(back.is_like(&path), "{raw:?} -> {json} -> {back:?}");
+            proptest::prop_assert_eq!(back.is_absolute(), path.is_absolute());
+        }
+    }
+
     #[test]
     fn test_vault_path_try_from() {
         let path_str = "/valid/path.md";
@@ -1156,6 +1674,49 @@ mod tests {
         assert_eq!(conflicted_empty.to_string(), "0");
     }
 
+    #[test]
+    fn get_free_name_picks_smallest_unused_suffix() {
+        let taken =
+            |names: &[&str]| -> HashSet<String> { names.iter().map(|n| n.to_string()).collect() };
+        let note = VaultPath::new("/dir/note.md");
+
+        assert_eq!(note.get_free_name(&taken(&[])), note);
+        assert_eq!(
+            note.get_free_name(&taken(&["note.md"])).to_string(),
+            "/dir/note_1.md"
+        );
+        assert_eq!(
+            note.get_free_name(&taken(&["note.md", "note_1.md"]))
+                .to_string(),
+            "/dir/note_2.md"
+        );
+        // Gaps are reused: a lone `note_5.md` does not push the next to `_6`.
+        assert_eq!(
+            note.get_free_name(&taken(&["note.md", "note_5.md"]))
+                .to_string(),
+            "/dir/note_1.md"
+        );
+        // A taken numbered name resolves within the same numbering.
+        assert_eq!(
+            VaultPath::new("note_1.md")
+                .get_free_name(&taken(&["note_1.md"]))
+                .to_string(),
+            "note_2.md"
+        );
+        assert_eq!(
+            VaultPath::new("dir")
+                .get_free_name(&taken(&["dir"]))
+                .to_string(),
+            "dir_1"
+        );
+        assert_eq!(
+            VaultPath::new("pic.png")
+                .get_free_name(&taken(&["pic.png"]))
+                .to_string(),
+            "pic_1.png"
+        );
+    }
+
     #[test]
     fn test_vault_path_get_clean_name() {
         let note_path = VaultPath::new("/path/to/note.md");
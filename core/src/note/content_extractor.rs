@@ -1,3 +1,4 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use log::debug;
 use pulldown_cmark::{Event, Parser, Tag, TagEnd};
 use regex::{Captures, Regex};
@@ -7,7 +8,7 @@ use url::Url;
 
 use crate::{
     nfs::{self, VaultPath},
-    note::{ContentChunk, NoteContentData},
+    note::{ContentChunk, LintKind, LintWarning, NoteContentData},
 };
 
 use super::NoteLink;
@@ -21,6 +22,10 @@ static WIKILINK_RX: LazyLock<Regex> =
 pub(crate) static HASHTAG_RX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r#"#(?P<ht_text>[A-Za-z0-9_]+)"#).unwrap());
 
+/// One entry of a frontmatter tag list: a run of label characters, plus `-`
+/// so that `in-progress` is never mistaken for `in`.
+static TAG_ENTRY_RX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"[A-Za-z0-9_-]+"#).unwrap());
+
 static MD_LINK_RX: LazyLock<Regex> = LazyLock::new(|| {
     // `text` accepts an empty match so empty-alt image links like `![](path)`
     // — which the editor generates on image paste — are still recognised.
@@ -245,7 +250,7 @@ pub fn get_chunks_and_links<S: AsRef<str>>(
     reference_path: &VaultPath,
     md_text: S,
 ) -> (Vec<ContentChunk>, Vec<super::NoteLink>) {
-    let raw = md_text.as_ref();
+    let raw = &*blank_comments(md_text.as_ref());
     let (frontmatter, body_with_wikilinks) = remove_frontmatter(raw);
 
     // Pass 1: collapse wikilinks AND record the byte ranges in the output
@@ -275,12 +280,9 @@ pub fn get_chunks_and_links<S: AsRef<str>>(
     let (text_lines, walk_links) = walk_indexing_events(&body_stripped, reference_path, &labels);
     links.extend(walk_links);
 
-    let mut chunks = chunks_from_text_lines(text_lines);
+    let mut chunks = chunks_from_text_lines(text_lines, &section_ranges(raw));
     if !frontmatter.is_empty() {
-        chunks.push(ContentChunk {
-            breadcrumb: "FrontMatter".to_string(),
-            text: frontmatter,
-        });
+        chunks.push(frontmatter_chunk(raw, frontmatter));
     }
 
     (chunks, links)
@@ -334,30 +336,92 @@ fn collapse_wikilinks_with_display_ranges(
 
 pub fn get_content_data<S: AsRef<str>>(md_text: S) -> NoteContentData {
     let hash = nfs::hash_text(md_text.as_ref());
-    let title = extract_title(md_text);
+    let title = extract_title(md_text.as_ref());
+    let excerpt = extract_excerpt(md_text);
 
-    NoteContentData { title, hash }
+    NoteContentData {
+        title,
+        hash,
+        excerpt,
+    }
 }
 
 pub fn get_content_chunks<S: AsRef<str>>(md_text: S) -> Vec<ContentChunk> {
-    let (frontmatter, text) = remove_frontmatter(md_text.as_ref());
+    let raw = &*blank_comments(md_text.as_ref());
+    let (frontmatter, text) = remove_frontmatter(raw);
 
     // Clean up wikilinks and hashtags for indexing
     let text = process_wikilinks(&text, |_link, _text| None);
     let text = cleanup_hashtags(&text);
 
-    let mut content_chunks = parse_text(&text);
+    let mut content_chunks = parse_text(&text, &section_ranges(raw));
 
     if !frontmatter.is_empty() {
-        content_chunks.push(ContentChunk {
-            breadcrumb: "FrontMatter".to_string(),
-            text: frontmatter,
-        })
+        content_chunks.push(frontmatter_chunk(raw, frontmatter))
     }
 
     content_chunks
 }
 
+/// The chunk whose section holds byte `offset` of `md_text`.
+///
+/// Picks the chunk starting closest before `offset`; an offset in the blank
+/// lines trailing a section still belongs to it, anything past that (a
+/// heading with no body, the opening frontmatter delimiter) has no chunk.
+pub fn chunk_at<S: AsRef<str>>(md_text: S, offset: usize) -> Option<ContentChunk> {
+    let raw = md_text.as_ref();
+    let chunk = get_content_chunks(raw)
+        .into_iter()
+        .filter(|chunk| chunk.start <= offset)
+        .max_by_key(|chunk| chunk.start)?;
+    let in_section = offset < chunk.end
+        || raw
+            .get(chunk.end..offset)
+            .is_some_and(|gap| gap.trim().is_empty());
+    in_section.then_some(chunk)
+}
+
+/// The `FrontMatter` chunk, spanning the lines between the delimiters.
+fn frontmatter_chunk(raw: &str, frontmatter: String) -> ContentChunk {
+    let start = frontmatter_delimiter(raw).map_or(0, |(_, offset)| offset);
+    // `frontmatter_end_byte` is past the closing delimiter line; the
+    // frontmatter itself stops where that line starts.
+    let closing_start = raw[..frontmatter_end_byte(raw)]
+        .trim_end_matches(['\r', '\n'])
+        .rfind('\n')
+        .map_or(start, |i| i + 1)
+        .max(start);
+    let end = start + raw[start..closing_start].trim_end().len();
+    ContentChunk {
+        breadcrumb: "FrontMatter".to_string(),
+        text: frontmatter,
+        start,
+        end,
+    }
+}
+
+/// Byte ranges of the heading sections of a note's source `raw`, in the
+/// order [`chunks_from_text_lines`] meets them: index 0 is the body before
+/// the first heading, index `k` runs from the `k`-th heading's line to the
+/// next heading. Frontmatter and trailing whitespace are left out.
+fn section_ranges(raw: &str) -> Vec<Range<usize>> {
+    let body_start = frontmatter_end_byte(raw);
+    let mut starts = vec![body_start];
+    starts.extend(
+        Parser::new(&raw[body_start..])
+            .into_offset_iter()
+            .filter(|(event, _)| matches!(event, Event::Start(Tag::Heading { .. })))
+            .map(|(_, range)| body_start + range.start),
+    );
+    let mut ends: Vec<usize> = starts[1..].to_vec();
+    ends.push(raw.len());
+    starts
+        .into_iter()
+        .zip(ends)
+        .map(|(start, end)| start..start + raw[start..end].trim_end().len())
+        .collect()
+}
+
 /// Process wikilinks with a custom handler function
 /// Handler returns None to remove the wikilink (keep only text), or Some(String) to replace it
 fn process_wikilinks<F>(md_text: &str, handler: F) -> String
@@ -418,6 +482,38 @@ pub(crate) fn code_char_ranges(md_text: &str) -> Vec<(usize, usize)> {
     ranges
 }
 
+/// Blanks every Obsidian-style `%%comment%%` in the note body so it never
+/// reaches the index, while the note on disk keeps it. Each commented byte
+/// becomes a space (newlines are kept), so byte offsets and line numbers
+/// still match the source and [`section_ranges`] can run on the result.
+/// `%%` inside code and frontmatter is left alone, as is an opening `%%`
+/// with no closing one.
+fn blank_comments(md_text: &str) -> std::borrow::Cow<'_, str> {
+    let body_start = frontmatter_end_byte(md_text);
+    let body = &md_text[body_start..];
+    if !body.contains("%%") {
+        return std::borrow::Cow::Borrowed(md_text);
+    }
+    let code = code_char_ranges(body);
+    let markers: Vec<usize> = body
+        .match_indices("%%")
+        .map(|(i, _)| i)
+        .filter(|i| !code.iter().any(|(s, e)| i >= s && i < e))
+        .collect();
+    let mut bytes = md_text.as_bytes().to_vec();
+    for pair in markers.chunks_exact(2) {
+        let (open, close) = (pair[0], pair[1] + 2);
+        for b in &mut bytes[body_start + open..body_start + close] {
+            if *b != b'\n' && *b != b'\r' {
+                *b = b' ';
+            }
+        }
+    }
+    // Only whole UTF-8 sequences inside comments were replaced, byte for byte,
+    // by ASCII spaces, so the result is still valid UTF-8.
+    std::borrow::Cow::Owned(String::from_utf8(bytes).expect("blanking keeps UTF-8 valid"))
+}
+
 /// Returns byte-offset ranges (start, end) within `md_text` covering every
 /// markdown link `[text](href)` (full span including the `[]` brackets and
 /// the `()` around the href). Used to exclude hashtag extraction inside
@@ -543,6 +639,152 @@ fn atx_heading_text(line: &str) -> Option<&str> {
     Some(text)
 }
 
+/// Prepares a note's text to become one section of a merged note: the
+/// frontmatter is dropped and surrounding blank lines trimmed. When the body
+/// doesn't already open with a heading, one is added from `title` so every
+/// merged section starts with a heading naming where it came from.
+pub(crate) fn section_for_merge(md_text: &str, title: &str) -> String {
+    let body = md_text[frontmatter_end_byte(md_text)..].trim();
+    let starts_with_heading = body
+        .lines()
+        .next()
+        .and_then(atx_heading_text)
+        .is_some_and(|text| !text.is_empty());
+    if starts_with_heading {
+        body.to_string()
+    } else if body.is_empty() {
+        format!("# {}", title)
+    } else {
+        format!("# {}\n\n{}", title, body)
+    }
+}
+
+/// Splits `md_text` at every ATX heading of exactly `level`, for
+/// [`NoteVault::split_note`](crate::NoteVault::split_note).
+///
+/// Each section runs from its heading line up to the next heading of the same
+/// or a shallower level, and is returned as `(heading text, section text)`.
+/// Everything outside a section — frontmatter, text before the first split
+/// heading, shallower headings and their direct text — is returned as the
+/// remainder, in order. Headings inside fenced code blocks are ignored.
+pub(crate) fn split_sections(md_text: &str, level: u8) -> (String, Vec<(String, String)>) {
+    let level = level as usize;
+    let body_start = frontmatter_end_byte(md_text);
+    let mut remainder = md_text[..body_start].to_string();
+    let mut sections: Vec<(String, String)> = vec![];
+    let mut in_section = false;
+    let mut open_fence: Option<(char, usize)> = None;
+
+    for line in md_text[body_start..].split_inclusive('\n') {
+        let stripped = line.trim_end_matches(['\n', '\r']);
+        if let Some((fence_char, fence_len)) = code_fence(stripped) {
+            open_fence = match open_fence {
+                None => Some((fence_char, fence_len)),
+                Some((c, len)) if c == fence_char && fence_len >= len => None,
+                still_open => still_open,
+            };
+        } else if open_fence.is_none() {
+            if let Some(title) = atx_heading_text(stripped) {
+                let trimmed = stripped.trim_start();
+                let heading_level = trimmed.len() - trimmed.trim_start_matches('#').len();
+                if heading_level == level && !title.is_empty() {
+                    sections.push((title.to_string(), String::new()));
+                    in_section = true;
+                } else if heading_level < level {
+                    in_section = false;
+                }
+            }
+        }
+        match sections.last_mut() {
+            Some((_, text)) if in_section => text.push_str(line),
+            _ => remainder.push_str(line),
+        }
+    }
+    (remainder, sections)
+}
+
+/// Line-based structural check behind [`NoteDetails::lint`](super::NoteDetails::lint).
+///
+/// Frontmatter is skipped, and nothing inside a fenced code block is checked
+/// except whether the fence is closed. Inline code spans are ignored when
+/// looking for broken links, so `` `[a](` `` is not reported.
+pub(crate) fn lint(md_text: &str) -> Vec<LintWarning> {
+    let body_start = frontmatter_end_byte(md_text);
+    let first_line = md_text[..body_start].matches('\n').count();
+    let mut warnings = vec![];
+    // (fence char, fence length, line) of the currently open fence.
+    let mut open_fence: Option<(char, usize, usize)> = None;
+
+    for (index, line) in md_text[body_start..].lines().enumerate() {
+        let line_no = first_line + index;
+        if let Some((fence_char, fence_len)) = code_fence(line) {
+            match open_fence {
+                None => open_fence = Some((fence_char, fence_len, line_no)),
+                Some((open_char, open_len, _))
+                    if fence_char == open_char
+                        && fence_len >= open_len
+                        && line.trim().trim_start_matches(fence_char).is_empty() =>
+                {
+                    open_fence = None
+                }
+                Some(_) => {}
+            }
+            continue;
+        }
+        if open_fence.is_some() {
+            continue;
+        }
+        if atx_heading_text(line).is_some_and(str::is_empty) {
+            warnings.push(LintWarning {
+                line: line_no,
+                kind: LintKind::EmptyHeading,
+            });
+        } else if has_broken_link(line) {
+            warnings.push(LintWarning {
+                line: line_no,
+                kind: LintKind::BrokenLink,
+            });
+        }
+    }
+    if let Some((_, _, line)) = open_fence {
+        warnings.push(LintWarning {
+            line,
+            kind: LintKind::UnclosedCodeFence,
+        });
+    }
+    warnings.sort_by_key(|w| w.line);
+    warnings
+}
+
+/// Recognizes a fenced code block delimiter — up to three spaces of
+/// indentation, then three or more backticks or tildes — returning the fence
+/// character and run length.
+fn code_fence(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+    (fence_len >= 3).then_some((fence_char, fence_len))
+}
+
+/// `true` when `line` opens a markdown link target (`](`) or a wikilink
+/// (`[[`) that is not closed later on the same line. Text inside inline code
+/// spans is ignored.
+fn has_broken_link(line: &str) -> bool {
+    // Odd-indexed segments of a backtick split are inside code spans.
+    line.split('`').step_by(2).any(|segment| {
+        let unclosed_target = segment
+            .match_indices("](")
+            .any(|(i, _)| !segment[i + 2..].contains(')'));
+        let unclosed_wikilink = segment
+            .match_indices("[[")
+            .any(|(i, _)| !segment[i + 2..].contains("]]"));
+        unclosed_target || unclosed_wikilink
+    })
+}
+
 /// Cached set of byte ranges that suppress autocomplete: frontmatter,
 /// fenced/inline code, markdown link bodies, closed wikilink spans.
 ///
@@ -719,7 +961,7 @@ pub(crate) fn get_markdown_and_links<S: AsRef<str>>(
     let md_text = process_wikilinks(md_text.as_ref(), |link, text| {
         if VaultPath::is_valid(link) {
             let link_path = VaultPath::note_path_from(link);
-            Some(format!("[{}]({})", text, link_path))
+            Some(format!("[{}]({})", text, link_path.encode_for_url()))
         } else {
             // Keep invalid wikilinks as-is
             Some(format!(
@@ -750,14 +992,14 @@ pub(crate) fn get_markdown_and_links<S: AsRef<str>>(
             // URL link
             links.push(NoteLink::url(link, text));
             link.to_string()
-        } else if VaultPath::is_valid(link) {
+        } else if VaultPath::is_valid(VaultPath::decode_url(link)) {
             // Vault path link
-            let path = VaultPath::new(link);
+            let path = VaultPath::new(VaultPath::decode_url(link));
 
             if path.is_note_file() {
                 // Absolute note path
                 links.push(NoteLink::note(&path, text));
-                path.to_string()
+                path.encode_for_url()
             } else {
                 // Relative path - resolve it
                 let ref_path = if reference_path.is_note() {
@@ -774,7 +1016,7 @@ pub(crate) fn get_markdown_and_links<S: AsRef<str>>(
                     links.push(NoteLink::vault_path(&abs_path, text));
                 }
 
-                abs_path.to_string()
+                abs_path.encode_for_url()
             }
         } else {
             debug!("link not counting {}", link);
@@ -882,6 +1124,137 @@ pub(crate) fn replace_note_links(
     (result, changed)
 }
 
+/// Whether `name` (without the leading `#`) is something the indexer would
+/// pick up as a label: one or more ASCII letters, digits or underscores.
+pub(crate) fn is_valid_label(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+/// Rewrites the label `old` to `new` in `md_text`, matching `old`
+/// case-insensitively as the index does. Covers inline `#old` tokens and
+/// entries of a frontmatter `tags` list (YAML `tags: [a, b]` or a `- a`
+/// block list, TOML `tags = ["a", "b"]`, quoted or `#`-prefixed entries
+/// included). Hashtags inside code, HTML, links, wikilinks and bare URLs are
+/// left alone, as is the rest of the frontmatter.
+///
+/// Returns `(updated_text, changed)` where `changed` is true when at least one
+/// replacement was made.
+pub(crate) fn replace_label(md_text: &str, old: &str, new: &str) -> (String, bool) {
+    let zones = ExclusionZones::from_text(md_text);
+    // Byte ranges holding the bare label name, in ascending order.
+    let mut names: Vec<(usize, usize)> = frontmatter_tag_names(md_text, old);
+    names.extend(
+        label_matches_inner(md_text)
+            .filter(|lm| lm.name.eq_ignore_ascii_case(old))
+            .filter(|lm| !zones.contains(lm.byte_start) && !in_bare_url(md_text, lm.byte_start))
+            .map(|lm| (lm.byte_start + 1, lm.byte_end)),
+    );
+    if names.is_empty() {
+        return (md_text.to_string(), false);
+    }
+
+    let mut out = String::with_capacity(md_text.len());
+    let mut last = 0;
+    for (start, end) in names {
+        out.push_str(&md_text[last..start]);
+        out.push_str(new);
+        last = end;
+    }
+    out.push_str(&md_text[last..]);
+    let changed = out != md_text;
+    (out, changed)
+}
+
+/// Whether byte `offset` sits in a whitespace-delimited token that contains
+/// `://`, i.e. a bare URL such as `https://example.com/#section`.
+fn in_bare_url(text: &str, offset: usize) -> bool {
+    let start = text[..offset]
+        .rfind(char::is_whitespace)
+        .map_or(0, |i| i + 1);
+    let end = text[offset..]
+        .find(char::is_whitespace)
+        .map_or(text.len(), |i| offset + i);
+    text[start..end].contains("://")
+}
+
+/// Byte ranges of the entries named `label` (case-insensitive) in the
+/// frontmatter `tags` list of `md_text`, covering only the name — not its
+/// quotes or `#`.
+fn frontmatter_tag_names(md_text: &str, label: &str) -> Vec<(usize, usize)> {
+    let Some((_, start)) = frontmatter_delimiter(md_text) else {
+        return vec![];
+    };
+    let end = frontmatter_end_byte(md_text);
+    if end == 0 {
+        return vec![];
+    }
+
+    // Whether the lines after a `tags` key still belong to its value: a YAML
+    // block list (`- a` lines) or a bracketed array left open.
+    #[derive(PartialEq)]
+    enum Continuation {
+        None,
+        BlockList,
+        OpenArray,
+    }
+
+    let mut names = vec![];
+    let mut continuation = Continuation::None;
+    let mut offset = start;
+    for line in md_text[start..end].split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim_start();
+        let value_start = match continuation {
+            Continuation::OpenArray => Some(0),
+            Continuation::BlockList if trimmed.starts_with('-') => {
+                Some(line.len() - trimmed.len() + 1)
+            }
+            _ => {
+                continuation = Continuation::None;
+                tags_key_len(line)
+            }
+        };
+        let Some(value_start) = value_start else {
+            continue;
+        };
+        let value = &line[value_start..];
+        if continuation == Continuation::None {
+            let value = value.trim();
+            continuation = if value.is_empty() {
+                Continuation::BlockList
+            } else if value.starts_with('[') && !value.contains(']') {
+                Continuation::OpenArray
+            } else {
+                Continuation::None
+            };
+        } else if continuation == Continuation::OpenArray && value.contains(']') {
+            continuation = Continuation::None;
+        }
+        names.extend(
+            TAG_ENTRY_RX
+                .find_iter(value)
+                .filter(|m| m.as_str().eq_ignore_ascii_case(label))
+                .map(|m| {
+                    let base = line_start + value_start;
+                    (base + m.start(), base + m.end())
+                }),
+        );
+    }
+    names
+}
+
+/// Length of the `tags:` (YAML) or `tags =` (TOML) key at the start of
+/// `line`, up to where its value begins; `None` for any other line.
+fn tags_key_len(line: &str) -> Option<usize> {
+    let rest = line.strip_prefix("tags")?;
+    let after_space = rest.trim_start_matches([' ', '\t']);
+    let separator = after_space
+        .strip_prefix(':')
+        .or(after_space.strip_prefix('='))?;
+    Some(line.len() - separator.len())
+}
+
 /// Process image links in already-converted markdown, calling `resolver` for each image.
 ///
 /// `resolver(alt_text, raw_path) -> (resolved_path_in_markdown, NoteLink)`:
@@ -914,8 +1287,13 @@ where
     (result.to_string(), image_links)
 }
 
+/// The note's display title: a non-empty frontmatter `title` when there is
+/// one, otherwise the first non-empty line of the body.
 pub fn extract_title<S: AsRef<str>>(md_text: S) -> String {
-    let (_frontmatter, md_text) = remove_frontmatter(md_text);
+    let (frontmatter, md_text) = remove_frontmatter(md_text);
+    if let Some(title) = frontmatter_title(&frontmatter) {
+        return title;
+    }
     let mut parser = Parser::new(md_text.as_ref());
     let result = loop_events(&mut parser);
 
@@ -930,19 +1308,72 @@ pub fn extract_title<S: AsRef<str>>(md_text: S) -> String {
         .unwrap_or_default()
 }
 
-fn parse_text(md_text: &str) -> Vec<ContentChunk> {
+/// Longest excerpt [`extract_excerpt`] returns, in characters (not counting
+/// the trailing `…`).
+pub const EXCERPT_LEN: usize = 200;
+
+/// The opening of a note's body as one line of plain text, for previews in
+/// note lists: headings, markup, comments and frontmatter are left out, as is
+/// the line the title was taken from, and whitespace is collapsed. Cut at a
+/// word boundary to at most [`EXCERPT_LEN`] characters, with a `…` appended
+/// when anything was dropped.
+pub fn extract_excerpt<S: AsRef<str>>(md_text: S) -> String {
+    let raw = blank_comments(md_text.as_ref());
+    let (frontmatter, text) = remove_frontmatter(&*raw);
+    let text = process_wikilinks(&text, |_link, _text| None);
+    let mut parser = Parser::new(&text);
+    let mut lines = loop_events(&mut parser)
+        .into_iter()
+        .filter(|line| !matches!(line, TextLine::Empty));
+    if frontmatter_title(&frontmatter).is_none() {
+        // Without a frontmatter title the first line is the title.
+        lines.next();
+    }
+
+    let body: Vec<String> = lines
+        .filter_map(|line| match line {
+            TextLine::Text(text) | TextLine::ListItem(_, text) => Some(text),
+            TextLine::Empty | TextLine::Header(..) => None,
+        })
+        .collect();
+    let mut excerpt = String::new();
+    for word in body.iter().flat_map(|text| text.split_whitespace()) {
+        let sep = usize::from(!excerpt.is_empty());
+        if excerpt.chars().count() + sep + word.chars().count() > EXCERPT_LEN {
+            if excerpt.is_empty() {
+                // A single word longer than the cap is cut mid-word.
+                excerpt.extend(word.chars().take(EXCERPT_LEN));
+            }
+            excerpt.push('…');
+            break;
+        }
+        if sep == 1 {
+            excerpt.push(' ');
+        }
+        excerpt.push_str(word);
+    }
+    excerpt
+}
+
+fn parse_text(md_text: &str, sections: &[Range<usize>]) -> Vec<ContentChunk> {
     let mut parser = Parser::new(md_text);
     let lines = loop_events(&mut parser);
-    chunks_from_text_lines(lines)
+    chunks_from_text_lines(lines, sections)
 }
 
-/// Converts a sequence of [`TextLine`] events into [`ContentChunk`]s.
-/// Shared between [`parse_text`] (standalone) and [`get_chunks_and_links`]'s
-/// single-walk indexing path.
-fn chunks_from_text_lines(lines: Vec<TextLine>) -> Vec<ContentChunk> {
+/// Converts a sequence of [`TextLine`] events into [`ContentChunk`]s, each
+/// spanning its entry in `sections` (see [`section_ranges`]). Shared between
+/// [`parse_text`] (standalone) and [`get_chunks_and_links`]'s single-walk
+/// indexing path.
+fn chunks_from_text_lines(lines: Vec<TextLine>, sections: &[Range<usize>]) -> Vec<ContentChunk> {
     let mut content_chunks = vec![];
     let mut current_breadcrumb: Vec<(u8, String)> = vec![];
     let mut current_content = vec![];
+    let mut section = 0;
+    // Only reached if the source and the processed text disagree on the
+    // headings; the chunk then spans the whole body.
+    let whole = sections.first().map_or(0, |r| r.start)..sections.last().map_or(0, |r| r.end);
+    let range_of = |section: usize| sections.get(section).cloned().unwrap_or(whole.clone());
 
     for text_line in lines {
         match text_line {
@@ -950,9 +1381,12 @@ fn chunks_from_text_lines(lines: Vec<TextLine>) -> Vec<ContentChunk> {
                 if !current_breadcrumb.is_empty() || !current_content.is_empty() {
                     let content = crate::utilities::remove_diacritics(&current_content.join("\n"));
                     if !content.trim().is_empty() {
+                        let range = range_of(section);
                         content_chunks.push(ContentChunk {
                             breadcrumb: join_breadcrumb(&current_breadcrumb),
                             text: content,
+                            start: range.start,
+                            end: range.end,
                         });
                     }
                 }
@@ -960,6 +1394,7 @@ fn chunks_from_text_lines(lines: Vec<TextLine>) -> Vec<ContentChunk> {
                 current_breadcrumb.retain(|(lvl, _)| *lvl < level);
                 current_breadcrumb.push((level, text));
                 current_content.clear();
+                section += 1;
             }
             TextLine::Empty => {}
             _ => {
@@ -971,9 +1406,12 @@ fn chunks_from_text_lines(lines: Vec<TextLine>) -> Vec<ContentChunk> {
     if !current_breadcrumb.is_empty() || !current_content.is_empty() {
         let content = crate::utilities::remove_diacritics(&current_content.join("\n"));
         if !content.trim().is_empty() {
+            let range = range_of(section);
             content_chunks.push(ContentChunk {
                 breadcrumb: join_breadcrumb(&current_breadcrumb),
                 text: content,
+                start: range.start,
+                end: range.end,
             });
         }
     }
@@ -1106,7 +1544,7 @@ fn walk_indexing_events(
             }
             Event::Code(cow_str) => {
                 let current_line = text_lines.pop().unwrap_or_default();
-                text_lines.push(current_line.append_text(format!("`{}`", cow_str)));
+                text_lines.push(current_line.append_code(&cow_str));
             }
             Event::InlineMath(cow_str)
             | Event::DisplayMath(cow_str)
@@ -1115,6 +1553,14 @@ fn walk_indexing_events(
             | Event::FootnoteReference(cow_str) => {
                 text_lines.push(TextLine::Text(cow_str.to_string()));
             }
+            Event::SoftBreak | Event::HardBreak
+                if matches!(text_lines.last(), Some(TextLine::Header(..))) =>
+            {
+                // A setext heading wrapped over several lines is still one
+                // heading.
+                let current_line = text_lines.pop().unwrap_or_default();
+                text_lines.push(current_line.append_text(" ".to_string()));
+            }
             Event::SoftBreak => {
                 text_lines.push(TextLine::Empty);
             }
@@ -1143,7 +1589,8 @@ fn emit_md_note_link(dest: &str, ref_path: &VaultPath, links: &mut Vec<NoteLink>
     if is_remote_url(dest) {
         return;
     }
-    if !VaultPath::is_valid(dest) {
+    let dest = VaultPath::decode_url(dest);
+    if !VaultPath::is_valid(&dest) {
         return;
     }
     let path = VaultPath::new(dest);
@@ -1311,6 +1758,92 @@ fn remove_frontmatter<S: AsRef<str>>(text: S) -> (String, String) {
     }
 }
 
+/// The value of the top-level `title:` (YAML) or `title =` (TOML) key in
+/// `frontmatter`, unquoted. `None` when the key is missing or empty.
+fn frontmatter_title(frontmatter: &str) -> Option<String> {
+    frontmatter_value(frontmatter, "title")
+}
+
+/// The value of the top-level `key:` (YAML) or `key =` (TOML) entry in
+/// `frontmatter`, unquoted. `None` when the key is missing or empty.
+fn frontmatter_value(frontmatter: &str, key: &str) -> Option<String> {
+    frontmatter.lines().find_map(|line| {
+        let rest = line.strip_prefix(key)?;
+        let rest = rest.trim_start_matches([' ', '\t']);
+        let value = rest.strip_prefix(':').or(rest.strip_prefix('='))?.trim();
+        let value = if let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+            inner.replace("\\\"", "\"").replace("\\\\", "\\")
+        } else if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+            inner.replace("''", "'")
+        } else {
+            value.to_string()
+        };
+        (!value.is_empty()).then_some(value)
+    })
+}
+
+/// The note's date from its frontmatter: the `created` key, else `date`.
+/// Takes an RFC 3339 timestamp, or a `YYYY-MM-DD` date with an optional
+/// `HH:MM[:SS]` time (after a space or `T`) read as UTC; a bare date is
+/// midnight. `None` when neither key holds a date in one of those forms.
+pub fn extract_frontmatter_date<S: AsRef<str>>(md_text: S) -> Option<DateTime<Utc>> {
+    let (frontmatter, _) = remove_frontmatter(md_text);
+    ["created", "date"]
+        .iter()
+        .find_map(|key| frontmatter_value(&frontmatter, key).and_then(|v| parse_date(&v)))
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Utc));
+    }
+    const FORMATS: [&str; 4] = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ];
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(|day| day.and_time(NaiveTime::MIN))
+        })
+        .map(|at| at.and_utc())
+}
+
+/// `md_text` with its frontmatter `title` set to `title`: the key is added
+/// to (or replaced in) an existing YAML or TOML block, or a new YAML block
+/// holding only the title is prepended. The value is written as a quoted
+/// string on one line, so any characters are safe.
+pub(crate) fn with_frontmatter_title(md_text: &str, title: &str) -> String {
+    let title = title.replace(['\r', '\n'], " ");
+    let quoted = format!("\"{}\"", title.replace('\\', "\\\\").replace('"', "\\\""));
+    let end = frontmatter_end_byte(md_text);
+    let Some((delimiter, start)) = frontmatter_delimiter(md_text).filter(|_| end > 0) else {
+        return format!("---\ntitle: {quoted}\n---\n{md_text}");
+    };
+    let entry = if delimiter == "+++" {
+        format!("title = {quoted}\n")
+    } else {
+        format!("title: {quoted}\n")
+    };
+    let kept: String = md_text[start..end]
+        .split_inclusive('\n')
+        .filter(|line| !is_title_key(line))
+        .collect();
+    format!("{}{entry}{kept}{}", &md_text[..start], &md_text[end..])
+}
+
+/// Whether `line` sets the top-level `title` key, empty or not.
+fn is_title_key(line: &str) -> bool {
+    line.strip_prefix("title")
+        .map(|rest| rest.trim_start_matches([' ', '\t']))
+        .is_some_and(|rest| rest.starts_with(':') || rest.starts_with('='))
+}
+
 #[derive(Debug, Default, Clone)]
 enum TextLine {
     #[default]
@@ -1334,6 +1867,15 @@ impl TextLine {
         }
     }
 
+    /// Inline code keeps its backticks in body text but not in a heading,
+    /// which becomes the note's title and the breadcrumb of its section.
+    fn append_code(&self, code: &str) -> TextLine {
+        match self {
+            TextLine::Header(..) => self.append_text(code.to_string()),
+            _ => self.append_text(format!("`{}`", code)),
+        }
+    }
+
     fn to_text(&self) -> String {
         match self {
             TextLine::Empty => String::new(),
@@ -1395,7 +1937,7 @@ fn loop_events(parser: &mut Parser) -> Vec<TextLine> {
             }
             Event::Code(cow_str) => {
                 let current_line = text_lines.pop().unwrap_or_default();
-                text_lines.push(current_line.append_text(format!("`{}`", cow_str)));
+                text_lines.push(current_line.append_code(&cow_str));
             }
             Event::InlineMath(cow_str)
             | Event::DisplayMath(cow_str)
@@ -1404,6 +1946,14 @@ fn loop_events(parser: &mut Parser) -> Vec<TextLine> {
             | Event::FootnoteReference(cow_str) => {
                 text_lines.push(TextLine::Text(cow_str.to_string()));
             }
+            Event::SoftBreak | Event::HardBreak
+                if matches!(text_lines.last(), Some(TextLine::Header(..))) =>
+            {
+                // A setext heading wrapped over several lines is still one
+                // heading.
+                let current_line = text_lines.pop().unwrap_or_default();
+                text_lines.push(current_line.append_text(" ".to_string()));
+            }
             Event::SoftBreak => {
                 text_lines.push(TextLine::Empty);
             }
@@ -1524,14 +2074,127 @@ mod test {
         nfs::VaultPath,
         note::{
             content_extractor::{get_content_chunks, get_content_data},
-            LinkType,
+            LinkType, NoteDetails,
         },
     };
 
     use super::{
-        get_markdown_and_links, is_remote_url, link_char_spans, link_target_filename,
-        replace_note_links, target_looks_like_image, wikilink_char_spans, LinkSpanKind,
+        blank_comments, extract_frontmatter_date, get_chunks_and_links, get_markdown_and_links,
+        is_remote_url, link_char_spans, link_target_filename, lint, replace_label,
+        replace_note_links, section_for_merge, split_sections, target_looks_like_image,
+        wikilink_char_spans, with_frontmatter_title, LinkSpanKind, EXCERPT_LEN,
     };
+    use crate::note::{LintKind, LintWarning};
+
+    // ---- blank_comments ----
+
+    #[test]
+    fn blank_comments_keeps_offsets_code_and_frontmatter() {
+        let text = "---\nnote: %%a%%\n---\nkeep %%drop\nme%% `%%code%%` end %%open";
+        let blanked = blank_comments(text);
+        assert_eq!(blanked.len(), text.len());
+        assert_eq!(
+            blanked,
+            "---\nnote: %%a%%\n---\nkeep       \n     `%%code%%` end %%open"
+        );
+        assert!(get_content_chunks("# T\nseen %%unseen%%")
+            .iter()
+            .all(|c| !c.text.contains("unseen")));
+    }
+
+    // ---- NoteDetails::preview ----
+
+    #[test]
+    fn preview_parses_text_for_a_note_that_does_not_exist() {
+        let path = VaultPath::new("/nowhere/unsaved-draft.md");
+        let preview = NoteDetails::preview(&path, "# Draft\nSee [[other]] #idea\n\n## Part\nmore");
+
+        assert_eq!(preview.title, "Draft");
+        assert_eq!(preview.chunks.len(), 2);
+        assert_eq!(preview.chunks[1].text, "more");
+        assert!(preview.markdown.text.contains("See [other](other.md)"));
+        assert!(preview
+            .markdown
+            .links
+            .iter()
+            .any(|l| l.ltype == LinkType::Hashtag && l.text == "idea"));
+    }
+
+    // ---- section_for_merge ----
+
+    #[test]
+    fn section_for_merge_adds_missing_heading_and_drops_frontmatter() {
+        assert_eq!(
+            section_for_merge("---\ntags: x\n---\n\nsome text\n\n", "idea"),
+            "# idea\n\nsome text"
+        );
+        assert_eq!(
+            section_for_merge("## Existing\nbody\n", "idea"),
+            "## Existing\nbody"
+        );
+        assert_eq!(section_for_merge("", "idea"), "# idea");
+    }
+
+    // ---- split_sections ----
+
+    #[test]
+    fn split_sections_cuts_at_the_requested_level() {
+        let text = "# Top\nintro\n## One\na\n### Deep\nb\n```\n## not a heading\n```\n## Two\nc\n# Other\nd\n";
+        let (remainder, sections) = split_sections(text, 2);
+        assert_eq!(remainder, "# Top\nintro\n# Other\nd\n");
+        assert_eq!(
+            sections,
+            vec![
+                (
+                    "One".to_string(),
+                    "## One\na\n### Deep\nb\n```\n## not a heading\n```\n".to_string()
+                ),
+                ("Two".to_string(), "## Two\nc\n".to_string()),
+            ]
+        );
+    }
+
+    // ---- lint ----
+
+    #[test]
+    fn lint_reports_empty_headings_and_broken_links() {
+        let text = "# Title\n##\nsee [docs](\nand [[wiki\nfine [x](y) and `[a](`";
+        assert_eq!(
+            lint(text),
+            vec![
+                LintWarning {
+                    line: 1,
+                    kind: LintKind::EmptyHeading
+                },
+                LintWarning {
+                    line: 2,
+                    kind: LintKind::BrokenLink
+                },
+                LintWarning {
+                    line: 3,
+                    kind: LintKind::BrokenLink
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_reports_unclosed_fence_and_ignores_its_content() {
+        let text = "---\ntitle: x\n---\nintro\n```rust\n# \n[a](\n";
+        assert_eq!(
+            lint(text),
+            vec![LintWarning {
+                line: 4,
+                kind: LintKind::UnclosedCodeFence
+            }]
+        );
+    }
+
+    #[test]
+    fn lint_closed_fences_and_clean_text_have_no_warnings() {
+        let text = "# Title\n~~~~\n```\n~~~\n~~~~\nbody #tag [a](b) [[c]]\n";
+        assert!(lint(text).is_empty());
+    }
 
     // ---- ByteToCharCursor / span tests on multi-byte input ----
 
@@ -1652,6 +2315,31 @@ mod test {
         assert_eq!(spans[1].end, 11);
     }
 
+    // ---- replace_label tests ----
+
+    #[test]
+    fn replace_label_rewrites_frontmatter_tag_lists() {
+        let yaml = "---\ntitle: wip\ntags:\n  - \"#WIP\"\n  - wip-list\nother: wip\n---\n#wip";
+        let (result, changed) = replace_label(yaml, "wip", "doing");
+        assert!(changed);
+        assert_eq!(
+            result,
+            "---\ntitle: wip\ntags:\n  - \"#doing\"\n  - wip-list\nother: wip\n---\n#doing"
+        );
+
+        let toml = "+++\ntags = [\n  \"a\",\n  \"wip\",\n]\n+++\n";
+        let (result, _) = replace_label(toml, "wip", "doing");
+        assert_eq!(result, "+++\ntags = [\n  \"a\",\n  \"doing\",\n]\n+++\n");
+    }
+
+    #[test]
+    fn replace_label_skips_code_links_and_urls() {
+        let text = "```\n#wip\n```\n[see](notes.md#wip) [[#wip]] <https://a.io/#wip>";
+        let (result, changed) = replace_label(text, "wip", "doing");
+        assert!(!changed);
+        assert_eq!(result, text);
+    }
+
     // ---- replace_note_links tests ----
 
     #[test]
@@ -1759,6 +2447,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn links_with_spaces_are_encoded_and_resolved() {
+        let markdown = "See [[note with spaces]] and [that one](my%20notes/a%20b.md)";
+        let note_path = VaultPath::new("/test_note.md");
+
+        let (md, links) = get_markdown_and_links(&note_path, markdown);
+        assert_eq!(
+            md,
+            "See [note with spaces](note%20with%20spaces.md) and [that one](/my%20notes/a%20b.md)"
+        );
+        let targets: Vec<_> = links.iter().map(|link| link.ltype.clone()).collect();
+        assert_eq!(
+            targets,
+            [
+                LinkType::Note(VaultPath::new("note with spaces.md")),
+                LinkType::Note(VaultPath::new("/my notes/a b.md")),
+            ]
+        );
+
+        let (_, indexed) = get_chunks_and_links(&note_path, markdown);
+        let indexed: Vec<_> = indexed.into_iter().map(|link| link.ltype).collect();
+        assert_eq!(indexed, targets);
+    }
+
     #[test]
     fn ignore_image_links() {
         let markdown = r#"This is an ![image](image.png)"#;
@@ -1857,6 +2569,59 @@ title"#;
         assert_eq!("something: nice\nother: else", content_chunks[1].get_text());
     }
 
+    #[test]
+    fn check_title_prefers_frontmatter_title() {
+        assert_eq!(
+            get_content_data("---\ntitle: 'It''s here'\n---\n# Heading").title,
+            "It's here"
+        );
+        assert_eq!(
+            get_content_data("+++\ntitle = \"Toml\"\n+++\nbody").title,
+            "Toml"
+        );
+        assert_eq!(get_content_data("---\ntitle:\n---\nbody").title, "body");
+    }
+
+    #[test]
+    fn frontmatter_date_prefers_created_then_date() {
+        let at = |text: &str| extract_frontmatter_date(text).map(|d| d.to_rfc3339());
+        assert_eq!(
+            at("---\ndate: 2019-03-04\n---\nbody").as_deref(),
+            Some("2019-03-04T00:00:00+00:00")
+        );
+        assert_eq!(
+            at("---\ndate: 2019-03-04\ncreated: \"2018-01-02 09:30\"\n---\n").as_deref(),
+            Some("2018-01-02T09:30:00+00:00")
+        );
+        assert_eq!(
+            at("+++\ncreated = \"2020-05-06T07:08:09+02:00\"\n+++\n").as_deref(),
+            Some("2020-05-06T05:08:09+00:00")
+        );
+        // An unparseable `created` falls back to `date`.
+        assert_eq!(
+            at("---\ncreated: someday\ndate: 2021-12-31\n---\n").as_deref(),
+            Some("2021-12-31T00:00:00+00:00")
+        );
+        assert_eq!(at("---\ndates: 2019-03-04\n---\n"), None);
+        assert_eq!(at("date: 2019-03-04"), None);
+    }
+
+    #[test]
+    fn with_frontmatter_title_adds_or_replaces_the_title() {
+        assert_eq!(
+            with_frontmatter_title("body", "A \"B\""),
+            "---\ntitle: \"A \\\"B\\\"\"\n---\nbody"
+        );
+        assert_eq!(
+            with_frontmatter_title("---\ntags: [a]\ntitle: old\n---\nbody", "New"),
+            "---\ntitle: \"New\"\ntags: [a]\n---\nbody"
+        );
+        assert_eq!(
+            with_frontmatter_title("+++\nx = 1\n+++\nbody", "T"),
+            "+++\ntitle = \"T\"\nx = 1\n+++\nbody"
+        );
+    }
+
     #[test]
     fn check_title_in_list() {
         let markdown = r#"- First Item
@@ -2190,6 +2955,58 @@ Some text"#;
         assert_eq!("Some text", content_chunks[0].get_text());
     }
 
+    #[test]
+    fn excerpt_is_the_plain_body_after_the_title() {
+        for (markdown, excerpt) in [
+            (
+                "# Title\nSome **bold** text\nwrapped.\n\n## Section\n- a [[target|link]]\n- item %%hidden%%",
+                "Some bold text wrapped. a link item",
+            ),
+            ("First line is the title\nthen the body", "then the body"),
+            (
+                "---\ntitle: From frontmatter\ntags: [x]\n---\nOpening line\n",
+                "Opening line",
+            ),
+            ("# Only a heading", ""),
+            ("", ""),
+        ] {
+            assert_eq!(excerpt, get_content_data(markdown).excerpt, "{markdown}");
+        }
+    }
+
+    #[test]
+    fn excerpt_is_cut_at_a_word_boundary() {
+        let body = "word ".repeat(100);
+        let excerpt = get_content_data(format!("# Title\n{body}")).excerpt;
+        assert!(excerpt.ends_with("word…"), "{excerpt}");
+        assert!(excerpt.chars().count() <= EXCERPT_LEN + 1);
+
+        let long = "x".repeat(EXCERPT_LEN * 2);
+        let excerpt = get_content_data(format!("# Title\n{long}")).excerpt;
+        assert_eq!(excerpt, format!("{}…", "x".repeat(EXCERPT_LEN)));
+    }
+
+    #[test]
+    fn check_title_is_plain_text() {
+        for (markdown, title) in [
+            ("# *italic*\nSome text", "italic"),
+            ("# [link](x)\nSome text", "link"),
+            ("# Using `code`\nSome text", "Using code"),
+            (
+                "A long title\nwrapped over **two** lines\n===\nSome text",
+                "A long title wrapped over two lines",
+            ),
+        ] {
+            let data = get_content_data(markdown);
+            let content_chunks = get_content_chunks(markdown);
+
+            assert_eq!(title, data.title, "{markdown}");
+            assert_eq!(1, content_chunks.len(), "{markdown}");
+            assert_eq!(title, content_chunks[0].get_breadcrumb(), "{markdown}");
+            assert_eq!("Some text", content_chunks[0].get_text(), "{markdown}");
+        }
+    }
+
     #[test]
     fn check_content_without_title() {
         let markdown = r#"Intro text
@@ -2347,6 +3164,54 @@ ls -la ./test
         assert_eq!("Some text", chunks[0].get_text());
     }
 
+    #[test]
+    fn chunks_span_their_source_sections() {
+        let markdown =
+            "---\ntitle: Hi\n---\nintro line\n\n# One\nfirst [[note|body]]\n\n## Two\nCafé text\n";
+        let expected = [
+            ("intro line", "intro line"),
+            ("first body", "# One\nfirst [[note|body]]"),
+            ("Cafe text", "## Two\nCafé text"),
+            ("title: Hi", "title: Hi"),
+        ];
+        let (indexed, _links) = super::get_chunks_and_links(&VaultPath::root(), markdown);
+        for chunks in [get_content_chunks(markdown), indexed] {
+            assert_eq!(chunks.len(), expected.len());
+            for (chunk, (text, section)) in chunks.iter().zip(expected) {
+                assert_eq!(chunk.text, text);
+                assert_eq!(&markdown[chunk.range()], section);
+            }
+        }
+    }
+
+    #[test]
+    fn chunk_at_finds_the_section_under_an_offset() {
+        let markdown = "---\ntitle: Hi\n---\nintro\n\n# One\nfirst\n\n## Two\nsecond\n# Empty\n";
+        let crumb_at = |needle: &str| {
+            let offset = markdown.find(needle).unwrap();
+            super::chunk_at(markdown, offset).map(|chunk| chunk.breadcrumb)
+        };
+        assert_eq!(crumb_at("title"), Some("FrontMatter".to_string()));
+        assert_eq!(crumb_at("intro"), Some(String::new()));
+        assert_eq!(crumb_at("# One"), Some("One".to_string()));
+        assert_eq!(crumb_at("\n## Two"), Some("One".to_string()));
+        assert_eq!(
+            crumb_at("second"),
+            Some(format!("One{}Two", crate::note::BREADCRUMB_SEP))
+        );
+        assert_eq!(super::chunk_at(markdown, 0), None);
+        assert_eq!(crumb_at("Empty"), None);
+        assert_eq!(super::chunk_at(markdown, markdown.len() + 1), None);
+    }
+
+    #[test]
+    fn verbatim_chunk_text_is_the_tail_of_its_section() {
+        let text = "# a\nalpha body\n\n# b\nbeta body";
+        for chunk in get_content_chunks(text) {
+            assert!(text[chunk.start..chunk.end].ends_with(&chunk.text));
+        }
+    }
+
     #[test]
     fn link_with_title_attribute_keeps_only_link_text() {
         // [text](url "title") — "title" must NOT appear in the chunk content.
@@ -2,10 +2,17 @@ pub(crate) mod content_extractor;
 
 use std::fmt::Display;
 
+use chrono::{DateTime, Utc};
+
 // Crate-internal whole-note operations (markdown pipeline, link rewriting).
 // The note module is the only door to the extractor: nothing outside `note/`
 // names `content_extractor` directly.
-pub(crate) use content_extractor::{process_image_links, replace_note_links};
+pub(crate) use content_extractor::{
+    is_valid_label, process_image_links, replace_label, replace_note_links, section_for_merge,
+    split_sections, with_frontmatter_title,
+};
+
+pub use content_extractor::EXCERPT_LEN;
 
 use crate::nfs::VaultPath;
 
@@ -89,7 +96,8 @@ impl NoteDetails {
     /// Builds a [`NoteDetails`] from a vault path and the note's raw text.
     ///
     /// The path is flattened (`.`/`..` segments resolved) on the way in; the
-    /// text is copied into the owned [`raw_text`] field unchanged.
+    /// text is copied into the owned [`raw_text`] field unchanged. Nothing
+    /// is read from disk, so `note_path` need not exist.
     ///
     /// [`raw_text`]: Self::raw_text
     pub fn new<S: AsRef<str>>(note_path: &VaultPath, text: S) -> Self {
@@ -99,17 +107,37 @@ impl NoteDetails {
         }
     }
 
+    /// Parses `text` as if it were the note at `note_path` — title, heading
+    /// chunks, rendered Markdown and links — for a live preview of text that
+    /// isn't saved (or whose note doesn't exist yet). No I/O.
+    pub fn preview<S: AsRef<str>>(note_path: &VaultPath, text: S) -> NotePreview {
+        let details = Self::new(note_path, text);
+        let (text, links) = details.get_markdown_and_links();
+        NotePreview {
+            title: details.get_title(),
+            chunks: details.get_content_chunks(),
+            markdown: MarkdownNote { text, links },
+        }
+    }
+
     /// Title of a note body, without constructing a `NoteDetails`.
     pub fn get_title_from_text<S: AsRef<str>>(text: S) -> String {
         content_extractor::extract_title(text)
     }
 
-    /// Indexable content data (title + hash) of a note body, without
+    /// Indexable content data (title, hash and excerpt) of a note body, without
     /// constructing a `NoteDetails`.
     pub fn content_data_of<S: AsRef<str>>(text: S) -> NoteContentData {
         content_extractor::get_content_data(text)
     }
 
+    /// The date in a note body's frontmatter (`created`, else `date`),
+    /// without constructing a `NoteDetails`. `None` when there is none or it
+    /// does not parse as a date.
+    pub fn frontmatter_date_of<S: AsRef<str>>(text: S) -> Option<DateTime<Utc>> {
+        content_extractor::extract_frontmatter_date(text)
+    }
+
     /// Heading-chunked content of a note body, without constructing a
     /// `NoteDetails`.
     pub fn content_chunks_of<S: AsRef<str>>(text: S) -> Vec<ContentChunk> {
@@ -126,13 +154,13 @@ impl NoteDetails {
         content_extractor::get_chunks_and_links(path, text)
     }
 
-    /// Title of this note (first non-empty line of the body, frontmatter
-    /// skipped).
+    /// Title of this note: the frontmatter `title` when set, otherwise the
+    /// first non-empty line of the body.
     pub fn get_title(&self) -> String {
         Self::get_title_from_text(&self.raw_text)
     }
 
-    /// Indexable content data (title + content hash) of this note.
+    /// Indexable content data (title, content hash and excerpt) of this note.
     pub fn get_content_data(&self) -> NoteContentData {
         Self::content_data_of(&self.raw_text)
     }
@@ -143,6 +171,18 @@ impl NoteDetails {
         Self::content_chunks_of(&self.raw_text)
     }
 
+    /// The [`ContentChunk`] whose section holds byte `offset` of
+    /// [`raw_text`], e.g. to show the breadcrumb of the section under the
+    /// cursor. `None` on a heading with no body, the opening frontmatter
+    /// delimiter, or an offset past the end of the text.
+    ///
+    /// Chunks are derived on demand, so this returns an owned chunk.
+    ///
+    /// [`raw_text`]: Self::raw_text
+    pub fn chunk_at(&self, offset: usize) -> Option<ContentChunk> {
+        content_extractor::chunk_at(&self.raw_text, offset)
+    }
+
     /// Heading chunks plus every link (note links, attachments, images,
     /// URLs, hashtags) of this note, resolved against its own [`path`].
     ///
@@ -151,6 +191,13 @@ impl NoteDetails {
         Self::chunks_and_links_of(&self.path, &self.raw_text)
     }
 
+    /// Read-only structural check of this note's Markdown, reporting
+    /// problems the parser silently tolerates (unclosed code fences, broken
+    /// link syntax, empty headings). See [`LintKind`] for what is detected.
+    pub fn lint(&self) -> Vec<LintWarning> {
+        content_extractor::lint(&self.raw_text)
+    }
+
     /// Rendered Markdown of this note plus its extracted links: wikilinks
     /// become standard Markdown links, note links resolve to vault-relative
     /// absolute paths, hashtags become `[#tag](#tag)` links.
@@ -159,6 +206,45 @@ impl NoteDetails {
     }
 }
 
+/// The kind of problem reported by [`NoteDetails::lint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    /// A ```` ``` ```` or `~~~` fence that is never closed, so the rest of the
+    /// note renders as code. Reported on the opening fence line.
+    UnclosedCodeFence,
+    /// Link syntax that is opened but never closed on the same line, such as
+    /// `[text](` without the closing `)` or `[[target` without `]]`.
+    BrokenLink,
+    /// A heading marker (`#` to `######`) with no heading text.
+    EmptyHeading,
+}
+
+impl Display for LintKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintKind::UnclosedCodeFence => write!(f, "Unclosed code fence"),
+            LintKind::BrokenLink => write!(f, "Broken link syntax"),
+            LintKind::EmptyHeading => write!(f, "Empty heading"),
+        }
+    }
+}
+
+/// A problem found in a note's Markdown by [`NoteDetails::lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    /// Zero-based line of the raw text (frontmatter included) where the
+    /// problem is.
+    pub line: usize,
+    /// What kind of problem it is.
+    pub kind: LintKind,
+}
+
+impl Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: {}", self.line + 1, self.kind)
+    }
+}
+
 /// A note's rendered Markdown together with the links extracted from it.
 ///
 /// The text is the result of the link-rewriting pipeline (wikilinks turned
@@ -174,6 +260,19 @@ pub struct MarkdownNote {
     pub links: Vec<NoteLink>,
 }
 
+/// What [`NoteDetails::preview`] parsed out of a note's in-memory text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NotePreview {
+    /// The note's title, as [`NoteDetails::get_title`] computes it.
+    pub title: String,
+    /// The note's heading chunks, as [`NoteDetails::get_content_chunks`]
+    /// computes them.
+    pub chunks: Vec<ContentChunk>,
+    /// The rendered Markdown and its links, resolved against the preview
+    /// path.
+    pub markdown: MarkdownNote,
+}
+
 /// NoteContentData contains the basic extracted data from the note
 /// for comparison and search in the DB, it is expensive to get
 /// so it is not a good idea to calculate it every time the content
@@ -181,18 +280,46 @@ pub struct MarkdownNote {
 /// (like the DB) for search and access.
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct NoteContentData {
-    /// The note's title (first non-empty body line, frontmatter skipped).
+    /// The note's title (frontmatter `title`, else the first non-empty body
+    /// line).
     pub title: String,
     /// XxHash64 digest of the note's full text, used to detect content
     /// changes cheaply during indexing.
     pub hash: u64,
+    /// The opening of the note's body as plain text, at most
+    /// [`EXCERPT_LEN`] characters, for a
+    /// preview line in note lists. Stored in the index next to the hash.
+    pub excerpt: String,
 }
 
 impl NoteContentData {
     /// Builds a [`NoteContentData`] from a precomputed title and content
-    /// hash.
+    /// hash, with no excerpt.
     pub fn new(title: String, hash: u64) -> Self {
-        Self { title, hash }
+        Self {
+            title,
+            hash,
+            excerpt: String::new(),
+        }
+    }
+
+    /// Sets the body excerpt (see [`excerpt`](Self::excerpt)).
+    pub fn with_excerpt(mut self, excerpt: String) -> Self {
+        self.excerpt = excerpt;
+        self
+    }
+
+    /// Whether `other` was computed from the same note text, judged by the
+    /// content hash alone (the title is derived from the text anyway).
+    ///
+    /// ```
+    /// use kimun_core::note::NoteContentData;
+    /// let seen = NoteContentData::new("Old title".to_string(), 42);
+    /// assert!(seen.is_same_content(&NoteContentData::new("Title".to_string(), 42)));
+    /// assert!(!seen.is_same_content(&NoteContentData::new("Old title".to_string(), 7)));
+    /// ```
+    pub fn is_same_content(&self, other: &NoteContentData) -> bool {
+        self.hash == other.hash
     }
 }
 
@@ -228,6 +355,15 @@ pub struct ContentChunk {
     pub breadcrumb: String,
     /// The chunk's body text (the content under its innermost heading).
     pub text: String,
+    /// Byte offset in the note's source text where the section this chunk
+    /// was built from starts: its heading line, or the top of the body for
+    /// content before the first heading.
+    pub start: usize,
+    /// Byte offset where that section ends (exclusive, trailing whitespace
+    /// left out). `text` is normalized — markup, wikilinks and diacritics
+    /// stripped — so `&source[start..end]` is the raw section it came from,
+    /// not a copy of `text`.
+    pub end: usize,
 }
 
 impl ContentChunk {
@@ -256,6 +392,12 @@ impl ContentChunk {
     pub fn get_text(&self) -> &str {
         &self.text
     }
+
+    /// Byte range of the chunk's section in the note's source text (see
+    /// [`start`](Self::start)).
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start..self.end
+    }
 }
 
 /// Classification of a link found in a note.
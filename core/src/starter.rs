@@ -0,0 +1,64 @@
+//! Starter notes — the sample content
+//! [`NoteVault::seed_starter_notes`](crate::NoteVault::seed_starter_notes)
+//! writes into an empty vault so a first run opens onto something to read
+//! rather than a blank screen.
+
+/// Vault-root name of the welcome note, without its extension.
+pub(crate) const WELCOME_NOTE: &str = "welcome";
+
+/// Vault-root name of the keyboard-shortcuts note, without its extension.
+pub(crate) const SHORTCUTS_NOTE: &str = "keyboard-shortcuts";
+
+/// The welcome note, pointing at [`SHORTCUTS_NOTE`] when one is seeded.
+pub(crate) fn welcome_text(with_shortcuts: bool) -> String {
+    let shortcuts = if with_shortcuts {
+        "- Read [[keyboard-shortcuts]] for the keys you'll use most.\n"
+    } else {
+        ""
+    };
+    format!(
+        "# Welcome to Kimün
+
+Kimün keeps your notes as plain Markdown files in this folder, and indexes
+them so you can find anything quickly.
+
+## Where to start
+
+{shortcuts}- Your key bindings are listed in [[help]], a note the app writes from your
+  configuration.
+- Open today's journal entry to see how daily notes work.
+- Link notes with `[[double brackets]]`; every link is tracked, so you can
+  see what points at a note from its backlinks.
+- Tag notes with `#hashtags` and search them with `#tag`.
+
+## Searching
+
+Plain words search the text of every note. Narrow things down with
+`@heading`, `/folder`, `=name`, `#tag`, or `<note` for the notes that link to
+a note. Prefix any term with `-` to exclude it.
+
+These starter notes are yours: edit or delete them whenever you like.
+"
+    )
+}
+
+/// Today's journal entry, showing what a daily note can hold. `title` is the
+/// entry's date, as the journal names it.
+pub(crate) fn journal_text(title: &str) -> String {
+    format!(
+        "# {title}
+
+This is a journal entry: one note per day, kept in the journal folder.
+
+## Today
+
+- [ ] Try linking to the [[welcome]] note
+- [ ] Add a #idea tag and search for it
+
+## Notes
+
+Write freely; the journal is a good home for anything that doesn't have a
+note of its own yet.
+"
+    )
+}
@@ -1,7 +1,8 @@
 //! VaultSync — brings the NoteIndex in step with the vault on disk.
 //!
 //! One call ([`VaultSync::run`]) owns the whole pipeline: read the cached
-//! entries from the index, walk the subtree in parallel, diff against the
+//! entries from the index, walk the subtree (in parallel unless a
+//! [`sequential`](VaultSync::sequential) walk is asked for), diff against the
 //! cache under a validation mode, and apply the resulting [`IndexDiff`] —
 //! optionally streaming discovered entries to the caller as they are found.
 //! The parallel walker, its thread-state plumbing, and the async/blocking
@@ -19,6 +20,7 @@ use crate::index::NoteIndex;
 use crate::nfs::{self, VaultPath};
 use crate::{NotesValidation, SearchResult};
 
+use ignore::ParallelVisitorBuilder;
 use visitor::NoteListVisitorBuilder;
 
 /// The sync pipeline over one vault: a [`NoteIndex`] plus the workspace root
@@ -26,20 +28,33 @@ use visitor::NoteListVisitorBuilder;
 pub(crate) struct VaultSync<'a> {
     index: &'a NoteIndex,
     workspace_path: &'a Path,
+    max_note_bytes: u64,
+    sequential: bool,
 }
 
 impl<'a> VaultSync<'a> {
-    pub(crate) fn new(index: &'a NoteIndex, workspace_path: &'a Path) -> Self {
+    pub(crate) fn new(index: &'a NoteIndex, workspace_path: &'a Path, max_note_bytes: u64) -> Self {
         Self {
             index,
             workspace_path,
+            max_note_bytes,
+            sequential: false,
         }
     }
 
+    /// Walks on a single thread in a fixed order (see
+    /// [`VaultConfig::sequential_walk`](crate::VaultConfig::sequential_walk))
+    /// instead of in parallel.
+    pub(crate) fn sequential(mut self, sequential: bool) -> Self {
+        self.sequential = sequential;
+        self
+    }
+
     /// Syncs the subtree at `path` into the index: cached entries are read,
     /// the filesystem is walked in parallel, every note is validated against
     /// the cache under `validation`, and the resulting [`IndexDiff`] is
-    /// applied atomically. When `sender` is given, every discovered entry
+    /// applied atomically. Notes over `max_note_bytes` are indexed without
+    /// their content. When `sender` is given, every discovered entry
     /// (note, directory, attachment) is streamed to it as the walk finds it.
     ///
     /// [`IndexDiff`]: crate::index::IndexDiff
@@ -53,9 +68,15 @@ impl<'a> VaultSync<'a> {
         debug!("Syncing subtree at {}", path);
         let cached_notes = self.index.get_notes(path, recursive).await?;
         let builder =
-            NoteListVisitorBuilder::new(self.workspace_path, validation, cached_notes, sender);
-        let walker = nfs::get_file_walker(self.workspace_path, path, recursive);
-        let builder = run_walker_blocking(walker, builder).await?;
+            NoteListVisitorBuilder::new(self.workspace_path, validation, cached_notes, sender)
+                .max_note_bytes(self.max_note_bytes);
+        let builder = if self.sequential {
+            let walker = nfs::get_sorted_file_walker(self.workspace_path, path, recursive);
+            run_sorted_walker_blocking(walker, builder).await?
+        } else {
+            let walker = nfs::get_file_walker(self.workspace_path, path, recursive);
+            run_walker_blocking(walker, builder).await?
+        };
         self.index.apply(builder.into_diff()).await?;
         Ok(())
     }
@@ -75,3 +96,22 @@ async fn run_walker_blocking(
     .await
     .map_err(|e| VaultError::TaskJoin(format!("vault walker: {}", e)))
 }
+
+/// [`run_walker_blocking`] for the sorted single-threaded walker: every entry
+/// goes through one visitor, on the blocking thread, in walk order.
+async fn run_sorted_walker_blocking(
+    walker: ignore::Walk,
+    builder: NoteListVisitorBuilder,
+) -> Result<NoteListVisitorBuilder, VaultError> {
+    tokio::task::spawn_blocking(move || {
+        let mut builder = builder;
+        let mut visitor = builder.build();
+        for entry in walker {
+            visitor.visit(entry);
+        }
+        drop(visitor);
+        builder
+    })
+    .await
+    .map_err(|e| VaultError::TaskJoin(format!("vault walker: {}", e)))
+}
@@ -1,22 +1,24 @@
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
-    sync::{mpsc::Sender, Arc, Mutex},
+    sync::{mpsc::Sender, Arc, Mutex, PoisonError},
 };
 
 use ignore::{ParallelVisitor, ParallelVisitorBuilder};
 use log::{error, warn};
 
 use crate::{
+    error::FSError,
     index::IndexDiff,
     nfs::{EntryData, NoteEntryData, VaultEntry, VaultPath},
-    note::NoteContentData,
+    note::{NoteContentData, NoteDetails},
     NotesValidation, SearchResult,
 };
 
 struct NoteListVisitor {
     workspace_path: PathBuf,
     validation: NotesValidation,
+    max_note_bytes: u64,
     notes_to_delete: Arc<Mutex<HashMap<VaultPath, (NoteEntryData, NoteContentData)>>>,
     notes_to_modify: Arc<Mutex<Vec<(NoteEntryData, String)>>>,
     notes_to_add: Arc<Mutex<Vec<(NoteEntryData, String)>>>,
@@ -49,6 +51,20 @@ impl NoteListVisitor {
         cached.size != disk.size || cached.modified_secs != disk.modified_secs
     }
 
+    /// Reads the note's content, unless the file is over `max_note_bytes`:
+    /// then it is indexed with empty content so its row (and real size) is
+    /// kept without reading or full-text indexing the file.
+    fn load_details(&self, data: &NoteEntryData, os_path: &Path) -> Result<NoteDetails, FSError> {
+        if data.size > self.max_note_bytes {
+            warn!(
+                "Note {} is {} bytes, over the {} byte limit; indexing it without content",
+                data.path, data.size, self.max_note_bytes
+            );
+            return Ok(NoteDetails::new(&data.path, String::new()));
+        }
+        data.load_details_from_os_path(os_path)
+    }
+
     /// Returns `None` only when the file could not be read; the caller treats
     /// that as "skip this iteration" so the cached entry (if any) survives
     /// untouched and will be re-checked next time.
@@ -60,7 +76,7 @@ impl NoteListVisitor {
         let cached_option = self
             .notes_to_delete
             .lock()
-            .unwrap()
+            .unwrap_or_else(PoisonError::into_inner)
             .remove(&data.path.canonical());
 
         match cached_option {
@@ -73,7 +89,7 @@ impl NoteListVisitor {
                 if !needs_reload {
                     return Some(cached_details);
                 }
-                match data.load_details_from_os_path(os_path) {
+                match self.load_details(data, os_path) {
                     Ok(details) => {
                         let new_content = details.get_content_data();
                         if self.validation == NotesValidation::Full
@@ -83,7 +99,7 @@ impl NoteListVisitor {
                         }
                         self.notes_to_modify
                             .lock()
-                            .unwrap()
+                            .unwrap_or_else(PoisonError::into_inner)
                             .push((data.to_owned(), details.raw_text));
                         Some(new_content)
                     }
@@ -101,12 +117,12 @@ impl NoteListVisitor {
                     }
                 }
             }
-            None => match data.load_details_from_os_path(os_path) {
+            None => match self.load_details(data, os_path) {
                 Ok(details) => {
                     let content = details.get_content_data();
                     self.notes_to_add
                         .lock()
-                        .unwrap()
+                        .unwrap_or_else(PoisonError::into_inner)
                         .push((data.to_owned(), details.raw_text));
                     Some(content)
                 }
@@ -141,6 +157,7 @@ impl ParallelVisitor for NoteListVisitor {
 pub struct NoteListVisitorBuilder {
     workspace_path: PathBuf,
     validation: NotesValidation,
+    max_note_bytes: u64,
     notes_to_delete: Arc<Mutex<HashMap<VaultPath, (NoteEntryData, NoteContentData)>>>,
     notes_to_modify: Arc<Mutex<Vec<(NoteEntryData, String)>>>,
     notes_to_add: Arc<Mutex<Vec<(NoteEntryData, String)>>>,
@@ -165,6 +182,7 @@ impl NoteListVisitorBuilder {
         Self {
             workspace_path: workspace_path.as_ref().to_path_buf(),
             validation,
+            max_note_bytes: u64::MAX,
             notes_to_delete: Arc::new(Mutex::new(notes_to_delete)),
             notes_to_modify: Arc::new(Mutex::new(Vec::new())),
             notes_to_add: Arc::new(Mutex::new(Vec::new())),
@@ -172,15 +190,30 @@ impl NoteListVisitorBuilder {
         }
     }
 
-    /// Consumes the builder and returns the accumulated [`IndexDiff`]. Must be
-    /// called after the parallel walker has finished — at that point all
-    /// visitor clones are dropped, so the inner `Arc<Mutex<...>>` are uniquely
-    /// owned and we can move the Vecs out without cloning.
+    /// Notes larger than `max_note_bytes` are indexed without their content.
+    /// Unlimited by default.
+    pub fn max_note_bytes(mut self, max_note_bytes: u64) -> Self {
+        self.max_note_bytes = max_note_bytes;
+        self
+    }
+
+    /// Consumes the builder and returns the accumulated [`IndexDiff`], each
+    /// list sorted by path. Must be called after the parallel walker has
+    /// finished — at that point all visitor clones are dropped, so the inner
+    /// `Arc<Mutex<...>>` are uniquely owned and we can move the Vecs out
+    /// without cloning.
     pub fn into_diff(self) -> IndexDiff {
+        let mut to_delete: Vec<VaultPath> =
+            take_arc_mutex(self.notes_to_delete).into_keys().collect();
+        to_delete.sort();
+        let mut to_add = take_arc_mutex(self.notes_to_add);
+        to_add.sort_by(|(a, _), (b, _)| a.path.cmp(&b.path));
+        let mut to_modify = take_arc_mutex(self.notes_to_modify);
+        to_modify.sort_by(|(a, _), (b, _)| a.path.cmp(&b.path));
         IndexDiff {
-            to_delete: take_arc_mutex(self.notes_to_delete).into_keys().collect(),
-            to_add: take_arc_mutex(self.notes_to_add),
-            to_modify: take_arc_mutex(self.notes_to_modify),
+            to_delete,
+            to_add,
+            to_modify,
         }
     }
 
@@ -207,14 +240,14 @@ impl NoteListVisitorBuilder {
 
 fn take_arc_mutex<T: Default>(arc: Arc<Mutex<T>>) -> T {
     match Arc::try_unwrap(arc) {
-        Ok(mutex) => mutex.into_inner().expect("visitor mutex poisoned"),
+        Ok(mutex) => mutex.into_inner().unwrap_or_else(PoisonError::into_inner),
         // Walker should drop every visitor clone before returning. If a
         // worker thread leaked its visitor (e.g. mid-panic), take the data
         // via the surviving lock so the index op still completes rather
         // than aborting the whole walk.
         Err(arc) => {
             log::warn!("visitor Arc still shared after walker exit — taking via lock");
-            std::mem::take(&mut *arc.lock().expect("visitor mutex poisoned"))
+            std::mem::take(&mut *arc.lock().unwrap_or_else(PoisonError::into_inner))
         }
     }
 }
@@ -224,6 +257,7 @@ impl<'s> ParallelVisitorBuilder<'s> for NoteListVisitorBuilder {
         Box::new(NoteListVisitor {
             workspace_path: self.workspace_path.clone(),
             validation: self.validation,
+            max_note_bytes: self.max_note_bytes,
             notes_to_delete: self.notes_to_delete.clone(),
             notes_to_modify: self.notes_to_modify.clone(),
             notes_to_add: self.notes_to_add.clone(),
@@ -613,6 +647,46 @@ mod tests {
         assert!(builder.get_notes_to_modify().is_empty());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn into_diff_sorts_each_list_by_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path();
+        for i in (0..20).rev() {
+            save_note(
+                workspace_path,
+                &VaultPath::new(format!("d{i}/n{i}.md")),
+                "x",
+            )
+            .await
+            .unwrap();
+        }
+        let cached = ["gone/b.md", "gone/a.md"]
+            .into_iter()
+            .map(|p| {
+                let entry = NoteEntryData {
+                    path: VaultPath::new(p),
+                    size: 0,
+                    modified_secs: 0,
+                };
+                (entry, NoteContentData::new(String::new(), 0))
+            })
+            .collect();
+
+        let mut builder =
+            NoteListVisitorBuilder::new(workspace_path, NotesValidation::None, cached, None);
+        let walker = crate::nfs::get_file_walker(workspace_path, &VaultPath::root(), true);
+        walker.visit(&mut builder);
+        let diff = builder.into_diff();
+
+        let added: Vec<&VaultPath> = diff.to_add.iter().map(|(e, _)| &e.path).collect();
+        assert_eq!(added.len(), 20);
+        assert!(added.is_sorted());
+        assert_eq!(
+            diff.to_delete,
+            [VaultPath::new("gone/a.md"), VaultPath::new("gone/b.md")].map(|p| p.canonical())
+        );
+    }
+
     /// A cached note that is still on disk but momentarily unreadable must
     /// NOT land in `to_delete` (regression: the old error branch re-inserted
     /// it into the delete map, wiping a live note's index rows on a
@@ -8,10 +8,10 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use kimun_server::{
     KimunRag,
-    config::RagConfig,
+    config::{LLM_MODEL_ENV, RagConfig},
     handlers::{
-        answer_handler, collection_hashes_handler, get_embeddings_handler, index_delete_handler,
-        index_docs_handler, job_status_handler,
+        answer_handler, answer_stream_handler, collection_hashes_handler, get_embeddings_handler,
+        index_delete_handler, index_docs_handler, job_status_handler,
     },
     server_state::AppState,
 };
@@ -35,6 +35,16 @@ struct Cli {
     /// Port to bind to (overrides config)
     #[arg(short, long)]
     port: Option<u16>,
+
+    /// Directory of the embedded SQLite vector store (overrides config; selects
+    /// the SQLite store even when the config names Qdrant)
+    #[arg(long)]
+    db_path: Option<std::path::PathBuf>,
+
+    /// LLM model to answer with, e.g. `gemini-2.5-pro` (overrides the `[llm]`
+    /// model; falls back to the KIMUN_LLM_MODEL environment variable)
+    #[arg(long)]
+    llm_model: Option<String>,
 }
 
 /// Why one `run_server` iteration ended: an operator asked for an in-process
@@ -100,7 +110,13 @@ async fn run_server(
         // source of truth, --default-config or not.
         RagConfig::load(cli.config.clone())?
     };
-    let config = config.merge_with_cli(cli.host.clone(), cli.port);
+    let config = config
+        .merge_with_cli(cli.host.clone(), cli.port, cli.db_path.clone())
+        .with_llm_model(
+            cli.llm_model
+                .clone()
+                .or_else(|| std::env::var(LLM_MODEL_ENV).ok()),
+        )?;
 
     tracing::info!("Configuration loaded successfully");
     tracing::debug!("Server: {}:{}", config.server.host, config.server.port);
@@ -180,6 +196,7 @@ async fn run_server(
         .route("/api/index/delete", post(index_delete_handler))
         .route("/api/embeddings", post(get_embeddings_handler))
         .route("/api/answer", post(answer_handler))
+        .route("/api/answer/stream", post(answer_stream_handler))
         .route(
             "/api/collections/{vault_id}/hashes",
             get(collection_hashes_handler),
@@ -366,7 +383,9 @@ async fn create_rag_from_config(
         .with_drop_window(
             config.reranker.drop_window_min,
             config.reranker.drop_window_max,
-        );
+        )
+        .with_candidate_pool(config.reranker.candidate_pool)
+        .with_embed_concurrency(config.server.embed_concurrency);
 
     // Enable reranking if configured. Initialization failure (typically: the
     // cross-encoder model download failed — offline, proxy — or an unreachable
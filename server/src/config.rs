@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// Environment variable overriding the `[llm]` model, like `--llm-model`
+/// (which wins when both are set).
+pub const LLM_MODEL_ENV: &str = "KIMUN_LLM_MODEL";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RagConfig {
     pub server: ServerConfig,
@@ -41,6 +45,12 @@ pub struct ServerConfig {
     pub port: u16,
     #[serde(default = "default_max_concurrent_jobs")]
     pub max_concurrent_jobs: usize,
+    /// How many embedding batches one indexing pass keeps in flight at once
+    /// (default 2). Raise it for an embedder with headroom; lower it when a
+    /// hosted provider keeps answering `429`. Rate-limited batches are retried
+    /// with backoff either way. Floored to 1.
+    #[serde(default = "default_embed_concurrency")]
+    pub embed_concurrency: usize,
 }
 
 /// Selects the embedder. All collections on a server share one embedder — the
@@ -330,6 +340,11 @@ pub struct RerankerConfig {
     pub drop_window_min: usize,
     #[serde(default = "default_drop_window_max")]
     pub drop_window_max: usize,
+    /// How many chunks each query pulls from the vector store before dedup,
+    /// reranking and the context cut (default 80). File-only knob for
+    /// retrieval experiments; floored to 1.
+    #[serde(default = "default_candidate_pool")]
+    pub candidate_pool: usize,
 }
 
 /// The context cut algorithm — how many retrieved chunks/notes each query
@@ -405,6 +420,10 @@ fn default_max_concurrent_jobs() -> usize {
     10
 }
 
+fn default_embed_concurrency() -> usize {
+    2
+}
+
 fn default_sqlite_path() -> PathBuf {
     PathBuf::from("./rag_sqlite")
 }
@@ -434,7 +453,7 @@ fn default_qdrant_collection() -> String {
 }
 
 fn default_gemini_model() -> String {
-    "gemini-2.5-flash-preview-04-17".to_string()
+    "gemini-2.5-flash".to_string()
 }
 
 fn default_mistral_model() -> String {
@@ -469,6 +488,10 @@ fn default_drop_window_max() -> usize {
     30
 }
 
+fn default_candidate_pool() -> usize {
+    80
+}
+
 /// A zero-config default: SQLite under the data dir, default `127.0.0.1`
 /// bind, no embedder (unconfigured, adr/0024), no LLM, no auth. This is what a
 /// first run with no config file boots from and writes to disk (adr/0022).
@@ -479,6 +502,7 @@ impl Default for RagConfig {
                 host: default_host(),
                 port: default_port(),
                 max_concurrent_jobs: default_max_concurrent_jobs(),
+                embed_concurrency: default_embed_concurrency(),
             },
             vector_db: VectorDbConfig::Sqlite {
                 path: generated_sqlite_path(),
@@ -496,6 +520,7 @@ impl Default for RagConfig {
                 score_range_cutoff: default_score_range_cutoff(),
                 drop_window_min: default_drop_window_min(),
                 drop_window_max: default_drop_window_max(),
+                candidate_pool: default_candidate_pool(),
             },
             auth: AuthConfig::default(),
         }
@@ -867,14 +892,52 @@ impl RagConfig {
         Ok(config)
     }
 
-    /// Merge configuration with CLI arguments
-    pub fn merge_with_cli(mut self, host: Option<String>, port: Option<u16>) -> Self {
+    /// Points the configured LLM at `model` (the `--llm-model` flag or
+    /// [`LLM_MODEL_ENV`]); `None` keeps the file's choice. Names are not
+    /// checked against a list — providers add and retire models faster than
+    /// releases ship, so an unknown one surfaces as the provider's error on the
+    /// first answer. Only what can never work is rejected: a blank name, or no
+    /// `[llm]` section to apply it to.
+    pub fn with_llm_model(mut self, model: Option<String>) -> anyhow::Result<Self> {
+        let Some(model) = model else {
+            return Ok(self);
+        };
+        let model = model.trim();
+        if model.is_empty() {
+            anyhow::bail!("The LLM model override is empty");
+        }
+        match &mut self.llm {
+            Some(
+                LlmConfig::Gemini { model: m, .. }
+                | LlmConfig::Mistral { model: m, .. }
+                | LlmConfig::Claude { model: m, .. }
+                | LlmConfig::OpenAI { model: m, .. },
+            ) => *m = model.to_string(),
+            None => anyhow::bail!(
+                "An LLM model override (`{model}`) needs an [llm] provider in the config"
+            ),
+        }
+        Ok(self)
+    }
+
+    /// Merge configuration with CLI arguments. A `db_path` selects the
+    /// embedded SQLite store at that directory, replacing whatever vector
+    /// store the file configured.
+    pub fn merge_with_cli(
+        mut self,
+        host: Option<String>,
+        port: Option<u16>,
+        db_path: Option<PathBuf>,
+    ) -> Self {
         if let Some(host) = host {
             self.server.host = host;
         }
         if let Some(port) = port {
             self.server.port = port;
         }
+        if let Some(path) = db_path {
+            self.vector_db = VectorDbConfig::Sqlite { path };
+        }
         self
     }
 }
@@ -985,6 +1048,52 @@ api_key = "k"
         assert!(cfg.apply_form(f).is_err());
     }
 
+    #[test]
+    fn cli_db_path_selects_the_sqlite_store() {
+        let cfg: RagConfig = toml::from_str("[server]\n[vector_db]\ntype = \"qdrant\"\n").unwrap();
+        let cfg = cfg.merge_with_cli(None, Some(9000), Some(PathBuf::from("/tmp/vecs")));
+        assert_eq!(cfg.server.port, 9000);
+        match cfg.vector_db {
+            VectorDbConfig::Sqlite { path } => assert_eq!(path, PathBuf::from("/tmp/vecs")),
+            other => panic!("expected sqlite, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn llm_model_override_replaces_the_configured_model() {
+        let cfg: RagConfig = toml::from_str(
+            "[server]\n[vector_db]\ntype = \"sqlite\"\n[llm]\nprovider = \"gemini\"\n",
+        )
+        .unwrap();
+        assert_eq!(cfg.llm.as_ref().unwrap().model(), "gemini-2.5-flash");
+
+        let cfg = cfg.with_llm_model(None).unwrap();
+        assert_eq!(cfg.llm.as_ref().unwrap().model(), "gemini-2.5-flash");
+        let cfg = cfg.with_llm_model(Some(" gemini-2.5-pro ".into())).unwrap();
+        assert_eq!(cfg.llm.as_ref().unwrap().model(), "gemini-2.5-pro");
+        assert_eq!(cfg.llm.as_ref().unwrap().provider(), "gemini");
+
+        assert!(cfg.with_llm_model(Some("  ".into())).is_err());
+        let semantic_only = RagConfig::default();
+        assert!(semantic_only.with_llm_model(Some("gpt-4o".into())).is_err());
+    }
+
+    #[test]
+    fn candidate_pool_defaults_and_survives_form_edits() {
+        let cfg: RagConfig =
+            toml::from_str("[server]\n[vector_db]\ntype = \"sqlite\"\n[reranker]\n").unwrap();
+        assert_eq!(cfg.reranker.candidate_pool, 80);
+
+        let cfg: RagConfig = toml::from_str(
+            "[server]\n[vector_db]\ntype = \"sqlite\"\n[reranker]\ncandidate_pool = 200\n",
+        )
+        .unwrap();
+        assert_eq!(cfg.reranker.candidate_pool, 200);
+        // File-only knob: the web form carries it through unchanged.
+        let saved = cfg.apply_form(form("none", "")).unwrap();
+        assert_eq!(saved.reranker.candidate_pool, 200);
+    }
+
     #[test]
     fn web_form_edits_the_strategy_knobs() {
         let cfg: RagConfig =
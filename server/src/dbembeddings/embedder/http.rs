@@ -7,6 +7,7 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use crate::ProviderRateLimited;
 use crate::document::FlattenedChunk;
 
 use super::Embedder;
@@ -133,7 +134,11 @@ impl HttpEmbedder {
         if let Some(key) = &self.api_key {
             request = request.bearer_auth(key);
         }
-        let response = request.send().await?.error_for_status()?;
+        let response = request.send().await?;
+        if let Some(limited) = ProviderRateLimited::from_response("embedder", &response) {
+            return Err(limited.into());
+        }
+        let response = response.error_for_status()?;
 
         match self.wire {
             EmbedWire::Ollama => {
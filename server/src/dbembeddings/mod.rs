@@ -42,6 +42,17 @@ pub struct EmbeddedChunk {
     pub vector: Vec<f32>,
 }
 
+/// The typed marker a [`VectorStore`] puts inside its `anyhow::Error` when a
+/// vector's width disagrees with the store's — the embedder or its model
+/// changed under an existing index. The pipeline downcasts it into
+/// [`RagError::DimensionMismatch`](crate::RagError::DimensionMismatch).
+#[derive(Debug, thiserror::Error)]
+#[error("vector has dimension {got} but the store is at {expected}")]
+pub(crate) struct DimensionMismatch {
+    pub(crate) expected: usize,
+    pub(crate) got: usize,
+}
+
 /// Pure vector storage, scoped per **collection** — one collection per vault,
 /// keyed by the vault's id (adr/0020). Adapters store, delete, and search rows;
 /// they never embed, split, or rank — that is pipeline policy above this seam.
@@ -51,7 +62,8 @@ pub struct EmbeddedChunk {
 /// delete against a collection that does not exist yet returns empty / no-op,
 /// never an error (reconciliation starts by reading hashes of a possibly
 /// never-pushed vault); `query` returns similarity scores (higher = better),
-/// best-first.
+/// best-first; a vector of the wrong width, stored or queried, fails with a
+/// [`DimensionMismatch`] instead of reaching the backend.
 #[async_trait]
 pub trait VectorStore: Send + Sync {
     /// Appends rows. Replacing a note's chunks is the pipeline's job (it
@@ -192,6 +204,35 @@ pub(crate) mod conformance {
         store.delete(c, &["x.md".to_string()]).await.unwrap();
     }
 
+    pub(crate) async fn wrong_width_vectors_are_a_dimension_mismatch(
+        store: &dyn VectorStore,
+        c: &str,
+    ) {
+        store.store(c, &[row("a.md", "h", "alpha")]).await.unwrap();
+        let mismatch = |e: anyhow::Error| {
+            let m = e
+                .downcast_ref::<DimensionMismatch>()
+                .unwrap_or_else(|| panic!("expected DimensionMismatch, got {e:#}"));
+            (m.expected, m.got)
+        };
+
+        let mut wide = row("b.md", "h", "beta");
+        wide.vector.push(1.0);
+        let err = store
+            .store(c, &[wide])
+            .await
+            .expect_err("store must refuse");
+        assert_eq!(mismatch(err), (DIM, DIM + 1));
+        let err = store
+            .query(c, vec![1.0; DIM - 1], 10)
+            .await
+            .expect_err("query must refuse");
+        assert_eq!(mismatch(err), (DIM, DIM - 1));
+
+        // Nothing of the refused write landed.
+        assert_eq!(store.indexed_notes(c).await.unwrap().len(), 1);
+    }
+
     pub(crate) async fn delete_removes_every_chunk_of_the_note(store: &dyn VectorStore, c: &str) {
         store
             .store(
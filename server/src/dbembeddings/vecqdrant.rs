@@ -12,7 +12,7 @@ use uuid::Uuid;
 
 use crate::document::FlattenedChunk;
 
-use super::{CollectionInfo, EmbeddedChunk, IndexedNote, VectorStore};
+use super::{CollectionInfo, DimensionMismatch, EmbeddedChunk, IndexedNote, VectorStore};
 
 pub struct VecQdrant {
     client: Qdrant,
@@ -70,11 +70,15 @@ impl VecQdrant {
             if let Some(existing) = self.collection_dimension(&name).await?
                 && existing != dim
             {
-                anyhow::bail!(
+                return Err(anyhow::Error::new(DimensionMismatch {
+                    expected: existing as usize,
+                    got: self.dim,
+                })
+                .context(format!(
                     "Qdrant collection `{name}` has dimension {existing} but the \
                      embedder produces {dim}. The embedder or model changed; drop \
                      the collection and re-index."
-                );
+                )));
             }
             return Ok(());
         }
@@ -195,6 +199,14 @@ impl VectorStore for VecQdrant {
         if rows.is_empty() {
             return Ok(());
         }
+        // Qdrant would reject the upsert anyway, but with an opaque message.
+        if let Some(row) = rows.iter().find(|r| r.vector.len() != self.dim) {
+            return Err(DimensionMismatch {
+                expected: self.dim,
+                got: row.vector.len(),
+            }
+            .into());
+        }
         self.ensure_collection(collection).await?;
         let name = self.collection_name(collection);
         let points = rows.iter().map(Self::row_to_point).collect::<Vec<_>>();
@@ -279,6 +291,13 @@ impl VectorStore for VecQdrant {
         if !self.collection_exists(&name).await? {
             return Ok(Vec::new());
         }
+        if vector.len() != self.dim {
+            return Err(DimensionMismatch {
+                expected: self.dim,
+                got: vector.len(),
+            }
+            .into());
+        }
 
         let search_result = self
             .client
@@ -505,6 +524,13 @@ mod tests {
         conformance::missing_collection_is_empty_not_error(&s, "nope").await;
     }
 
+    #[tokio::test]
+    #[ignore = "needs a live Qdrant (set QDRANT_URL, default localhost:6334)"]
+    async fn conformance_wrong_width() {
+        let s = store("width").await;
+        conformance::wrong_width_vectors_are_a_dimension_mismatch(&s, "v").await;
+    }
+
     #[tokio::test]
     #[ignore = "needs a live Qdrant (set QDRANT_URL, default localhost:6334)"]
     async fn conformance_delete() {
@@ -25,7 +25,7 @@ use log::debug;
 use sqlx::Row;
 use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
 
-use super::{CollectionInfo, EmbeddedChunk, IndexedNote, VectorStore};
+use super::{CollectionInfo, DimensionMismatch, EmbeddedChunk, IndexedNote, VectorStore};
 use crate::document::FlattenedChunk;
 
 /// The database file inside the store directory.
@@ -154,12 +154,16 @@ impl VectorStore for VecSqlite {
         // collection and re-index (adr: dimension change is destructive).
         let cid = match Self::collection(&mut *tx, collection).await? {
             Some((_, existing)) if existing != self.dim => {
-                anyhow::bail!(
+                return Err(anyhow::Error::new(DimensionMismatch {
+                    expected: existing,
+                    got: self.dim,
+                })
+                .context(format!(
                     "SQLite collection `{collection}` has dimension {existing} but the \
                      embedder produces {}. The embedder or model changed; drop \
                      the collection and re-index.",
                     self.dim
-                );
+                )));
             }
             Some((id, _)) => id,
             None => {
@@ -174,12 +178,16 @@ impl VectorStore for VecSqlite {
         };
         for row in rows {
             if row.vector.len() != self.dim {
-                anyhow::bail!(
+                return Err(anyhow::Error::new(DimensionMismatch {
+                    expected: self.dim,
+                    got: row.vector.len(),
+                })
+                .context(format!(
                     "Chunk of `{}` has dimension {} but the store is at {}",
                     row.chunk.doc_path,
                     row.vector.len(),
                     self.dim
-                );
+                )));
             }
             sqlx::query(
                 "INSERT INTO chunks (collection_id, path, hash, title, date, text, embedding)
@@ -269,11 +277,11 @@ impl VectorStore for VecSqlite {
             return Ok(Vec::new());
         };
         if vector.len() != self.dim {
-            anyhow::bail!(
-                "Query vector has dimension {} but the store is at {}",
-                vector.len(),
-                self.dim
-            );
+            return Err(DimensionMismatch {
+                expected: self.dim,
+                got: vector.len(),
+            }
+            .into());
         }
         // Same trick as at write time: a normalized query makes the score the
         // cosine similarity, matching the Qdrant backend's semantics.
@@ -506,6 +514,12 @@ mod tests {
         conformance::fingerprint_slot_never_appears_as_a_collection(&s).await;
     }
 
+    #[tokio::test]
+    async fn conformance_wrong_width() {
+        let (_dir, s) = store().await;
+        conformance::wrong_width_vectors_are_a_dimension_mismatch(&s, "v").await;
+    }
+
     #[tokio::test]
     async fn dimension_mismatch_fails_loudly() {
         let dir = tempfile::tempdir().unwrap();
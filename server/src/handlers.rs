@@ -1,7 +1,7 @@
 use axum::{
     Json,
     extract::State,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header},
     response::{IntoResponse, Response},
 };
 use serde::{Deserialize, Serialize};
@@ -42,11 +42,17 @@ pub struct QueryRequest {
     /// score shape on both surfaces and ignore it (adr/0029).
     #[serde(default)]
     pub context_size: Option<ContextSize>,
+    /// An exact result count; wins over `context_size` when both are set.
+    #[serde(default)]
+    pub top_k: Option<usize>,
 }
 
-/// Result count: the per-request `context_size` override, or the server default.
-fn resolve_top_k(context_size: Option<ContextSize>, default: usize) -> usize {
-    context_size.map(|c| c.to_top_k()).unwrap_or(default)
+/// Result count: the per-request `top_k` or `context_size` override, or the
+/// server default.
+fn resolve_top_k(context_size: Option<ContextSize>, top_k: Option<usize>, default: usize) -> usize {
+    top_k
+        .or(context_size.map(|c| c.to_top_k()))
+        .unwrap_or(default)
 }
 
 #[derive(Debug, Deserialize, Clone, Copy)]
@@ -84,6 +90,8 @@ pub struct AnswerRequest {
     #[serde(default)]
     pub context_size: Option<ContextSize>,
     #[serde(default)]
+    pub top_k: Option<usize>,
+    #[serde(default)]
     pub history: Vec<HistoryTurn>,
 }
 
@@ -145,6 +153,8 @@ impl ChunkResult {
 pub struct AnswerResponse {
     pub answer: String,
     pub sources: Vec<ChunkResult>,
+    /// The model that wrote `answer`.
+    pub model: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -167,17 +177,31 @@ impl IntoResponse for RagError {
     fn into_response(self) -> Response {
         let status = match &self {
             RagError::Validation(_) => StatusCode::BAD_REQUEST,
-            RagError::NotFound(_) => StatusCode::NOT_FOUND,
+            RagError::NotFound(_) | RagError::Empty => StatusCode::NOT_FOUND,
+            RagError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
             RagError::SemanticOnly | RagError::Unconfigured => StatusCode::SERVICE_UNAVAILABLE,
-            RagError::Backend(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RagError::Embedding(_)
+            | RagError::Llm(_)
+            | RagError::Storage(_)
+            | RagError::DimensionMismatch { .. }
+            | RagError::Backend(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
-        (
+        let mut response = (
             status,
             Json(ErrorResponse {
                 error: self.to_string(),
             }),
         )
-            .into_response()
+            .into_response();
+        if let RagError::RateLimited {
+            retry_after: Some(delay),
+        } = &self
+        {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(delay.as_secs()));
+        }
+        response
     }
 }
 
@@ -253,7 +277,11 @@ pub async fn get_embeddings_handler(
     Json(request): Json<QueryRequest>,
 ) -> Result<Json<EmbeddingsResponse>, RagError> {
     let collection = CollectionKey::parse(&request.vault_id)?;
-    let top_k = resolve_top_k(request.context_size, state.config.reranker.top_k);
+    let top_k = resolve_top_k(
+        request.context_size,
+        request.top_k,
+        state.config.reranker.top_k,
+    );
 
     let started = std::time::Instant::now();
     let results = state
@@ -292,7 +320,11 @@ pub async fn answer_handler(
         return Err(RagError::SemanticOnly);
     }
     let job_id = Uuid::new_v4();
-    let top_k = resolve_top_k(request.context_size, state.config.reranker.top_k);
+    let top_k = resolve_top_k(
+        request.context_size,
+        request.top_k,
+        state.config.reranker.top_k,
+    );
     let history: Vec<(String, String)> = request
         .history
         .into_iter()
@@ -333,6 +365,7 @@ pub async fn answer_handler(
                 let result = serde_json::json!({
                     "answer": answer.text,
                     "sources": sources,
+                    "model": answer.model,
                 })
                 .to_string();
                 state_clone
@@ -357,6 +390,67 @@ pub async fn answer_handler(
     }))
 }
 
+/// [`answer_handler`] streamed: the answer is generated in-request and sent
+/// as it arrives, newline-delimited JSON. The first line carries the final
+/// `sources` and `model`, each later one a `text` piece; a generation failure
+/// past that point ends the body with an `error` line, since the status has
+/// already gone out. Failures before the first line (no LLM, empty
+/// collection, retrieval) are plain error responses, as from `/api/answer`.
+pub async fn answer_stream_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AnswerRequest>,
+) -> Result<Response, RagError> {
+    use futures::StreamExt;
+
+    let collection = CollectionKey::parse(&request.vault_id)?;
+    let rag = state.rag()?.clone();
+    if !rag.can_answer() {
+        return Err(RagError::SemanticOnly);
+    }
+    let top_k = resolve_top_k(
+        request.context_size,
+        request.top_k,
+        state.config.reranker.top_k,
+    );
+    let history: Vec<(String, String)> = request
+        .history
+        .into_iter()
+        .map(|t| (t.question, t.answer))
+        .collect();
+
+    let answer = rag
+        .answer_stream(&collection, &request.query, &history, top_k)
+        .await?;
+    let sources: Vec<ChunkResult> = answer
+        .sources
+        .into_iter()
+        .map(|(ordinal, scored)| ChunkResult::from_scored(ordinal, scored))
+        .collect();
+    let head = serde_json::json!({ "sources": sources, "model": answer.model });
+    let text = answer.text.scan(false, |failed, item| {
+        // Nothing follows an error line.
+        if *failed {
+            return futures::future::ready(None);
+        }
+        let line = match item {
+            Ok(text) => serde_json::json!({ "text": text }),
+            Err(e) => {
+                *failed = true;
+                serde_json::json!({ "error": e.to_string() })
+            }
+        };
+        futures::future::ready(Some(line))
+    });
+    let lines = futures::stream::once(futures::future::ready(head))
+        .chain(text)
+        .map(|line| Ok::<_, std::convert::Infallible>(format!("{line}\n")));
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        axum::body::Body::from_stream(lines),
+    )
+        .into_response())
+}
+
 /// Delete notes by path from a vault's collection (used by the client when a
 /// note is removed).
 pub async fn index_delete_handler(
@@ -465,6 +559,17 @@ mod tests {
         assert!(r.history.is_empty());
     }
 
+    #[test]
+    fn exact_top_k_wins_over_context_size() {
+        let r: AnswerRequest = serde_json::from_str(
+            r#"{"vault_id":"v1","query":"q","context_size":"large","top_k":3}"#,
+        )
+        .unwrap();
+        assert_eq!(resolve_top_k(r.context_size, r.top_k, 20), 3);
+        assert_eq!(resolve_top_k(r.context_size, None, 20), 40);
+        assert_eq!(resolve_top_k(None, None, 20), 20);
+    }
+
     #[test]
     fn answer_request_parses_history_pairs() {
         let r: AnswerRequest = serde_json::from_str(
@@ -477,7 +582,8 @@ mod tests {
     }
 
     /// The status table is the wire contract (the client keys on codes, never on
-    /// message text): Validation→400, NotFound→404, SemanticOnly→503, Backend→500.
+    /// message text): Validation→400, NotFound/Empty→404, RateLimited→429,
+    /// SemanticOnly→503, backend failures→500.
     #[test]
     fn rag_error_maps_to_the_contracted_status_codes() {
         let cases = [
@@ -485,6 +591,22 @@ mod tests {
             (RagError::NotFound("gone".into()), StatusCode::NOT_FOUND),
             (RagError::SemanticOnly, StatusCode::SERVICE_UNAVAILABLE),
             (RagError::Unconfigured, StatusCode::SERVICE_UNAVAILABLE),
+            (RagError::Empty, StatusCode::NOT_FOUND),
+            (
+                RagError::RateLimited { retry_after: None },
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (
+                RagError::Storage(anyhow::anyhow!("disk")),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                RagError::DimensionMismatch {
+                    expected: 1024,
+                    got: 768,
+                },
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
             (
                 RagError::Backend(anyhow::anyhow!("boom")),
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -505,6 +627,16 @@ mod tests {
         assert_eq!(json["error"], "vault_id must be non-empty");
     }
 
+    #[test]
+    fn rate_limited_carries_retry_after_header() {
+        let resp = RagError::RateLimited {
+            retry_after: Some(std::time::Duration::from_secs(30)),
+        }
+        .into_response();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "30");
+    }
+
     #[tokio::test]
     async fn data_handlers_reject_unconfigured_with_503() {
         // An unconfigured server has no pipeline: every data endpoint rejects
@@ -520,6 +652,7 @@ mod tests {
                 vault_id: "vault-1".into(),
                 query: "q".into(),
                 context_size: None,
+                top_k: None,
             }),
         )
         .await
@@ -547,6 +680,7 @@ mod tests {
                 vault_id: "vault-1".into(),
                 query: "q".into(),
                 context_size: None,
+                top_k: None,
                 history: vec![],
             }),
         )
@@ -554,6 +688,20 @@ mod tests {
         .expect_err("unconfigured must reject answering");
         assert!(matches!(err, RagError::Unconfigured));
 
+        let err = answer_stream_handler(
+            State(state.clone()),
+            Json(AnswerRequest {
+                vault_id: "vault-1".into(),
+                query: "q".into(),
+                context_size: None,
+                top_k: None,
+                history: vec![],
+            }),
+        )
+        .await
+        .expect_err("unconfigured must reject streamed answering");
+        assert!(matches!(err, RagError::Unconfigured));
+
         let err = index_delete_handler(
             State(state.clone()),
             Json(DeleteRequest {
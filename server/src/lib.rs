@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use dbembeddings::embedder::Embedder;
-use dbembeddings::{CollectionInfo, EmbeddedChunk, VectorStore};
+use dbembeddings::{CollectionInfo, DimensionMismatch, EmbeddedChunk, VectorStore};
+use futures::{Stream, StreamExt, TryStreamExt};
 use llmclients::LLMClient;
 use log::debug;
 
@@ -80,6 +83,17 @@ impl Display for CollectionKey {
 pub struct Answer {
     pub text: String,
     pub sources: Vec<(usize, ScoredChunk)>,
+    /// The LLM's [`model_id`](LLMClient::model_id).
+    pub model: String,
+}
+
+/// [`Answer`] with its text still arriving: `text` yields the answer in
+/// order, piece by piece; `sources` are complete up front.
+pub struct AnswerStream {
+    pub text: Pin<Box<dyn Stream<Item = Result<String, RagError>> + Send>>,
+    pub sources: Vec<(usize, ScoredChunk)>,
+    /// The LLM's [`model_id`](LLMClient::model_id).
+    pub model: String,
 }
 
 /// Preview of where the **context cut** slices an answer's LLM context on a
@@ -102,8 +116,9 @@ pub struct CutPreview {
 /// The server's domain error: every way a request can fail, each variant
 /// carrying its meaning rather than a status code. The HTTP mapping lives in
 /// one place (`IntoResponse` in the handlers module): `Validation` → 400,
-/// `NotFound` → 404, `SemanticOnly`/`Unconfigured` → 503 (adr/0022, adr/0024),
-/// `Backend` → 500.
+/// `NotFound`/`Empty` → 404, `RateLimited` → 429 (with `Retry-After`),
+/// `SemanticOnly`/`Unconfigured` → 503 (adr/0022, adr/0024), and the backend
+/// failures (`Embedding`, `Llm`, `Storage`, `Backend`) → 500.
 #[derive(Debug, thiserror::Error)]
 pub enum RagError {
     /// The request itself is malformed (bad vault id, unparseable job id).
@@ -124,6 +139,33 @@ pub enum RagError {
         "no embedder configured; this server is unconfigured — open the web UI to configure one"
     )]
     Unconfigured,
+    /// The embedder failed to produce vectors (model error, endpoint down).
+    #[error("embedder error: {0}")]
+    Embedding(anyhow::Error),
+    /// The LLM call failed for a reason other than rate limiting.
+    #[error("LLM error: {0}")]
+    Llm(anyhow::Error),
+    /// The vector store failed (SQLite/Qdrant unreachable, corrupt rows).
+    #[error("vector store error: {0}")]
+    Storage(anyhow::Error),
+    /// An upstream provider (LLM or HTTP embedder) throttled the request.
+    /// `retry_after` carries the provider's `Retry-After` delay when it sent
+    /// one, so a caller can back off and retry instead of giving up.
+    #[error("rate limited by the provider{}", retry_hint(.retry_after))]
+    RateLimited { retry_after: Option<Duration> },
+    /// A vector's width disagrees with what it is checked against: the
+    /// embedder returned vectors of another size than it declares, or the
+    /// store's data was written by an embedder of another width. Either way
+    /// the embedder or its model changed under the index; fix the config or
+    /// re-index.
+    #[error(
+        "embedding dimension mismatch: expected {expected}, got {got}; the embedder or its model changed — re-index the vault"
+    )]
+    DimensionMismatch { expected: usize, got: usize },
+    /// The vault has nothing indexed to answer from — distinct from a failure,
+    /// so a caller can report "index first" instead of an error.
+    #[error("nothing indexed for this vault")]
+    Empty,
     /// Decided boundary: `anyhow` is the server's *internal* error currency
     /// (stores, embedders, rerankers, config); `RagError` is the public
     /// surface, and this variant is the one funnel between them. The `#[from]`
@@ -133,6 +175,89 @@ pub enum RagError {
     Backend(#[from] anyhow::Error),
 }
 
+impl RagError {
+    /// Funnels an embedder failure: a provider throttle becomes
+    /// [`RateLimited`](Self::RateLimited), anything else
+    /// [`Embedding`](Self::Embedding).
+    fn embedding(e: anyhow::Error) -> Self {
+        Self::rate_limited_or(e, Self::Embedding)
+    }
+
+    /// Funnels an LLM failure, same split as [`Self::embedding`].
+    fn llm(e: anyhow::Error) -> Self {
+        Self::rate_limited_or(e, Self::Llm)
+    }
+
+    /// Funnels a vector-store failure: a width disagreement becomes
+    /// [`DimensionMismatch`](Self::DimensionMismatch), anything else
+    /// [`Storage`](Self::Storage).
+    fn storage(e: anyhow::Error) -> Self {
+        match e.downcast_ref::<DimensionMismatch>() {
+            Some(m) => Self::DimensionMismatch {
+                expected: m.expected,
+                got: m.got,
+            },
+            None => Self::Storage(e),
+        }
+    }
+
+    /// Checks a vector the embedder produced against the width it declares,
+    /// before it reaches the store.
+    fn check_dimension(expected: usize, vector: &[f32]) -> Result<(), Self> {
+        match vector.len() {
+            got if got == expected => Ok(()),
+            got => Err(Self::DimensionMismatch { expected, got }),
+        }
+    }
+
+    fn rate_limited_or(e: anyhow::Error, other: fn(anyhow::Error) -> Self) -> Self {
+        match e.downcast_ref::<ProviderRateLimited>() {
+            Some(limited) => Self::RateLimited {
+                retry_after: limited.retry_after,
+            },
+            None => other(e),
+        }
+    }
+}
+
+fn retry_hint(retry_after: &Option<Duration>) -> String {
+    retry_after
+        .map(|d| format!("; retry after {}s", d.as_secs()))
+        .unwrap_or_default()
+}
+
+/// The typed marker an HTTP backend (LLM client or embedder) puts inside its
+/// `anyhow::Error` when the provider answers `429 Too Many Requests`. Internals
+/// keep speaking `anyhow`; the pipeline downcasts this one case into
+/// [`RagError::RateLimited`] at the public boundary.
+#[derive(Debug, thiserror::Error)]
+#[error("{provider} rate limited the request")]
+pub(crate) struct ProviderRateLimited {
+    pub(crate) provider: String,
+    pub(crate) retry_after: Option<Duration>,
+}
+
+impl ProviderRateLimited {
+    /// `Some` when `response` is a 429. Only the delta-seconds form of
+    /// `Retry-After` is read; an HTTP-date (rare from API providers) leaves
+    /// the delay unknown.
+    pub(crate) fn from_response(provider: &str, response: &reqwest::Response) -> Option<Self> {
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return None;
+        }
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+        Some(Self {
+            provider: provider.to_string(),
+            retry_after,
+        })
+    }
+}
+
 // ── Pipeline policy ─────────────────────────────────────────────────────────
 // The one home of the retrieval/indexing numbers. Stores never see these.
 
@@ -143,8 +268,19 @@ const CHUNK_TARGET: usize = 800;
 const CHUNK_MAX: usize = 1536;
 /// Chunks embedded (and stored) per batch during indexing.
 const EMBED_BATCH: usize = 100;
-/// Candidate pool pulled from the vector store per query, before dedup and
-/// reranking cut it down.
+/// Default cap on embedding batches in flight at once during indexing (config
+/// `embed_concurrency`). Small on purpose: hosted embedders rate-limit early.
+const EMBED_CONCURRENCY: usize = 2;
+/// Retries of a batch the embedder rate-limited before the index pass fails
+/// with [`RagError::RateLimited`].
+const EMBED_RETRIES: u32 = 4;
+/// Wait before the first retry of a rate-limited batch when the provider sends
+/// no `Retry-After`; doubles on every retry.
+const EMBED_BACKOFF: Duration = Duration::from_millis(500);
+/// Longest single wait before a retry, a provider's `Retry-After` included.
+const EMBED_BACKOFF_MAX: Duration = Duration::from_secs(60);
+/// Default candidate pool pulled from the vector store per query, before dedup
+/// and reranking cut it down (config `candidate_pool`).
 const CANDIDATE_POOL: usize = 80;
 /// The score-range cut's default cutoff (config `score_range_cutoff`): a
 /// chunk survives when its min-max-normalized score —
@@ -200,6 +336,12 @@ pub struct KimunRag {
     /// `drop_window_min`/`drop_window_max`, defaults [`DROP_WINDOW_MIN`] and
     /// [`DROP_WINDOW_MAX`]).
     drop_window: (usize, usize),
+    /// How many chunks one query pulls from the store (config
+    /// `candidate_pool`, default [`CANDIDATE_POOL`]).
+    candidate_pool: usize,
+    /// How many embedding batches an index pass keeps in flight (config
+    /// `embed_concurrency`, default [`EMBED_CONCURRENCY`]).
+    embed_concurrency: usize,
     /// One-shot marker for the "reranker scores outside 0..1" warning, so a
     /// misconfigured backend logs once per run instead of per query.
     score_scale_warned: std::sync::atomic::AtomicBool,
@@ -229,6 +371,8 @@ impl KimunRag {
             context_cut: ContextCut::default(),
             score_range_cutoff: SCORE_RANGE_DEFAULT_CUTOFF,
             drop_window: (DROP_WINDOW_MIN, DROP_WINDOW_MAX),
+            candidate_pool: CANDIDATE_POOL,
+            embed_concurrency: EMBED_CONCURRENCY,
             score_scale_warned: std::sync::atomic::AtomicBool::new(false),
             expected_fingerprint: None,
             fingerprint_checked: tokio::sync::OnceCell::new(),
@@ -270,6 +414,24 @@ impl KimunRag {
         self
     }
 
+    /// Tune how many chunks a query pulls from the vector store before dedup,
+    /// reranking and the context cut (default [`CANDIDATE_POOL`]). A bigger
+    /// pool gives the reranker and the adaptive cuts more to work with at the
+    /// cost of latency; floored to 1 so a zero cannot empty every query.
+    pub fn with_candidate_pool(mut self, candidate_pool: usize) -> Self {
+        self.candidate_pool = candidate_pool.max(1);
+        self
+    }
+
+    /// Bound how many embedding batches an index pass sends at once (default
+    /// [`EMBED_CONCURRENCY`]). Higher is faster against an embedder with
+    /// headroom and trips a hosted provider's rate limit sooner; floored to 1,
+    /// which embeds one batch at a time.
+    pub fn with_embed_concurrency(mut self, embed_concurrency: usize) -> Self {
+        self.embed_concurrency = embed_concurrency.max(1);
+        self
+    }
+
     /// Attach a reranker (built by [`reranker::from_config`]). The caller owns
     /// the failure policy: reranker initialization is non-fatal at the server
     /// level — on error nothing is attached and results fall back to plain
@@ -308,7 +470,9 @@ impl KimunRag {
         };
         self.fingerprint_checked
             .get_or_try_init(|| async {
-                let wiped = enforce_embedder_fingerprint(self.store.as_ref(), expected).await?;
+                let wiped = enforce_embedder_fingerprint(self.store.as_ref(), expected)
+                    .await
+                    .map_err(RagError::storage)?;
                 if wiped {
                     log::warn!(
                         "Embedder changed (fingerprint now `{expected}`): wiped ALL stored \
@@ -398,7 +562,8 @@ impl KimunRag {
     /// reranker is active, vector scores otherwise (adr/0029); `top_k` only
     /// applies under the `fixed` cut. Fails with [`RagError::SemanticOnly`]
     /// when no LLM is configured; the gate runs before the vector search so no
-    /// work is thrown away.
+    /// work is thrown away. Fails with [`RagError::Empty`] when the vault has
+    /// nothing indexed.
     ///
     /// `history` (prior question/answer pairs) does two jobs. It is forwarded
     /// verbatim to the LLM call, and — since a terse follow-up ("yes",
@@ -415,11 +580,65 @@ impl KimunRag {
         history: &[(String, String)],
         top_k: usize,
     ) -> Result<Answer, RagError> {
+        let (llm, numbered) = self
+            .answer_context(collection, question, history, top_k)
+            .await?;
+        let text = llm
+            .ask(question, history, &numbered)
+            .await
+            .map_err(RagError::llm)?;
+        Ok(Answer {
+            text,
+            sources: numbered,
+            model: llm.model_id().to_string(),
+        })
+    }
+
+    /// [`Self::answer`] with the LLM's text streamed as it is generated. The
+    /// sources are final before the first token — retrieval and the context
+    /// cut run up front, with the same gates and errors as `answer` — so a
+    /// caller can show them while the text is still arriving.
+    pub async fn answer_stream(
+        &self,
+        collection: &CollectionKey,
+        question: &str,
+        history: &[(String, String)],
+        top_k: usize,
+    ) -> Result<AnswerStream, RagError> {
+        let (llm, numbered) = self
+            .answer_context(collection, question, history, top_k)
+            .await?;
+        let tokens = llm
+            .ask_stream(question, history, &numbered)
+            .await
+            .map_err(RagError::llm)?;
+        Ok(AnswerStream {
+            text: Box::pin(tokens.map(|item| item.map_err(RagError::llm))),
+            sources: numbered,
+            model: llm.model_id().to_string(),
+        })
+    }
+
+    /// The shared front half of `answer` and `answer_stream`: the LLM gate,
+    /// then retrieval, ranking and the context cut, numbered for citation.
+    async fn answer_context(
+        &self,
+        collection: &CollectionKey,
+        question: &str,
+        history: &[(String, String)],
+        top_k: usize,
+    ) -> Result<(Arc<dyn LLMClient + Send + Sync>, Vec<(usize, ScoredChunk)>), RagError> {
         let llm = self.llm_client.clone().ok_or(RagError::SemanticOnly)?;
         // Retrieval/rerank see the conversation-conditioned query, NOT the bare
         // question — so a terse follow-up still retrieves on-topic context.
         let query = build_retrieval_query(question, history);
         let raw = self.retrieve(collection, &query).await?;
+        // An empty pool means an empty collection: the vector query returns
+        // the nearest chunks whatever their distance. Asking the LLM with no
+        // context would only produce a refusal, so say so instead.
+        if raw.is_empty() {
+            return Err(RagError::Empty);
+        }
         let mut context = self.rank(&query, raw).await;
         let cut = self.cut_len(&context, top_k);
         context.truncate(cut);
@@ -433,11 +652,7 @@ impl KimunRag {
             .enumerate()
             .map(|(i, chunk)| (i + 1, chunk))
             .collect();
-        let text = llm.ask(question, history, &numbered).await?;
-        Ok(Answer {
-            text,
-            sources: numbered,
-        })
+        Ok((llm, numbered))
     }
 
     /// Ranks the retrieved pool: the full pool through the reranker when one
@@ -518,11 +733,17 @@ impl KimunRag {
         query: &str,
     ) -> Result<Vec<ScoredChunk>, RagError> {
         self.check_fingerprint().await?;
-        let vector = self.embedder.prompt_embedding(query).await?;
+        let vector = self
+            .embedder
+            .prompt_embedding(query)
+            .await
+            .map_err(RagError::embedding)?;
+        RagError::check_dimension(self.embedder.dimension(), &vector)?;
         let raw = self
             .store
-            .query(collection.as_str(), vector, CANDIDATE_POOL)
-            .await?;
+            .query(collection.as_str(), vector, self.candidate_pool)
+            .await
+            .map_err(RagError::storage)?;
         Ok(deduplicate_chunks(raw))
     }
 
@@ -536,7 +757,11 @@ impl KimunRag {
         docs: &[KimunDoc],
     ) -> Result<IndexStats, RagError> {
         self.check_fingerprint().await?;
-        let indexed_notes = self.store.indexed_notes(collection.as_str()).await?;
+        let indexed_notes = self
+            .store
+            .indexed_notes(collection.as_str())
+            .await
+            .map_err(RagError::storage)?;
         debug!(
             "Indexing {} docs against {} already indexed",
             docs.len(),
@@ -582,12 +807,16 @@ impl KimunRag {
             for row in self
                 .store
                 .chunks_with_vectors(collection.as_str(), &stale_paths)
-                .await?
+                .await
+                .map_err(RagError::storage)?
             {
                 let EmbeddedChunk { chunk, vector } = row;
                 vector_cache.insert(cache_key(&chunk.title, &chunk.text), vector);
             }
-            self.store.delete(collection.as_str(), &stale_paths).await?;
+            self.store
+                .delete(collection.as_str(), &stale_paths)
+                .await
+                .map_err(RagError::storage)?;
         }
 
         // Sub-split sections to the embedding window, then embed each
@@ -609,35 +838,19 @@ impl KimunRag {
             debug!("Reusing {} stored vectors for unchanged chunks", rows.len());
         }
 
-        // Embed EVERYTHING before storing ANYTHING. The old rows are already
-        // deleted, so a note must end this pass either fully present at its
-        // new hash or wholly absent: absent notes drop out of /hashes and the
-        // client's reconcile re-pushes them (self-healing), while a partial
-        // store at the new hash would read as complete (hash == hash) and the
-        // missing chunks would never be repaired.
-        for batch in to_embed.chunks(EMBED_BATCH) {
-            let embeddings = self.embedder.generate_embeddings(batch).await?;
-            if embeddings.len() != batch.len() {
-                return Err(RagError::Backend(anyhow::anyhow!(
-                    "embedder returned {} vectors for {} chunks",
-                    embeddings.len(),
-                    batch.len()
-                )));
-            }
-            rows.extend(
-                batch
-                    .iter()
-                    .zip(embeddings)
-                    .map(|(chunk, vector)| EmbeddedChunk {
-                        chunk: chunk.clone(),
-                        vector,
-                    }),
-            );
+        // Store each note once its last chunk has a vector, in one store()
+        // call — atomic in both backends (one SQLite tx / one Qdrant upsert).
+        // The old rows are already deleted, so a note must end this pass
+        // either fully present at its new hash or wholly absent: absent notes
+        // drop out of /hashes and the client's reconcile re-pushes them
+        // (self-healing), while a partial store at the new hash would read as
+        // complete (hash == hash) and the missing chunks would never be
+        // repaired. A failed batch ends the pass with the notes it touched
+        // still absent.
+        let mut waiting: HashMap<String, usize> = HashMap::new();
+        for chunk in &to_embed {
+            *waiting.entry(chunk.doc_path.clone()).or_default() += 1;
         }
-
-        // Store per note, and each store() call is atomic in both backends
-        // (one SQLite tx / one Qdrant upsert) — so a mid-loop failure leaves
-        // every note either complete or absent, never partial.
         let mut by_path: HashMap<String, Vec<EmbeddedChunk>> = HashMap::new();
         for row in rows {
             by_path
@@ -645,13 +858,111 @@ impl KimunRag {
                 .or_default()
                 .push(row);
         }
-        for note_rows in by_path.into_values() {
-            self.store.store(collection.as_str(), &note_rows).await?;
+        // Notes that reused a stored vector for every chunk are ready now.
+        let ready: Vec<String> = by_path
+            .keys()
+            .filter(|path| !waiting.contains_key(*path))
+            .cloned()
+            .collect();
+        for path in ready {
+            let note_rows = by_path.remove(&path).unwrap_or_default();
+            self.store
+                .store(collection.as_str(), &note_rows)
+                .await
+                .map_err(RagError::storage)?;
+        }
+
+        // Batches run concurrently, at most `embed_concurrency` in flight, and
+        // are stored in batch order as they come back, so a large pass never
+        // holds more than a window of vectors. A note's chunks are adjacent,
+        // so it is stored with the batch holding its last chunk. (The batch
+        // futures are built up front rather than in a `map` closure, which
+        // trips a higher-ranked lifetime error once the pass is spawned.)
+        let batches: Vec<_> = to_embed
+            .chunks(EMBED_BATCH)
+            .map(|batch| async move {
+                let embeddings = self.embed_with_backoff(batch).await?;
+                Ok::<_, RagError>((batch, embeddings))
+            })
+            .collect();
+        let mut embedded = futures::stream::iter(batches).buffered(self.embed_concurrency);
+        while let Some((batch, embeddings)) = embedded.try_next().await? {
+            if embeddings.len() != batch.len() {
+                return Err(RagError::Embedding(anyhow::anyhow!(
+                    "embedder returned {} vectors for {} chunks",
+                    embeddings.len(),
+                    batch.len()
+                )));
+            }
+            for vector in &embeddings {
+                RagError::check_dimension(self.embedder.dimension(), vector)?;
+            }
+            for (chunk, vector) in batch.iter().zip(embeddings) {
+                let path = &chunk.doc_path;
+                by_path
+                    .entry(path.clone())
+                    .or_default()
+                    .push(EmbeddedChunk {
+                        chunk: chunk.clone(),
+                        vector,
+                    });
+                let left = waiting
+                    .get_mut(path)
+                    .expect("every chunk to embed is counted");
+                *left -= 1;
+                if *left == 0 {
+                    waiting.remove(path);
+                    let note_rows = by_path.remove(path).unwrap_or_default();
+                    self.store
+                        .store(collection.as_str(), &note_rows)
+                        .await
+                        .map_err(RagError::storage)?;
+                }
+            }
         }
 
         Ok(stats)
     }
 
+    /// Embeds one indexing batch, retrying up to [`EMBED_RETRIES`] times when
+    /// the provider rate-limits it: each retry waits the provider's
+    /// `Retry-After`, or else an exponential backoff from [`EMBED_BACKOFF`],
+    /// capped at [`EMBED_BACKOFF_MAX`]. The batch keeps its concurrency permit
+    /// while it waits, so a throttled pass slows down instead of piling on.
+    async fn embed_with_backoff(
+        &self,
+        batch: &[FlattenedChunk],
+    ) -> Result<Vec<Vec<f32>>, RagError> {
+        let mut backoff = EMBED_BACKOFF;
+        let mut retries = 0;
+        loop {
+            let e = match self.embedder.generate_embeddings(batch).await {
+                Ok(embeddings) => return Ok(embeddings),
+                Err(e) => e,
+            };
+            let Some(limited) = e.downcast_ref::<ProviderRateLimited>() else {
+                return Err(RagError::embedding(e));
+            };
+            if retries == EMBED_RETRIES {
+                return Err(RagError::embedding(e));
+            }
+            let wait = limited
+                .retry_after
+                .unwrap_or(backoff)
+                .min(EMBED_BACKOFF_MAX);
+            log::warn!(
+                "Embedder rate limited a batch of {} chunks; retrying in {}ms ({}/{})",
+                batch.len(),
+                wait.as_millis(),
+                retries + 1,
+                EMBED_RETRIES
+            );
+            tokio::time::sleep(wait).await;
+            backoff = (backoff * 2).min(EMBED_BACKOFF_MAX);
+            retries += 1;
+        }
+    }
+
     /// Remove notes (all their chunks) from a vault's collection.
     pub async fn delete_notes(
         &self,
@@ -659,8 +970,10 @@ impl KimunRag {
         paths: &[String],
     ) -> Result<(), RagError> {
         self.check_fingerprint().await?;
-        self.store.delete(collection.as_str(), paths).await?;
-        Ok(())
+        self.store
+            .delete(collection.as_str(), paths)
+            .await
+            .map_err(RagError::storage)
     }
 
     /// Reconcile support: the `{note path → content hash}` set the server holds
@@ -670,7 +983,11 @@ impl KimunRag {
         collection: &CollectionKey,
     ) -> Result<HashMap<String, String>, RagError> {
         self.check_fingerprint().await?;
-        let notes = self.store.indexed_notes(collection.as_str()).await?;
+        let notes = self
+            .store
+            .indexed_notes(collection.as_str())
+            .await
+            .map_err(RagError::storage)?;
         Ok(notes
             .into_iter()
             .map(|(path, note)| (path, note.content_hash))
@@ -680,13 +997,19 @@ impl KimunRag {
     /// Every collection with its indexed-note count (admin UI).
     pub async fn collections(&self) -> Result<Vec<CollectionInfo>, RagError> {
         self.check_fingerprint().await?;
-        Ok(self.store.list_collections().await?)
+        self.store
+            .list_collections()
+            .await
+            .map_err(RagError::storage)
     }
 
     /// Just the collection names (vault ids).
     pub async fn collection_names(&self) -> Result<Vec<String>, RagError> {
         self.check_fingerprint().await?;
-        Ok(self.store.collection_names().await?)
+        self.store
+            .collection_names()
+            .await
+            .map_err(RagError::storage)
     }
 }
 
@@ -742,7 +1065,7 @@ fn sanitize_scores(scored: Vec<(usize, f64)>) -> (Vec<(usize, f64)>, bool) {
 }
 
 /// The [`ContextCut::ScoreRange`] cut. No count cut at all — scores are
-/// min-max normalized within the (already [`CANDIDATE_POOL`]-capped) pool and
+/// min-max normalized within the (already candidate-pool-capped) pool and
 /// a chunk survives when its normalized score reaches `cutoff` (config
 /// `score_range_cutoff`, default [`SCORE_RANGE_DEFAULT_CUTOFF`]): the pool's own
 /// spread decides how many chunks
@@ -1118,6 +1441,10 @@ pub(crate) mod test_support {
 
     #[async_trait]
     impl LLMClient for FakeLlm {
+        fn model_id(&self) -> &str {
+            "fake-model"
+        }
+
         async fn ask(
             &self,
             _: &str,
@@ -1228,6 +1555,7 @@ mod tests {
         let rag = rag(section_heavy_store(), true);
         let answer = rag.answer(&key("vault-1"), "q", &[], 2).await.unwrap();
         assert_eq!(answer.text, "answer");
+        assert_eq!(answer.model, "fake-model");
         assert_eq!(answer.sources.len(), 3);
         assert_eq!(answer.sources[0].1.1.doc_path, "/a.md");
         assert_eq!(
@@ -1239,6 +1567,32 @@ mod tests {
         assert_eq!(ordinals, vec![1, 2, 3]);
     }
 
+    #[tokio::test]
+    async fn answer_stream_matches_answer_sources_and_text() {
+        use futures::StreamExt;
+        let rag = rag(section_heavy_store(), true);
+        let streamed = rag
+            .answer_stream(&key("vault-1"), "q", &[], 2)
+            .await
+            .unwrap();
+        let whole = rag.answer(&key("vault-1"), "q", &[], 2).await.unwrap();
+        let streamed_paths: Vec<&str> = streamed
+            .sources
+            .iter()
+            .map(|(_, (_, c))| c.doc_path.as_str())
+            .collect();
+        let whole_paths: Vec<&str> = whole
+            .sources
+            .iter()
+            .map(|(_, (_, c))| c.doc_path.as_str())
+            .collect();
+        assert_eq!(streamed_paths, whole_paths);
+        // FakeLlm has no streaming endpoint: the default impl yields it whole.
+        let pieces: Vec<String> = streamed.text.map(|t| t.unwrap()).collect().await;
+        assert_eq!(pieces.concat(), whole.text);
+        assert_eq!(streamed.model, whole.model);
+    }
+
     #[tokio::test]
     async fn answer_without_reranker_has_no_count_floor() {
         // A spiky top score trims the context to the genuinely relevant
@@ -1699,6 +2053,54 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn answer_on_an_empty_collection_is_typed_empty() {
+        let store = FakeVectorStore {
+            results: Vec::new(),
+            ..Default::default()
+        };
+        match rag(store, true).answer(&key("vault-1"), "q", &[], 5).await {
+            Err(RagError::Empty) => {}
+            other => panic!("expected Empty, got {:?}", other.map(|a| a.text)),
+        }
+    }
+
+    #[tokio::test]
+    async fn provider_throttle_surfaces_as_rate_limited() {
+        struct ThrottledEmbedder;
+        #[async_trait::async_trait]
+        impl Embedder for ThrottledEmbedder {
+            async fn generate_embeddings(
+                &self,
+                _: &[FlattenedChunk],
+            ) -> anyhow::Result<Vec<Vec<f32>>> {
+                anyhow::bail!("unused")
+            }
+            async fn prompt_embedding(&self, _: &str) -> anyhow::Result<Vec<f32>> {
+                Err(ProviderRateLimited {
+                    provider: "embedder".into(),
+                    retry_after: Some(Duration::from_secs(7)),
+                }
+                .into())
+            }
+            fn dimension(&self) -> usize {
+                8
+            }
+        }
+
+        let rag = KimunRag::new(
+            Arc::new(FakeVectorStore::default()),
+            Arc::new(ThrottledEmbedder),
+            None,
+        );
+        match rag.search(&key("vault-1"), "q", 5).await {
+            Err(RagError::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(7)))
+            }
+            other => panic!("expected RateLimited, got {:?}", other.map(|r| r.len())),
+        }
+    }
+
     // ── Conversation-conditioned retrieval query (terse follow-up fix) ────────
 
     #[test]
@@ -1824,6 +2226,10 @@ mod tests {
         struct RecordingLlm(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
         #[async_trait::async_trait]
         impl LLMClient for RecordingLlm {
+            fn model_id(&self) -> &str {
+                "recording-model"
+            }
+
             async fn ask(
                 &self,
                 question: &str,
@@ -2082,6 +2488,232 @@ mod tests {
         assert!(store.stored.lock().unwrap().is_empty());
     }
 
+    /// Declares 8 dimensions but produces 4 — a misconfigured `dimension`.
+    struct NarrowEmbedder;
+    #[async_trait::async_trait]
+    impl Embedder for NarrowEmbedder {
+        async fn generate_embeddings(
+            &self,
+            content: &[FlattenedChunk],
+        ) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(vec![vec![1.0; 4]; content.len()])
+        }
+        async fn prompt_embedding(&self, _: &str) -> anyhow::Result<Vec<f32>> {
+            Ok(vec![1.0; 4])
+        }
+        fn dimension(&self) -> usize {
+            8
+        }
+    }
+
+    #[tokio::test]
+    async fn embedder_width_disagreeing_with_its_dimension_is_a_mismatch() {
+        let store = Arc::new(FakeVectorStore::default());
+        let rag = KimunRag::new(store.clone(), Arc::new(NarrowEmbedder), None);
+
+        match rag.index(&key("v"), &docs(1)).await {
+            Err(RagError::DimensionMismatch { expected, got }) => {
+                assert_eq!((expected, got), (8, 4))
+            }
+            other => panic!("expected DimensionMismatch, got {:?}", other.map(|_| ())),
+        }
+        assert!(store.stored_rows.lock().unwrap().is_empty());
+
+        match rag.search(&key("v"), "q", 5).await {
+            Err(RagError::DimensionMismatch { expected, got }) => {
+                assert_eq!((expected, got), (8, 4))
+            }
+            other => panic!(
+                "expected DimensionMismatch, got {:?}",
+                other.map(|r| r.len())
+            ),
+        }
+    }
+
+    #[test]
+    fn store_dimension_marker_survives_context() {
+        let e = anyhow::Error::new(DimensionMismatch {
+            expected: 1024,
+            got: 768,
+        })
+        .context("collection `v` has dimension 1024");
+        match RagError::storage(e) {
+            RagError::DimensionMismatch { expected, got } => {
+                assert_eq!((expected, got), (1024, 768))
+            }
+            other => panic!("expected DimensionMismatch, got {other}"),
+        }
+        assert!(matches!(
+            RagError::storage(anyhow::anyhow!("disk")),
+            RagError::Storage(_)
+        ));
+    }
+
+    /// `n` one-section notes with distinct texts, so every chunk needs the
+    /// embedder.
+    fn docs(n: usize) -> Vec<KimunDoc> {
+        (0..n)
+            .map(|i| KimunDoc {
+                path: format!("{i}.md"),
+                hash: "h".to_string(),
+                sections: vec![KimunSection {
+                    title: "T".to_string(),
+                    text: format!("text {i}"),
+                }],
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn index_bounds_embedding_batches_in_flight() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Tracks how many batches are being embedded at once.
+        #[derive(Default)]
+        struct SlowEmbedder {
+            in_flight: AtomicUsize,
+            max_in_flight: AtomicUsize,
+        }
+        #[async_trait::async_trait]
+        impl Embedder for SlowEmbedder {
+            async fn generate_embeddings(
+                &self,
+                content: &[FlattenedChunk],
+            ) -> anyhow::Result<Vec<Vec<f32>>> {
+                let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(vec![vec![1.0; 8]; content.len()])
+            }
+            async fn prompt_embedding(&self, _: &str) -> anyhow::Result<Vec<f32>> {
+                Ok(vec![1.0; 8])
+            }
+            fn dimension(&self) -> usize {
+                8
+            }
+        }
+
+        let store = Arc::new(FakeVectorStore::default());
+        let embedder = Arc::new(SlowEmbedder::default());
+        let rag = KimunRag::new(store.clone(), embedder.clone(), None).with_embed_concurrency(2);
+
+        // Five batches' worth of chunks.
+        rag.index(&key("v"), &docs(EMBED_BATCH * 4 + 1))
+            .await
+            .unwrap();
+
+        assert_eq!(embedder.max_in_flight.load(Ordering::SeqCst), 2);
+        assert_eq!(store.stored_rows.lock().unwrap().len(), EMBED_BATCH * 4 + 1);
+    }
+
+    #[tokio::test]
+    async fn index_stores_notes_embedded_before_a_failed_batch() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Embeds the first batch, then fails.
+        #[derive(Default)]
+        struct FailsSecond {
+            calls: AtomicUsize,
+        }
+        #[async_trait::async_trait]
+        impl Embedder for FailsSecond {
+            async fn generate_embeddings(
+                &self,
+                content: &[FlattenedChunk],
+            ) -> anyhow::Result<Vec<Vec<f32>>> {
+                if self.calls.fetch_add(1, Ordering::SeqCst) > 0 {
+                    anyhow::bail!("embedder down");
+                }
+                Ok(vec![vec![1.0; 8]; content.len()])
+            }
+            async fn prompt_embedding(&self, _: &str) -> anyhow::Result<Vec<f32>> {
+                Ok(vec![1.0; 8])
+            }
+            fn dimension(&self) -> usize {
+                8
+            }
+        }
+
+        let store = Arc::new(FakeVectorStore::default());
+        let rag = KimunRag::new(store.clone(), Arc::new(FailsSecond::default()), None)
+            .with_embed_concurrency(1);
+
+        assert!(matches!(
+            rag.index(&key("v"), &docs(EMBED_BATCH + 1)).await,
+            Err(RagError::Embedding(_))
+        ));
+        // The first batch's notes landed whole; the last note is absent.
+        let stored = store.stored_rows.lock().unwrap();
+        assert_eq!(stored.len(), EMBED_BATCH);
+        assert!(
+            stored
+                .iter()
+                .all(|row| row.chunk.doc_path != format!("{EMBED_BATCH}.md"))
+        );
+    }
+
+    #[tokio::test]
+    async fn index_retries_a_rate_limited_batch() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Rate-limits the first `throttles` calls, then embeds.
+        struct ThrottleOnce {
+            calls: AtomicUsize,
+            throttles: usize,
+        }
+        #[async_trait::async_trait]
+        impl Embedder for ThrottleOnce {
+            async fn generate_embeddings(
+                &self,
+                content: &[FlattenedChunk],
+            ) -> anyhow::Result<Vec<Vec<f32>>> {
+                if self.calls.fetch_add(1, Ordering::SeqCst) < self.throttles {
+                    return Err(ProviderRateLimited {
+                        provider: "embedder".into(),
+                        retry_after: Some(Duration::ZERO),
+                    }
+                    .into());
+                }
+                Ok(vec![vec![1.0; 8]; content.len()])
+            }
+            async fn prompt_embedding(&self, _: &str) -> anyhow::Result<Vec<f32>> {
+                Ok(vec![1.0; 8])
+            }
+            fn dimension(&self) -> usize {
+                8
+            }
+        }
+
+        let store = Arc::new(FakeVectorStore::default());
+        let rag = KimunRag::new(
+            store.clone(),
+            Arc::new(ThrottleOnce {
+                calls: AtomicUsize::new(0),
+                throttles: 1,
+            }),
+            None,
+        );
+        rag.index(&key("v"), &docs(3)).await.unwrap();
+        assert_eq!(store.stored_rows.lock().unwrap().len(), 3);
+
+        // A provider that never lets up fails the pass once retries run out.
+        let store = Arc::new(FakeVectorStore::default());
+        let rag = KimunRag::new(
+            store.clone(),
+            Arc::new(ThrottleOnce {
+                calls: AtomicUsize::new(0),
+                throttles: usize::MAX,
+            }),
+            None,
+        );
+        match rag.index(&key("v"), &docs(3)).await {
+            Err(RagError::RateLimited { .. }) => {}
+            other => panic!("expected RateLimited, got {:?}", other.map(|_| ())),
+        }
+        assert!(store.stored_rows.lock().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn fingerprint_fresh_store_records_without_wiping() {
         let store = FakeVectorStore::default();
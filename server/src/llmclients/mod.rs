@@ -9,22 +9,49 @@
 //! provider's env var at composition time) — the client never reads the
 //! environment and never panics on a missing key.
 
+use std::collections::VecDeque;
+use std::pin::Pin;
+
 use anyhow::bail;
 use async_trait::async_trait;
+use futures::{Stream, stream};
 use log::debug;
 use serde::{Deserialize, Serialize};
 
+use crate::ProviderRateLimited;
 use crate::config::LlmConfig;
 use crate::document::FlattenedChunk;
 
+/// An answer arriving piecewise: each item is the next slice of text, in
+/// order; concatenated they are the whole answer.
+pub type TokenStream = Pin<Box<dyn Stream<Item = anyhow::Result<String>> + Send>>;
+
 #[async_trait]
 pub trait LLMClient: Send + Sync {
+    /// The model that writes the answers, as configured (e.g.
+    /// `gemini-2.5-flash`) — reported back with every answer so runs against
+    /// different models can be told apart.
+    fn model_id(&self) -> &str;
+
     async fn ask(
         &self,
         question: &str,
         history: &[(String, String)],
         context: &[(usize, (f64, FlattenedChunk))],
     ) -> anyhow::Result<String>;
+
+    /// [`ask`](Self::ask), delivered as the provider produces it. The default
+    /// waits for the whole answer and yields it as a single item, so a client
+    /// without a streaming endpoint still works behind the streaming surface.
+    async fn ask_stream(
+        &self,
+        question: &str,
+        history: &[(String, String)],
+        context: &[(usize, (f64, FlattenedChunk))],
+    ) -> anyhow::Result<TokenStream> {
+        let answer = self.ask(question, history, context).await?;
+        Ok(Box::pin(stream::once(async move { Ok(answer) })))
+    }
 }
 
 const OPENAI_URL: &str = "https://api.openai.com/v1";
@@ -33,6 +60,7 @@ const ANTHROPIC_URL: &str = "https://api.anthropic.com";
 const GEMINI_URL: &str = "https://generativelanguage.googleapis.com";
 
 /// The provider's wire shape — the only thing that differs between providers.
+#[derive(Clone, Copy)]
 enum Wire {
     /// OpenAI chat-completions dialect: `{base}/chat/completions`, bearer auth.
     /// Speaks for OpenAI, Mistral, and any OpenAI-compatible endpoint.
@@ -79,17 +107,10 @@ impl ChatClient {
     }
 }
 
-#[async_trait]
-impl LLMClient for ChatClient {
-    async fn ask(
-        &self,
-        question: &str,
-        history: &[(String, String)],
-        context: &[(usize, (f64, FlattenedChunk))],
-    ) -> anyhow::Result<String> {
-        let messages = chat_messages(history, build_prompt(question, history, context));
-
-        let request = match &self.wire {
+impl ChatClient {
+    /// The provider request for `messages`, in streaming or one-shot form.
+    fn request(&self, messages: Vec<ChatMessage>, stream: bool) -> reqwest::RequestBuilder {
+        match &self.wire {
             Wire::OpenAiCompat => self
                 .http
                 .post(format!("{}/chat/completions", self.base_url))
@@ -97,6 +118,7 @@ impl LLMClient for ChatClient {
                 .json(&ChatRequest {
                     model: self.model.clone(),
                     max_tokens: None,
+                    stream,
                     messages,
                 }),
             Wire::Anthropic => self
@@ -107,26 +129,69 @@ impl LLMClient for ChatClient {
                 .json(&ChatRequest {
                     model: self.model.clone(),
                     max_tokens: Some(4096),
+                    stream,
                     messages,
                 }),
             Wire::Gemini => {
                 let contents = gemini_contents(messages);
+                // Gemini streams from a separate method, as server-sent events
+                // only with `alt=sse`.
+                let method = if stream {
+                    "streamGenerateContent?alt=sse&"
+                } else {
+                    "generateContent?"
+                };
                 self.http
                     .post(format!(
-                        "{}/v1beta/models/{}:generateContent?key={}",
+                        "{}/v1beta/models/{}:{method}key={}",
                         self.base_url, self.model, self.api_key
                     ))
                     .json(&GeminiRequest { contents })
             }
-        };
+        }
+    }
 
+    /// Sends `request`, turning a 429 into the rate-limit marker and any other
+    /// non-success status into an error carrying the provider's body.
+    async fn send(&self, request: reqwest::RequestBuilder) -> anyhow::Result<reqwest::Response> {
         let response = request.send().await?;
 
+        if let Some(limited) = ProviderRateLimited::from_response(self.provider, &response) {
+            return Err(limited.into());
+        }
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await?;
+            // A retired or mistyped model is the usual 404: name it.
+            if status == reqwest::StatusCode::NOT_FOUND {
+                bail!(
+                    "{} API error: {} (is model `{}` available?)\n{}",
+                    self.provider,
+                    status,
+                    self.model,
+                    body
+                );
+            }
             bail!("{} API error: {}\n{}", self.provider, status, body);
         }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LLMClient for ChatClient {
+    fn model_id(&self) -> &str {
+        &self.model
+    }
+
+    async fn ask(
+        &self,
+        question: &str,
+        history: &[(String, String)],
+        context: &[(usize, (f64, FlattenedChunk))],
+    ) -> anyhow::Result<String> {
+        let messages = chat_messages(history, build_prompt(question, history, context));
+        let response = self.send(self.request(messages, false)).await?;
 
         let answer = match &self.wire {
             Wire::OpenAiCompat => {
@@ -180,6 +245,119 @@ impl LLMClient for ChatClient {
 
         Ok(answer)
     }
+
+    async fn ask_stream(
+        &self,
+        question: &str,
+        history: &[(String, String)],
+        context: &[(usize, (f64, FlattenedChunk))],
+    ) -> anyhow::Result<TokenStream> {
+        let messages = chat_messages(history, build_prompt(question, history, context));
+        let response = self.send(self.request(messages, true)).await?;
+        Ok(sse_tokens(response, self.wire))
+    }
+}
+
+/// Reads a provider's server-sent-events body as answer text. Bytes are split
+/// into lines only at `\n`, so a multi-byte char torn across network chunks
+/// is decoded whole; each `data:` line is handed to [`sse_delta`].
+fn sse_tokens(response: reqwest::Response, wire: Wire) -> TokenStream {
+    struct State {
+        response: reqwest::Response,
+        wire: Wire,
+        buf: Vec<u8>,
+        pending: VecDeque<anyhow::Result<String>>,
+        done: bool,
+    }
+
+    impl State {
+        fn drain_lines(&mut self) {
+            while let Some(end) = self.buf.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = self.buf.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                let Some(data) = line.trim_end().strip_prefix("data:") else {
+                    continue;
+                };
+                match sse_delta(self.wire, data.trim_start()) {
+                    Ok(Some(text)) if !text.is_empty() => self.pending.push_back(Ok(text)),
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.pending.push_back(Err(e));
+                        self.done = true;
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    let state = State {
+        response,
+        wire,
+        buf: Vec::new(),
+        pending: VecDeque::new(),
+        done: false,
+    };
+    Box::pin(stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                return Some((item, state));
+            }
+            if state.done {
+                return None;
+            }
+            match state.response.chunk().await {
+                Ok(Some(bytes)) => state.buf.extend_from_slice(&bytes),
+                Ok(None) => {
+                    // Flush a final event the provider didn't newline-terminate.
+                    state.buf.push(b'\n');
+                    state.done = true;
+                }
+                Err(e) => {
+                    state.done = true;
+                    return Some((Err(e.into()), state));
+                }
+            }
+            state.drain_lines();
+        }
+    }))
+}
+
+/// The answer text in one SSE `data:` payload, or `None` for the events that
+/// carry none (role headers, usage, message start/stop, OpenAI's `[DONE]`).
+fn sse_delta(wire: Wire, data: &str) -> anyhow::Result<Option<String>> {
+    if data == "[DONE]" {
+        return Ok(None);
+    }
+    Ok(match wire {
+        Wire::OpenAiCompat => {
+            let event: ChatStreamEvent = serde_json::from_str(data)?;
+            let text: String = event
+                .choices
+                .into_iter()
+                .filter_map(|c| c.delta.content)
+                .collect();
+            Some(text)
+        }
+        Wire::Anthropic => {
+            let event: AnthropicStreamEvent = serde_json::from_str(data)?;
+            match event.kind.as_str() {
+                "content_block_delta" => event.delta.and_then(|d| d.text),
+                "error" => bail!("Anthropic stream error: {data}"),
+                _ => None,
+            }
+        }
+        Wire::Gemini => {
+            let event: GeminiResponse = serde_json::from_str(data)?;
+            let text: String = event
+                .candidates
+                .into_iter()
+                .flat_map(|c| c.content.parts)
+                .map(|p| p.text)
+                .collect();
+            Some(text)
+        }
+    })
 }
 
 /// History pairs + the final RAG prompt as one chat transcript. Shared by the
@@ -352,6 +530,8 @@ struct ChatRequest {
     model: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
     messages: Vec<ChatMessage>,
 }
 
@@ -379,6 +559,39 @@ struct ChatUsage {
     completion_tokens: u32,
 }
 
+/// One OpenAI-compat streaming chunk: `choices[].delta.content`.
+#[derive(Deserialize)]
+struct ChatStreamEvent {
+    #[serde(default)]
+    choices: Vec<ChatStreamChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatStreamChoice {
+    delta: ChatStreamDelta,
+}
+
+#[derive(Deserialize)]
+struct ChatStreamDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// One Anthropic streaming event; only `content_block_delta` carries text.
+#[derive(Deserialize)]
+struct AnthropicStreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    delta: Option<AnthropicStreamDelta>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicStreamDelta {
+    #[serde(default)]
+    text: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct AnthropicResponse {
     content: Vec<AnthropicContent>,
@@ -826,6 +1039,106 @@ mod tests {
         assert!(msg.contains("bad key"), "missing body in: {msg}");
     }
 
+    /// Serves `body` as a `text/event-stream` on `route`.
+    async fn mock_sse(route: &str, body: &'static str) -> String {
+        let app = Router::new().route(
+            route,
+            post(move || async move { ([("content-type", "text/event-stream")], body) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    async fn collect(stream: TokenStream) -> Vec<String> {
+        use futures::StreamExt;
+        stream.map(|t| t.unwrap()).collect().await
+    }
+
+    #[tokio::test]
+    async fn openai_compat_stream_yields_deltas_until_done() {
+        let base = mock_sse(
+            "/chat/completions",
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n\
+             data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n\
+             data: {\"choices\":[{\"delta\":{\"content\":\"lo ñ\"}}]}\n\n\
+             data: [DONE]\n\n",
+        )
+        .await;
+        let stream = client(Wire::OpenAiCompat, base)
+            .ask_stream("q?", &[], &[])
+            .await
+            .unwrap();
+        assert_eq!(collect(stream).await, vec!["Hel", "lo ñ"]);
+    }
+
+    #[tokio::test]
+    async fn anthropic_stream_keeps_only_text_deltas() {
+        let base = mock_sse(
+            "/v1/messages",
+            "event: message_start\ndata: {\"type\":\"message_start\"}\n\n\
+             event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"one \"}}\n\n\
+             event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"two\"}}\n\n\
+             event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n",
+        )
+        .await;
+        let stream = client(Wire::Anthropic, base)
+            .ask_stream("q?", &[], &[])
+            .await
+            .unwrap();
+        assert_eq!(collect(stream).await.concat(), "one two");
+    }
+
+    #[tokio::test]
+    async fn gemini_stream_uses_the_sse_method() {
+        let base = mock_sse(
+            "/v1beta/models/test-model:streamGenerateContent",
+            "data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"gem\"}]}}]}\n\n\
+             data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"ini\"}]}}]}",
+        )
+        .await;
+        let stream = client(Wire::Gemini, base)
+            .ask_stream("q?", &[], &[])
+            .await
+            .unwrap();
+        // The final event has no trailing newline: it must still be flushed.
+        assert_eq!(collect(stream).await, vec!["gem", "ini"]);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_surfaces_as_typed_marker_with_retry_after() {
+        let app = Router::new().route(
+            "/chat/completions",
+            post(|| async {
+                (
+                    axum::http::StatusCode::TOO_MANY_REQUESTS,
+                    [("retry-after", "12")],
+                    "slow down",
+                )
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let err = client(Wire::OpenAiCompat, format!("http://{addr}"))
+            .ask("q?", &[], &[])
+            .await
+            .expect_err("429 must surface as an error");
+        let limited = err
+            .downcast_ref::<ProviderRateLimited>()
+            .expect("429 must carry the rate-limit marker");
+        assert_eq!(
+            limited.retry_after,
+            Some(std::time::Duration::from_secs(12))
+        );
+    }
+
     #[test]
     fn from_config_maps_providers_to_wires_and_default_urls() {
         let cases: Vec<(LlmConfig, &str)> = vec![
@@ -51,7 +51,7 @@ impl App {
         let settings: SharedSettings = Arc::new(RwLock::new(loaded_settings));
 
         let vault = {
-            let (workspace_path, cache_path, inbox) = {
+            let (workspace_path, cache_path, inbox, lowercase) = {
                 let s = settings.read().unwrap();
                 let path = s.resolve_workspace_path();
                 let name = s
@@ -65,10 +65,10 @@ impl App {
                     .as_ref()
                     .and_then(|wc| wc.get_current_workspace())
                     .map(|entry| entry.effective_inbox_path());
-                (path, cache, inbox)
+                (path, cache, inbox, s.lowercase_paths())
             };
             if let Some(workspace) = workspace_path {
-                let mut config = VaultConfig::new(&workspace);
+                let mut config = VaultConfig::new(&workspace).with_lowercase_paths(lowercase);
                 if let Some(cp) = cache_path {
                     config = config.with_db_path(cp);
                 }
@@ -123,13 +123,12 @@ impl DocMeta {
                     self.link_meta = Some((target.clone(), None));
                     if let Some(tx) = tx {
                         // Resolve like follow_link does: strip a `#fragment`,
-                        // then resolve relative targets against this note.
-                        let target_clean = target
-                            .split('#')
-                            .next()
-                            .unwrap_or(target)
-                            .trim_end()
-                            .to_string();
+                        // decode, then resolve relative targets against this
+                        // note.
+                        let target_clean = VaultPath::decode_url(
+                            target.split('#').next().unwrap_or(target).trim_end(),
+                        )
+                        .into_owned();
                         let vault = self.vault.clone();
                         let t2 = target.clone();
                         let note_path = current_note.clone();
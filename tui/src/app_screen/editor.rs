@@ -14,6 +14,7 @@ use crate::app_screen::editor_input::{
     Classification, CycleDir, EditorIntent, EditorOp, InputCtx, OverlayOpen, PanelFallback,
     classify,
 };
+use crate::app_screen::nav_history::NavHistory;
 use crate::app_screen::overlay_host::OverlayHost;
 use crate::app_screen::panel_set::PanelSet;
 use crate::app_screen::{AppScreen, ScreenKind};
@@ -69,6 +70,8 @@ pub struct EditorScreen {
     /// Async document/status state: backlink count, git summary, link
     /// affordance cache, pending emphasis needles (see `doc_meta.rs`).
     doc_meta: crate::app_screen::doc_meta::DocMeta,
+    /// Back/forward history over the notes opened in this screen.
+    nav: NavHistory,
     /// App-global update notice, seeded by `AppEvent::Update(UpdateFlow::Available)`. Drives
     /// the footer indicator; `None` when up to date or the check found nothing.
     update: Option<crate::update::UpdateStatus>,
@@ -96,6 +99,10 @@ pub struct EditorScreen {
     /// writes for the same path can never collide. Drop aborts the
     /// in-flight task so the spawned future cannot outlive the screen.
     autosave_task: SingleSlotTask<()>,
+    /// Notes whose recovery copy was offered at launch and left for later.
+    /// Saves never write or clear their copy, so it waits untouched until
+    /// it is restored or discarded.
+    pending_recovery: Vec<VaultPath>,
 }
 
 impl EditorScreen {
@@ -150,6 +157,7 @@ impl EditorScreen {
                 },
             ),
             doc_meta: crate::app_screen::doc_meta::DocMeta::new(vault.clone()),
+            nav: NavHistory::new(),
             update: None,
             rag_status: crate::rag::RagStatus::Disabled,
             ask,
@@ -161,6 +169,7 @@ impl EditorScreen {
             autosave: AutosaveTimer::new(),
             overlays: OverlayHost::new(),
             autosave_task: SingleSlotTask::empty(),
+            pending_recovery: vec![],
         }
     }
 }
@@ -273,12 +282,17 @@ impl EditorScreen {
         }
 
         // Image attachment — resolve the (potentially relative) path against
-        // the current note's directory, convert to an OS path, hand off to the
-        // OS default handler. Images are not notes, so skip the note lookup.
+        // the current note's directory to an OS path inside the vault, hand
+        // off to the OS default handler. Images are not notes, so skip the
+        // note lookup.
         if kimun_core::note::scan::target_looks_like_image(&target) {
-            let parent = self.path.get_parent_path().0;
-            let resolved = parent.append(&VaultPath::new(target.trim()));
-            let os_path = self.vault.path_to_pathbuf(&resolved);
+            let os_path = match self.vault.resolve_resource(&self.path, &target).await {
+                Ok(os_path) => os_path,
+                Err(e) => {
+                    self.footer.flash(format!("Cannot open image: {e}"), tx);
+                    return;
+                }
+            };
             match open::that_detached(&os_path) {
                 Ok(()) => self.footer.flash(format!("Opening {target}"), tx),
                 Err(e) => self.footer.flash(format!("Cannot open image: {e}"), tx),
@@ -293,6 +307,8 @@ impl EditorScreen {
         // Resolve the (possibly relative, e.g. `../work/anton.md`) target
         // against this note's directory so the existence lookup uses the same
         // absolute path the note is stored under. Bare names stay name-lookups.
+        // Markdown link targets may be percent-encoded (`my%20note.md`).
+        let target_clean = kimun_core::nfs::VaultPath::decode_url(target_clean);
         let path = kimun_core::nfs::VaultPath::note_path_from(target_clean)
             .resolve_link_in_note(&self.path);
         match self.vault.open_or_search(&path).await {
@@ -347,6 +363,8 @@ impl EditorScreen {
             return;
         }
 
+        self.nav.visit(&self.path, &path);
+
         // Save current note before switching
         self.try_save().await;
 
@@ -500,39 +518,106 @@ impl EditorScreen {
         // stops any further await points in the spawned task. The editor
         // stays dirty so the next session retries; the spawned task
         // either finishes against the disk on its own or is killed when
-        // the process exits.
+        // the process exits. The buffer goes to a recovery copy (a
+        // separate file, so no race) in case neither ever lands.
         if self.autosave_task.is_in_flight() {
             match self.autosave_task.await_with_timeout(SAVE_TIMEOUT).await {
                 Some(_) => {} // completed (success or panic) — slot already cleared
                 None => {
                     // Timeout: abort the spawned task and bail.
                     self.autosave_task.abort();
+                    if let Some(text) = self.dirty_text() {
+                        self.save_recovery(&text).await;
+                    }
                     return;
                 }
             }
         }
         // No note editor mounted (an attachment is shown) → nothing to save.
-        let Some(text) = self
-            .panels
-            .editor()
-            .filter(|e| e.is_dirty())
-            .map(|e| e.get_text())
-        else {
+        let Some(text) = self.dirty_text() else {
             return;
         };
         // Same cap on our own save so quit cannot hang on a stuck
         // disk. A timeout returns Err(_); we skip mark_saved so the
-        // editor stays dirty for any subsequent retry.
+        // editor stays dirty for any subsequent retry, and keep a
+        // recovery copy in case this was the last chance (quit).
         let save = self.vault.save_note(&self.path, &text);
         if let Ok(Ok((_, content))) = tokio::time::timeout(SAVE_TIMEOUT, save).await {
             if let Some(ed) = self.panels.editor_mut() {
                 ed.mark_saved(text);
             }
+            // A copy left by an earlier failed autosave is stale now; one
+            // offered at launch is not ours to clear.
+            if !self.has_pending_recovery()
+                && let Err(e) = self.vault.discard_recovery(&self.path).await
+            {
+                tracing::warn!("Could not clear the recovery copy of {}: {e}", self.path);
+            }
             let path = self.path.clone();
             self.note_saved(&path, content.title);
+        } else {
+            self.save_recovery(&text).await;
         }
     }
 
+    /// The open note's text when it has unsaved edits.
+    fn dirty_text(&self) -> Option<String> {
+        self.panels
+            .editor()
+            .filter(|e| e.is_dirty())
+            .map(|e| e.get_text())
+    }
+
+    /// Whether the open note has a recovery copy left for later (see
+    /// `pending_recovery`).
+    fn has_pending_recovery(&self) -> bool {
+        self.pending_recovery.iter().any(|p| p.is_like(&self.path))
+    }
+
+    /// Last resort for a failed save: write `text` to the note's recovery
+    /// copy, which the next launch offers to restore. Capped like the save
+    /// itself so a wedged disk cannot hang quit. A copy left for later is
+    /// never overwritten.
+    async fn save_recovery(&self, text: &str) {
+        if self.has_pending_recovery() {
+            tracing::error!(
+                "Save failed; {} already has a recovery copy, so the edits were not kept",
+                self.path
+            );
+            return;
+        }
+        let write = self.vault.save_recovery(&self.path, text);
+        match tokio::time::timeout(SAVE_TIMEOUT, write).await {
+            Ok(Ok(())) => tracing::warn!("Save failed; kept a recovery copy of {}", self.path),
+            Ok(Err(e)) => tracing::error!("Could not write a recovery copy of {}: {e}", self.path),
+            Err(_) => tracing::error!("Timed out writing a recovery copy of {}", self.path),
+        }
+    }
+
+    /// Look for recovery copies left by an earlier session (a failed save, or
+    /// a process killed mid-autosave) and, when any is newer than its note,
+    /// ask whether to restore them. A copy older than its note (the save
+    /// landed after all) is not offered.
+    fn check_recovery(&self, tx: &AppTx) {
+        let vault = self.vault.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            match vault.list_recovery().await {
+                Ok(copies) => {
+                    let paths: Vec<VaultPath> = copies
+                        .into_iter()
+                        .filter(|c| c.is_newer_than_note())
+                        .map(|c| c.path)
+                        .collect();
+                    if !paths.is_empty() {
+                        tx.send(AppEvent::FileOp(FileOp::ShowRecovery(paths))).ok();
+                    }
+                }
+                Err(e) => tracing::error!("Could not list recovery copies: {e}"),
+            }
+        });
+    }
+
     /// Fire-and-forget autosave used by the periodic timer. The save runs in
     /// a spawned tokio task so the main event loop is never blocked by the
     /// filesystem + SQLite write. Completion is reported back as
@@ -541,6 +626,11 @@ impl EditorScreen {
     /// on the `SingleSlotTask` slot is the "is a save in flight" signal;
     /// it flips to false on both successful completion AND panic, so a
     /// single panicked task can never permanently disable autosave.
+    ///
+    /// Each tick first writes the buffer to the note's recovery copy and
+    /// clears it once the save lands, so a process killed mid-save (where
+    /// `try_save` never runs) still leaves the text for the next launch. A
+    /// note whose copy was left for later keeps that copy as it is.
     fn spawn_autosave(&mut self, tx: &AppTx) {
         // A previous task that hasn't reported completion yet still holds the
         // lock on the file system + SQLite path; let it finish first.
@@ -555,12 +645,21 @@ impl EditorScreen {
         }
         let text = ed.get_text();
         let revision = ed.content_revision();
+        let keep_copy = !self.has_pending_recovery();
         let vault = self.vault.clone();
         let path = self.path.clone();
         let tx = tx.clone();
         self.autosave_task.spawn(async move {
+            if keep_copy && let Err(e) = vault.save_recovery(&path, &text).await {
+                tracing::warn!("Could not write a recovery copy of {path}: {e}");
+            }
             let (saved_revision, title) = match vault.save_note(&path, &text).await {
-                Ok((_, content)) => (Some(revision), Some(content.title)),
+                Ok((_, content)) => {
+                    if keep_copy && let Err(e) = vault.discard_recovery(&path).await {
+                        tracing::warn!("Could not clear the recovery copy of {path}: {e}");
+                    }
+                    (Some(revision), Some(content.title))
+                }
                 Err(_) => (None, None),
             };
             let _ = tx.send(AppEvent::AutosaveCompleted {
@@ -828,6 +927,21 @@ impl EditorScreen {
                     .ok();
             }
             EditorOp::ToggleQueryPanel => self.toggle_backlinks(tx),
+            EditorOp::GoBack => match self.nav.back(&self.path) {
+                Some(path) => {
+                    tx.send(AppEvent::open(path)).ok();
+                }
+                None => self.footer.flash("No earlier note".to_string(), tx),
+            },
+            EditorOp::GoForward => match self.nav.forward(&self.path) {
+                Some(path) => {
+                    tx.send(AppEvent::open(path)).ok();
+                }
+                None => self.footer.flash("No later note".to_string(), tx),
+            },
+            EditorOp::GoToParent => {
+                tx.send(AppEvent::open(self.path.get_parent_path().0)).ok();
+            }
             EditorOp::OpenFileBrowserReveal => {
                 self.open_drawer_view(DrawerView::Files, tx);
                 self.reveal_note_dir_in_sidebar(tx);
@@ -1038,20 +1152,46 @@ impl EditorScreen {
                 self.refresh_sidebar_if_showing(&path.get_parent_path().0, tx);
             }
             FileOp::Deleted(path) => {
+                self.nav.forget(&path);
                 self.on_entry_op(path, tx).await;
             }
             FileOp::Renamed { from, to } => {
                 // Note rename → targeted row update (and retarget the editor if
                 // it is the open note). Directory rename keeps the full reload.
                 if from.is_note() {
+                    self.nav.rename(&from, &to);
                     self.on_note_renamed(from, to, tx).await;
                 } else {
                     self.on_entry_op(from, tx).await;
                 }
             }
             FileOp::Moved { from, .. } => {
+                self.nav.forget(&from);
                 self.on_entry_op(from, tx).await;
             }
+            FileOp::ShowRecovery(paths) => {
+                self.pending_recovery.extend(paths.iter().cloned());
+                self.present_overlay(Box::new(ActiveDialog::recovery(paths, self.vault.clone())));
+            }
+            FileOp::RecoveryDiscarded(paths) => {
+                self.pending_recovery.retain(|p| !paths.contains(p));
+                self.footer
+                    .flash("Discarded unsaved changes".to_string(), tx);
+            }
+            FileOp::Recovered(paths) => {
+                self.pending_recovery.retain(|p| !paths.contains(p));
+                let msg = match paths.len() {
+                    1 => "Restored 1 note".to_string(),
+                    n => format!("Restored {n} notes"),
+                };
+                self.footer.flash(msg, tx);
+                // The open note was rewritten under the editor: reload it,
+                // unless it has edits of its own (reopening would save them
+                // over the restored text).
+                if paths.iter().any(|p| p.is_like(&self.path)) && self.dirty_text().is_none() {
+                    self.open_path(self.path.clone(), None, tx).await;
+                }
+            }
         }
     }
 
@@ -1395,6 +1535,32 @@ impl EditorScreen {
         }
     }
 
+    /// Open the existing journal entry before / after the open one (or
+    /// today, when the open note isn't a journal entry), skipping days
+    /// without an entry.
+    fn open_adjacent_journal(&mut self, dir: kimun_core::Direction, tx: &AppTx) {
+        let from = self
+            .vault
+            .journal_date(&self.path)
+            .unwrap_or_else(|| chrono::Utc::now().date_naive());
+        let vault = self.vault.clone();
+        let tx2 = tx.clone();
+        tokio::spawn(async move {
+            let event = match vault.adjacent_journal(from, dir).await {
+                Ok(Some((_, path))) => AppEvent::open(path),
+                Ok(None) => AppEvent::FlashMessage(
+                    match dir {
+                        kimun_core::Direction::Previous => "No earlier journal entry",
+                        kimun_core::Direction::Next => "No later journal entry",
+                    }
+                    .to_string(),
+                ),
+                Err(e) => AppEvent::FlashMessage(format!("journal lookup failed: {e}")),
+            };
+            tx2.send(event).ok();
+        });
+    }
+
     /// Switch the drawer to `view`, reveal it, and focus it. The per-view
     /// reveal side effects live in `drawer_view_revealed` (the heavy work
     /// that keeps the reveal in the host rather than in `PanelSet`).
@@ -1727,6 +1893,12 @@ impl EditorScreen {
             LeaderAction::NoteDaily => {
                 tx.send(AppEvent::OpenJournal).ok();
             }
+            LeaderAction::NoteDailyPrev => {
+                self.open_adjacent_journal(kimun_core::Direction::Previous, tx)
+            }
+            LeaderAction::NoteDailyNext => {
+                self.open_adjacent_journal(kimun_core::Direction::Next, tx)
+            }
             LeaderAction::NoteFromTemplate => {
                 self.footer.flash("templates — coming soon".to_string(), tx);
             }
@@ -1799,6 +1971,23 @@ impl EditorScreen {
                 tx.send(AppEvent::OpenScreen(ScreenEvent::OpenOnboarding))
                     .ok();
             }
+            LeaderAction::VaultShortcutsNote => {
+                // Regenerated on every run so the note always matches the
+                // live bindings; opened once written.
+                let vault = self.vault.clone();
+                let kb = self.settings.read().unwrap().key_bindings.clone();
+                let tx2 = tx.clone();
+                tokio::spawn(async move {
+                    match kb.save_cheat_sheet(&vault).await {
+                        Ok(path) => tx2.send(AppEvent::open(path)).ok(),
+                        Err(e) => tx2
+                            .send(AppEvent::FlashMessage(format!(
+                                "shortcuts note failed: {e}"
+                            )))
+                            .ok(),
+                    };
+                });
+            }
 
             // +window
             LeaderAction::WindowZen => {
@@ -1916,6 +2105,7 @@ impl AppScreen for EditorScreen {
     async fn on_enter(&mut self, tx: &AppTx) {
         self.app_tx = Some(tx.clone());
         self.open_path(self.path.clone(), None, tx).await;
+        self.check_recovery(tx);
     }
 
     fn handle_input(&mut self, event: &InputEvent, tx: &AppTx) -> EventState {
@@ -1973,11 +2163,7 @@ impl AppScreen for EditorScreen {
             .as_ref()
             .map(ratatui::text::Line::width)
             .unwrap_or_default();
-        let breadcrumb = self
-            .path
-            .to_string()
-            .trim_start_matches('/')
-            .replace('/', " / ");
+        let breadcrumb = self.path.to_string_with(" / ", false);
         let title_cols = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
@@ -2833,7 +3019,10 @@ mod tests {
         screen.on_enter(&tx).await;
 
         let to = VaultPath::note_path_from("new");
-        vault.rename_note(&from, &to).await.unwrap();
+        vault
+            .rename_note(&from, &to, kimun_core::ConflictPolicy::Error)
+            .await
+            .unwrap();
         screen
             .handle_app_message(
                 AppEvent::FileOp(FileOp::Renamed {
@@ -2851,6 +3040,145 @@ mod tests {
         );
     }
 
+    /// A save that fails on the way out keeps the buffer as a recovery copy,
+    /// and the next editor screen over the vault offers it back.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn failed_exit_save_keeps_a_recovery_copy() {
+        let vault = crate::test_support::temp_vault("editor-recovery").await;
+        vault.validate_and_init().await.unwrap();
+        let note = VaultPath::new("/doomed.md");
+        vault.create_note(&note, "body").await.unwrap();
+        let settings = std::sync::Arc::new(std::sync::RwLock::new(
+            crate::settings::AppSettings::default(),
+        ));
+        let mut screen = EditorScreen::new(vault.clone(), note.clone(), settings.clone());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        screen.on_enter(&tx).await;
+        screen.panels.focus(PanelKind::Editor);
+        screen.handle_input(&chr('x'), &tx);
+        let text = screen.panels.editor().unwrap().get_text();
+        assert!(screen.panels.editor().unwrap().is_dirty());
+
+        // A directory where the note file was makes the write fail.
+        let file = note.to_pathbuf(vault.workspace_path());
+        std::fs::remove_file(&file).unwrap();
+        std::fs::create_dir(&file).unwrap();
+        screen.on_exit(&tx).await;
+        let copies = vault.list_recovery().await.unwrap();
+        let paths: Vec<_> = copies.into_iter().map(|c| c.path).collect();
+        assert_eq!(paths, std::slice::from_ref(&note));
+
+        let mut next = EditorScreen::new(vault.clone(), VaultPath::root(), settings);
+        next.on_enter(&tx).await;
+        let offered = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(AppEvent::FileOp(FileOp::ShowRecovery(paths))) = rx.recv().await {
+                    return paths;
+                }
+            }
+        })
+        .await
+        .expect("the restore prompt is requested");
+        assert_eq!(offered, std::slice::from_ref(&note));
+
+        std::fs::remove_dir(&file).unwrap();
+        vault.recover(&note).await.unwrap();
+        assert_eq!(vault.get_note_text(&note).await.unwrap(), text);
+    }
+
+    /// Each autosave tick writes the buffer to a recovery copy first, so a
+    /// failed save leaves it behind; the next save that lands clears it.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn autosave_keeps_a_recovery_copy_until_a_save_lands() {
+        let vault = crate::test_support::temp_vault("editor-autosave-recovery").await;
+        vault.validate_and_init().await.unwrap();
+        let note = VaultPath::new("/draft.md");
+        vault.create_note(&note, "body").await.unwrap();
+        let settings = std::sync::Arc::new(std::sync::RwLock::new(
+            crate::settings::AppSettings::default(),
+        ));
+        let mut screen = EditorScreen::new(vault.clone(), note.clone(), settings);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        screen.on_enter(&tx).await;
+        screen.panels.focus(PanelKind::Editor);
+        screen.handle_input(&chr('x'), &tx);
+
+        async fn autosave_done(rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>) -> bool {
+            tokio::time::timeout(Duration::from_secs(5), async {
+                loop {
+                    if let Some(AppEvent::AutosaveCompleted { saved_revision, .. }) =
+                        rx.recv().await
+                    {
+                        return saved_revision.is_some();
+                    }
+                }
+            })
+            .await
+            .expect("the autosave reports back")
+        }
+
+        // A directory where the note file was makes the save fail.
+        let file = note.to_pathbuf(vault.workspace_path());
+        std::fs::remove_file(&file).unwrap();
+        std::fs::create_dir(&file).unwrap();
+        screen.spawn_autosave(&tx);
+        assert!(!autosave_done(&mut rx).await);
+        let copies = vault.list_recovery().await.unwrap();
+        assert_eq!(copies.len(), 1);
+        assert_eq!(copies[0].path, note);
+
+        // The task reports back just before it finishes; let it wind down so
+        // the next tick isn't skipped as still in flight.
+        screen
+            .autosave_task
+            .await_with_timeout(Duration::from_secs(5))
+            .await;
+        std::fs::remove_dir(&file).unwrap();
+        screen.spawn_autosave(&tx);
+        assert!(autosave_done(&mut rx).await);
+        assert!(vault.list_recovery().await.unwrap().is_empty());
+    }
+
+    /// A recovery copy offered at launch and left for later is neither
+    /// overwritten by an autosave tick nor cleared once the save lands.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn autosave_leaves_a_pending_recovery_copy_alone() {
+        let vault = crate::test_support::temp_vault("editor-pending-recovery").await;
+        vault.validate_and_init().await.unwrap();
+        let note = VaultPath::new("/draft.md");
+        vault.create_note(&note, "body").await.unwrap();
+        vault.save_recovery(&note, "earlier edits").await.unwrap();
+        let settings = std::sync::Arc::new(std::sync::RwLock::new(
+            crate::settings::AppSettings::default(),
+        ));
+        let mut screen = EditorScreen::new(vault.clone(), note.clone(), settings);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        screen.on_enter(&tx).await;
+        screen
+            .handle_file_op(FileOp::ShowRecovery(vec![note.clone()]), &tx)
+            .await;
+        screen.dismiss_overlay();
+        screen.panels.focus(PanelKind::Editor);
+        screen.handle_input(&chr('x'), &tx);
+
+        screen.spawn_autosave(&tx);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(AppEvent::AutosaveCompleted { saved_revision, .. }) = rx.recv().await {
+                    assert!(saved_revision.is_some());
+                    return;
+                }
+            }
+        })
+        .await
+        .expect("the autosave reports back");
+        screen.try_save().await;
+
+        assert_eq!(vault.list_recovery().await.unwrap().len(), 1);
+        vault.recover(&note).await.unwrap();
+        assert_eq!(vault.get_note_text(&note).await.unwrap(), "earlier edits");
+    }
+
     /// Opening a note marks its sidebar row; saving it (AutosaveCompleted with a
     /// new title) updates that row's title in place.
     #[tokio::test(flavor = "multi_thread")]
@@ -174,6 +174,11 @@ pub enum EditorOp {
     /// Switch to the Ask workspace and focus its composer (F6 and leader
     /// `a a`).
     OpenAsk,
+    /// Step through the note history (see `nav_history.rs`).
+    GoBack,
+    GoForward,
+    /// Browse the open note's directory.
+    GoToParent,
 }
 
 /// Resolve one raw input event into its [`Classification`] under the editor
@@ -341,6 +346,15 @@ pub(crate) fn classify_tail(
                 open: OverlayOpen::SavedSearches,
             }),
             Some(ActionShortcuts::OpenAsk) => Some(EditorIntent::Op(EditorOp::OpenAsk)),
+            Some(ActionShortcuts::GoBack) if ctx.overlay.is_none() => {
+                Some(EditorIntent::Op(EditorOp::GoBack))
+            }
+            Some(ActionShortcuts::GoForward) if ctx.overlay.is_none() => {
+                Some(EditorIntent::Op(EditorOp::GoForward))
+            }
+            Some(ActionShortcuts::GoToParent) if ctx.overlay.is_none() => {
+                Some(EditorIntent::Op(EditorOp::GoToParent))
+            }
             Some(ActionShortcuts::OpenSortDialog) => {
                 // Sort applies only when a list is focused (the drawer's
                 // Find / Files views). When the editor is focused, do NOT
@@ -0,0 +1,158 @@
+//! **NavHistory** — the editor screen's back/forward stack over opened notes
+//! (GoBack / GoForward).
+//!
+//! Every note open goes through `AppEvent::OpenPath`, so a back/forward step
+//! cannot open its target directly: it arms the target as `pending` and sends
+//! the ordinary open. When that open lands, [`NavHistory::visit`] recognizes
+//! the pending target and leaves the stacks alone instead of recording a new
+//! visit (which would clear the forward stack).
+
+use kimun_core::nfs::VaultPath;
+
+/// How many notes the back stack remembers; older entries fall off.
+pub const NAV_HISTORY_SIZE: usize = 100;
+
+#[derive(Debug, Default)]
+pub struct NavHistory {
+    back: Vec<VaultPath>,
+    forward: Vec<VaultPath>,
+    /// Target of an in-flight back/forward step, consumed by `visit`.
+    pending: Option<VaultPath>,
+}
+
+impl NavHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record navigating from `from` to `to`. Re-opening the open note is not
+    /// a step; a fresh step clears the forward stack, like a browser.
+    pub fn visit(&mut self, from: &VaultPath, to: &VaultPath) {
+        if self.pending.take().is_some_and(|p| p.is_like(to)) {
+            return;
+        }
+        if from.is_like(to) || !from.is_note() {
+            return;
+        }
+        self.back.push(from.clone());
+        if self.back.len() > NAV_HISTORY_SIZE {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    /// Step back from `current`: returns the note to open, or `None` at the
+    /// start of the history.
+    pub fn back(&mut self, current: &VaultPath) -> Option<VaultPath> {
+        let target = self.back.pop()?;
+        self.forward.push(current.clone());
+        self.pending = Some(target.clone());
+        Some(target)
+    }
+
+    /// Step forward from `current`: returns the note to open, or `None` when
+    /// nothing was stepped back over.
+    pub fn forward(&mut self, current: &VaultPath) -> Option<VaultPath> {
+        let target = self.forward.pop()?;
+        self.back.push(current.clone());
+        self.pending = Some(target.clone());
+        Some(target)
+    }
+
+    /// Point every entry for `from` at `to` (the note was renamed).
+    pub fn rename(&mut self, from: &VaultPath, to: &VaultPath) {
+        for p in self.back.iter_mut().chain(self.forward.iter_mut()) {
+            if p.is_like(from) {
+                *p = to.clone();
+            }
+        }
+    }
+
+    /// Drop every entry for `path` (the note was deleted or moved away).
+    pub fn forget(&mut self, path: &VaultPath) {
+        self.back.retain(|p| !p.is_like(path));
+        self.forward.retain(|p| !p.is_like(path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(s: &str) -> VaultPath {
+        VaultPath::note_path_from(s)
+    }
+
+    #[test]
+    fn back_and_forward_walk_the_visits() {
+        let mut nav = NavHistory::new();
+        nav.visit(&p("a"), &p("b"));
+        nav.visit(&p("b"), &p("c"));
+
+        let back = nav.back(&p("c")).unwrap();
+        assert_eq!(back, p("b"));
+        nav.visit(&p("c"), &back);
+        let back = nav.back(&p("b")).unwrap();
+        assert_eq!(back, p("a"));
+        nav.visit(&p("b"), &back);
+        assert_eq!(nav.back(&p("a")), None);
+
+        let fwd = nav.forward(&p("a")).unwrap();
+        assert_eq!(fwd, p("b"));
+        nav.visit(&p("a"), &fwd);
+        assert_eq!(nav.forward(&p("b")), Some(p("c")));
+    }
+
+    #[test]
+    fn a_fresh_visit_clears_forward() {
+        let mut nav = NavHistory::new();
+        nav.visit(&p("a"), &p("b"));
+        let back = nav.back(&p("b")).unwrap();
+        nav.visit(&p("b"), &back);
+        nav.visit(&p("a"), &p("c"));
+        assert_eq!(nav.forward(&p("c")), None);
+        assert_eq!(nav.back(&p("c")), Some(p("a")));
+    }
+
+    #[test]
+    fn reopening_the_same_note_or_leaving_a_directory_is_not_a_step() {
+        let mut nav = NavHistory::new();
+        nav.visit(&p("a"), &p("a"));
+        nav.visit(&VaultPath::root(), &p("a"));
+        assert_eq!(nav.back(&p("a")), None);
+    }
+
+    #[test]
+    fn back_stack_is_capped() {
+        let mut nav = NavHistory::new();
+        for i in 0..NAV_HISTORY_SIZE + 10 {
+            nav.visit(&p(&i.to_string()), &p(&(i + 1).to_string()));
+        }
+        let mut steps = 0;
+        let mut current = p(&(NAV_HISTORY_SIZE + 10).to_string());
+        while let Some(prev) = nav.back(&current) {
+            nav.visit(&current, &prev);
+            current = prev;
+            steps += 1;
+        }
+        assert_eq!(steps, NAV_HISTORY_SIZE);
+        assert_eq!(current, p("10"));
+    }
+
+    #[test]
+    fn rename_retargets_entries() {
+        let mut nav = NavHistory::new();
+        nav.visit(&p("a"), &p("b"));
+        nav.rename(&p("a"), &p("z"));
+        assert_eq!(nav.back(&p("b")), Some(p("z")));
+    }
+
+    #[test]
+    fn forget_drops_a_deleted_note() {
+        let mut nav = NavHistory::new();
+        nav.visit(&p("a"), &p("b"));
+        nav.visit(&p("b"), &p("c"));
+        nav.forget(&p("b"));
+        assert_eq!(nav.back(&p("c")), Some(p("a")));
+    }
+}
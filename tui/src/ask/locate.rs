@@ -2,10 +2,9 @@
 //! full text, for the Ask workspace's Source reader (CONTEXT.md: **Ask
 //! workspace**, `SourcesPanel`). Three-step resolution, most confident
 //! first: an exact substring match of the retrieved chunk text (first
-//! occurrence wins on a duplicate); the `ContentChunk` core's own chunker
-//! computes for the note (matched by innermost heading), located by
-//! substring; and, only when that chunk's text can't be found verbatim in
-//! the note (e.g. it was normalized server-side), core's
+//! occurrence wins on a duplicate); the source range of the `ContentChunk`
+//! core's own chunker computes for the note (matched by innermost heading);
+//! and, only when no chunk carries that heading, core's
 //! `note::scan::heading_section_range` — content analysis over raw note
 //! text belongs in core, not the TUI.
 
@@ -28,19 +27,18 @@ pub fn section_range(note_text: &str, heading: &str, chunk_text: &str) -> Option
     // Recompute the note's own chunks (core's chunker, not the server's) and
     // find the one whose innermost heading matches.
     let (chunks, _links) = NoteDetails::chunks_and_links_of(&VaultPath::root(), note_text);
-    let chunk = chunks.iter().find(|c| {
+    if let Some(chunk) = chunks.iter().find(|c| {
         c.breadcrumb_last()
             .is_some_and(|h| h.eq_ignore_ascii_case(heading))
-    })?;
-
-    if let Some(start) = note_text.find(&chunk.text) {
-        return Some(start..start + chunk.text.len());
+    }) {
+        // The chunk's text is normalized (diacritics stripped, lists
+        // reformatted), but it records the section it came from.
+        return Some(chunk.range());
     }
 
-    // Last resort: the chunk's own text isn't a verbatim substring either
-    // (normalization — core's chunker strips diacritics, reformats lists,
-    // etc.). Fall back to the heading line itself; this is core content
-    // analysis, so it lives in `note::scan`, not here.
+    // Last resort: no chunk carries the heading as given (e.g. the server
+    // returned it diacritics-stripped). Match the heading line itself; this
+    // is core content analysis, so it lives in `note::scan`, not here.
     scan::heading_section_range(note_text, heading)
 }
 
@@ -71,11 +69,11 @@ mod tests {
     #[test]
     fn section_range_matches_via_chunk_when_exact_text_absent_but_chunk_matches() {
         // Two headings; chunk_text doesn't match verbatim anywhere, but the
-        // heading resolves via core's own chunker (whose text, being derived
-        // straight from the note, is itself found verbatim).
+        // heading resolves via core's own chunker, which records the
+        // section's source range.
         let note = "# one\nfirst body\n# two\nsecond body\n";
         let r = section_range(note, "two", "does not appear literally").unwrap();
-        assert!(note[r].contains("second body"));
+        assert_eq!(&note[r], "# two\nsecond body");
     }
 
     #[test]
@@ -0,0 +1,183 @@
+// tui/src/cli/commands/ask.rs
+use std::io::Write;
+
+use color_eyre::eyre::{Result, eyre};
+use kimun_core::NoteVault;
+use kimun_server_client::dto::AnswerResult;
+use kimun_server_client::{ContextSize, RagClient};
+
+use crate::settings::AppSettings;
+
+pub async fn run(
+    settings: &AppSettings,
+    vault: &NoteVault,
+    question: &str,
+    top_k: Option<usize>,
+    show_sources: bool,
+    show_model: bool,
+) -> Result<()> {
+    let global = settings.workspace_config.as_ref().map(|wc| &wc.global);
+    let url = global
+        .and_then(|g| g.kimun_server_url.clone())
+        .ok_or_else(|| eyre!("No Kimün server configured. Set kimun_server_url to use `ask`."))?;
+    let token = global.and_then(|g| g.kimun_server_token.clone());
+    let client = RagClient::new(url, token, vault.vault_id().await?.to_string());
+
+    let health = client.health().await?;
+    if health.llm_provider.is_none() {
+        return Err(eyre!(
+            "The server has no LLM configured; it only answers semantic searches."
+        ));
+    }
+    // An empty collection would send the LLM a question with no context and
+    // come back with a guess; stop before that.
+    if client.server_hashes().await?.is_empty() {
+        return Err(eyre!(
+            "Nothing from this workspace is indexed on the server yet. \
+             Run `kimun` once to sync it, then ask again."
+        ));
+    }
+
+    // Print the answer as the server streams it, rather than sitting silent
+    // until the whole of it is written.
+    let mut stdout = std::io::stdout();
+    let answer = client
+        .ask_stream(question, &[], top_k.map(ContextSize::Exact), |text| {
+            let _ = write!(stdout, "{text}");
+            let _ = stdout.flush();
+        })
+        .await?;
+    write_footer(&mut stdout, &answer, show_sources, show_model)?;
+    Ok(())
+}
+
+/// What follows the streamed answer: a newline to end it, then the cited
+/// sources and the model when asked for.
+fn write_footer(
+    out: &mut impl Write,
+    answer: &AnswerResult,
+    show_sources: bool,
+    show_model: bool,
+) -> std::io::Result<()> {
+    if !answer.answer.ends_with('\n') {
+        writeln!(out)?;
+    }
+
+    if show_sources && !answer.sources.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "Sources:")?;
+        for (i, source) in answer.sources.iter().enumerate() {
+            // 0 = an older server without ordinals; fall back to position.
+            let ordinal = if source.ordinal == 0 {
+                i + 1
+            } else {
+                source.ordinal
+            };
+            if source.title.is_empty() {
+                writeln!(
+                    out,
+                    "[{}] {} ({:.2})",
+                    ordinal, source.path, source.similarity_score
+                )?;
+            } else {
+                writeln!(
+                    out,
+                    "[{}] {} — {} ({:.2})",
+                    ordinal, source.path, source.title, source.similarity_score
+                )?;
+            }
+        }
+    }
+
+    if show_model {
+        writeln!(out)?;
+        // None = an older server that doesn't report it.
+        writeln!(
+            out,
+            "Model: {}",
+            answer
+                .model
+                .as_deref()
+                .unwrap_or("unknown (server too old)")
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use kimun_server_client::ChunkResult;
+
+    use super::*;
+    use crate::cli::CliCommand;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(subcommand)]
+        command: CliCommand,
+    }
+
+    fn top_k(args: &[&str]) -> Result<Option<usize>, clap::Error> {
+        let args = ["kimun", "ask", "why?"].iter().chain(args);
+        match Cli::try_parse_from(args)?.command {
+            CliCommand::Ask { top_k, .. } => Ok(top_k),
+            _ => unreachable!("parsed an ask command"),
+        }
+    }
+
+    #[test]
+    fn top_k_must_be_at_least_one() {
+        assert_eq!(top_k(&[]).unwrap(), None);
+        assert_eq!(top_k(&["--top-k", "1"]).unwrap(), Some(1));
+        assert_eq!(top_k(&["--top-k", "12"]).unwrap(), Some(12));
+        assert!(top_k(&["--top-k", "0"]).is_err());
+        assert!(top_k(&["--top-k", "-3"]).is_err());
+    }
+
+    fn source(path: &str, title: &str, ordinal: usize) -> ChunkResult {
+        ChunkResult {
+            path: path.to_string(),
+            title: title.to_string(),
+            date: None,
+            content: String::new(),
+            hash: String::new(),
+            similarity_score: 0.5,
+            ordinal,
+        }
+    }
+
+    fn footer(answer: &AnswerResult, show_sources: bool, show_model: bool) -> String {
+        let mut out = Vec::new();
+        write_footer(&mut out, answer, show_sources, show_model).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn footer_ends_the_answer_then_lists_sources_and_model() {
+        let answer = AnswerResult {
+            answer: "Because.".to_string(),
+            sources: vec![source("/a.md", "Alpha", 2), source("/b.md", "", 0)],
+            model: Some("small-model".to_string()),
+        };
+        assert_eq!(footer(&answer, false, false), "\n");
+        assert_eq!(
+            footer(&answer, true, true),
+            "\n\nSources:\n[2] /a.md — Alpha (0.50)\n[2] /b.md (0.50)\n\nModel: small-model\n"
+        );
+    }
+
+    #[test]
+    fn footer_keeps_an_answer_ending_in_a_newline_and_old_servers() {
+        let answer = AnswerResult {
+            answer: "Because.\n".to_string(),
+            sources: vec![],
+            model: None,
+        };
+        assert_eq!(footer(&answer, true, false), "");
+        assert_eq!(
+            footer(&answer, false, true),
+            "\nModel: unknown (server too old)\n"
+        );
+    }
+}
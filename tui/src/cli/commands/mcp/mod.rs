@@ -8,7 +8,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use color_eyre::eyre::{Result, eyre};
-use kimun_core::{NoteVault, nfs::VaultPath};
+use kimun_core::{ConflictPolicy, NoteVault, nfs::VaultPath};
 use rmcp::{
     ErrorData as McpError, RoleServer, ServerHandler, ServiceExt,
     handler::server::{
@@ -522,8 +522,12 @@ impl KimunHandler {
             .append(&VaultPath::note_path_from(&p.new_name))
             .absolute();
 
-        match self.vault.rename_note(&from, &to).await {
-            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+        match self
+            .vault
+            .rename_note(&from, &to, ConflictPolicy::Error)
+            .await
+        {
+            Ok(_) => Ok(CallToolResult::success(vec![Content::text(format!(
                 "Note renamed: {} → {}",
                 from, to
             ))])),
@@ -545,8 +549,12 @@ impl KimunHandler {
         let from = Self::resolve_path(&p.path);
         let to = Self::resolve_path(&p.new_path);
 
-        match self.vault.rename_note(&from, &to).await {
-            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+        match self
+            .vault
+            .rename_note(&from, &to, ConflictPolicy::Error)
+            .await
+        {
+            Ok(_) => Ok(CallToolResult::success(vec![Content::text(format!(
                 "Note moved: {} → {}",
                 from, to
             ))])),
@@ -220,8 +220,7 @@ fn run_use(settings: &mut AppSettings, name: String) -> Result<()> {
         .workspace_config
         .as_mut()
         .expect("workspace_config must exist")
-        .global
-        .current_workspace = name.clone();
+        .set_current_workspace(name.clone());
     settings.save_to_disk()?;
 
     println!("Switched to workspace '{}'.", name);
@@ -138,15 +138,14 @@ pub async fn create_and_init_vault(config_path: Option<PathBuf>) -> Result<(Note
     let cache_path = settings.cache_path_for(&workspace_name);
     // Backups on: every command built through this helper (search/notes/labels
     // are read-only no-ops, journal writes do get backed up) and the MCP server.
-    let mut vault = NoteVault::new(
-        VaultConfig::new(&workspace_path)
-            .with_db_path(cache_path)
-            .with_backup(true),
-    )
-    .await?;
     let inbox = resolve_inbox_path(&settings);
-    vault.set_inbox_path(kimun_core::nfs::VaultPath::new(&inbox));
-    vault.validate_and_init().await?;
+    let (vault, _report) = VaultConfig::new(&workspace_path)
+        .with_db_path(cache_path)
+        .with_backup(true)
+        .with_lowercase_paths(settings.lowercase_paths())
+        .with_inbox_path(kimun_core::nfs::VaultPath::new(&inbox))
+        .open()
+        .await?;
 
     Ok((vault, workspace_name))
 }
@@ -46,6 +46,19 @@ pub enum CliCommand {
     Journal(JournalArgs),
     /// Start the MCP server (stdio transport)
     Mcp,
+    /// Ask the configured Kimün server a question about the workspace
+    Ask {
+        question: String,
+        /// Number of context chunks to retrieve (server default when omitted)
+        #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        top_k: Option<usize>,
+        /// Print the cited source notes after the answer
+        #[arg(long)]
+        show_sources: bool,
+        /// Print which LLM model wrote the answer
+        #[arg(long)]
+        show_model: bool,
+    },
     /// List all hashtag labels in the vault with note counts
     Labels {
         #[arg(long, value_enum, default_value = "text")]
@@ -71,13 +84,14 @@ pub async fn run_cli(command: CliCommand, config_path: Option<std::path::PathBuf
             let quick_note_path = resolve_quick_note_path(&settings);
             let inbox_path = resolve_inbox_path(&settings);
             let cache_path = settings.cache_path_for(&workspace_name);
-            let mut vault = NoteVault::new(
+            let vault = NoteVault::new(
                 VaultConfig::new(&workspace_path)
                     .with_db_path(cache_path)
-                    .with_backup(true),
+                    .with_backup(true)
+                    .with_lowercase_paths(settings.lowercase_paths())
+                    .with_inbox_path(kimun_core::nfs::VaultPath::new(&inbox_path)),
             )
             .await?;
-            vault.set_inbox_path(kimun_core::nfs::VaultPath::new(&inbox_path));
             if vault.index_ready() {
                 commands::note_ops::run(subcommand, &vault, &quick_note_path, &workspace_name).await
             } else {
@@ -98,6 +112,30 @@ pub async fn run_cli(command: CliCommand, config_path: Option<std::path::PathBuf
             let (vault, workspace_name) = create_and_init_vault(config_path).await?;
             commands::journal::run(args, &vault, &workspace_name).await
         }
+        CliCommand::Ask {
+            question,
+            top_k,
+            show_sources,
+            show_model,
+        } => {
+            let (settings, workspace_path, workspace_name) =
+                load_and_resolve_workspace(config_path)?;
+            // Only the vault id is needed; skip the local index entirely.
+            let vault = NoteVault::new(
+                VaultConfig::new(&workspace_path)
+                    .with_db_path(settings.cache_path_for(&workspace_name)),
+            )
+            .await?;
+            commands::ask::run(
+                &settings,
+                &vault,
+                &question,
+                top_k,
+                show_sources,
+                show_model,
+            )
+            .await
+        }
         CliCommand::Mcp => commands::mcp::run(config_path).await,
         CliCommand::Labels { format } => {
             let (vault, workspace_name) = create_and_init_vault(config_path).await?;
@@ -469,7 +469,7 @@ impl AutocompleteController {
             }
             let items: Vec<Suggestion> = match kind {
                 TriggerKind::Wikilink => suggestions
-                    .notes_by_prefix(&query, limit)
+                    .links_matching(&query, limit)
                     .await
                     .into_iter()
                     .map(|item| Suggestion {
@@ -848,6 +848,22 @@ mod tests {
         assert!(!names.contains(&"novel"));
     }
 
+    #[tokio::test]
+    async fn wikilink_popup_finds_notes_by_title() {
+        let (_tmp, vault) = new_vault_with(&["roadmap"], &[("q3", "# Roadmap for Q3")]).await;
+        let mut c = make_controller(vault, AutocompleteMode::Both);
+        let host = FakeHost::new("see [[road", 10);
+        c.sync(&host);
+        drain_results(&mut c).await;
+        let st = c.state().unwrap();
+        let names: Vec<&str> = st.items.iter().map(|s| s.display.as_str()).collect();
+        assert_eq!(names, ["roadmap", "q3"], "name prefix ranks first");
+        assert_eq!(
+            st.items[1].secondary.as_deref(),
+            Some("Roadmap for Q3 · /q3.md")
+        );
+    }
+
     #[tokio::test]
     async fn saved_search_popup_loads_matching_searches() {
         let (_tmp, vault) = new_vault_with(&[], &[]).await;
@@ -4,6 +4,7 @@ pub use file_ops_menu::FileOpsMenuDialog;
 pub use help_dialog::HelpDialog;
 pub use move_dialog::MoveDialog;
 pub use quick_note_modal::QuickNoteModal;
+pub use recovery_dialog::RecoveryDialog;
 pub use rename_dialog::RenameDialog;
 pub use save_search_dialog::SaveSearchDialog;
 pub use sort_dialog::SortDialog;
@@ -49,6 +50,7 @@ pub mod file_ops_menu;
 pub mod help_dialog;
 pub mod move_dialog;
 pub mod quick_note_modal;
+pub mod recovery_dialog;
 pub mod rename_dialog;
 pub mod save_search_dialog;
 pub mod sort_dialog;
@@ -69,6 +71,7 @@ pub enum ActiveDialog {
     Sort(SortDialog),
     ThemePicker(ThemePickerDialog),
     UpdateAvailable(UpdateAvailableDialog),
+    Recovery(RecoveryDialog),
 }
 
 impl ActiveDialog {
@@ -86,6 +89,7 @@ impl ActiveDialog {
             ActiveDialog::Sort(_) => {}              // no error state
             ActiveDialog::ThemePicker(_) => {}       // no error state
             ActiveDialog::UpdateAvailable(_) => {}   // no error state
+            ActiveDialog::Recovery(d) => d.error = Some(msg),
         }
     }
 
@@ -114,6 +118,11 @@ impl ActiveDialog {
         ActiveDialog::UpdateAvailable(UpdateAvailableDialog::new(status))
     }
 
+    /// The restore prompt for notes with recovery copies (on launch).
+    pub fn recovery(paths: Vec<kimun_core::nfs::VaultPath>, vault: Arc<NoteVault>) -> Self {
+        ActiveDialog::Recovery(RecoveryDialog::new(paths, vault))
+    }
+
     pub fn quick_note(vault: Arc<NoteVault>) -> Self {
         ActiveDialog::QuickNote(QuickNoteModal::new(vault))
     }
@@ -279,6 +288,7 @@ impl Component for ActiveDialog {
             ActiveDialog::Sort(d) => d.handle_input(event, tx),
             ActiveDialog::ThemePicker(d) => d.handle_key(*key, tx),
             ActiveDialog::UpdateAvailable(d) => d.handle_key(*key, tx),
+            ActiveDialog::Recovery(d) => d.handle_key(*key, tx),
         }
     }
 
@@ -296,6 +306,7 @@ impl Component for ActiveDialog {
             ActiveDialog::Sort(d) => d.render(f, rect, theme, focused),
             ActiveDialog::ThemePicker(d) => d.render(f, rect, theme, focused),
             ActiveDialog::UpdateAvailable(d) => d.render(f, rect, theme, focused),
+            ActiveDialog::Recovery(d) => d.render(f, rect, theme, focused),
         }
     }
 }
@@ -0,0 +1,267 @@
+use std::sync::Arc;
+
+use kimun_core::NoteVault;
+use kimun_core::nfs::VaultPath;
+use ratatui::Frame;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::Paragraph;
+
+use crate::components::Component;
+use crate::components::event_state::EventState;
+use crate::components::events::{AppEvent, AppTx, FileOp, OverlayData};
+use crate::components::panel::{ModalSpec, modal_chrome};
+use crate::settings::themes::Theme;
+
+/// Most note paths listed before the rest collapse into "…and N more".
+const MAX_LISTED: usize = 5;
+
+/// Dialog shown on launch when notes have recovery copies newer than the
+/// note itself — text a failed or interrupted save left aside (see
+/// `NoteVault::save_recovery`). Restoring
+/// saves each copy over its note; discarding deletes the copies; Esc keeps
+/// them for the next launch.
+///
+/// ```text
+/// ┌─ Unsaved Changes Found ──────────────────────────────┐
+/// │                                                      │
+/// │  2 notes were not saved when Kimün last closed.      │
+/// │  ────────────────────────────────────────────────    │
+/// │    /notes/plans.md                                   │
+/// │    /journal/2026-10-16.md                            │
+/// │                                                      │
+/// │  [R] Restore   [D] Discard   [Esc] Later             │
+/// └──────────────────────────────────────────────────────┘
+/// ```
+pub struct RecoveryDialog {
+    pub paths: Vec<VaultPath>,
+    pub vault: Arc<NoteVault>,
+    pub error: Option<String>,
+}
+
+impl RecoveryDialog {
+    pub fn new(paths: Vec<VaultPath>, vault: Arc<NoteVault>) -> Self {
+        Self {
+            paths,
+            vault,
+            error: None,
+        }
+    }
+
+    /// Handle a raw [`KeyEvent`]. Other keys are swallowed while open.
+    pub fn handle_key(&mut self, key: KeyEvent, tx: &AppTx) -> EventState {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('r') | KeyCode::Char('R') => {
+                let paths = self.paths.clone();
+                let vault = Arc::clone(&self.vault);
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    for path in &paths {
+                        if let Err(e) = vault.recover(path).await {
+                            tx.send(AppEvent::OverlayData(OverlayData::Error(format!(
+                                "{path}: {e}"
+                            ))))
+                            .ok();
+                            return;
+                        }
+                    }
+                    tx.send(AppEvent::CloseOverlay).ok();
+                    tx.send(AppEvent::FileOp(FileOp::Recovered(paths))).ok();
+                });
+                EventState::Consumed
+            }
+            KeyCode::Char('d') | KeyCode::Char('D') => {
+                let paths = self.paths.clone();
+                let vault = Arc::clone(&self.vault);
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    for path in &paths {
+                        if let Err(e) = vault.discard_recovery(path).await {
+                            tx.send(AppEvent::OverlayData(OverlayData::Error(e.to_string())))
+                                .ok();
+                            return;
+                        }
+                    }
+                    tx.send(AppEvent::CloseOverlay).ok();
+                    tx.send(AppEvent::FileOp(FileOp::RecoveryDiscarded(paths)))
+                        .ok();
+                });
+                EventState::Consumed
+            }
+            KeyCode::Esc => {
+                tx.send(AppEvent::CloseOverlay).ok();
+                EventState::Consumed
+            }
+            _ => EventState::Consumed,
+        }
+    }
+
+    /// The listed path rows, with an overflow row when there are more than
+    /// [`MAX_LISTED`].
+    fn listed_rows(&self) -> Vec<String> {
+        let mut rows: Vec<String> = self
+            .paths
+            .iter()
+            .take(MAX_LISTED)
+            .map(|p| format!("    {p}"))
+            .collect();
+        if self.paths.len() > MAX_LISTED {
+            rows.push(format!("    …and {} more", self.paths.len() - MAX_LISTED));
+        }
+        rows
+    }
+}
+
+impl Component for RecoveryDialog {
+    fn render(&mut self, f: &mut Frame, rect: Rect, theme: &Theme, _focused: bool) {
+        let listed = self.listed_rows();
+        let height = 8 + listed.len() as u16 + u16::from(self.error.is_some());
+        let popup_area = super::fixed_centered_rect(58, height, rect);
+
+        let inner = modal_chrome(
+            f,
+            popup_area,
+            theme,
+            ModalSpec {
+                title: Some(" Unsaved Changes Found "),
+                border: Some(Style::default().fg(theme.accent.to_ratatui())),
+                ..Default::default()
+            },
+        );
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),                   // 0: spacer
+                Constraint::Length(1),                   // 1: summary
+                Constraint::Length(1),                   // 2: separator
+                Constraint::Length(listed.len() as u16), // 3: paths
+                Constraint::Length(1),                   // 4: spacer
+                Constraint::Length(1),                   // 5: hint
+                Constraint::Length(1),                   // 6: error (may be unused)
+                Constraint::Min(0),                      // 7: remainder
+            ])
+            .split(inner);
+
+        let bg = theme.bg_panel.to_ratatui();
+        let fg = theme.fg.to_ratatui();
+        let gray = theme.gray.to_ratatui();
+
+        let summary = match self.paths.len() {
+            1 => "  1 note was not saved when Kimün last closed.".to_string(),
+            n => format!("  {n} notes were not saved when Kimün last closed."),
+        };
+        f.render_widget(
+            Paragraph::new(summary)
+                .style(Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD)),
+            rows[1],
+        );
+
+        super::render_separator(f, rows[2], gray, bg);
+
+        f.render_widget(
+            Paragraph::new(listed.join("\n")).style(Style::default().fg(fg).bg(bg)),
+            rows[3],
+        );
+
+        f.render_widget(
+            Paragraph::new("  [R] Restore   [D] Discard   [Esc] Later")
+                .style(Style::default().fg(gray).bg(bg)),
+            rows[5],
+        );
+
+        if let Some(msg) = &self.error {
+            super::render_error_row(f, rows[6], msg, theme);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kimun_core::VaultConfig;
+    use ratatui::crossterm::event::KeyModifiers;
+    use tokio::sync::mpsc;
+
+    async fn vault_with_recovery(dir: &std::path::Path, paths: &[&str]) -> Arc<NoteVault> {
+        let vault = NoteVault::new(VaultConfig::new(dir)).await.unwrap();
+        vault.validate_and_init().await.unwrap();
+        for path in paths {
+            vault
+                .save_recovery(&VaultPath::new(path), format!("recovered {path}"))
+                .await
+                .unwrap();
+        }
+        Arc::new(vault)
+    }
+
+    #[tokio::test]
+    async fn long_lists_collapse_into_an_overflow_row() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let vault = vault_with_recovery(dir.path(), &[]).await;
+        let paths: Vec<VaultPath> = (0..7)
+            .map(|i| VaultPath::new(format!("/n{i}.md")))
+            .collect();
+        let dialog = RecoveryDialog::new(paths, vault);
+
+        let rows = dialog.listed_rows();
+        assert_eq!(rows.len(), MAX_LISTED + 1);
+        assert_eq!(rows[0], "    /n0.md");
+        assert_eq!(rows[MAX_LISTED], "    …and 2 more");
+    }
+
+    #[tokio::test]
+    async fn restore_saves_every_copy_and_reports_the_paths() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let vault = vault_with_recovery(dir.path(), &["/a.md", "/b.md"]).await;
+        let paths: Vec<VaultPath> = vault
+            .list_recovery()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.path)
+            .collect();
+        let mut dialog = RecoveryDialog::new(paths.clone(), vault.clone());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let state = dialog.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &tx);
+        assert_eq!(state, EventState::Consumed);
+
+        assert!(matches!(rx.recv().await, Some(AppEvent::CloseOverlay)));
+        match rx.recv().await {
+            Some(AppEvent::FileOp(FileOp::Recovered(restored))) => assert_eq!(restored, paths),
+            other => panic!("expected Recovered, got {other:?}"),
+        }
+        assert!(vault.list_recovery().await.unwrap().is_empty());
+        assert_eq!(
+            vault.get_note_text(&VaultPath::new("/a.md")).await.unwrap(),
+            "recovered /a.md"
+        );
+    }
+
+    #[tokio::test]
+    async fn discard_drops_the_copies_without_restoring() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let vault = vault_with_recovery(dir.path(), &["/a.md"]).await;
+        let paths: Vec<VaultPath> = vault
+            .list_recovery()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.path)
+            .collect();
+        let mut dialog = RecoveryDialog::new(paths, vault.clone());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        dialog.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE), &tx);
+
+        assert!(matches!(rx.recv().await, Some(AppEvent::CloseOverlay)));
+        assert!(vault.list_recovery().await.unwrap().is_empty());
+        assert!(!vault.exists(&VaultPath::new("/a.md")).await);
+    }
+}
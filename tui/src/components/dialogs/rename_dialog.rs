@@ -153,6 +153,14 @@ impl RenameDialog {
     }
 }
 
+/// The name `input` is stored under when sanitizing changes it (illegal
+/// characters replaced, trailing dots dropped, …); `None` when it is kept
+/// as typed.
+fn sanitized_name(input: &str) -> Option<String> {
+    let sanitized = VaultPath::sanitize(input);
+    (sanitized != input).then_some(sanitized)
+}
+
 // ---------------------------------------------------------------------------
 // Component trait
 // ---------------------------------------------------------------------------
@@ -261,16 +269,26 @@ impl Component for RenameDialog {
             indicator_rows[1],
         );
 
-        // Row 5: validation status text.
+        // Row 5: validation status text. An available name that sanitizing
+        // changes previews what it will actually be called.
         let (status_text, status_style) = match self.validation_state {
-            ValidationState::Idle => ("", Style::default()),
-            ValidationState::Pending => ("  Checking...", Style::default().fg(gray).bg(bg)),
-            ValidationState::Available => (
-                "  Available",
-                Style::default().fg(theme.green.to_ratatui()).bg(bg),
+            ValidationState::Idle => (String::new(), Style::default()),
+            ValidationState::Pending => (
+                "  Checking...".to_string(),
+                Style::default().fg(gray).bg(bg),
             ),
+            ValidationState::Available => match sanitized_name(self.input.value()) {
+                Some(name) => (
+                    format!("  Available as {name}"),
+                    Style::default().fg(theme.yellow.to_ratatui()).bg(bg),
+                ),
+                None => (
+                    "  Available".to_string(),
+                    Style::default().fg(theme.green.to_ratatui()).bg(bg),
+                ),
+            },
             ValidationState::Taken => (
-                "  Already exists",
+                "  Already exists".to_string(),
                 Style::default().fg(theme.red.to_ratatui()).bg(bg),
             ),
         };
@@ -358,6 +376,13 @@ mod tests {
         assert_eq!(dialog.input.value(), expected_filename);
     }
 
+    #[test]
+    fn sanitized_name_only_reports_changed_names() {
+        assert_eq!(sanitized_name("plain name"), None);
+        assert_eq!(sanitized_name("What? Now"), Some("What_ Now".to_string()));
+        assert_eq!(sanitized_name("draft."), Some("draft".to_string()));
+    }
+
     /// Verifies that pressing `Esc` sends `AppEvent::CloseOverlay` and returns
     /// `EventState::Consumed`, without touching the vault.
     #[test]
@@ -19,11 +19,15 @@ impl WorkspaceSwitcherModal {
     pub fn new(settings: &AppSettings) -> Self {
         let mut workspaces: Vec<(String, bool)> = Vec::new();
         if let Some(ref wc) = settings.workspace_config {
+            // Recently opened first, then the rest alphabetically.
             let current = &wc.global.current_workspace;
-            let mut names: Vec<&String> = wc.workspaces.keys().collect();
-            names.sort();
-            for name in names {
-                workspaces.push((name.clone(), name == current));
+            let recent = wc.recent_workspaces();
+            let rest = wc
+                .workspaces
+                .keys()
+                .filter(|n| !recent.contains(&n.as_str()));
+            for name in recent.iter().copied().chain(rest.map(String::as_str)) {
+                workspaces.push((name.to_string(), name == current));
             }
         }
         let mut list_state = ListState::default();
@@ -243,6 +243,13 @@ pub enum FileOp {
     Renamed { from: VaultPath, to: VaultPath },
     /// Confirmation that an entry was successfully moved.
     Moved { from: VaultPath, to: VaultPath },
+    /// Notes found with recovery copies on launch — show the restore prompt.
+    ShowRecovery(Vec<VaultPath>),
+    /// Confirmation that these notes were restored from their recovery
+    /// copies.
+    Recovered(Vec<VaultPath>),
+    /// Confirmation that these notes' recovery copies were discarded.
+    RecoveryDiscarded(Vec<VaultPath>),
 }
 
 /// An async result addressed to the open overlay — **Overlay data** in
@@ -383,6 +390,9 @@ mod tests {
             AppEvent::FileOp(FileOp::Deleted(_)) => {}
             AppEvent::FileOp(FileOp::Renamed { from: _, to: _ }) => {}
             AppEvent::FileOp(FileOp::Moved { from: _, to: _ }) => {}
+            AppEvent::FileOp(FileOp::ShowRecovery(_)) => {}
+            AppEvent::FileOp(FileOp::Recovered(_)) => {}
+            AppEvent::FileOp(FileOp::RecoveryDiscarded(_)) => {}
             AppEvent::OverlayData(OverlayData::Error(_)) => {}
             AppEvent::Ask(AskData::AnswerReady {
                 turn_id: _,
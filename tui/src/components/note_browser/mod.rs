@@ -2,8 +2,8 @@ use std::sync::Arc;
 use std::sync::mpsc::Receiver;
 
 use chrono::NaiveDate;
-use kimun_core::NoteVault;
 use kimun_core::nfs::VaultPath;
+use kimun_core::{NoteMeta, NoteVault};
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Style;
@@ -59,9 +59,13 @@ pub struct NoteBrowserModal {
     vault: Arc<NoteVault>,
     tx: AppTx,
     preview_text: String,
+    /// Indexed facts about the previewed note (path, modified date, size,
+    /// tags), shown above its text. `None` while loading or for a note the
+    /// index doesn't know.
+    preview_meta: Option<NoteMeta>,
     // Preview async loading
     preview_task: Option<tokio::task::JoinHandle<()>>,
-    preview_rx: Option<Receiver<String>>,
+    preview_rx: Option<Receiver<(String, Option<NoteMeta>)>>,
     /// Path the preview pane is currently showing (or loading). Compared at
     /// render time against the engine's selected row so an async server-side
     /// reload that auto-selects a different row still refreshes the preview.
@@ -163,6 +167,7 @@ impl NoteBrowserModal {
             vault,
             tx,
             preview_text: String::new(),
+            preview_meta: None,
             preview_task: None,
             preview_rx: None,
             preview_path: None,
@@ -204,7 +209,12 @@ impl NoteBrowserModal {
 
         let handle = tokio::spawn(async move {
             let text = vault.get_note_text(&path).await.unwrap_or_default();
-            result_tx.send(text).ok();
+            let meta = vault
+                .metadata_for(std::slice::from_ref(&path))
+                .await
+                .ok()
+                .and_then(|metas| metas.into_iter().next());
+            result_tx.send((text, meta)).ok();
             tx.send(AppEvent::Redraw).ok();
         });
         self.preview_task = Some(handle);
@@ -213,8 +223,9 @@ impl NoteBrowserModal {
     fn poll_preview(&mut self) {
         let Some(rx) = &self.preview_rx else { return };
         match rx.try_recv() {
-            Ok(text) => {
+            Ok((text, meta)) => {
                 self.preview_text = text;
+                self.preview_meta = meta;
                 self.preview_rx = None;
                 self.preview_task = None;
             }
@@ -236,6 +247,7 @@ impl NoteBrowserModal {
             self.schedule_preview(path);
         } else {
             self.preview_text.clear();
+            self.preview_meta = None;
             if let Some(h) = self.preview_task.take() {
                 h.abort();
             }
@@ -259,6 +271,7 @@ impl NoteBrowserModal {
             Some(path) => self.schedule_preview(path),
             None => {
                 self.preview_text.clear();
+                self.preview_meta = None;
                 if let Some(h) = self.preview_task.take() {
                     h.abort();
                 }
@@ -512,8 +525,22 @@ impl Overlay for NoteBrowserModal {
             .borders(Borders::ALL)
             .border_style(theme.border_style(false))
             .style(modal_style);
-        let preview_inner = preview_block.inner(columns[1]);
+        let mut preview_inner = preview_block.inner(columns[1]);
         f.render_widget(preview_block, columns[1]);
+        if let Some(meta) = &self.preview_meta {
+            let lines = preview_meta_lines(meta);
+            let height = (lines.len() as u16 + 1).min(preview_inner.height);
+            let parts = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(height), Constraint::Min(0)])
+                .split(preview_inner);
+            f.render_widget(
+                Paragraph::new(lines.join("\n"))
+                    .style(modal_style.fg(theme.fg_secondary.to_ratatui())),
+                parts[0],
+            );
+            preview_inner = parts[1];
+        }
         f.render_widget(
             Paragraph::new(highlight_matches(
                 &self.preview_text,
@@ -558,6 +585,25 @@ impl Overlay for NoteBrowserModal {
 // Shared helpers
 // ---------------------------------------------------------------------------
 
+/// The preview pane's metadata header: the note's path, then its modified
+/// date and size, then its tags when it has any.
+fn preview_meta_lines(meta: &NoteMeta) -> Vec<String> {
+    use crate::util::format::{format_mtime, human_size};
+    let mut lines = vec![
+        meta.path.display_name(),
+        format!(
+            "{} · {}",
+            format_mtime(meta.modified_secs),
+            human_size(meta.size)
+        ),
+    ];
+    if !meta.labels.is_empty() {
+        let tags: Vec<String> = meta.labels.iter().map(|l| format!("#{l}")).collect();
+        lines.push(tags.join(" "));
+    }
+    lines
+}
+
 pub(crate) fn format_journal_date(date: NaiveDate) -> String {
     date.format("%A, %B %-d, %Y").to_string()
 }
@@ -758,6 +804,57 @@ mod tests {
         );
     }
 
+    /// The preview loads the selected note's indexed metadata with its text.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn preview_loads_note_metadata() {
+        let vault = temp_vault("modal-meta").await;
+        vault.validate_and_init().await.unwrap();
+        let path = VaultPath::new("/a.md");
+        vault.create_note(&path, "# A\n#work hello").await.unwrap();
+        let settings = AppSettings::default();
+        let (tx, _rx) = unbounded_channel();
+        let mut modal = NoteBrowserModal::new(
+            "test",
+            BrowserScope::Query,
+            OneNoteSource { path: path.clone() },
+            vault,
+            settings.key_bindings.clone(),
+            settings.icons(),
+            tx,
+        );
+        modal.list.poll_until_idle().await;
+        modal.refresh_preview_from_list();
+        for _ in 0..200 {
+            modal.poll_preview();
+            if modal.preview_meta.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+
+        assert_eq!(modal.preview_text, "# A\n#work hello");
+        let meta = modal.preview_meta.as_ref().expect("metadata loaded");
+        assert_eq!(meta.path, path);
+        assert_eq!(meta.labels, ["work"]);
+    }
+
+    #[test]
+    fn preview_meta_lines_show_path_date_size_and_tags() {
+        let mut meta = NoteMeta {
+            path: VaultPath::new("/notes/a.md"),
+            title: "A".to_string(),
+            modified_secs: 0,
+            size: 2048,
+            labels: vec![],
+        };
+        assert_eq!(
+            preview_meta_lines(&meta),
+            ["notes/a.md", "1970-01-01 00:00 · 2.0 KB"]
+        );
+        meta.labels = vec!["idea".to_string(), "work".to_string()];
+        assert_eq!(preview_meta_lines(&meta)[2], "#idea #work");
+    }
+
     /// Pressing Esc closes the modal.
     #[tokio::test]
     async fn esc_closes_modal() {
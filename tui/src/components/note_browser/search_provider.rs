@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use kimun_core::NoteVault;
@@ -10,6 +11,10 @@ use crate::components::file_list::FileListEntry;
 use crate::components::query_vars::QueryContext;
 use crate::components::search_list::{Emit, ResolvingRowSource, RowSource, Unresolvable};
 
+/// How long the browser waits after a keystroke before searching: long enough
+/// that fast typing runs one full-text query instead of one per character.
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
 /// Build the note-browser search source: a [`SearchNotesProvider`] wrapped so it
 /// resolves `{note}` against `current_note` and falls back to the recent-notes
 /// (empty-query) view when a note-dependent query has no note to resolve
@@ -98,6 +103,10 @@ impl RowSource<FileListEntry> for SearchNotesProvider {
         };
         emit.replace(entries);
     }
+
+    fn query_debounce(&self) -> Duration {
+        SEARCH_DEBOUNCE
+    }
 }
 
 #[cfg(test)]
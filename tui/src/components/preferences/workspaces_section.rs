@@ -356,6 +356,7 @@ mod tests {
                     created: chrono::Utc::now(),
                     quick_note_path: None,
                     inbox_path: None,
+                    lowercase_paths: false,
                     resolved_path: None,
                 },
             );
@@ -368,6 +369,7 @@ mod tests {
                 mouse: true,
                 kimun_server_url: None,
                 kimun_server_token: None,
+                recent_workspaces: Vec::new(),
             },
             workspaces: ws_map,
         });
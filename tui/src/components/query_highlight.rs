@@ -15,7 +15,8 @@ use crate::settings::themes::Theme;
 
 /// Style for a token class, per the spec §9 role table mapped onto the real
 /// grammar: field keys yellow, tag values aqua, note targets blue, quoted
-/// green, date/number purple, negation red, plain terms fg.
+/// green, date/number purple, negation red, boolean operators orange, plain
+/// terms fg.
 fn class_style(class: QueryTokenClass, theme: &Theme) -> Style {
     match class {
         QueryTokenClass::Negation => Style::default().fg(theme.red.to_ratatui()),
@@ -27,6 +28,9 @@ fn class_style(class: QueryTokenClass, theme: &Theme) -> Style {
             Style::default().fg(theme.purple.to_ratatui())
         }
         QueryTokenClass::Term => Style::default().fg(theme.fg.to_ratatui()),
+        QueryTokenClass::Operator => Style::default()
+            .fg(theme.orange.to_ratatui())
+            .add_modifier(Modifier::BOLD),
         QueryTokenClass::Unterminated => Style::default()
             .fg(theme.red.to_ratatui())
             .add_modifier(Modifier::UNDERLINED),
@@ -114,6 +118,8 @@ pub fn emphasis_needles(query: &str) -> Vec<String> {
     let mut needles: Vec<String> = terms
         .terms
         .iter()
+        .chain(terms.any_terms.iter().flatten())
+        .chain(terms.proximity.iter().flat_map(|p| &p.words))
         .map(|t| t.to_lowercase())
         .chain(
             terms
@@ -131,8 +137,8 @@ pub fn emphasis_needles(query: &str) -> Vec<String> {
 }
 
 /// The one-line reason for the query's parse problem, if any — surfaced in
-/// the FIND header. The lenient grammar's only real error is an unterminated
-/// quote.
+/// the FIND header. The lenient grammar's only flagged problem is an
+/// unterminated quote (read literally by the parser).
 pub fn error_reason(query: &str) -> Option<&'static str> {
     kimun_core::query_has_unterminated_quote(query).then_some("unterminated quote")
 }
@@ -1,9 +1,12 @@
 //! Generation-stamped async-load lifecycle shared by one-shot and streamed
 //! delivery. A new load bumps the generation and aborts the prior task;
-//! `drain` discards any results stamped with a stale generation.
+//! `drain` discards any results stamped with a stale generation. A load may
+//! wait out a debounce window first, so a superseded one is aborted before it
+//! ever queries its source.
 
 use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::Duration;
 
 use super::seams::{Emit, Loaded, RowSource, SearchRow};
 
@@ -30,6 +33,17 @@ impl<R: SearchRow> LoadEngine<R> {
     }
 
     pub(super) fn start(&mut self, source: Arc<dyn RowSource<R>>, query: String) {
+        self.start_after(source, query, Duration::ZERO);
+    }
+
+    /// Like [`start`](Self::start), but the load only queries `source` once
+    /// `delay` has passed without another load starting.
+    pub(super) fn start_after(
+        &mut self,
+        source: Arc<dyn RowSource<R>>,
+        query: String,
+        delay: Duration,
+    ) {
         if let Some(t) = self.task.take() {
             t.abort();
         }
@@ -37,6 +51,9 @@ impl<R: SearchRow> LoadEngine<R> {
         self.loading = true;
         let emit = Emit::new(self.tx.clone(), self.generation, self.redraw.clone());
         self.task = Some(tokio::spawn(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
             source.load(&query, emit).await;
         }));
     }
@@ -145,6 +145,9 @@ pub struct SearchList<R: SearchRow> {
     /// [`Focus::List`], `handle_key` returns [`KeyReaction::ListVerb`]; the
     /// engine attaches no meaning to them.
     list_verbs: Vec<char>,
+    /// Builder override of the source's query debounce window; `None` uses
+    /// [`RowSource::query_debounce`].
+    query_debounce: Option<std::time::Duration>,
 }
 
 /// Mouse interaction result from [`SearchList::handle_mouse`].
@@ -173,6 +176,7 @@ pub struct SearchListBuilder<R: SearchRow> {
     intercept: Vec<KeyCombo>,
     icons: Icons,
     debounce: Option<std::time::Duration>,
+    query_debounce: Option<std::time::Duration>,
     highlight_query: bool,
     opening_focus: Focus,
     list_verbs: Vec<char>,
@@ -192,6 +196,7 @@ impl<R: SearchRow> SearchList<R> {
             intercept: Vec::new(),
             icons: Icons::new(false),
             debounce: None,
+            query_debounce: None,
             highlight_query: false,
             opening_focus: Focus::Input,
             list_verbs: Vec::new(),
@@ -269,6 +274,7 @@ impl<R: SearchRow> SearchList<R> {
             // registers verbs; otherwise the surface keeps plain Esc→Cancel.
             focus_enabled: b.opening_focus == Focus::List || !b.list_verbs.is_empty(),
             list_verbs: b.list_verbs,
+            query_debounce: b.query_debounce,
         }
     }
 
@@ -434,7 +440,11 @@ impl<R: SearchRow> SearchList<R> {
     /// local display. The generation guard in `LoadEngine` drops stale results.
     fn requery(&mut self) {
         if self.source.reload_on_query() {
-            self.loader.start(self.source.clone(), self.query.clone());
+            let delay = self
+                .query_debounce
+                .unwrap_or_else(|| self.source.query_debounce());
+            self.loader
+                .start_after(self.source.clone(), self.query.clone(), delay);
         }
         // Recompute now so the query-fresh leading row (and local filter, for
         // non-reload sources) reflect the new query in this frame. Reload
@@ -975,6 +985,12 @@ impl<R: SearchRow> SearchListBuilder<R> {
         self.debounce = Some(d);
         self
     }
+    /// Override the source's [`RowSource::query_debounce`] window for
+    /// query-driven reloads.
+    pub fn query_debounce(mut self, d: std::time::Duration) -> Self {
+        self.query_debounce = Some(d);
+        self
+    }
     pub fn build(self) -> SearchList<R> {
         SearchList::new(self)
     }
@@ -1389,6 +1405,32 @@ mod tests {
         assert_eq!(list.selected_row().unwrap().name, "alpha");
     }
 
+    /// A burst of keystrokes inside the source's debounce window runs only the
+    /// last query; the initial load doesn't wait.
+    #[tokio::test]
+    async fn debounced_source_loads_only_the_last_query() {
+        struct Recording(Arc<std::sync::Mutex<Vec<String>>>);
+        #[async_trait::async_trait]
+        impl RowSource<TestRow> for Recording {
+            async fn load(&self, query: &str, emit: Emit<TestRow>) {
+                self.0.lock().unwrap().push(query.to_string());
+                emit.replace(vec![TestRow::new(query)]);
+            }
+            fn query_debounce(&self) -> std::time::Duration {
+                std::time::Duration::from_millis(30)
+            }
+        }
+        let loaded = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut list = SearchList::builder(Recording(loaded.clone()), noop_redraw()).build();
+        list.poll_until_idle().await;
+        for q in ["a", "ab", "abc"] {
+            list.set_query(q);
+        }
+        list.poll_until_idle().await;
+        assert_eq!(*loaded.lock().unwrap(), ["", "abc"]);
+        assert_eq!(list.rows()[0].name, "abc");
+    }
+
     #[tokio::test]
     async fn streamed_rows_arrive_then_done_and_filter_locally() {
         let src = ScriptedStreamSource {
@@ -75,6 +75,10 @@ impl<R: SearchRow> RowSource<R> for ResolvingRowSource<R> {
     fn reload_on_query(&self) -> bool {
         self.inner.reload_on_query()
     }
+
+    fn query_debounce(&self) -> std::time::Duration {
+        self.inner.query_debounce()
+    }
 }
 
 #[cfg(test)]
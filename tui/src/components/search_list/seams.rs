@@ -3,6 +3,7 @@
 //! engine.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use ratatui::widgets::ListItem;
@@ -117,6 +118,13 @@ pub trait SuggestionSource: Send + Sync + 'static {
     async fn notes_by_prefix(&self, prefix: &str, limit: usize) -> Vec<SuggestionItem>;
     async fn tags_by_prefix(&self, prefix: &str, limit: usize) -> Vec<SuggestionItem>;
 
+    /// Wikilink targets for `query`, matched against note names and titles.
+    /// `display` is the name inserted on accept. Defaults to
+    /// [`notes_by_prefix`](Self::notes_by_prefix).
+    async fn links_matching(&self, query: &str, limit: usize) -> Vec<SuggestionItem> {
+        self.notes_by_prefix(query, limit).await
+    }
+
     /// Saved searches whose name matches `prefix` (case-insensitive). Each
     /// item's `display` is the name and `secondary` the stored query — the
     /// popup preview AND the text inserted on accept.
@@ -148,6 +156,26 @@ impl SuggestionSource for VaultSuggestions {
             })
             .unwrap_or_default()
     }
+    async fn links_matching(&self, query: &str, limit: usize) -> Vec<SuggestionItem> {
+        // The title leads the secondary line: a note found by its title
+        // would otherwise show only an unrelated-looking filename.
+        self.vault
+            .suggest_links(query, limit)
+            .await
+            .map(|v| {
+                v.into_iter()
+                    .map(|n| SuggestionItem {
+                        display: n.name,
+                        secondary: Some(if n.title.is_empty() {
+                            n.path.to_string()
+                        } else {
+                            format!("{} · {}", n.title, n.path)
+                        }),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
     async fn tags_by_prefix(&self, prefix: &str, limit: usize) -> Vec<SuggestionItem> {
         self.vault
             .suggest_tags_by_prefix(prefix, limit)
@@ -203,6 +231,15 @@ pub trait RowSource<R: SearchRow>: Send + Sync + 'static {
     fn reload_on_query(&self) -> bool {
         true
     }
+
+    /// How long a query keystroke waits before re-running `load` (default:
+    /// not at all). A source backed by a costly query sets a short window so
+    /// a burst of typing only runs the last query: each keystroke restarts
+    /// the window, aborting the load still waiting on it. The initial load
+    /// and explicit reloads never wait.
+    fn query_debounce(&self) -> Duration {
+        Duration::ZERO
+    }
 }
 
 /// A [`RowSource`] for the synchronous build path: its rows are supplied at
@@ -62,6 +62,11 @@ pub enum ActionShortcuts {
     /// The command palette (Ctrl+Shift+P by default): every leader command
     /// as a fuzzy list.
     OpenCommandPalette,
+    // Note history (editor screen)
+    GoBack,
+    GoForward,
+    /// Browse the directory holding the open note.
+    GoToParent,
 }
 
 impl ActionShortcuts {
@@ -77,7 +82,10 @@ impl ActionShortcuts {
             | ActionShortcuts::ToggleQueryPanel
             | ActionShortcuts::OpenSavedSearches
             | ActionShortcuts::SaveCurrentQuery
-            | ActionShortcuts::SwitchWorkspace => ShortcutCategory::Navigation,
+            | ActionShortcuts::SwitchWorkspace
+            | ActionShortcuts::GoBack
+            | ActionShortcuts::GoForward
+            | ActionShortcuts::GoToParent => ShortcutCategory::Navigation,
 
             ActionShortcuts::SearchNotes
             | ActionShortcuts::OpenNote
@@ -117,6 +125,9 @@ impl ActionShortcuts {
             ActionShortcuts::FindInBuffer => "Find in note".into(),
             ActionShortcuts::Leader => "Leader menu".into(),
             ActionShortcuts::OpenCommandPalette => "Command palette".into(),
+            ActionShortcuts::GoBack => "Back".into(),
+            ActionShortcuts::GoForward => "Forward".into(),
+            ActionShortcuts::GoToParent => "Go to parent directory".into(),
             ActionShortcuts::Text(ta) => match ta {
                 TextAction::Bold => "Bold".into(),
                 TextAction::Italic => "Italic".into(),
@@ -156,6 +167,9 @@ impl Display for ActionShortcuts {
             ActionShortcuts::FindInBuffer => "FindInBuffer".to_string(),
             ActionShortcuts::Leader => "Leader".to_string(),
             ActionShortcuts::OpenCommandPalette => "OpenCommandPalette".to_string(),
+            ActionShortcuts::GoBack => "GoBack".to_string(),
+            ActionShortcuts::GoForward => "GoForward".to_string(),
+            ActionShortcuts::GoToParent => "GoToParent".to_string(),
         };
         write!(f, "{}", action)
     }
@@ -195,6 +209,9 @@ impl TryFrom<String> for ActionShortcuts {
             "FindInBuffer" => ActionShortcuts::FindInBuffer,
             "Leader" => ActionShortcuts::Leader,
             "OpenCommandPalette" => ActionShortcuts::OpenCommandPalette,
+            "GoBack" => ActionShortcuts::GoBack,
+            "GoForward" => ActionShortcuts::GoForward,
+            "GoToParent" => ActionShortcuts::GoToParent,
             _ => {
                 if let Some(text_action) = value.strip_prefix("TextEditor-") {
                     match TextAction::try_from(text_action.to_string()) {
@@ -376,6 +393,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn navigation_history_roundtrip() {
+        for action in [
+            ActionShortcuts::GoBack,
+            ActionShortcuts::GoForward,
+            ActionShortcuts::GoToParent,
+        ] {
+            assert_eq!(action.category(), ShortcutCategory::Navigation);
+            assert_eq!(ActionShortcuts::try_from(action.to_string()), Ok(action));
+        }
+    }
+
     #[test]
     fn file_operations_roundtrip() {
         assert_eq!(
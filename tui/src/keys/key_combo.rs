@@ -1,4 +1,4 @@
-use std::{fmt::Display, hash::Hash};
+use std::{fmt::Display, hash::Hash, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
@@ -58,6 +58,25 @@ impl TryFrom<String> for KeyCombo {
     }
 }
 
+/// Parses a combo as the user would type it: the serialized
+/// `mod1+mod2 & Key` form or the plain `ctrl+shift+k` form. Modifiers are
+/// case-insensitive; unknown modifiers and keys are errors naming the culprit.
+impl FromStr for KeyCombo {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (modifiers, key) = match s.rsplit_once('&') {
+            Some((m, k)) => (m, k),
+            None => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+        Ok(KeyCombo {
+            modifiers: modifiers.parse()?,
+            key: key.parse()?,
+        })
+    }
+}
+
 impl From<KeyCombo> for String {
     fn from(value: KeyCombo) -> Self {
         value.to_string()
@@ -121,6 +140,17 @@ impl KeyCombo {
         self.is_letter_chord() || is_symbol_combo || self.key.is_fkey()
     }
 
+    /// User-facing rendering, e.g. `Ctrl+Shift+K` or `⌘+N` on macOS (see
+    /// [`KeyModifiers::label`]). `Display` stays the config-file format.
+    pub fn label(&self) -> String {
+        let modifiers = self.modifiers.label();
+        if modifiers.is_empty() {
+            self.key.to_string()
+        } else {
+            format!("{}+{}", modifiers, self.key)
+        }
+    }
+
     /// Ctrl/Alt (optional shift) plus a letter key — the chord shape both
     /// the binding validator and the editor's footer chord flash test for.
     /// One predicate so the two sites cannot drift.
@@ -160,6 +190,12 @@ const META_CMD: &str = CMD;
 #[cfg(not(target_os = "macos"))]
 const META_CMD: &str = META;
 
+// User-facing names (see `KeyModifiers::label`).
+#[cfg(target_os = "macos")]
+const META_CMD_LABEL: &str = "⌘";
+#[cfg(not(target_os = "macos"))]
+const META_CMD_LABEL: &str = "Meta";
+
 impl TryFrom<String> for KeyModifiers {
     type Error = String;
 
@@ -181,6 +217,19 @@ impl TryFrom<String> for KeyModifiers {
     }
 }
 
+impl FromStr for KeyModifiers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s
+            .split('+')
+            .map(|m| m.trim().to_lowercase())
+            .collect::<Vec<_>>()
+            .join("+");
+        KeyModifiers::try_from(normalized)
+    }
+}
+
 impl From<KeyModifiers> for String {
     fn from(value: KeyModifiers) -> Self {
         value.to_string()
@@ -231,6 +280,33 @@ impl KeyModifiers {
         KeyModifiers::default()
     }
 
+    /// The modifier the default bindings are built on. Ctrl on every
+    /// platform: terminals don't pass Cmd/Meta through to the app, so a
+    /// macOS user presses Ctrl here too.
+    pub fn primary() -> Self {
+        KeyModifiers::new().and_ctrl()
+    }
+
+    /// User-facing rendering: `Ctrl+Alt+Shift`, with Cmd/Meta shown as `⌘`
+    /// on macOS and `Meta` elsewhere. Empty when no modifier is set.
+    /// `Display` stays the lowercase config-file format.
+    pub fn label(&self) -> String {
+        let mut labels = vec![];
+        if self.is_ctrl() {
+            labels.push("Ctrl");
+        }
+        if self.is_alt() {
+            labels.push("Alt");
+        }
+        if self.is_meta_cmd() {
+            labels.push(META_CMD_LABEL);
+        }
+        if self.is_shift() {
+            labels.push("Shift");
+        }
+        labels.join("+")
+    }
+
     pub fn is_empty(&self) -> bool {
         !self.alt && !self.ctrl && !self.cmd && !self.shift
     }
@@ -389,6 +465,66 @@ mod tests {
         assert_eq!(parsed, kc);
     }
 
+    #[test]
+    fn primary_modifier_is_ctrl() {
+        assert_eq!(KeyModifiers::primary(), KeyModifiers::new().and_ctrl());
+        assert_eq!(KeyModifiers::primary().label(), "Ctrl");
+    }
+
+    #[test]
+    fn label_renders_for_users() {
+        let kc = KeyCombo::new(KeyModifiers::new().and_shift().and_ctrl(), KeyStrike::KeyK);
+        assert_eq!(kc.label(), "Ctrl+Shift+K");
+        assert_eq!(
+            KeyCombo::new(KeyModifiers::default(), KeyStrike::F4).label(),
+            "F4"
+        );
+
+        let meta = KeyModifiers::new().and_meta_cmd().label();
+        #[cfg(target_os = "macos")]
+        assert_eq!(meta, "⌘");
+        #[cfg(not(target_os = "macos"))]
+        assert_eq!(meta, "Meta");
+    }
+
+    #[test]
+    fn from_str_round_trips_display() {
+        for kc in [
+            KeyCombo::new(KeyModifiers::new().and_ctrl(), KeyStrike::KeyQ),
+            KeyCombo::new(KeyModifiers::new().and_ctrl().and_shift(), KeyStrike::KeyL),
+            KeyCombo::new(KeyModifiers::new().and_alt(), KeyStrike::Comma),
+            KeyCombo::new(KeyModifiers::new().and_meta_cmd(), KeyStrike::KeyN),
+            KeyCombo::new(KeyModifiers::default(), KeyStrike::F4),
+            KeyCombo::new(KeyModifiers::default(), KeyStrike::Tab),
+        ] {
+            assert_eq!(kc.to_string().parse::<KeyCombo>(), Ok(kc));
+        }
+    }
+
+    #[test]
+    fn from_str_accepts_typed_combos() {
+        let expected = KeyCombo::new(KeyModifiers::new().and_ctrl().and_shift(), KeyStrike::KeyK);
+        assert_eq!("ctrl+shift+k".parse::<KeyCombo>(), Ok(expected));
+        assert_eq!("Ctrl + Shift + K".parse::<KeyCombo>(), Ok(expected));
+        assert_eq!("ctrl+shift & K".parse::<KeyCombo>(), Ok(expected));
+        assert_eq!(
+            "f7".parse::<KeyCombo>(),
+            Ok(KeyCombo::new(KeyModifiers::default(), KeyStrike::F7))
+        );
+    }
+
+    #[test]
+    fn from_str_names_the_bad_part() {
+        assert_eq!(
+            "hyper+k".parse::<KeyCombo>(),
+            Err("Non valid modifier value: hyper".to_string())
+        );
+        assert_eq!(
+            "ctrl+banana".parse::<KeyCombo>(),
+            Err("Unknown key: 'banana'".to_string())
+        );
+    }
+
     #[test]
     fn deserialize_legacy_no_modifier_with_ampersand() {
         // Old config files wrote " & <Tab>" for no-modifier Tab — must still parse.
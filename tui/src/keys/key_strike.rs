@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
@@ -474,6 +474,49 @@ impl TryFrom<String> for KeyStrike {
     }
 }
 
+/// Parses a key as the user would type it: the serialized form (`K`, `F2`,
+/// `<Enter>`), a lowercase letter, or a bare key name (`enter`, `f1`, `esc`).
+/// Unlike `TryFrom<String>` — which maps anything unrecognized to
+/// [`KeyStrike::Unknown`] so old configs keep loading — this rejects it.
+impl FromStr for KeyStrike {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let named = match s.to_lowercase().as_str() {
+            "backspace" => Some(KeyStrike::Backspace),
+            "enter" | "return" => Some(KeyStrike::Enter),
+            "space" => Some(KeyStrike::Space),
+            "tab" => Some(KeyStrike::Tab),
+            "del" | "delete" => Some(KeyStrike::Delete),
+            "end" => Some(KeyStrike::End),
+            "home" => Some(KeyStrike::Home),
+            "insert" => Some(KeyStrike::Insert),
+            "pgdn" | "pagedown" => Some(KeyStrike::PageDown),
+            "pgup" | "pageup" => Some(KeyStrike::PageUp),
+            "down" => Some(KeyStrike::ArrowDown),
+            "left" => Some(KeyStrike::ArrowLeft),
+            "right" => Some(KeyStrike::ArrowRight),
+            "up" => Some(KeyStrike::ArrowUp),
+            "esc" | "escape" => Some(KeyStrike::Escape),
+            "f1" | "<f1>" => Some(KeyStrike::F1),
+            _ => None,
+        };
+        if let Some(key) = named {
+            return Ok(key);
+        }
+        let candidate = if s.len() > 1 && s.starts_with(['f', 'F']) {
+            s.to_uppercase()
+        } else {
+            s.to_string()
+        };
+        match KeyStrike::try_from(candidate) {
+            Ok(KeyStrike::Unknown) | Err(_) => Err(format!("Unknown key: '{}'", s)),
+            Ok(key) => Ok(key),
+        }
+    }
+}
+
 impl From<KeyStrike> for String {
     fn from(value: KeyStrike) -> Self {
         value.to_string()
@@ -570,4 +613,35 @@ mod fkey_tests {
         assert!(!KeyStrike::KeyA.is_fkey());
         assert!(!KeyStrike::Escape.is_fkey());
     }
+
+    #[test]
+    fn from_str_round_trips_display() {
+        for key in [
+            KeyStrike::KeyA,
+            KeyStrike::KeyZ,
+            KeyStrike::Digit5,
+            KeyStrike::Comma,
+            KeyStrike::Backslash,
+            KeyStrike::Enter,
+            KeyStrike::Tab,
+            KeyStrike::ArrowUp,
+            KeyStrike::Escape,
+            KeyStrike::F1,
+            KeyStrike::F12,
+        ] {
+            assert_eq!(key.to_string().parse::<KeyStrike>(), Ok(key));
+        }
+    }
+
+    #[test]
+    fn from_str_accepts_typed_names_and_rejects_unknown_keys() {
+        assert_eq!("k".parse::<KeyStrike>(), Ok(KeyStrike::KeyK));
+        assert_eq!("f5".parse::<KeyStrike>(), Ok(KeyStrike::F5));
+        assert_eq!("Enter".parse::<KeyStrike>(), Ok(KeyStrike::Enter));
+        assert_eq!(
+            "hyper".parse::<KeyStrike>(),
+            Err("Unknown key: 'hyper'".to_string())
+        );
+        assert!("".parse::<KeyStrike>().is_err());
+    }
 }
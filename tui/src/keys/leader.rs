@@ -28,6 +28,9 @@ pub enum LeaderAction {
     // +note
     NoteNew,
     NoteDaily,
+    /// Open the closest earlier / later existing journal entry.
+    NoteDailyPrev,
+    NoteDailyNext,
     NoteFromTemplate,
     NoteRename,
     NoteMove,
@@ -46,6 +49,8 @@ pub enum LeaderAction {
     VaultConfig,
     VaultTheme,
     VaultPreferences,
+    /// Write the key bindings cheat sheet note and open it.
+    VaultShortcutsNote,
     // +window
     WindowZen,
     WindowSplit,
@@ -101,6 +106,8 @@ impl LeaderAction {
             LeaderAction::FindHeadings => "find.headings",
             LeaderAction::NoteNew => "note.new",
             LeaderAction::NoteDaily => "note.daily",
+            LeaderAction::NoteDailyPrev => "note.daily-prev",
+            LeaderAction::NoteDailyNext => "note.daily-next",
             LeaderAction::NoteFromTemplate => "note.template",
             LeaderAction::NoteRename => "note.rename",
             LeaderAction::NoteMove => "note.move",
@@ -118,6 +125,7 @@ impl LeaderAction {
             LeaderAction::VaultConfig => "vault.config",
             LeaderAction::VaultTheme => "vault.theme",
             LeaderAction::VaultPreferences => "vault.settings",
+            LeaderAction::VaultShortcutsNote => "vault.shortcuts-note",
             LeaderAction::WindowZen => "window.zen",
             LeaderAction::WindowSplit => "window.split",
             LeaderAction::WindowGrowDrawer => "window.grow",
@@ -143,7 +151,7 @@ impl LeaderAction {
     }
 
     /// Every action, for id lookup and docs.
-    pub const ALL: [LeaderAction; 52] = [
+    pub const ALL: [LeaderAction; 55] = [
         LeaderAction::OpenDrawer(DrawerView::Files),
         LeaderAction::OpenDrawer(DrawerView::Find),
         LeaderAction::OpenDrawer(DrawerView::Tags),
@@ -159,6 +167,8 @@ impl LeaderAction {
         LeaderAction::FindHeadings,
         LeaderAction::NoteNew,
         LeaderAction::NoteDaily,
+        LeaderAction::NoteDailyPrev,
+        LeaderAction::NoteDailyNext,
         LeaderAction::NoteFromTemplate,
         LeaderAction::NoteRename,
         LeaderAction::NoteMove,
@@ -176,6 +186,7 @@ impl LeaderAction {
         LeaderAction::VaultConfig,
         LeaderAction::VaultTheme,
         LeaderAction::VaultPreferences,
+        LeaderAction::VaultShortcutsNote,
         LeaderAction::WindowZen,
         LeaderAction::WindowSplit,
         LeaderAction::WindowGrowDrawer,
@@ -227,6 +238,8 @@ impl LeaderAction {
             LeaderAction::FindHeadings => "headings",
             LeaderAction::NoteNew => "new note",
             LeaderAction::NoteDaily => "daily",
+            LeaderAction::NoteDailyPrev => "previous daily",
+            LeaderAction::NoteDailyNext => "next daily",
             LeaderAction::NoteFromTemplate => "from template",
             LeaderAction::NoteRename => "rename",
             LeaderAction::NoteMove => "move",
@@ -242,6 +255,7 @@ impl LeaderAction {
             LeaderAction::VaultConfig => "config",
             LeaderAction::VaultTheme => "theme picker",
             LeaderAction::VaultPreferences => "preferences",
+            LeaderAction::VaultShortcutsNote => "shortcuts note",
             LeaderAction::WindowZen => "zen",
             LeaderAction::WindowSplit => "split",
             LeaderAction::WindowGrowDrawer => "grow drawer",
@@ -344,6 +358,8 @@ pub fn leader_tree() -> LeaderNode {
                     children: vec![
                         ('n', leaf("new", A::NoteNew)),
                         ('d', leaf("daily", A::NoteDaily)),
+                        ('[', leaf("previous daily", A::NoteDailyPrev)),
+                        (']', leaf("next daily", A::NoteDailyNext)),
                         ('t', leaf("from template", A::NoteFromTemplate)),
                         ('r', leaf("rename", A::NoteRename)),
                         ('m', leaf("move", A::NoteMove)),
@@ -399,6 +415,7 @@ pub fn leader_tree() -> LeaderNode {
                         ('c', leaf("config", A::VaultConfig)),
                         ('t', leaf("theme picker", A::VaultTheme)),
                         ('p', leaf("preferences", A::VaultPreferences)),
+                        ('k', leaf("shortcuts note", A::VaultShortcutsNote)),
                         ('o', leaf("guided setup", A::AppOnboarding)),
                         ('u', leaf("check for updates", A::AppCheckUpdates)),
                     ],
@@ -1,20 +1,63 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+};
 
 use action_shortcuts::ActionShortcuts;
 use itertools::Itertools;
 use key_combo::{KeyCombo, KeyModifiers};
 use key_strike::KeyStrike;
+use kimun_core::{NoteVault, error::VaultError, nfs::VaultPath};
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers as CKeyMods};
 use serde::{Deserialize, Serialize, de::Visitor, ser::SerializeMap};
 
+/// Name of the generated key-bindings note, without its extension (see
+/// [`KeyBindings::save_cheat_sheet`]).
+pub const CHEAT_SHEET_NOTE: &str = "help";
+
+/// Frontmatter that opens every generated cheat sheet. Only a note carrying
+/// it is ever replaced by a later save.
+const CHEAT_SHEET_MARK: &str = "---\nkimun: cheat-sheet\n---\n";
+
 pub mod action_shortcuts;
 pub mod key_combo;
 pub mod key_strike;
 pub mod leader;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct KeyBindings {
     bindings: HashMap<KeyCombo, ActionShortcuts>,
+    /// Combos `from_hashmap` dropped because another action already held
+    /// them, with the action that lost. Reported by [`KeyBindings::validate`].
+    shadowed: Vec<(KeyCombo, ActionShortcuts)>,
+}
+
+/// A problem in a key-bindings map, as found by [`KeyBindings::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindingConflict {
+    /// `combo` was bound to several actions; only the first of `actions`
+    /// fires.
+    DuplicateCombo {
+        combo: KeyCombo,
+        actions: Vec<ActionShortcuts>,
+    },
+    /// An action the defaults bind has no combo left.
+    Unbound(ActionShortcuts),
+}
+
+impl Display for BindingConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BindingConflict::DuplicateCombo { combo, actions } => write!(
+                f,
+                "{} is bound to {}; only {} fires",
+                combo,
+                actions.iter().join(", "),
+                actions[0]
+            ),
+            BindingConflict::Unbound(action) => write!(f, "{} has no key binding", action),
+        }
+    }
 }
 
 impl Serialize for KeyBindings {
@@ -118,6 +161,16 @@ impl<'de> Deserialize<'de> for KeyBindings {
     }
 }
 
+/// Equal when they bind the same combos to the same actions; the `shadowed`
+/// diagnostics don't count.
+impl PartialEq for KeyBindings {
+    fn eq(&self, other: &Self) -> bool {
+        self.bindings == other.bindings
+    }
+}
+
+impl Eq for KeyBindings {}
+
 impl Display for KeyBindings {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut bindings: Vec<(ActionShortcuts, Vec<KeyCombo>)> = vec![];
@@ -155,6 +208,7 @@ impl KeyBindings {
     pub fn empty() -> Self {
         KeyBindings {
             bindings: HashMap::default(),
+            shadowed: Vec::new(),
         }
     }
 
@@ -187,12 +241,68 @@ impl KeyBindings {
         bindings
     }
 
+    /// Renders the bindings as a markdown cheat sheet: one table per
+    /// [`ShortcutCategory`](action_shortcuts::ShortcutCategory), in the same
+    /// order and with the same labels as the F1 help dialog.
+    pub fn to_markdown(&self) -> String {
+        let mut by_category: BTreeMap<_, Vec<(String, String)>> = BTreeMap::new();
+        for (action, combos) in self
+            .to_hashmap()
+            .into_iter()
+            .sorted_by_key(|(action, _)| action.to_string())
+        {
+            let keys = combos
+                .iter()
+                .map(|c| format!("`{}`", c.label().replace('|', "\\|")))
+                .join(" / ");
+            by_category
+                .entry(action.category())
+                .or_default()
+                .push((action.label(), keys));
+        }
+
+        let mut out = String::from("# Keyboard shortcuts\n");
+        for (category, rows) in by_category {
+            out.push_str(&format!(
+                "\n## {category}\n\n| Action | Keys |\n| ------ | ---- |\n"
+            ));
+            for (label, keys) in rows {
+                out.push_str(&format!("| {label} | {keys} |\n"));
+            }
+        }
+        out
+    }
+
+    /// Writes [`Self::to_markdown`] to [`CHEAT_SHEET_NOTE`] at the vault root,
+    /// replacing the previous copy, and returns the note's path. A note of
+    /// that name the user wrote (one without the generated frontmatter) is
+    /// left alone and the cheat sheet takes the next free name instead.
+    pub async fn save_cheat_sheet(&self, vault: &NoteVault) -> Result<VaultPath, VaultError> {
+        let wanted = VaultPath::note_path_from(CHEAT_SHEET_NOTE);
+        let mut taken = HashSet::new();
+        let mut path = wanted.clone();
+        while vault.exists(&path).await
+            && !vault
+                .get_note_text(&path)
+                .await?
+                .starts_with(CHEAT_SHEET_MARK)
+        {
+            taken.insert(path.get_name());
+            path = wanted.get_free_name(&taken);
+        }
+        let text = format!("{CHEAT_SHEET_MARK}\n{}", self.to_markdown());
+        vault.save_note(&path, text).await?;
+        Ok(path)
+    }
+
     pub fn from_hashmap(bindings: HashMap<ActionShortcuts, Vec<KeyCombo>>) -> KeyBindings {
         let mut kb = KeyBindings::empty();
         for (action, combos) in &bindings {
             tracing::debug!("from_hashmap: action={} combos={:?}", action, combos);
         }
-        for (action, combos) in bindings {
+        // Sorted so a combo claimed by two actions resolves the same way on
+        // every load; the loser is kept in `shadowed` for `validate`.
+        for (action, combos) in bindings.into_iter().sorted_by(|a, b| a.0.cmp(&b.0)) {
             for combo in combos {
                 let valid = combo.is_valid_binding();
                 tracing::debug!(
@@ -202,9 +312,7 @@ impl KeyBindings {
                     combo.modifiers,
                     valid
                 );
-                if valid {
-                    kb.bindings.insert(combo.to_owned(), action.to_owned());
-                } else {
+                if !valid {
                     tracing::warn!(
                         "Skipping invalid key combo '{}' for action '{}': \
                          only ctrl/alt (with optional shift) + a letter, digit, or \
@@ -212,11 +320,49 @@ impl KeyBindings {
                         combo,
                         action
                     );
+                } else if kb.bindings.get(&combo).is_some_and(|a| *a != action) {
+                    kb.shadowed.push((combo, action.to_owned()));
+                } else {
+                    kb.bindings.insert(combo, action.to_owned());
                 }
             }
         }
         kb
     }
+
+    /// Reports combos bound to more than one action (as loaded by
+    /// [`Self::from_hashmap`], e.g. from the config file) and actions the
+    /// defaults bind that are left without any combo.
+    pub fn validate(&self) -> Vec<BindingConflict> {
+        let mut duplicates: BTreeMap<KeyCombo, Vec<ActionShortcuts>> = BTreeMap::new();
+        for (combo, action) in &self.shadowed {
+            duplicates
+                .entry(*combo)
+                .or_insert_with(|| self.bindings.get(combo).into_iter().cloned().collect())
+                .push(action.to_owned());
+        }
+        let mut conflicts: Vec<BindingConflict> = duplicates
+            .into_iter()
+            .map(|(combo, actions)| BindingConflict::DuplicateCombo { combo, actions })
+            .collect();
+
+        let bound: std::collections::HashSet<&ActionShortcuts> = self.bindings.values().collect();
+        conflicts.extend(
+            crate::settings::default_keybindings()
+                .to_hashmap()
+                .into_keys()
+                .filter(|action| !bound.contains(action))
+                .sorted()
+                .map(BindingConflict::Unbound),
+        );
+        conflicts
+    }
+
+    /// Carries `other`'s shadowed combos over, for rebuilds that go through
+    /// [`Self::to_hashmap`] (which only sees the winning bindings).
+    pub(crate) fn keep_shadowed_from(&mut self, other: &KeyBindings) {
+        self.shadowed.extend(other.shadowed.iter().cloned());
+    }
 }
 
 /// Canonical default combo for [`ActionShortcuts::Quit`]. Sourced once so the
@@ -395,11 +541,67 @@ pub fn key_event_to_combo(event: &KeyEvent) -> Option<KeyCombo> {
 #[cfg(test)]
 mod tests {
     use super::{
-        KeyBindings,
+        BindingConflict, CHEAT_SHEET_MARK, CHEAT_SHEET_NOTE, KeyBindings, VaultPath,
         action_shortcuts::{ActionShortcuts, TextAction},
+        key_combo::{KeyCombo, KeyModifiers},
         key_strike::KeyStrike,
     };
 
+    #[test]
+    fn to_markdown_groups_bindings_by_category() {
+        let mut km = KeyBindings::empty();
+        km.batch_add()
+            .with_ctrl()
+            .add(KeyStrike::KeyQ, ActionShortcuts::Quit)
+            .add(KeyStrike::KeyB, ActionShortcuts::Text(TextAction::Bold))
+            .add(KeyStrike::KeyT, ActionShortcuts::ToggleSidebar)
+            .add(KeyStrike::KeyE, ActionShortcuts::OpenFileBrowser);
+        km.batch_add().add(KeyStrike::F7, ActionShortcuts::GoBack);
+        let md = km.to_markdown();
+
+        let nav = md.find("## Navigation").unwrap();
+        let text = md.find("## Text Editing").unwrap();
+        let other = md.find("## Other").unwrap();
+        assert!(md.starts_with("# Keyboard shortcuts\n"));
+        assert!(nav < text && text < other);
+        assert!(!md.contains("## Notes"), "empty categories are skipped");
+        assert!(md.contains("| Back | `F7` |"));
+        assert!(md.contains("| Bold | `Ctrl+B` |"));
+    }
+
+    #[tokio::test]
+    async fn save_cheat_sheet_overwrites_the_help_note() {
+        let vault = crate::test_support::temp_vault("cheat-sheet").await;
+        vault.validate_and_init().await.unwrap();
+        let mut km = KeyBindings::empty();
+        km.batch_add()
+            .with_ctrl()
+            .add(KeyStrike::KeyQ, ActionShortcuts::Quit);
+
+        let path = km.save_cheat_sheet(&vault).await.unwrap();
+        km.batch_add()
+            .with_ctrl()
+            .add(KeyStrike::KeyK, ActionShortcuts::SearchNotes);
+        assert_eq!(path, km.save_cheat_sheet(&vault).await.unwrap());
+
+        let text = vault.get_note_text(&path).await.unwrap();
+        assert_eq!(text, format!("{CHEAT_SHEET_MARK}\n{}", km.to_markdown()));
+    }
+
+    #[tokio::test]
+    async fn save_cheat_sheet_keeps_a_users_help_note() {
+        let vault = crate::test_support::temp_vault("cheat-sheet-user").await;
+        vault.validate_and_init().await.unwrap();
+        let own = VaultPath::note_path_from(CHEAT_SHEET_NOTE);
+        vault.create_note(&own, "# My help").await.unwrap();
+        let km = KeyBindings::empty();
+
+        let path = km.save_cheat_sheet(&vault).await.unwrap();
+        assert_eq!(path, VaultPath::note_path_from("help_1"));
+        assert_eq!(path, km.save_cheat_sheet(&vault).await.unwrap());
+        assert_eq!(vault.get_note_text(&own).await.unwrap(), "# My help");
+    }
+
     #[test]
     fn serialize_key_binding() {
         let mut km = KeyBindings::empty();
@@ -463,6 +665,69 @@ Quit = ["ctrl & Q"]
         assert_eq!(expected_km, km);
     }
 
+    #[test]
+    fn validate_reports_a_combo_bound_twice() {
+        let toml_str = r#"NewJournal = ["ctrl & N"]
+FollowLink = ["ctrl & N", "ctrl & Y"]
+Quit = ["ctrl & Q"]
+"#;
+        let km: KeyBindings = toml::from_str(toml_str).expect("should not error");
+        let ctrl_n = KeyCombo::new(KeyModifiers::new().and_ctrl(), KeyStrike::KeyN);
+
+        // The winner is stable (action order), not HashMap iteration order.
+        assert_eq!(km.get_action(&ctrl_n), Some(ActionShortcuts::NewJournal));
+        let duplicates: Vec<_> = km
+            .validate()
+            .into_iter()
+            .filter(|c| matches!(c, BindingConflict::DuplicateCombo { .. }))
+            .collect();
+        assert_eq!(
+            duplicates,
+            vec![BindingConflict::DuplicateCombo {
+                combo: ctrl_n,
+                actions: vec![ActionShortcuts::NewJournal, ActionShortcuts::FollowLink],
+            }]
+        );
+        assert_eq!(
+            duplicates[0].to_string(),
+            "ctrl&N is bound to NewJournal, FollowLink; only NewJournal fires"
+        );
+    }
+
+    #[test]
+    fn equality_ignores_shadowed_combos() {
+        let shadowing: KeyBindings = toml::from_str(
+            r#"NewJournal = ["ctrl & N"]
+FollowLink = ["ctrl & N", "ctrl & Y"]
+"#,
+        )
+        .unwrap();
+        let plain: KeyBindings = toml::from_str(
+            r#"NewJournal = ["ctrl & N"]
+FollowLink = ["ctrl & Y"]
+"#,
+        )
+        .unwrap();
+
+        assert_ne!(shadowing.validate(), plain.validate());
+        assert_eq!(shadowing, plain);
+    }
+
+    #[test]
+    fn validate_reports_actions_left_without_a_binding() {
+        let mut km = KeyBindings::empty();
+        km.batch_add()
+            .with_ctrl()
+            .add(KeyStrike::KeyQ, ActionShortcuts::Quit);
+        let conflicts = km.validate();
+
+        assert!(conflicts.contains(&BindingConflict::Unbound(ActionShortcuts::SearchNotes)));
+        assert!(!conflicts.contains(&BindingConflict::Unbound(ActionShortcuts::Quit)));
+        // No default binding, so never reported.
+        assert!(!conflicts.contains(&BindingConflict::Unbound(ActionShortcuts::ToggleQueryPanel)));
+        assert!(crate::settings::default_keybindings().validate().is_empty());
+    }
+
     #[test]
     fn deserialize_skips_entry_with_unknown_action() {
         let toml_str = r#"NewJournal = ["ctrl & N"]
@@ -248,12 +248,12 @@ async fn main() -> Result<()> {
 
 /// Build a fresh `NoteVault` for whatever workspace the settings currently
 /// resolve to, wiring the configured cache path and the workspace's inbox
-/// path. Returns `None` if no workspace is configured or the vault fails to
+/// path and naming settings. Returns `None` if no workspace is configured or the vault fails to
 /// open.
 async fn rebuild_vault(
     settings: &crate::settings::SharedSettings,
 ) -> Option<std::sync::Arc<kimun_core::NoteVault>> {
-    let (workspace_path, cache_path, inbox_path) = {
+    let (workspace_path, cache_path, inbox_path, lowercase) = {
         let s = settings.read().unwrap();
         let wp = s.resolve_workspace_path();
         let name = s.current_workspace_name();
@@ -263,10 +263,10 @@ async fn rebuild_vault(
             .as_ref()
             .and_then(|wc| wc.get_current_workspace())
             .map(|e| e.effective_inbox_path());
-        (wp, cache, ip)
+        (wp, cache, ip, s.lowercase_paths())
     };
     let workspace = workspace_path?;
-    let mut config = kimun_core::VaultConfig::new(&workspace);
+    let mut config = kimun_core::VaultConfig::new(&workspace).with_lowercase_paths(lowercase);
     if let Some(cp) = cache_path {
         config = config.with_db_path(cp);
     }
@@ -593,7 +593,7 @@ async fn handle_app_message(msg: AppEvent, app: &mut App, tx: &AppTx) -> io::Res
             {
                 let mut s = app.settings.write().unwrap();
                 if let Some(ref mut wc) = s.workspace_config {
-                    wc.global.current_workspace = name;
+                    wc.set_current_workspace(name);
                 }
                 s.save_to_disk().ok();
             }
@@ -236,17 +236,15 @@ impl ConfigMigration {
                             "failed to create cache dir {cache_dir:?}: {e}"
                         ))
                     })?;
-                    if let Err(rename_err) = std::fs::rename(&old_db, &new_db) {
-                        // EXDEV: source and destination on different filesystems —
-                        // rename(2) cannot cross mount points; fall back to copy + unlink.
-                        if rename_err.raw_os_error() == Some(libc_exdev_code()) {
-                            std::fs::copy(&old_db, &new_db)?;
-                            std::fs::remove_file(&old_db)?;
-                        } else {
-                            return Err(SettingsError::Migration(format!(
-                                "failed to move {:?} -> {:?}: {}",
-                                old_db, new_db, rename_err
-                            )));
+                    move_file(&old_db, &new_db)?;
+                    // The index runs in WAL mode: committed pages can still
+                    // live in the `-wal` sidecar, so it (and its `-shm`
+                    // index) must travel with the database or the moved
+                    // file is short of data.
+                    for suffix in ["-wal", "-shm"] {
+                        let old_sidecar = sidecar_path(&old_db, suffix);
+                        if old_sidecar.exists() {
+                            move_file(&old_sidecar, &sidecar_path(&new_db, suffix))?;
                         }
                     }
                     tracing::info!("migrated {:?} -> {:?}", old_db, new_db);
@@ -326,6 +324,7 @@ impl ConfigMigration {
                     created: chrono::Utc::now(),
                     quick_note_path: None,
                     inbox_path: None,
+                    lowercase_paths: false,
                     resolved_path: None,
                 };
                 wc.workspaces.insert(name, entry);
@@ -353,6 +352,30 @@ impl ConfigMigration {
     }
 }
 
+/// Moves `from` to `to`, falling back to copy + unlink when they sit on
+/// different filesystems (EXDEV: rename(2) cannot cross mount points).
+fn move_file(from: &std::path::Path, to: &std::path::Path) -> Result<(), SettingsError> {
+    if let Err(rename_err) = std::fs::rename(from, to) {
+        if rename_err.raw_os_error() == Some(libc_exdev_code()) {
+            std::fs::copy(from, to)?;
+            std::fs::remove_file(from)?;
+        } else {
+            return Err(SettingsError::Migration(format!(
+                "failed to move {:?} -> {:?}: {}",
+                from, to, rename_err
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// `db` with `suffix` appended to its file name, e.g. `kimun.sqlite-wal`.
+fn sidecar_path(db: &std::path::Path, suffix: &str) -> std::path::PathBuf {
+    let mut name = db.as_os_str().to_owned();
+    name.push(suffix);
+    std::path::PathBuf::from(name)
+}
+
 #[cfg(unix)]
 fn libc_exdev_code() -> i32 {
     18 // EXDEV on Linux
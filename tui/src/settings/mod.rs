@@ -194,7 +194,7 @@ pub struct AppSettings {
     pub config_file: Option<PathBuf>,
 }
 
-fn default_keybindings() -> KeyBindings {
+pub(crate) fn default_keybindings() -> KeyBindings {
     let mut kb = KeyBindings::empty();
     kb.batch_add()
         .with_ctrl()
@@ -276,6 +276,14 @@ fn default_keybindings() -> KeyBindings {
     kb.batch_add()
         .add(KeyStrike::F5, ActionShortcuts::SwitchWorkspace);
 
+    // Note history — F7/F8 back/forward, F9 up to the note's directory. The
+    // usual chords don't survive a terminal: Ctrl+[ is Esc, and Alt+arrows
+    // are the editor's word motions.
+    kb.batch_add()
+        .add(KeyStrike::F7, ActionShortcuts::GoBack)
+        .add(KeyStrike::F8, ActionShortcuts::GoForward)
+        .add(KeyStrike::F9, ActionShortcuts::GoToParent);
+
     // Ctrl+D — save the current query to saved searches. Ctrl-only by design:
     // Ctrl+Shift is unreliable on some terminals, Ctrl+S is taken by
     // Strikethrough, and Ctrl+{A,C,X,Z} are claimed by the editor. Ctrl+D is
@@ -527,7 +535,8 @@ impl AppSettings {
         let settings_file_path = Self::default_config_file_path()?;
 
         if !settings_file_path.exists() {
-            let default_settings = Self::default();
+            let mut default_settings = Self::default();
+            default_settings.resolve_paths(Self::config_dir_of(&settings_file_path));
             default_settings.save_to_disk()?;
             Ok(default_settings)
         } else {
@@ -539,10 +548,7 @@ impl AppSettings {
             match toml::from_str::<AppSettings>(toml.as_ref()) {
                 Ok(mut setting) => {
                     setting.config_file = Some(settings_file_path.clone());
-                    let config_dir = settings_file_path
-                        .parent()
-                        .unwrap_or(std::path::Path::new("."));
-                    setting.resolve_paths(config_dir);
+                    setting.resolve_paths(Self::config_dir_of(&settings_file_path));
                     if config_migration::ConfigMigration::run(&mut setting)? {
                         setting.save_to_disk()?;
                     }
@@ -558,7 +564,8 @@ impl AppSettings {
                     );
                     let corrupt_path = settings_file_path.with_extension("toml.corrupt");
                     let _ = fs::rename(&settings_file_path, &corrupt_path);
-                    let defaults = Self::default();
+                    let mut defaults = Self::default();
+                    defaults.resolve_paths(Self::config_dir_of(&settings_file_path));
                     defaults.save_to_disk()?;
                     Ok(defaults)
                 }
@@ -571,10 +578,9 @@ impl AppSettings {
             fs::create_dir_all(parent)?;
         }
         if !path.exists() {
-            let default_settings = Self {
-                config_file: Some(path),
-                ..Self::default()
-            };
+            let mut default_settings = Self::default();
+            default_settings.resolve_paths(Self::config_dir_of(&path));
+            default_settings.config_file = Some(path);
             default_settings.save_to_disk()?;
             return Ok(default_settings);
         }
@@ -585,8 +591,7 @@ impl AppSettings {
                 setting.config_file = Some(path.clone());
 
                 // Resolve ~ and relative paths against the config file's directory.
-                let config_dir = path.parent().unwrap_or(std::path::Path::new("."));
-                setting.resolve_paths(config_dir);
+                setting.resolve_paths(Self::config_dir_of(&path));
 
                 // Run config migrations (e.g. Phase 1 → Phase 2 workspace_dir).
                 if config_migration::ConfigMigration::run(&mut setting)? {
@@ -594,6 +599,9 @@ impl AppSettings {
                 }
 
                 setting.merge_missing_default_bindings();
+                for conflict in setting.key_bindings.validate() {
+                    tracing::warn!("key bindings: {conflict}");
+                }
                 Ok(setting)
             }
             Err(e) => {
@@ -605,10 +613,9 @@ impl AppSettings {
                 );
                 let corrupt_path = path.with_extension("toml.corrupt");
                 let _ = fs::rename(&path, &corrupt_path);
-                let defaults = Self {
-                    config_file: Some(path),
-                    ..Self::default()
-                };
+                let mut defaults = Self::default();
+                defaults.resolve_paths(Self::config_dir_of(&path));
+                defaults.config_file = Some(path);
                 defaults.save_to_disk()?;
                 Ok(defaults)
             }
@@ -646,7 +653,9 @@ impl AppSettings {
                 }
             }
         }
-        self.key_bindings = KeyBindings::from_hashmap(current);
+        let mut merged = KeyBindings::from_hashmap(current);
+        merged.keep_shadowed_from(&self.key_bindings);
+        self.key_bindings = merged;
     }
 
     // We set a new workspace to work with, remember to save the data
@@ -693,6 +702,23 @@ impl AppSettings {
             .or_else(|| self.workspace_dir.clone())
     }
 
+    /// Whether the current workspace lowercases the names of the notes and
+    /// directories it creates (see [`lowercase_paths`](workspace_config::WorkspaceEntry::lowercase_paths)).
+    /// Off when no workspace is configured.
+    pub fn lowercase_paths(&self) -> bool {
+        self.workspace_config
+            .as_ref()
+            .and_then(|wc| wc.get_current_workspace())
+            .is_some_and(|entry| entry.lowercase_paths)
+    }
+
+    /// Directory relative paths in the config file at `path` resolve against.
+    /// Fresh defaults use it too, so a first run never puts the cache in the
+    /// process's working directory.
+    fn config_dir_of(path: &std::path::Path) -> &std::path::Path {
+        path.parent().unwrap_or(std::path::Path::new("."))
+    }
+
     /// Resolve `~` and relative paths in workspace entries.
     /// Relative paths are resolved against `base` (typically the config file's
     /// parent directory). Called once after deserialization.
@@ -923,6 +949,37 @@ mod tests {
         assert_eq!(settings.autosave_interval_secs, 5);
     }
 
+    #[test]
+    fn lowercase_paths_is_read_from_the_current_workspace() {
+        let toml = r#"
+[global]
+current_workspace = "work"
+
+[workspaces.work]
+path = "/tmp/work"
+created = "2024-01-01T00:00:00Z"
+lowercase_paths = true
+
+[workspaces.home]
+path = "/tmp/home"
+created = "2024-01-01T00:00:00Z"
+"#;
+        let mut settings: AppSettings = toml::from_str(toml).unwrap();
+        assert!(settings.lowercase_paths());
+
+        // Off unless set, and left out of the file while off.
+        settings
+            .workspace_config
+            .as_mut()
+            .unwrap()
+            .global
+            .current_workspace = "home".into();
+        assert!(!settings.lowercase_paths());
+        let saved = toml::to_string(&settings).unwrap();
+        assert_eq!(saved.matches("lowercase_paths").count(), 1);
+        assert!(!AppSettings::default().lowercase_paths());
+    }
+
     /// Verify the full load path: TOML with FileOperations = ["F2"] → keybinding lookup.
     #[test]
     fn f2_file_operations_survives_toml_deserialize() {
@@ -1234,6 +1291,17 @@ created = "2026-01-01T00:00:00Z"
         assert!(entry.resolved_path.is_none());
         assert_eq!(*entry.effective_path(), PathBuf::from("/absolute/notes"));
     }
+
+    #[test]
+    fn fresh_config_puts_the_cache_next_to_it() {
+        let base = tempfile::TempDir::new().unwrap();
+        let settings = AppSettings::load_from_file(base.path().join("config.toml")).unwrap();
+
+        assert_eq!(
+            settings.cache_path_for("work").parent().unwrap(),
+            base.path().canonicalize().unwrap()
+        );
+    }
 }
 
 #[cfg(test)]
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+/// How many entries [`GlobalConfig::recent_workspaces`] keeps.
+pub const MAX_RECENT_WORKSPACES: usize = 10;
+
 #[derive(Debug, Clone)]
 pub enum WorkspaceConfigError {
     DuplicateWorkspace {
@@ -62,6 +65,11 @@ pub struct GlobalConfig {
     /// Bearer token for the RAG server, when it requires one.
     #[serde(default)]
     pub kimun_server_token: Option<String>,
+    /// Names of the workspaces switched to, most recent first, capped at
+    /// [`MAX_RECENT_WORKSPACES`]. Orders the workspace switcher; read through
+    /// [`WorkspaceConfig::recent_workspaces`], which skips stale entries.
+    #[serde(default)]
+    pub recent_workspaces: Vec<String>,
 }
 
 fn default_update_check() -> bool {
@@ -82,6 +90,11 @@ pub struct WorkspaceEntry {
     pub quick_note_path: Option<String>,
     #[serde(default)]
     pub inbox_path: Option<String>,
+    /// Give new notes and directories lowercase names on disk, as Kimün
+    /// used to. Off by default: names keep the capitalization they were
+    /// typed with. Lookups and links are case-insensitive either way.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub lowercase_paths: bool,
     /// Absolute resolved path for runtime use. Not serialized — `path` is
     /// written to disk as the user configured it (relative, ~/..., or absolute).
     #[serde(skip)]
@@ -125,6 +138,7 @@ impl WorkspaceConfig {
                 mouse: true,
                 kimun_server_url: None,
                 kimun_server_token: None,
+                recent_workspaces: Vec::new(),
             },
             workspaces: BTreeMap::new(),
         }
@@ -154,6 +168,7 @@ impl WorkspaceConfig {
             created: Utc::now(),
             quick_note_path: None,
             inbox_path: None,
+            lowercase_paths: false,
             resolved_path: None,
         };
 
@@ -176,6 +191,31 @@ impl WorkspaceConfig {
         self.workspaces.get(name)
     }
 
+    /// Makes `name` the current workspace and moves it to the front of the
+    /// recent list.
+    pub fn set_current_workspace(&mut self, name: String) {
+        let recent = &mut self.global.recent_workspaces;
+        recent.retain(|n| *n != name);
+        recent.insert(0, name.clone());
+        recent.truncate(MAX_RECENT_WORKSPACES);
+        self.global.current_workspace = name;
+    }
+
+    /// Recently opened workspace names, most recent first. Names no longer
+    /// configured (removed or renamed) or whose directory is gone are skipped.
+    pub fn recent_workspaces(&self) -> Vec<&str> {
+        self.global
+            .recent_workspaces
+            .iter()
+            .filter(|n| {
+                self.workspaces
+                    .get(*n)
+                    .is_some_and(|e| e.effective_path().is_dir())
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
     pub fn from_phase1_migration(workspace_dir: PathBuf, last_paths: Vec<String>) -> Self {
         let mut config = Self::new_empty();
 
@@ -185,6 +225,7 @@ impl WorkspaceConfig {
             created: Utc::now(),
             quick_note_path: None,
             inbox_path: None,
+            lowercase_paths: false,
             resolved_path: None,
         };
 
@@ -260,4 +301,34 @@ mod validate_tests {
             .unwrap();
         assert_eq!(wc.global.current_workspace, "fresh");
     }
+
+    #[test]
+    fn set_current_workspace_tracks_recent_order() {
+        let mut wc = WorkspaceConfig::new_empty();
+        for name in ["a", "b", "a", "c"] {
+            wc.set_current_workspace(name.to_string());
+        }
+        assert_eq!(wc.global.current_workspace, "c");
+        assert_eq!(wc.global.recent_workspaces, vec!["c", "a", "b"]);
+
+        for i in 0..MAX_RECENT_WORKSPACES {
+            wc.set_current_workspace(format!("w{i}"));
+        }
+        assert_eq!(wc.global.recent_workspaces.len(), MAX_RECENT_WORKSPACES);
+        assert!(!wc.global.recent_workspaces.contains(&"b".to_string()));
+    }
+
+    #[test]
+    fn recent_workspaces_skips_missing_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut wc = WorkspaceConfig::new_empty();
+        wc.add_workspace("here".to_string(), dir.path().to_path_buf())
+            .unwrap();
+        wc.add_workspace("gone".to_string(), dir.path().join("missing"))
+            .unwrap();
+        for name in ["here", "gone", "removed"] {
+            wc.set_current_workspace(name.to_string());
+        }
+        assert_eq!(wc.recent_workspaces(), vec!["here"]);
+    }
 }
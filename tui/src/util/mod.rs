@@ -1,4 +1,5 @@
 //! Editor-agnostic utilities shared across components.
 
+pub mod format;
 pub mod git_status;
 pub mod single_slot_task;
@@ -184,6 +184,50 @@ created = "2026-01-01T00:00:00Z"
     assert_eq!(lines, vec!["a.md", "b.md", "c.md"]);
 }
 
+#[test]
+fn v2_to_v3_moves_wal_sidecars_with_db() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let cfg_path = tmp.path().join("config.toml");
+    let workspace_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(workspace_dir.path().join("kimun.sqlite"), b"db").unwrap();
+    std::fs::write(workspace_dir.path().join("kimun.sqlite-wal"), b"wal").unwrap();
+    std::fs::write(workspace_dir.path().join("kimun.sqlite-shm"), b"shm").unwrap();
+
+    std::fs::write(
+        &cfg_path,
+        format!(
+            r#"
+config_version = 2
+cache_dir = "."
+
+[global]
+current_workspace = "notes"
+
+[workspaces.notes]
+path = "{}"
+last_paths = []
+created = "2026-01-01T00:00:00Z"
+"#,
+            workspace_dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    kimun_notes::settings::AppSettings::load_from_file(cfg_path.clone()).unwrap();
+
+    let cache_dir = tmp.path().canonicalize().unwrap();
+    assert_eq!(
+        std::fs::read(cache_dir.join("notes.kimuncache-wal")).unwrap(),
+        b"wal"
+    );
+    assert_eq!(
+        std::fs::read(cache_dir.join("notes.kimuncache-shm")).unwrap(),
+        b"shm"
+    );
+    assert!(!workspace_dir.path().join("kimun.sqlite-wal").exists());
+    assert!(!workspace_dir.path().join("kimun.sqlite-shm").exists());
+}
+
 #[test]
 fn v2_to_v3_aborts_on_invalid_workspace_name() {
     let tmp = tempfile::TempDir::new().unwrap();
@@ -18,10 +18,7 @@ async fn json_output_includes_required_fields() {
             size: 1024,
             modified_secs: 1711454400,
         },
-        NoteContentData {
-            title: "Test Note".to_string(),
-            hash: 0x123456789abcdef0,
-        },
+        NoteContentData::new("Test Note".to_string(), 0x123456789abcdef0),
     )];
 
     let content_map = vec![(
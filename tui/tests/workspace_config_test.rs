@@ -14,6 +14,7 @@ fn workspace_config_serializes_to_toml() {
             mouse: true,
             kimun_server_url: None,
             kimun_server_token: None,
+            recent_workspaces: Vec::new(),
         },
         workspaces: BTreeMap::from([(
             "default".to_string(),
@@ -25,6 +26,7 @@ fn workspace_config_serializes_to_toml() {
                     .with_timezone(&Utc),
                 quick_note_path: None,
                 inbox_path: None,
+                lowercase_paths: false,
                 resolved_path: None,
             },
         )]),
@@ -50,6 +52,7 @@ fn workspace_serialization_order_is_deterministic() {
         created,
         quick_note_path: None,
         inbox_path: None,
+        lowercase_paths: false,
         resolved_path: None,
     };
     let make = || WorkspaceConfig {
@@ -59,6 +62,7 @@ fn workspace_serialization_order_is_deterministic() {
             mouse: true,
             kimun_server_url: None,
             kimun_server_token: None,
+            recent_workspaces: Vec::new(),
         },
         // Inserted out of alphabetical order on purpose.
         workspaces: BTreeMap::from([
@@ -105,6 +109,7 @@ fn mouse_false_round_trips() {
             mouse: false,
             kimun_server_url: None,
             kimun_server_token: None,
+            recent_workspaces: Vec::new(),
         },
         workspaces: BTreeMap::new(),
     };